@@ -0,0 +1,47 @@
+//! Separação frontmatter/corpo, no mesmo formato aceito pelo `gray-matter`.
+
+/// Um `topic.mdx` dividido em YAML e corpo.
+#[derive(Debug, Clone, Copy)]
+pub struct Document<'a> {
+    pub yaml: &'a str,
+    pub body: &'a str,
+    /// Linha (1-based) da primeira linha do YAML, logo após o `---` de abertura.
+    pub yaml_line: usize,
+    /// Linha (1-based) da primeira linha do corpo.
+    pub body_line: usize,
+}
+
+/// Retorna `None` se o arquivo não começa com `---` ou se o bloco não fecha.
+pub fn split(raw: &str) -> Option<Document<'_>> {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let rest = raw
+        .strip_prefix("---\r\n")
+        .or_else(|| raw.strip_prefix("---\n"))?;
+
+    let mut offset = 0;
+    for (line, chunk) in (2..).zip(rest.split_inclusive('\n')) {
+        if chunk.trim_end() == "---" {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + chunk.len()..];
+            return Some(Document {
+                yaml,
+                body,
+                yaml_line: 2,
+                body_line: line + 1,
+            });
+        }
+        offset += chunk.len();
+    }
+    None
+}
+
+/// Linha (1-based, relativa ao arquivo) da chave `key:` no topo do YAML.
+pub fn key_line(doc: &Document<'_>, key: &str) -> Option<usize> {
+    doc.yaml
+        .lines()
+        .position(|line| {
+            line.strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with(':'))
+        })
+        .map(|idx| doc.yaml_line + idx)
+}
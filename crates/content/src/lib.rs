@@ -0,0 +1,24 @@
+//! Modelo tipado e varredura do conteúdo em `data/`.
+//!
+//! [`Content::scan`] reproduz o `scanContent` de `plugins/vite-plugin-content.ts`:
+//! lê `data/languages/*.json`, `data/registry/rooms/*.yaml`,
+//! `data/registry/roadmaps/*.yaml` e todo `data/content/**/topic.mdx` com seus
+//! `examples/*` (menos as traduções, como `rust.en.rs`), produzindo
+//! `rooms`/`topics`/`languages`/`roadmaps` no mesmo formato de
+//! `src/shared/types/index.ts`. [`output`] grava o resultado
+//! dividido em um arquivo JSON por sala e por tópico.
+
+mod error;
+pub mod frontmatter;
+pub mod model;
+pub mod output;
+pub mod registry;
+mod scan;
+
+pub use error::Error;
+pub use scan::{
+    Content, collation_key, find_topic_files, load_languages, load_roadmaps, load_rooms, locale_of,
+    parse_topic, relative_path,
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use themelion_content::Content;
+use themelion_content::output;
+
+/// Compila `data/` em JSONs divididos por sala e por tópico, lidos pelo
+/// `vite-plugin-content` quando `THEMELION_CONTENT_DIR` aponta para a saída.
+#[derive(Parser)]
+#[command(name = "themelion-content", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+
+    /// Diretório de saída
+    #[arg(long, default_value = ".content")]
+    out: PathBuf,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let content = Content::scan(&cli.root).context("Erro ao escanear o conteúdo")?;
+    let stats = output::write_split(&content, &cli.out)
+        .with_context(|| format!("Erro ao gravar em {}", cli.out.display()))?;
+
+    eprintln!(
+        "{} salas, {} tópicos, {} linguagens, {} trilhas → {} ({} gravados, {} sem mudança, {} removidos)",
+        content.rooms.len(),
+        content.topics.len(),
+        content.languages.len(),
+        content.roadmaps.len(),
+        cli.out.display(),
+        stats.written,
+        stats.unchanged,
+        stats.removed,
+    );
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Espelho de `src/shared/types/index.ts`. Os nomes dos campos em JSON são
+//! os mesmos que o app consome via `virtual:content`.
+
+use serde::{Deserialize, Serialize};
+
+// === Dificuldade ===
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl Difficulty {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Difficulty::Beginner => "beginner",
+            Difficulty::Intermediate => "intermediate",
+            Difficulty::Advanced => "advanced",
+        }
+    }
+}
+
+// === Status de implementação ===
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TopicStatus {
+    Implemented,
+    Planned,
+    Unregistered,
+}
+
+// === Linguagem de programação ===
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Language {
+    pub id: String,
+    pub name: String,
+    pub ext: String,
+    pub monaco_language: String,
+    pub label: String,
+    pub icon: String,
+}
+
+// === Tópico (conteúdo) ===
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicFrontmatter {
+    pub title: String,
+    pub slug: String,
+    pub room: String,
+    pub category: String,
+    pub difficulty: Difficulty,
+    pub order: u32,
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub related_tools: Option<Vec<RelatedTool>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_generated: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedTool {
+    pub name: String,
+    pub search_term: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Topic {
+    #[serde(flatten)]
+    pub frontmatter: TopicFrontmatter,
+    pub status: TopicStatus,
+    pub content_path: String,
+    pub content: String,
+    pub examples: Vec<TopicExample>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicExample {
+    pub language_id: String,
+    pub file_path: String,
+    pub code: String,
+}
+
+// === Sala de estudo (room) ===
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Room {
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub order: u32,
+    pub categories: Vec<Category>,
+}
+
+// === Categoria ===
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Category {
+    pub slug: String,
+    pub name: String,
+    pub order: u32,
+    pub topics: Vec<TopicSummary>,
+}
+
+// === Resumo de tópico (sem conteúdo completo) ===
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicSummary {
+    pub slug: String,
+    pub title: String,
+    pub difficulty: Difficulty,
+    pub order: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TopicStatus>,
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+// === Roadmap (trilha de carreira) ===
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Roadmap {
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub stages: Vec<Stage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stage {
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub order: u32,
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
@@ -0,0 +1,158 @@
+//! Saída dividida do compilador de conteúdo.
+//!
+//! ```text
+//! <out>/manifest.json        ordem das salas e tópicos + arquivos de cada um
+//! <out>/languages.json
+//! <out>/roadmaps.json
+//! <out>/rooms/<sala>.json    Room
+//! <out>/topics/<sala>/<slug>.json   Topic
+//! ```
+//!
+//! Tópicos ficam por sala porque o mesmo slug pode aparecer em mais de uma.
+//!
+//! Arquivos com conteúdo idêntico não são regravados, então uma mudança em um
+//! `topic.mdx` toca apenas o JSON daquele tópico (e o watcher do Vite só vê ele).
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::TopicFrontmatter;
+use crate::scan::Content;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub languages: String,
+    pub roadmaps: String,
+    pub rooms: Vec<ManifestEntry>,
+    pub topics: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// Sala do tópico; ausente nas entradas de sala.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub room: Option<String>,
+    pub slug: String,
+    /// Caminho relativo ao diretório de saída.
+    pub file: String,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WriteStats {
+    pub written: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+}
+
+pub fn write_split(content: &Content, out_dir: &Path) -> Result<WriteStats> {
+    let mut writer = Writer {
+        out_dir,
+        stats: WriteStats::default(),
+        kept: HashSet::new(),
+    };
+
+    let rooms = content
+        .rooms
+        .iter()
+        .map(|room| {
+            let file = format!("rooms/{}.json", room.slug);
+            writer.write(&file, room)?;
+            Ok(ManifestEntry {
+                room: None,
+                slug: room.slug.clone(),
+                file,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let topics = content
+        .topics
+        .iter()
+        .map(|topic| {
+            let TopicFrontmatter { room, slug, .. } = &topic.frontmatter;
+            let file = format!("topics/{room}/{slug}.json");
+            writer.write(&file, topic)?;
+            Ok(ManifestEntry {
+                room: Some(room.clone()),
+                slug: slug.clone(),
+                file,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    writer.write("languages.json", &content.languages)?;
+    writer.write("roadmaps.json", &content.roadmaps)?;
+    writer.write(
+        "manifest.json",
+        &Manifest {
+            languages: "languages.json".to_string(),
+            roadmaps: "roadmaps.json".to_string(),
+            rooms,
+            topics,
+        },
+    )?;
+
+    writer.prune("rooms")?;
+    writer.prune("topics")?;
+    Ok(writer.stats)
+}
+
+struct Writer<'a> {
+    out_dir: &'a Path,
+    stats: WriteStats,
+    kept: HashSet<PathBuf>,
+}
+
+impl Writer<'_> {
+    fn write<T: Serialize>(&mut self, file: &str, value: &T) -> Result<()> {
+        let path = self.out_dir.join(file);
+        let json = serde_json::to_vec_pretty(value).map_err(|source| Error::Json {
+            path: path.clone(),
+            source,
+        })?;
+
+        if fs::read(&path).is_ok_and(|current| current == json) {
+            self.stats.unchanged += 1;
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(Error::io(parent))?;
+            }
+            fs::write(&path, json).map_err(Error::io(&path))?;
+            self.stats.written += 1;
+        }
+        self.kept.insert(path);
+        Ok(())
+    }
+
+    /// Remove JSONs de salas/tópicos que deixaram de existir, e os
+    /// diretórios de sala que ficaram vazios.
+    fn prune(&mut self, subdir: &str) -> Result<()> {
+        self.prune_dir(&self.out_dir.join(subdir))
+    }
+
+    fn prune_dir(&mut self, dir: &Path) -> Result<()> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Ok(());
+        };
+        for entry in entries {
+            let path = entry.map_err(Error::io(dir))?.path();
+            if path.is_dir() {
+                self.prune_dir(&path)?;
+                // Só sai se estiver vazio
+                let _ = fs::remove_dir(&path);
+            } else if path.extension().is_some_and(|ext| ext == "json")
+                && !self.kept.contains(&path)
+            {
+                fs::remove_file(&path).map_err(Error::io(&path))?;
+                self.stats.removed += 1;
+            }
+        }
+        Ok(())
+    }
+}
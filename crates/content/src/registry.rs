@@ -0,0 +1,158 @@
+//! Formato bruto dos arquivos de `data/registry/`, antes da conversão para o modelo.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Category, Roadmap, Room, Stage};
+
+/// `data/registry/rooms/<sala>.yaml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomFile {
+    pub room: String,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub order: u32,
+    #[serde(default)]
+    pub categories: Vec<Category>,
+}
+
+/// `data/registry/roadmaps/<trilha>.yaml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoadmapFile {
+    pub roadmap: String,
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    #[serde(default)]
+    pub stages: Vec<Stage>,
+}
+
+impl From<RoomFile> for Room {
+    fn from(file: RoomFile) -> Self {
+        Room {
+            slug: file.room,
+            name: file.name,
+            description: file.description,
+            icon: file.icon,
+            order: file.order,
+            categories: file.categories,
+        }
+    }
+}
+
+impl From<RoadmapFile> for Roadmap {
+    fn from(file: RoadmapFile) -> Self {
+        Roadmap {
+            slug: file.roadmap,
+            name: file.name,
+            description: file.description,
+            icon: file.icon,
+            stages: file.stages,
+        }
+    }
+}
+
+/// Linhas (1-based) das entradas de tópico em um YAML de sala, para que
+/// diagnósticos apontem `arquivo:linha` no registry.
+#[derive(Debug, Default, Clone)]
+pub struct RoomLines {
+    topics: HashMap<String, TopicLines>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TopicLines {
+    /// Linha do `- slug: ...` que abre a entrada.
+    pub slug: usize,
+    fields: HashMap<String, usize>,
+}
+
+impl TopicLines {
+    /// Linha do campo, ou a da própria entrada se o campo não existir.
+    pub fn field(&self, key: &str) -> usize {
+        self.fields.get(key).copied().unwrap_or(self.slug)
+    }
+}
+
+impl RoomLines {
+    /// Varredura textual: entradas de tópico são os itens `- slug:` mais
+    /// indentados que o `topics:` da categoria corrente.
+    pub fn parse(raw: &str) -> Self {
+        let mut topics: HashMap<String, TopicLines> = HashMap::new();
+        let mut topics_indent: Option<usize> = None;
+        // Slug e indentação da entrada aberta; None em entradas repetidas
+        let mut current: Option<(String, usize)> = None;
+
+        for (idx, line) in raw.lines().enumerate() {
+            let number = idx + 1;
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            if trimmed == "topics:" {
+                topics_indent = Some(indent);
+                current = None;
+                continue;
+            }
+
+            if let Some(item) = trimmed.strip_prefix("- ") {
+                current = None;
+                if topics_indent.is_none_or(|t| indent <= t) {
+                    // Novo item fora de um bloco topics: (outra categoria)
+                    topics_indent = None;
+                    continue;
+                }
+                if let Some(("slug", value)) = field(item) {
+                    let slug = unquote(value).to_string();
+                    if !topics.contains_key(&slug) {
+                        topics.insert(
+                            slug.clone(),
+                            TopicLines {
+                                slug: number,
+                                fields: HashMap::from([("slug".to_string(), number)]),
+                            },
+                        );
+                        current = Some((slug, indent));
+                    }
+                }
+                continue;
+            }
+
+            match &current {
+                Some((slug, item_indent)) if indent > *item_indent => {
+                    if let (Some((key, _)), Some(entry)) = (field(trimmed), topics.get_mut(slug)) {
+                        entry.fields.entry(key.to_string()).or_insert(number);
+                    }
+                }
+                _ => current = None,
+            }
+        }
+
+        Self { topics }
+    }
+
+    pub fn topic(&self, slug: &str) -> Option<&TopicLines> {
+        self.topics.get(slug)
+    }
+}
+
+fn field(text: &str) -> Option<(&str, &str)> {
+    let (key, value) = text.split_once(':')?;
+    let key = key.trim();
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    valid.then(|| (key, value.trim()))
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
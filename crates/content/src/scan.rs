@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+use crate::frontmatter;
+use crate::model::{Language, Roadmap, Room, Topic, TopicExample, TopicFrontmatter, TopicStatus};
+use crate::registry::{RoadmapFile, RoomFile};
+use crate::{Error, Result};
+
+/// Conteúdo completo, como exportado por `virtual:content`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Content {
+    pub rooms: Vec<Room>,
+    pub topics: Vec<Topic>,
+    pub languages: Vec<Language>,
+    pub roadmaps: Vec<Roadmap>,
+}
+
+impl Content {
+    pub fn scan(root: &Path) -> Result<Self> {
+        // 1. Escanear linguagens
+        let languages = load_languages(root)?;
+
+        // 2. Escanear registry (rooms)
+        let mut rooms: Vec<Room> = load_rooms(root)?
+            .into_iter()
+            .map(|(_, file)| {
+                let mut room = Room::from(file);
+                for topic in room.categories.iter_mut().flat_map(|c| &mut c.topics) {
+                    topic.status = Some(TopicStatus::Planned);
+                }
+                room
+            })
+            .collect();
+        rooms.sort_by_key(|room| room.order);
+
+        // 3. Escanear roadmaps
+        let mut roadmaps: Vec<Roadmap> = Vec::new();
+        for (_, file) in load_roadmaps(root)? {
+            let roadmap = Roadmap::from(file);
+            // Mesma semântica do Map: slug repetido substitui, mantendo a posição
+            match roadmaps.iter_mut().find(|r| r.slug == roadmap.slug) {
+                Some(existing) => *existing = roadmap,
+                None => roadmaps.push(roadmap),
+            }
+        }
+        roadmaps.sort_by(|a, b| {
+            collation_key(&a.name)
+                .cmp(&collation_key(&b.name))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        // 4. Escanear conteúdo MDX. Um tópico é (sala, slug): o mesmo slug
+        // pode existir em várias salas
+        let mut topics: Vec<Topic> = Vec::new();
+        let mut index: HashMap<(String, String), usize> = HashMap::new();
+        for file in find_topic_files(root)? {
+            let topic = parse_topic(root, &file, &languages)?;
+            let key = (
+                topic.frontmatter.room.clone(),
+                topic.frontmatter.slug.clone(),
+            );
+            match index.get(&key) {
+                Some(&idx) => topics[idx] = topic,
+                None => {
+                    index.insert(key, topics.len());
+                    topics.push(topic);
+                }
+            }
+        }
+
+        // 5. Reconciliar status
+        for room in &mut rooms {
+            for topic in room.categories.iter_mut().flat_map(|c| &mut c.topics) {
+                if index.contains_key(&(room.slug.clone(), topic.slug.clone())) {
+                    topic.status = Some(TopicStatus::Implemented);
+                }
+            }
+        }
+
+        Ok(Self {
+            rooms,
+            topics,
+            languages,
+            roadmaps,
+        })
+    }
+}
+
+/// `data/languages/*.json`, sem o campo `runner` (backend-only).
+pub fn load_languages(root: &Path) -> Result<Vec<Language>> {
+    files_with_extension(&root.join("data/languages"), "json")?
+        .into_iter()
+        .map(|path| {
+            let raw = read(&path)?;
+            serde_json::from_str(&raw).map_err(|source| Error::Json { path, source })
+        })
+        .collect()
+}
+
+/// `data/registry/rooms/*.yaml`, com o caminho de cada arquivo.
+pub fn load_rooms(root: &Path) -> Result<Vec<(PathBuf, RoomFile)>> {
+    load_yaml(&root.join("data/registry/rooms"))
+}
+
+/// `data/registry/roadmaps/*.yaml`, com o caminho de cada arquivo.
+pub fn load_roadmaps(root: &Path) -> Result<Vec<(PathBuf, RoadmapFile)>> {
+    load_yaml(&root.join("data/registry/roadmaps"))
+}
+
+/// Todo `topic.mdx` sob `data/content`, em ordem estável.
+pub fn find_topic_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut pending = vec![root.join("data/content")];
+    while let Some(dir) = pending.pop() {
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&dir).map_err(Error::io(&dir))? {
+            let path = entry.map_err(Error::io(&dir))?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.file_name().is_some_and(|name| name == "topic.mdx") {
+                found.push(path);
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Locale de um arquivo traduzido: `topic.en.mdx` → `en`,
+/// `rust.pt-PT.rs` → `pt-PT`. O original em pt-BR não tem sufixo.
+pub fn locale_of(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    let mut parts = name.rsplit('.');
+    let (_ext, locale, _stem) = (parts.next()?, parts.next()?, parts.next()?);
+    let (language, region) = locale.split_once('-').unwrap_or((locale, ""));
+    let language_ok =
+        (2..=3).contains(&language.len()) && language.bytes().all(|b| b.is_ascii_lowercase());
+    let region_ok =
+        region.is_empty() || (region.len() == 2 && region.bytes().all(|b| b.is_ascii_uppercase()));
+    (language_ok && region_ok).then_some(locale)
+}
+
+/// Lê um `topic.mdx` e os arquivos de `examples/` ao lado dele, sem as
+/// traduções (`rust.en.rs`).
+pub fn parse_topic(root: &Path, file: &Path, languages: &[Language]) -> Result<Topic> {
+    let raw = read(file)?;
+    let doc = frontmatter::split(&raw).ok_or_else(|| Error::MissingFrontmatter {
+        path: file.to_path_buf(),
+    })?;
+    let frontmatter: TopicFrontmatter =
+        serde_yaml::from_str(doc.yaml).map_err(|source| Error::Yaml {
+            path: file.to_path_buf(),
+            source,
+        })?;
+
+    let examples_dir = file.with_file_name("examples");
+    let examples = if examples_dir.is_dir() {
+        let mut paths: Vec<PathBuf> = fs::read_dir(&examples_dir)
+            .map_err(Error::io(&examples_dir))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && locale_of(path).is_none())
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let ext = path
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let language_id = languages
+                    .iter()
+                    .find(|l| l.ext == format!(".{ext}"))
+                    .map_or(ext, |l| l.id.clone());
+                Ok(TopicExample {
+                    language_id,
+                    file_path: relative_path(root, &path),
+                    code: read(&path)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Topic {
+        frontmatter,
+        status: TopicStatus::Implemented,
+        content_path: relative_path(root, file),
+        content: doc.body.trim().to_string(),
+        examples,
+    })
+}
+
+/// Chave de ordenação próxima de `localeCompare`: sem acentos e sem caixa.
+pub fn collation_key(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn load_yaml<T: serde::de::DeserializeOwned>(dir: &Path) -> Result<Vec<(PathBuf, T)>> {
+    files_with_extension(dir, "yaml")?
+        .into_iter()
+        .map(|path| {
+            let raw = read(&path)?;
+            let data = serde_yaml::from_str(&raw).map_err(|source| Error::Yaml {
+                path: path.clone(),
+                source,
+            })?;
+            Ok((path, data))
+        })
+        .collect()
+}
+
+fn files_with_extension(dir: &Path, ext: &str) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(Error::io(dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == ext))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(Error::io(path))
+}
+
+/// Caminho relativo à raiz com `/`, como o `globSync` do plugin devolve.
+pub fn relative_path(root: &Path, path: &Path) -> String {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
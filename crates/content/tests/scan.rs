@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::Path;
+
+use themelion_content::Content;
+use themelion_content::model::TopicStatus;
+use themelion_content::output;
+
+fn write(root: &Path, file: &str, text: &str) {
+    let path = root.join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, text).unwrap();
+}
+
+fn room(slug: &str, order: u32) -> String {
+    format!(
+        r#"room: {slug}
+name: "{slug}"
+description: ""
+icon: "brain"
+order: {order}
+categories:
+  - slug: "estruturas"
+    name: "Estruturas"
+    order: 1
+    topics:
+      - slug: "hash-tables"
+        title: "Hash Tables"
+        difficulty: beginner
+        order: 1
+      - slug: "tries"
+        title: "Tries"
+        difficulty: advanced
+        order: 2
+"#
+    )
+}
+
+fn topic(room: &str, slug: &str, body: &str) -> String {
+    format!(
+        r#"---
+title: "{slug}"
+slug: "{slug}"
+room: "{room}"
+category: "estruturas"
+difficulty: "beginner"
+order: 1
+---
+
+{body}
+"#
+    )
+}
+
+/// Duas salas com o mesmo slug `hash-tables`, cada uma com o seu `topic.mdx`.
+fn fixture() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    write(
+        root,
+        "data/languages/rust.json",
+        r#"{"id": "rust", "name": "Rust", "ext": ".rs", "runner": "rust-runner",
+            "monacoLanguage": "rust", "label": "", "icon": "rust"}"#,
+    );
+    write(
+        root,
+        "data/registry/rooms/backend.yaml",
+        &room("backend", 2),
+    );
+    write(
+        root,
+        "data/registry/rooms/fundamentos.yaml",
+        &room("fundamentos", 1),
+    );
+    fs::create_dir_all(root.join("data/registry/roadmaps")).unwrap();
+    write(
+        root,
+        "data/content/fundamentos/estruturas/hash-tables/topic.mdx",
+        &topic(
+            "fundamentos",
+            "hash-tables",
+            "## O que é\n\nTabela na memória.",
+        ),
+    );
+    write(
+        root,
+        "data/content/fundamentos/estruturas/hash-tables/examples/rust.rs",
+        "fn main() {}\n",
+    );
+    write(
+        root,
+        "data/content/backend/estruturas/hash-tables/topic.mdx",
+        &topic("backend", "hash-tables", "## O que é\n\nCache distribuído."),
+    );
+    dir
+}
+
+#[test]
+fn keeps_one_topic_per_room_and_slug() {
+    let dir = fixture();
+    let content = Content::scan(dir.path()).unwrap();
+
+    let mut found: Vec<(&str, &str)> = content
+        .topics
+        .iter()
+        .map(|t| (t.frontmatter.room.as_str(), t.content.as_str()))
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            ("backend", "## O que é\n\nCache distribuído."),
+            ("fundamentos", "## O que é\n\nTabela na memória."),
+        ]
+    );
+
+    let fundamentos = content
+        .topics
+        .iter()
+        .find(|t| t.frontmatter.room == "fundamentos");
+    let examples = &fundamentos.unwrap().examples;
+    assert_eq!(examples.len(), 1);
+    assert_eq!(examples[0].language_id, "rust");
+}
+
+#[test]
+fn reconciles_status_within_each_room() {
+    let dir = fixture();
+    fs::remove_dir_all(dir.path().join("data/content/backend")).unwrap();
+    let content = Content::scan(dir.path()).unwrap();
+
+    let status = |room: &str, slug: &str| {
+        content
+            .rooms
+            .iter()
+            .find(|r| r.slug == room)
+            .unwrap()
+            .categories
+            .iter()
+            .flat_map(|c| &c.topics)
+            .find(|t| t.slug == slug)
+            .unwrap()
+            .status
+    };
+    assert_eq!(
+        status("fundamentos", "hash-tables"),
+        Some(TopicStatus::Implemented)
+    );
+    assert_eq!(status("backend", "hash-tables"), Some(TopicStatus::Planned));
+    assert_eq!(status("fundamentos", "tries"), Some(TopicStatus::Planned));
+}
+
+#[test]
+fn writes_topics_under_their_room() {
+    let dir = fixture();
+    let out = dir.path().join(".content");
+    let content = Content::scan(dir.path()).unwrap();
+
+    let stats = output::write_split(&content, &out).unwrap();
+    assert_eq!(stats.written, 2 + 2 + 3);
+    assert!(out.join("topics/fundamentos/hash-tables.json").is_file());
+    assert!(out.join("topics/backend/hash-tables.json").is_file());
+
+    let manifest: output::Manifest =
+        serde_json::from_str(&fs::read_to_string(out.join("manifest.json")).unwrap()).unwrap();
+    let mut files: Vec<_> = manifest
+        .topics
+        .iter()
+        .map(|e| (e.room.as_deref().unwrap(), e.file.as_str()))
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        [
+            ("backend", "topics/backend/hash-tables.json"),
+            ("fundamentos", "topics/fundamentos/hash-tables.json"),
+        ]
+    );
+
+    // Segunda passada sem mudança não regrava nada
+    let again = output::write_split(&content, &out).unwrap();
+    assert_eq!((again.written, again.removed), (0, 0));
+}
+
+#[test]
+fn prunes_removed_topics_and_empty_rooms() {
+    let dir = fixture();
+    let out = dir.path().join(".content");
+    output::write_split(&Content::scan(dir.path()).unwrap(), &out).unwrap();
+
+    fs::remove_dir_all(dir.path().join("data/content/backend")).unwrap();
+    let stats = output::write_split(&Content::scan(dir.path()).unwrap(), &out).unwrap();
+
+    assert_eq!(stats.removed, 1);
+    assert!(!out.join("topics/backend").exists());
+    assert!(out.join("topics/fundamentos/hash-tables.json").is_file());
+}
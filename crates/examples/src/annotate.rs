@@ -0,0 +1,309 @@
+//! Anotações por trecho de um `rust.rs`, para o site mostrar como selos na
+//! margem do código: a complexidade que os comentários `// O(n)` afirmam e o
+//! que a árvore sintática (syn) revela sobre empréstimos, movimentos, clones,
+//! alocações e chamadas que podem entrar em pânico.
+//!
+//! A leitura é só sintática, sem tipos: `x.clone()` é marcado como clone
+//! mesmo quando `x` é `Copy`, e `v[i]` como indexação que pode falhar mesmo
+//! quando o índice é obviamente válido.
+
+use proc_macro2::Span;
+use serde::Serialize;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Expr, Token};
+
+use crate::lexer::{self, Kind as SpanKind};
+use crate::parity;
+use crate::{Error, Example, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Kind {
+    /// Classe de complexidade afirmada por um comentário `O(...)`.
+    Complexity,
+    /// `&x` ou `&mut x`.
+    Borrow,
+    /// Closure `move`, `into_*()` ou `drop(x)`: o valor muda de dono.
+    Move,
+    /// `.clone()`/`.cloned()`.
+    Clone,
+    /// `vec![]`, `format!`, `Box::new`, `.to_string()`, `.collect()`...
+    Allocation,
+    /// `unwrap`, `expect`, indexação e as macros de pânico.
+    Panic,
+}
+
+/// Um selo: o trecho, o tipo e o texto curto que aparece na margem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    pub kind: Kind,
+    /// Linha e coluna (1-based, em caracteres) do começo do trecho.
+    pub line: usize,
+    pub column: usize,
+    /// Linha e coluna logo depois do fim do trecho.
+    pub end_line: usize,
+    pub end_column: usize,
+    /// Texto do selo: `O(n)`, `&mut`, `clone`, `unwrap`...
+    pub label: String,
+    /// Explicação para o tooltip.
+    pub detail: String,
+}
+
+/// Anotações de um exemplo, na ordem do arquivo.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExampleAnnotations {
+    pub topic: String,
+    pub file: String,
+    pub annotations: Vec<Annotation>,
+}
+
+pub fn annotate(example: &Example) -> Result<ExampleAnnotations> {
+    let ast = parse(example)?;
+    let mut visitor = Visitor::default();
+    visitor.visit_file(&ast);
+    let mut annotations = visitor.annotations;
+    annotations.extend(complexity(&example.source));
+    annotations.sort_by_key(|a| (a.line, a.column, a.kind));
+
+    Ok(ExampleAnnotations {
+        topic: example.topic.clone(),
+        file: example.file.clone(),
+        annotations,
+    })
+}
+
+pub(crate) fn parse(example: &Example) -> Result<syn::File> {
+    syn::parse_file(&example.source).map_err(|err| Error::Parse {
+        file: example.file.clone(),
+        line: err.span().start().line,
+        message: err.to_string(),
+    })
+}
+
+/// Argumentos de `println!`, `vec!`...: expressões comuns, que o syn guarda
+/// como tokens. `None` para macros com outra sintaxe (`vec![0; n]`).
+pub(crate) fn macro_args(mac: &syn::Macro) -> Option<Punctuated<Expr, Token![,]>> {
+    mac.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        .ok()
+}
+
+/// Comentários com `O(...)` fora das faixas. O comentário no fim de uma linha
+/// anota o código dela; o que ocupa sozinho a linha de cima, a linha seguinte.
+fn complexity(source: &str) -> Vec<Annotation> {
+    let banner_lines: Vec<usize> = parity::banners(source).iter().map(|b| b.line).collect();
+    let lines: Vec<&str> = source.lines().collect();
+    let mut annotations = Vec::new();
+
+    for span in lexer::scan(source) {
+        if span.kind != SpanKind::LineComment {
+            continue;
+        }
+        let text = source[span.range.clone()]
+            .trim_start_matches('/')
+            .trim_start_matches('!')
+            .trim();
+        let Some(label) = big_o(text) else { continue };
+
+        let line = source[..span.range.start].matches('\n').count() + 1;
+        if banner_lines.contains(&line) {
+            continue;
+        }
+        let line_start = source[..span.range.start].rfind('\n').map_or(0, |n| n + 1);
+        let before = &source[line_start..span.range.start];
+        let target = if before.trim().is_empty() {
+            // Comentário sozinho anota a linha de baixo, se for código; em um
+            // parágrafo de várias linhas, é explicação solta
+            let is_comment = |index: usize| {
+                lines
+                    .get(index)
+                    .is_some_and(|l| parity::comment(l).is_some())
+            };
+            let next = lines.get(line).filter(|l| !l.trim().is_empty());
+            match next {
+                Some(code) if !is_comment(line) && (line < 2 || !is_comment(line - 2)) => {
+                    (line + 1, code.trim_end())
+                }
+                _ => continue,
+            }
+        } else {
+            (line, before.trim_end())
+        };
+        let (line, code) = target;
+        let indent = code.len() - code.trim_start().len();
+        annotations.push(Annotation {
+            kind: Kind::Complexity,
+            line,
+            column: code[..indent].chars().count() + 1,
+            end_line: line,
+            end_column: code.chars().count() + 1,
+            label: label.to_string(),
+            detail: text.to_string(),
+        });
+    }
+    annotations
+}
+
+/// Primeiro `O(...)` do texto, com parênteses aninhados (`O(n log(n))`).
+fn big_o(text: &str) -> Option<&str> {
+    let mut from = 0;
+    while let Some(found) = text[from..].find("O(") {
+        let at = from + found;
+        from = at + 2;
+        let prev = text[..at].chars().next_back();
+        if prev.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let mut depth = 0;
+        for (i, c) in text[at + 1..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(&text[at..at + 1 + i + 1]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+#[derive(Default)]
+struct Visitor {
+    annotations: Vec<Annotation>,
+}
+
+impl Visitor {
+    fn push(&mut self, kind: Kind, (start, end): (Span, Span), label: &str, detail: &str) {
+        let (start, end) = (start.start(), end.end());
+        self.annotations.push(Annotation {
+            kind,
+            line: start.line,
+            column: start.column + 1,
+            end_line: end.line,
+            end_column: end.column + 1,
+            label: label.to_string(),
+            detail: detail.to_string(),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for Visitor {
+    fn visit_expr_reference(&mut self, expr: &'ast syn::ExprReference) {
+        let span = (expr.span(), expr.span());
+        if expr.mutability.is_some() {
+            self.push(Kind::Borrow, span, "&mut", "empréstimo exclusivo");
+        } else {
+            self.push(Kind::Borrow, span, "&", "empréstimo compartilhado");
+        }
+        visit::visit_expr_reference(self, expr);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        // Do nome do método ao `)`, sem o receptor
+        let span = (call.method.span(), call.paren_token.span.close());
+        let annotation = match method.as_str() {
+            "clone" | "cloned" => {
+                Some((Kind::Clone, "cópia do valor (profunda em String, Vec...)"))
+            }
+            "to_string" | "to_owned" | "to_vec" => Some((Kind::Allocation, "aloca uma cópia")),
+            "collect" => Some((Kind::Allocation, "aloca a coleção de destino")),
+            "unwrap" | "unwrap_err" => Some((Kind::Panic, "entra em pânico se for None/Err")),
+            "expect" | "expect_err" => Some((
+                Kind::Panic,
+                "entra em pânico com a mensagem se for None/Err",
+            )),
+            name if name.starts_with("into_") => Some((Kind::Move, "consome o receptor (move)")),
+            _ => None,
+        };
+        if let Some((kind, detail)) = annotation {
+            self.push(kind, span, &method, detail);
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(path) = &*call.func {
+            let segments: Vec<String> = path
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            let name = segments[segments.len().saturating_sub(2)..].join("::");
+            let annotation = match name.as_str() {
+                "Box::new" | "Rc::new" | "Arc::new" => Some((Kind::Allocation, "aloca no heap")),
+                "String::from"
+                | "Vec::with_capacity"
+                | "String::with_capacity"
+                | "HashMap::with_capacity"
+                | "HashSet::with_capacity" => Some((Kind::Allocation, "aloca no heap")),
+                "drop" => Some((Kind::Move, "move o valor e o descarta")),
+                _ => None,
+            };
+            if let Some((kind, detail)) = annotation {
+                self.push(kind, (call.span(), call.span()), &name, detail);
+            }
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast syn::ExprClosure) {
+        if let Some(capture) = &closure.capture {
+            let span = (capture.span, capture.span);
+            self.push(
+                Kind::Move,
+                span,
+                "move",
+                "a closure toma posse do que captura",
+            );
+        }
+        visit::visit_expr_closure(self, closure);
+    }
+
+    fn visit_expr_index(&mut self, index: &'ast syn::ExprIndex) {
+        let span = (index.span(), index.span());
+        self.push(
+            Kind::Panic,
+            span,
+            "[]",
+            "entra em pânico se o índice estiver fora dos limites",
+        );
+        visit::visit_expr_index(self, index);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default();
+        let span = (mac.span(), mac.delimiter.span().close());
+        match name.as_str() {
+            "vec" => self.push(Kind::Allocation, span, "vec!", "aloca no heap"),
+            "format" => self.push(Kind::Allocation, span, "format!", "aloca uma String"),
+            "panic" | "todo" | "unimplemented" | "unreachable" => {
+                self.push(Kind::Panic, span, &format!("{name}!"), "entra em pânico")
+            }
+            "assert" | "assert_eq" | "assert_ne" => self.push(
+                Kind::Panic,
+                span,
+                &format!("{name}!"),
+                "entra em pânico se a condição falhar",
+            ),
+            _ => {}
+        }
+
+        for arg in macro_args(mac).iter().flatten() {
+            self.visit_expr(arg);
+        }
+    }
+}
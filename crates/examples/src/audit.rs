@@ -0,0 +1,374 @@
+//! Auditoria dos pontos de pânico de um `rust.rs`, seguindo o checklist de
+//! revisão de `data/agents/languages/rust.md` ("não unwrap() sem
+//! justificativa", "unsafe justificado (comentário explicando)").
+//!
+//! Cada ponto precisa de uma justificativa por perto: no fim da própria linha,
+//! no fim da linha onde o comando começa ou na linha de cima. Só conta o
+//! comentário que se declara justificativa, aberto por um marcador
+//! (`// SAFETY:`, `// PANIC:`, `// unwrap:`, `// overflow:`, `// clone:`).
+//! Procurar palavras como "invariante" ou "garante" no meio do texto aceitaria
+//! `// isto NÃO garante nada`; e um comentário qualquer ao lado, como
+//! `// pode falhar se não for UTF-8 válido`, descreve o risco em vez de
+//! afastá-lo. Saída esperada (`// 50`) também não explica nada.
+//!
+//! `unwrap()` e `unsafe` sem justificativa reprovam o exemplo; indexação,
+//! divisão e aritmética que pode transbordar sem justificativa são só avisos.
+//! Como em [`crate::annotate`], a leitura é sintática: `a / b` com floats
+//! também aparece, e `a + b` só escapa quando um dos lados é literal.
+
+use std::collections::HashMap;
+
+use proc_macro2::Span;
+use serde::Serialize;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, Lit};
+
+use crate::annotate::{macro_args, parse};
+use crate::expect;
+use crate::lexer::{self, Kind as SpanKind};
+use crate::{Example, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FindingKind {
+    /// `unwrap()`/`unwrap_err()`.
+    Unwrap,
+    /// `expect("...")`: a mensagem já conta como justificativa.
+    Expect,
+    /// `v[i]`.
+    Index,
+    /// `panic!`, `todo!`, `unimplemented!`, `unreachable!`.
+    PanicMacro,
+    /// `/` ou `%` por algo que não é um literal.
+    Division,
+    /// `a + b`, `a - b` e `a * b` entre não literais, `len() - n`, `.pow()`.
+    Overflow,
+    /// Bloco, função ou `impl` `unsafe`.
+    Unsafe,
+}
+
+impl FindingKind {
+    /// Itens do checklist: sem justificativa, reprovam o exemplo.
+    pub fn blocking(self) -> bool {
+        matches!(self, Self::Unwrap | Self::Unsafe)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unwrap => "unwrap",
+            Self::Expect => "expect",
+            Self::Index => "indexação",
+            Self::PanicMacro => "pânico",
+            Self::Division => "divisão",
+            Self::Overflow => "overflow",
+            Self::Unsafe => "unsafe",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub line: usize,
+    pub column: usize,
+    /// A linha do código, sem a indentação.
+    pub code: String,
+    /// Comentário (ou mensagem do `expect`) que explica o ponto.
+    pub explanation: Option<String>,
+}
+
+impl Finding {
+    pub fn blocks(&self) -> bool {
+        self.kind.blocking() && self.explanation.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    pub topic: String,
+    pub file: String,
+    pub findings: Vec<Finding>,
+}
+
+impl AuditReport {
+    pub fn unexplained(&self) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(|f| f.explanation.is_none())
+    }
+
+    pub fn blocking(&self) -> usize {
+        self.findings.iter().filter(|f| f.blocks()).count()
+    }
+}
+
+pub fn audit(example: &Example) -> Result<AuditReport> {
+    let ast = parse(example)?;
+    let mut visitor = Visitor::default();
+    visitor.visit_file(&ast);
+
+    let comments = Comments::new(&example.source);
+    let lines: Vec<&str> = example.source.lines().collect();
+    let mut findings: Vec<Finding> = visitor
+        .sites
+        .into_iter()
+        .map(|site| Finding {
+            kind: site.kind,
+            line: site.line,
+            column: site.column,
+            code: lines
+                .get(site.line - 1)
+                .map(|l| l.trim().to_string())
+                .unwrap_or_default(),
+            explanation: site
+                .message
+                .or_else(|| comments.near(site.line, site.statement_line)),
+        })
+        .collect();
+    findings.sort_by_key(|f| (f.line, f.column));
+
+    Ok(AuditReport {
+        topic: example.topic.clone(),
+        file: example.file.clone(),
+        findings,
+    })
+}
+
+/// Marcadores que abrem um comentário de justificativa (`// SAFETY: ...`),
+/// comparados sem diferenciar maiúsculas.
+const MARKERS: [&str; 5] = ["safety", "panic", "unwrap", "overflow", "clone"];
+
+/// Comentários de justificativa do arquivo, pela linha em que estão.
+pub(crate) struct Comments {
+    /// Texto e se o comentário está sozinho na linha.
+    by_line: HashMap<usize, (String, bool)>,
+}
+
+impl Comments {
+    pub(crate) fn new(source: &str) -> Self {
+        let output: Vec<usize> = expect::instrument(source)
+            .claims
+            .iter()
+            .flat_map(|claim| claim.comment_line..claim.comment_line + claim.expected.len())
+            .collect();
+        let mut by_line = HashMap::new();
+        for span in lexer::scan(source) {
+            if span.kind != SpanKind::LineComment {
+                continue;
+            }
+            let line = source[..span.range.start].matches('\n').count() + 1;
+            let line_start = source[..span.range.start].rfind('\n').map_or(0, |n| n + 1);
+            let alone = source[line_start..span.range.start].trim().is_empty();
+            let text = source[span.range]
+                .trim_start_matches('/')
+                .trim_start_matches('!')
+                .trim();
+            if justifies(text) && !output.contains(&line) {
+                by_line.insert(line, (text.to_string(), alone));
+            }
+        }
+        Self { by_line }
+    }
+
+    /// No fim da linha do ponto ou da linha onde o comando começa, ou sozinho
+    /// logo acima de uma das duas.
+    pub(crate) fn near(&self, line: usize, statement_line: usize) -> Option<String> {
+        let trailing = |line: usize| self.by_line.get(&line).filter(|(_, alone)| !alone);
+        let above = |line: usize| {
+            line.checked_sub(1)
+                .and_then(|above| self.by_line.get(&above))
+                .filter(|(_, alone)| *alone)
+        };
+        trailing(line)
+            .or_else(|| trailing(statement_line))
+            .or_else(|| above(line))
+            .or_else(|| above(statement_line))
+            .map(|(text, _)| text.clone())
+    }
+}
+
+/// `SAFETY: ...`, `unwrap: ...` e os outros [`MARKERS`].
+fn justifies(text: &str) -> bool {
+    text.split_once(':').is_some_and(|(marker, _)| {
+        MARKERS
+            .iter()
+            .any(|m| marker.trim().eq_ignore_ascii_case(m))
+    })
+}
+
+struct Site {
+    kind: FindingKind,
+    line: usize,
+    column: usize,
+    statement_line: usize,
+    message: Option<String>,
+}
+
+#[derive(Default)]
+struct Visitor {
+    sites: Vec<Site>,
+    /// Linha onde começa o comando (ou item) sendo percorrido.
+    statement_line: usize,
+}
+
+impl Visitor {
+    fn push(&mut self, kind: FindingKind, span: Span, message: Option<String>) {
+        let start = span.start();
+        self.sites.push(Site {
+            kind,
+            line: start.line,
+            column: start.column + 1,
+            statement_line: self.statement_line.max(1),
+            message,
+        });
+    }
+
+    fn within(&mut self, span: Span, visit: impl FnOnce(&mut Self)) {
+        let outer = self.statement_line;
+        self.statement_line = span.start().line;
+        visit(self);
+        self.statement_line = outer;
+    }
+}
+
+impl<'ast> Visit<'ast> for Visitor {
+    fn visit_stmt(&mut self, stmt: &'ast syn::Stmt) {
+        self.within(stmt.span(), |v| visit::visit_stmt(v, stmt));
+    }
+
+    fn visit_item(&mut self, item: &'ast syn::Item) {
+        self.within(item.span(), |v| visit::visit_item(v, item));
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        match method.as_str() {
+            "unwrap" | "unwrap_err" => self.push(FindingKind::Unwrap, call.method.span(), None),
+            "expect" | "expect_err" => {
+                let message = call.args.first().and_then(string_literal);
+                self.push(FindingKind::Expect, call.method.span(), message);
+            }
+            "pow" => self.push(FindingKind::Overflow, call.method.span(), None),
+            _ => {}
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_index(&mut self, index: &'ast syn::ExprIndex) {
+        self.push(FindingKind::Index, index.bracket_token.span.open(), None);
+        visit::visit_expr_index(self, index);
+    }
+
+    fn visit_expr_binary(&mut self, binary: &'ast syn::ExprBinary) {
+        let (left, right) = (&*binary.left, &*binary.right);
+        let kind = match binary.op {
+            BinOp::Div(_) | BinOp::Rem(_) | BinOp::DivAssign(_) | BinOp::RemAssign(_)
+                if !is_literal(right) =>
+            {
+                Some(FindingKind::Division)
+            }
+            // `len() - 1` com a coleção vazia dá underflow em usize
+            BinOp::Sub(_) | BinOp::SubAssign(_) if mentions_len(left) => {
+                Some(FindingKind::Overflow)
+            }
+            BinOp::Add(_)
+            | BinOp::AddAssign(_)
+            | BinOp::Sub(_)
+            | BinOp::SubAssign(_)
+            | BinOp::Mul(_)
+            | BinOp::MulAssign(_)
+                if !is_literal(left) && !is_literal(right) && !is_concat(right) =>
+            {
+                Some(FindingKind::Overflow)
+            }
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            self.push(kind, binary.op.span(), None);
+        }
+        visit::visit_expr_binary(self, binary);
+    }
+
+    fn visit_expr_unsafe(&mut self, block: &'ast syn::ExprUnsafe) {
+        self.push(FindingKind::Unsafe, block.unsafe_token.span, None);
+        visit::visit_expr_unsafe(self, block);
+    }
+
+    fn visit_signature(&mut self, signature: &'ast syn::Signature) {
+        if let Some(token) = &signature.unsafety {
+            self.push(FindingKind::Unsafe, token.span, None);
+        }
+        visit::visit_signature(self, signature);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        if let Some(token) = &item.unsafety {
+            self.push(FindingKind::Unsafe, token.span, None);
+        }
+        visit::visit_item_impl(self, item);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default();
+        if matches!(
+            name.as_str(),
+            "panic" | "todo" | "unimplemented" | "unreachable"
+        ) {
+            let message = macro_args(mac).and_then(|args| args.first().and_then(string_literal));
+            self.push(FindingKind::PanicMacro, mac.span(), message);
+        }
+        for arg in macro_args(mac).iter().flatten() {
+            self.visit_expr(arg);
+        }
+    }
+}
+
+fn string_literal(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(text),
+            ..
+        }) => Some(text.value()),
+        _ => None,
+    }
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Paren(inner) => is_literal(&inner.expr),
+        _ => false,
+    }
+}
+
+/// `texto + &outro`: concatenação de `String`, não aritmética.
+fn is_concat(right: &Expr) -> bool {
+    match right {
+        Expr::Reference(_) => true,
+        Expr::Paren(inner) => is_concat(&inner.expr),
+        _ => false,
+    }
+}
+
+/// A expressão chama `len()`, `count()` ou `capacity()` em algum ponto.
+fn mentions_len(expr: &Expr) -> bool {
+    struct Finder(bool);
+    impl<'ast> Visit<'ast> for Finder {
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            self.0 |= matches!(
+                call.method.to_string().as_str(),
+                "len" | "count" | "capacity"
+            );
+            visit::visit_expr_method_call(self, call);
+        }
+    }
+    let mut finder = Finder(false);
+    finder.visit_expr(expr);
+    finder.0
+}
@@ -0,0 +1,194 @@
+//! Cobertura dos `examples/`: cada tópico implementado × cada linguagem de
+//! `data/languages/`, agrupado por sala.
+//!
+//! Aponta dois buracos que o site esconde: o `<CodeTabs />` de um tópico sem
+//! nenhum exemplo renderiza uma caixa vazia, e um arquivo cuja extensão não
+//! bate com nenhuma linguagem vira uma aba com o id cru (`.js` → `js`).
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use themelion_content::Content;
+use themelion_content::model::Topic;
+use themelion_mdx::Block;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Coverage {
+    /// Ids de `data/languages/`, na ordem das colunas.
+    pub languages: Vec<String>,
+    pub rooms: Vec<RoomCoverage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomCoverage {
+    pub room: String,
+    pub name: String,
+    /// Tópicos com pelo menos um exemplo.
+    pub with_examples: usize,
+    /// Tópicos com exemplo em cada linguagem.
+    pub per_language: BTreeMap<String, usize>,
+    pub topics: Vec<TopicCoverage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicCoverage {
+    pub slug: String,
+    pub title: String,
+    pub category: String,
+    pub code_tabs: bool,
+    /// Linguagens com exemplo, na ordem de [`Coverage::languages`].
+    pub languages: Vec<String>,
+    pub missing: Vec<String>,
+    /// Exemplos cuja extensão não corresponde a nenhuma linguagem.
+    pub unknown: Vec<String>,
+}
+
+impl TopicCoverage {
+    /// `<CodeTabs />` no corpo sem nenhum arquivo em `examples/`.
+    pub fn empty_tabs(&self) -> bool {
+        self.code_tabs && self.languages.is_empty() && self.unknown.is_empty()
+    }
+
+    pub fn has_problems(&self) -> bool {
+        self.empty_tabs() || !self.unknown.is_empty()
+    }
+}
+
+pub fn coverage(content: &Content) -> Coverage {
+    let languages: Vec<String> = content.languages.iter().map(|l| l.id.clone()).collect();
+
+    let rooms = content
+        .rooms
+        .iter()
+        .map(|room| {
+            let position = |topic: &Topic| {
+                let meta = &topic.frontmatter;
+                let category = room
+                    .categories
+                    .iter()
+                    .position(|c| c.slug == meta.category)
+                    .unwrap_or(usize::MAX);
+                (category, meta.order, meta.slug.clone())
+            };
+            let mut topics: Vec<&Topic> = content
+                .topics
+                .iter()
+                .filter(|t| t.frontmatter.room == room.slug)
+                .collect();
+            topics.sort_by_key(|t| position(t));
+
+            let topics: Vec<TopicCoverage> = topics
+                .into_iter()
+                .map(|topic| topic_coverage(topic, &languages))
+                .collect();
+            let per_language = languages
+                .iter()
+                .map(|id| {
+                    let count = topics.iter().filter(|t| t.languages.contains(id)).count();
+                    (id.clone(), count)
+                })
+                .collect();
+            RoomCoverage {
+                room: room.slug.clone(),
+                name: room.name.clone(),
+                with_examples: topics.iter().filter(|t| !t.languages.is_empty()).count(),
+                per_language,
+                topics,
+            }
+        })
+        .collect();
+
+    Coverage { languages, rooms }
+}
+
+fn topic_coverage(topic: &Topic, languages: &[String]) -> TopicCoverage {
+    let present = |id: &String| topic.examples.iter().any(|e| &e.language_id == id);
+    let code_tabs = themelion_mdx::parse(&topic.content)
+        .blocks()
+        .any(|block| *block == Block::CodeTabs);
+    TopicCoverage {
+        slug: topic.frontmatter.slug.clone(),
+        title: topic.frontmatter.title.clone(),
+        category: topic.frontmatter.category.clone(),
+        code_tabs,
+        languages: languages.iter().filter(|id| present(id)).cloned().collect(),
+        missing: languages
+            .iter()
+            .filter(|id| !present(id))
+            .cloned()
+            .collect(),
+        unknown: topic
+            .examples
+            .iter()
+            .filter(|e| !languages.contains(&e.language_id))
+            .map(|e| e.file_path.clone())
+            .collect(),
+    }
+}
+
+/// Resumo por sala e, para cada sala com tópicos, a matriz tópico ×
+/// linguagem com os avisos.
+pub fn markdown(coverage: &Coverage) -> String {
+    let columns = coverage.languages.join(" | ");
+    let align = ":-:|".repeat(coverage.languages.len());
+
+    let mut md = String::from("# Cobertura de exemplos\n\n");
+    md.push_str(&format!(
+        "| Sala | Tópicos | Com exemplos | {columns} |\n|---|--:|--:|{align}\n"
+    ));
+    for room in &coverage.rooms {
+        let counts: Vec<String> = coverage
+            .languages
+            .iter()
+            .map(|id| room.per_language[id].to_string())
+            .collect();
+        md.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            cell(&room.name),
+            room.topics.len(),
+            room.with_examples,
+            counts.join(" | ")
+        ));
+    }
+
+    for room in coverage.rooms.iter().filter(|r| !r.topics.is_empty()) {
+        md.push_str(&format!("\n## {}\n\n", room.name));
+        md.push_str(&format!(
+            "| Tópico | {columns} | Avisos |\n|---|{align}---|\n"
+        ));
+        for topic in &room.topics {
+            let marks: Vec<&str> = coverage
+                .languages
+                .iter()
+                .map(|id| {
+                    if topic.languages.contains(id) {
+                        "✅"
+                    } else {
+                        "—"
+                    }
+                })
+                .collect();
+            let mut warnings = Vec::new();
+            if topic.empty_tabs() {
+                warnings.push("`<CodeTabs />` sem `examples/`".to_string());
+            }
+            for file in &topic.unknown {
+                warnings.push(format!("`{file}` sem linguagem"));
+            }
+            md.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                topic.slug,
+                marks.join(" | "),
+                cell(&warnings.join("; "))
+            ));
+        }
+    }
+    md
+}
+
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
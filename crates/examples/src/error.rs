@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Content(#[from] themelion_content::Error),
+
+    #[error("Erro ao acessar {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{file}:{line}: cabeçalho Cargo.toml inválido: {message}")]
+    Header {
+        file: String,
+        line: usize,
+        message: String,
+    },
+
+    #[error("{file}:{line}: Rust inválido: {message}")]
+    Parse {
+        file: String,
+        line: usize,
+        message: String,
+    },
+
+    #[error("{file}: lista \"{marker}\" não encontrada ou sem itens")]
+    Checklist { file: String, marker: String },
+
+    #[error("Erro ao gerar o zip de {topic}: {source}")]
+    Zip {
+        topic: String,
+        source: zip::result::ZipError,
+    },
+
+    #[error(
+        "{} não foi gerado por `themelion-examples export`; escolha outro destino",
+        path.display()
+    )]
+    NotExported { path: PathBuf },
+
+    #[error("Cargo indisponível: {0}")]
+    Cargo(String),
+}
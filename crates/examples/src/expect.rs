@@ -0,0 +1,375 @@
+//! Confere os comentários de saída esperada dos exemplos:
+//!
+//! ```text
+//! println!("Bytes: {}", texto.len());          // 17
+//! println!("Clássicos: {:?}", classicos);
+//! // Clássicos: ["Dom Casmurro"]
+//! ```
+//!
+//! Cada `println!` é trocado por uma macro que imprime um marcador antes da
+//! saída, então cada linha de stdout é atribuída ao `println!` que a gerou,
+//! mesmo em laços e `match`. A troca acontece dentro da mesma linha, então os
+//! números de linha do exemplo continuam valendo.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::lexer::{self, Kind, Span};
+
+const MACRO: &str = "__themelion_expect";
+const MARKER: &str = "\u{1}themelion-expect:";
+
+/// Saída afirmada por um comentário.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Claim {
+    /// Índice do `println!` no arquivo (o mesmo impresso no marcador).
+    pub id: usize,
+    /// Linha do `println!`.
+    pub line: usize,
+    /// Linha do comentário.
+    pub comment_line: usize,
+    /// Uma linha por linha de comentário.
+    pub expected: Vec<String>,
+}
+
+/// Fonte com os `println!` instrumentados e as afirmações encontradas.
+#[derive(Debug, Clone)]
+pub struct Instrumented {
+    pub source: String,
+    pub claims: Vec<Claim>,
+}
+
+/// Troca `println!(` por `__themelion_expect!(<id>, ` e coleta o comentário
+/// de cada um: o que vem depois do `;` na mesma linha ou, se não houver, as
+/// linhas só de comentário logo abaixo.
+pub fn instrument(source: &str) -> Instrumented {
+    let spans = lexer::scan(source);
+    let mask = lexer::code_mask(source, &spans);
+    let lines = LineIndex::new(source);
+    let bytes = source.as_bytes();
+
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let mut claims = Vec::new();
+    let mut id = 0;
+
+    let mut from = 0;
+    while let Some(found) = source[from..].find("println!") {
+        let at = from + found;
+        from = at + "println!".len();
+        let prev = at.checked_sub(1).map(|p| bytes[p]);
+        if !mask[at] || lexer::is_ident_byte(prev, false) {
+            continue;
+        }
+        let Some(open) = next_code_byte(bytes, &mask, from).filter(|&o| bytes[o] == b'(') else {
+            continue;
+        };
+        let Some(close) = matching_paren(bytes, &mask, open) else {
+            continue;
+        };
+
+        edits.push((at, at + "println!".len(), format!("{MACRO}!")));
+        edits.push((open + 1, open + 1, format!("{id}, ")));
+
+        let end_line = lines.line_of(close);
+        let claim = trailing_comment(source, &spans, &lines, close, end_line)
+            .map(|(line, text)| (line, vec![text]))
+            .or_else(|| following_comments(source, &spans, &lines, end_line));
+        if let Some((comment_line, expected)) = claim {
+            claims.push(Claim {
+                id,
+                line: lines.line_of(at),
+                comment_line,
+                expected,
+            });
+        }
+        id += 1;
+    }
+
+    if id > 0
+        && let Some(first) = first_item(source, &mask, &lines)
+    {
+        // Definida na mesma linha do primeiro item para não deslocar as linhas
+        let definition = format!(
+            "macro_rules! {MACRO} {{ ($id:expr, $($arg:tt)*) => {{{{ \
+             println!(\"\\u{{1}}themelion-expect:{{}}\", $id); println!($($arg)*); }}}}; }} "
+        );
+        edits.push((first, first, definition));
+    }
+
+    edits.sort_by_key(|(start, _, _)| *start);
+    let mut out = String::with_capacity(source.len() + edits.len() * 16);
+    let mut last = 0;
+    for (start, end, text) in edits {
+        out.push_str(&source[last..start]);
+        out.push_str(&text);
+        last = end;
+    }
+    out.push_str(&source[last..]);
+
+    Instrumented {
+        source: out,
+        claims,
+    }
+}
+
+/// Linhas de stdout de cada execução de cada `println!`: id → execuções.
+pub fn segments(stdout: &str) -> HashMap<usize, Vec<Vec<String>>> {
+    let mut segments: HashMap<usize, Vec<Vec<String>>> = HashMap::new();
+    let mut current: Option<usize> = None;
+
+    for line in stdout.lines() {
+        let (text, marker) = match line.find(MARKER) {
+            Some(pos) => (&line[..pos], line[pos + MARKER.len()..].parse().ok()),
+            None => (line, None),
+        };
+        // Texto antes do marcador vem de um `print!` sem quebra de linha
+        if !(marker.is_some() && text.is_empty())
+            && let Some(id) = current
+            && let Some(lines) = segments.get_mut(&id).and_then(|s| s.last_mut())
+        {
+            lines.push(text.to_string());
+        }
+        if let Some(id) = marker {
+            segments.entry(id).or_default().push(Vec::new());
+            current = Some(id);
+        }
+    }
+    segments
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ClaimStatus {
+    Match,
+    Mismatch,
+    /// O `println!` não rodou (outro braço do `match`, ou o programa parou antes).
+    NotExecuted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimReport {
+    pub line: usize,
+    pub comment_line: usize,
+    pub status: ClaimStatus,
+    pub expected: Vec<String>,
+    pub actual: Vec<String>,
+}
+
+/// Compara a afirmação com as execuções do `println!`; basta uma bater.
+///
+/// Cada linha esperada precisa aparecer na linha de saída correspondente.
+/// Aspas em volta do valor (`// "https"`) são opcionais, `...` vale como
+/// curinga e linhas de comentário além do que o `println!` imprimiu são
+/// tratadas como explicação, não como saída.
+pub fn compare(claim: &Claim, runs: Option<&Vec<Vec<String>>>) -> ClaimReport {
+    let mut report = ClaimReport {
+        line: claim.line,
+        comment_line: claim.comment_line,
+        status: ClaimStatus::NotExecuted,
+        expected: claim.expected.clone(),
+        actual: Vec::new(),
+    };
+    let Some(runs) = runs.filter(|r| !r.is_empty()) else {
+        return report;
+    };
+
+    let matches = |actual: &[String]| {
+        if let [expected] = claim.expected.as_slice() {
+            return actual.iter().any(|line| fragment_matches(expected, line));
+        }
+        !actual.is_empty()
+            && claim
+                .expected
+                .iter()
+                .zip(actual)
+                .all(|(expected, line)| fragment_matches(expected.trim(), line.trim()))
+    };
+
+    match runs.iter().find(|run| matches(run)) {
+        Some(run) => {
+            report.status = ClaimStatus::Match;
+            report.actual = run.clone();
+        }
+        None => {
+            report.status = ClaimStatus::Mismatch;
+            report.actual = runs[0].clone();
+        }
+    }
+    report
+}
+
+fn fragment_matches(expected: &str, actual: &str) -> bool {
+    let mut candidates = vec![expected];
+    if let Some((quoted, inner)) = quoted_prefix(expected) {
+        candidates.push(quoted);
+        candidates.push(inner);
+    }
+    candidates
+        .into_iter()
+        .any(|candidate| wildcard_contains(candidate, actual))
+}
+
+/// `"valor" comentário` → (`"valor"`, `valor`).
+fn quoted_prefix(text: &str) -> Option<(&str, &str)> {
+    let quote = text.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let bytes = text.as_bytes();
+    let close = (1..bytes.len()).find(|&i| bytes[i] == quote as u8 && bytes[i - 1] != b'\\')?;
+    Some((&text[..=close], &text[1..close]))
+}
+
+/// `expected` aparece em `actual`, com `...`/`…` casando qualquer trecho. As
+/// pontas não podem cortar uma palavra ou número: `// 1` não casa com `17`.
+fn wildcard_contains(expected: &str, actual: &str) -> bool {
+    let parts: Vec<&str> = expected.split("...").flat_map(|p| p.split('…')).collect();
+    let last = parts.len() - 1;
+    let mut from = 0;
+    for (index, part) in parts.iter().enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        let found = actual[from..]
+            .match_indices(part)
+            .map(|(pos, _)| from + pos)
+            .find(|&pos| {
+                let end = pos + part.len();
+                (index > 0 || !cuts_word(actual[..pos].chars().next_back(), part.chars().next()))
+                    && (index < last
+                        || !cuts_word(actual[end..].chars().next(), part.chars().next_back()))
+            });
+        match found {
+            Some(pos) => from = pos + part.len(),
+            None => return false,
+        }
+    }
+    true
+}
+
+fn cuts_word(outside: Option<char>, edge: Option<char>) -> bool {
+    matches!((outside, edge), (Some(o), Some(e)) if o.is_alphanumeric() && e.is_alphanumeric())
+}
+
+/// `// ...` depois do fim do `println!`, na mesma linha.
+fn trailing_comment(
+    source: &str,
+    spans: &[Span],
+    lines: &LineIndex,
+    close: usize,
+    line: usize,
+) -> Option<(usize, String)> {
+    let span = spans.iter().find(|s| {
+        s.kind == Kind::LineComment && s.range.start > close && lines.line_of(s.range.start) == line
+    })?;
+    let text = comment_text(&source[span.range.clone()]).trim().to_string();
+    (!text.is_empty()).then_some((line, text))
+}
+
+/// Linhas só de comentário logo abaixo de `line`, até uma linha vazia ou de código.
+fn following_comments(
+    source: &str,
+    spans: &[Span],
+    lines: &LineIndex,
+    line: usize,
+) -> Option<(usize, Vec<String>)> {
+    let mut expected = Vec::new();
+    let mut number = line + 1;
+    while let Some(range) = lines.range(source, number) {
+        let text = &source[range.clone()];
+        let indent = text.len() - text.trim_start().len();
+        let is_comment = spans
+            .iter()
+            .any(|s| s.kind == Kind::LineComment && s.range.start == range.start + indent);
+        if !is_comment {
+            break;
+        }
+        let body = comment_text(text.trim()).trim_end();
+        if body.trim().is_empty() {
+            break;
+        }
+        expected.push(body.to_string());
+        number += 1;
+    }
+    (!expected.is_empty()).then_some((line + 1, expected))
+}
+
+/// Texto de `// texto`, sem o `//` e sem o espaço seguinte.
+fn comment_text(comment: &str) -> &str {
+    let text = comment.trim_start_matches('/');
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+fn next_code_byte(bytes: &[u8], mask: &[bool], mut i: usize) -> Option<usize> {
+    while i < bytes.len() {
+        if mask[i] && !bytes[i].is_ascii_whitespace() {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+fn matching_paren(bytes: &[u8], mask: &[bool], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for i in open..bytes.len() {
+        if !mask[i] {
+            continue;
+        }
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Início do primeiro item de código, depois de comentários e de `#![...]`.
+fn first_item(source: &str, mask: &[bool], lines: &LineIndex) -> Option<usize> {
+    (1..=lines.len()).find_map(|number| {
+        let range = lines.range(source, number)?;
+        let text = &source[range.clone()];
+        let indent = text.len() - text.trim_start().len();
+        let start = range.start + indent;
+        let is_item = start < range.end && mask[start] && !text.trim_start().starts_with("#!");
+        is_item.then_some(start)
+    })
+}
+
+/// Início de cada linha, para converter byte ↔ linha (1-based).
+struct LineIndex {
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(source: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        Self { starts }
+    }
+
+    fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    fn line_of(&self, byte: usize) -> usize {
+        self.starts.partition_point(|&start| start <= byte)
+    }
+
+    /// Bytes da linha, sem o `\n` (nem `\r`).
+    fn range(&self, source: &str, line: usize) -> Option<std::ops::Range<usize>> {
+        let start = *self.starts.get(line.checked_sub(1)?)?;
+        let end = self.starts.get(line).map_or(source.len(), |&next| next - 1);
+        let end = if source[start..end].ends_with('\r') {
+            end - 1
+        } else {
+            end
+        };
+        Some(start..end)
+    }
+}
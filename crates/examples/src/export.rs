@@ -0,0 +1,291 @@
+//! Os `examples/rust.rs` como projetos Cargo de verdade, para quem quer
+//! rodar o exemplo fora do site:
+//!
+//! ```text
+//! <workspace>/Cargo.toml                  [workspace] com um membro por tópico
+//! <workspace>/<sala>/<slug>/Cargo.toml    pacote `example-<sala>-<slug>`, dependências do cabeçalho
+//! <workspace>/<sala>/<slug>/src/main.rs
+//! <zips>/<sala>/<slug>.zip                o mesmo crate, avulso, dentro de `<slug>/`
+//! ```
+//!
+//! Tudo vai por sala porque o mesmo slug existe em mais de uma.
+//!
+//! O zip é o que o botão "Baixar projeto Cargo" do site entrega; o crate de
+//! dentro declara o próprio `[workspace]` e compila onde for aberto. As datas
+//! dentro do zip são fixas, então um exemplo que não mudou gera os mesmos
+//! bytes e o arquivo não é regravado.
+//!
+//! O `Cargo.toml` do workspace começa com o comentário de [`MARKER`]: um
+//! destino que já tem outro `Cargo.toml` é recusado, e só são apagadas as
+//! pastas de dentro dele com um pacote `example-<sala>-<slug>` que não é mais
+//! membro.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use themelion_content::output::WriteStats;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::header::{self, Header};
+use crate::project::{manifest, package_name, write_if_changed};
+use crate::{Error, Example, Result};
+
+const GITIGNORE: &str = "/target\n";
+
+/// Primeira linha do `Cargo.toml` de um workspace gerado.
+const MARKER: &str = "# Gerado por `themelion-examples export`; não edite à mão.";
+
+/// Título de cada tópico por (sala, slug), para os READMEs; sem título, vale
+/// o slug.
+pub type Titles = HashMap<(String, String), String>;
+
+fn title<'a>(titles: &'a Titles, example: &'a Example) -> &'a str {
+    titles
+        .get(&(example.room.clone(), example.topic.clone()))
+        .unwrap_or(&example.topic)
+}
+
+/// Nome do crate: único no workspace mesmo com slugs repetidos entre salas.
+fn crate_name(example: &Example) -> String {
+    format!("{}-{}", example.room, example.topic)
+}
+
+/// Pasta do crate, relativa ao workspace (ou do zip, relativa aos zips).
+fn crate_dir(example: &Example) -> String {
+    format!("{}/{}", example.room, example.topic)
+}
+
+/// Arquivos de um crate, relativos à pasta dele.
+fn crate_files(
+    example: &Example,
+    header: &Header,
+    title: &str,
+    standalone: bool,
+) -> Vec<(&'static str, String)> {
+    let package = package_name(&crate_name(example));
+    let run = if standalone {
+        "cargo run".to_string()
+    } else {
+        format!("cargo run -p {package}")
+    };
+    let readme = format!(
+        "# {title}\n\nExemplo em Rust do tópico `{}` do Themelion, gerado a partir de \
+         `{}`.\n\n```sh\n{run}\n```\n",
+        example.topic, example.file
+    );
+    let mut files = vec![
+        (
+            "Cargo.toml",
+            manifest(&crate_name(example), header, standalone),
+        ),
+        ("src/main.rs", example.source.clone()),
+        ("README.md", readme),
+    ];
+    if standalone {
+        files.push((".gitignore", GITIGNORE.to_string()));
+    }
+    files
+}
+
+/// Grava o workspace em `dir`.
+pub fn write_workspace(examples: &[Example], titles: &Titles, dir: &Path) -> Result<WriteStats> {
+    let root_manifest = dir.join("Cargo.toml");
+    if let Ok(current) = fs::read_to_string(&root_manifest)
+        && !current.starts_with(MARKER)
+    {
+        return Err(Error::NotExported {
+            path: root_manifest,
+        });
+    }
+
+    let mut stats = WriteStats::default();
+    let mut write = |path: PathBuf, content: &str| -> Result<()> {
+        if write_if_changed(&path, content)? {
+            stats.written += 1;
+        } else {
+            stats.unchanged += 1;
+        }
+        Ok(())
+    };
+
+    let mut members = Vec::new();
+    for example in examples {
+        let header = header::parse(&example.file, &example.source)?;
+        let title = title(titles, example);
+        for (file, content) in crate_files(example, &header, title, false) {
+            write(dir.join(crate_dir(example)).join(file), &content)?;
+        }
+        members.push(crate_dir(example));
+    }
+
+    let list: String = members.iter().map(|m| format!("    \"{m}\",\n")).collect();
+    write(
+        dir.join("Cargo.toml"),
+        &format!("{MARKER}\n[workspace]\nresolver = \"2\"\nmembers = [\n{list}]\n"),
+    )?;
+    write(dir.join(".gitignore"), GITIGNORE)?;
+
+    let rows: String = examples
+        .iter()
+        .map(|e| {
+            let title = title(titles, e);
+            format!("| {title} | `{}` |\n", package_name(&crate_name(e)))
+        })
+        .collect();
+    write(
+        dir.join("README.md"),
+        &format!(
+            "# Exemplos do Themelion em Rust\n\nUm crate por tópico, com as dependências do \
+             cabeçalho `// Cargo.toml:` de cada `examples/rust.rs`.\n\n```sh\n\
+             cargo run -p example-<sala>-<tópico>\n```\n\n| Tópico | Pacote |\n|---|---|\n{rows}"
+        ),
+    )?;
+
+    // Crates de tópicos que perderam o exemplo, nas pastas de sala
+    let kept: HashSet<&String> = members.iter().collect();
+    for room in read_dir(dir)? {
+        let Some(room_name) = file_name(&room) else {
+            continue;
+        };
+        if !is_real_dir(&room) {
+            continue;
+        }
+        // Crate do layout antigo, `<workspace>/<slug>/`
+        if is_generated_crate(&room, &room_name) {
+            remove_dir(&room)?;
+            stats.removed += 1;
+            continue;
+        }
+        let mut removed = false;
+        for path in read_dir(&room)? {
+            let Some(name) = file_name(&path) else {
+                continue;
+            };
+            if !kept.contains(&format!("{room_name}/{name}"))
+                && is_generated_crate(&path, &format!("{room_name}-{name}"))
+            {
+                remove_dir(&path)?;
+                stats.removed += 1;
+                removed = true;
+            }
+        }
+        // A sala some junto com o último crate; se sobrou outra coisa, fica
+        if removed {
+            let _ = fs::remove_dir(&room);
+        }
+    }
+    Ok(stats)
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name().map(|n| n.to_string_lossy().into_owned())
+}
+
+/// Pasta de verdade, não symlink, que levaria para fora do workspace.
+fn is_real_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|meta| meta.is_dir())
+}
+
+fn remove_dir(path: &Path) -> Result<()> {
+    fs::remove_dir_all(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Pasta de verdade com o pacote `example-<nome>` que [`write_workspace`] gera.
+fn is_generated_crate(path: &Path, name: &str) -> bool {
+    let package = fs::read_to_string(path.join("Cargo.toml"))
+        .ok()
+        .and_then(|text| text.parse::<toml::Table>().ok())
+        .and_then(|manifest| {
+            let name = manifest.get("package")?.get("name")?.as_str()?;
+            Some(name.to_string())
+        });
+    is_real_dir(path) && package.is_some_and(|package| package == package_name(name))
+}
+
+/// Grava `<dir>/<sala>/<slug>.zip` para cada exemplo e apaga os zips de
+/// tópicos que não têm mais exemplo.
+pub fn write_zips(examples: &[Example], titles: &Titles, dir: &Path) -> Result<WriteStats> {
+    let mut stats = WriteStats::default();
+    let mut kept = HashSet::new();
+    for example in examples {
+        let path = dir.join(format!("{}.zip", crate_dir(example)));
+        if write_if_changed(&path, zip(example, titles)?)? {
+            stats.written += 1;
+        } else {
+            stats.unchanged += 1;
+        }
+        kept.insert(path);
+    }
+
+    // Os zips do layout antigo, sem sala, ficam na raiz e também saem
+    let rooms = read_dir(dir)?.into_iter().filter(|path| is_real_dir(path));
+    for room in rooms.collect::<Vec<_>>() {
+        for path in read_dir(&room)? {
+            if prune_zip(&path, &kept)? {
+                stats.removed += 1;
+            }
+        }
+        let _ = fs::remove_dir(&room);
+    }
+    for path in read_dir(dir)? {
+        if prune_zip(&path, &kept)? {
+            stats.removed += 1;
+        }
+    }
+    Ok(stats)
+}
+
+fn prune_zip(path: &Path, kept: &HashSet<PathBuf>) -> Result<bool> {
+    if path.extension().is_none_or(|e| e != "zip") || kept.contains(path) {
+        return Ok(false);
+    }
+    fs::remove_file(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(true)
+}
+
+/// O crate avulso do exemplo, dentro de uma pasta `<slug>/`.
+pub fn zip(example: &Example, titles: &Titles) -> Result<Vec<u8>> {
+    let header = header::parse(&example.file, &example.source)?;
+    let title = title(titles, example);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(zip::DateTime::default())
+        .unix_permissions(0o644);
+
+    let error = |source| Error::Zip {
+        topic: example.topic.clone(),
+        source,
+    };
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (file, content) in crate_files(example, &header, title, true) {
+        zip.start_file(format!("{}/{file}", example.topic), options)
+            .map_err(error)?;
+        zip.write_all(content.as_bytes())
+            .map_err(|source| error(source.into()))?;
+    }
+    Ok(zip.finish().map_err(error)?.into_inner())
+}
+
+/// Entradas de `dir`; nenhuma se ele ainda não existe.
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    entries
+        .map(|entry| {
+            entry.map(|e| e.path()).map_err(|source| Error::Io {
+                path: dir.to_path_buf(),
+                source,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+//! Dependências declaradas no cabeçalho de comentários de um `rust.rs`:
+//!
+//! ```text
+//! // Cargo.toml:
+//! // [dependencies]
+//! // serde = { version = "1", features = ["derive"] }
+//! // serde_json = "1"
+//! ```
+
+use crate::{Error, Result};
+
+/// Trecho de `Cargo.toml` copiado do cabeçalho, já validado como TOML.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Header {
+    /// Linha (1-based) do `// Cargo.toml:`, se houver.
+    pub line: Option<usize>,
+    pub manifest: String,
+    /// Nomes das dependências declaradas.
+    pub dependencies: Vec<String>,
+}
+
+/// Lê o bloco `// Cargo.toml:` do começo do arquivo. Exemplos sem o bloco
+/// usam só a biblioteca padrão e recebem um [`Header`] vazio.
+pub fn parse(file: &str, source: &str) -> Result<Header> {
+    let mut lines = source.lines().enumerate();
+
+    // O cabeçalho termina na primeira linha que não é comentário
+    let Some((start, _)) = lines
+        .by_ref()
+        .take_while(|(_, line)| line.trim_start().starts_with("//"))
+        .find(|(_, line)| comment(line).is_some_and(|c| c.eq_ignore_ascii_case("cargo.toml:")))
+    else {
+        return Ok(Header::default());
+    };
+
+    let mut manifest = String::new();
+    for (_, line) in source.lines().enumerate().skip(start + 1) {
+        let Some(text) = comment(line) else { break };
+        // Fim do bloco: linha vazia ou separador `// ====`
+        if text.is_empty() || text.starts_with("==") || text.starts_with("--") {
+            break;
+        }
+        manifest.push_str(text);
+        manifest.push('\n');
+    }
+
+    let table: toml::Table = toml::from_str(&manifest).map_err(|err| Error::Header {
+        file: file.to_string(),
+        line: start + 1,
+        message: err.message().to_string(),
+    })?;
+    let dependencies = table
+        .get("dependencies")
+        .and_then(|deps| deps.as_table())
+        .map(|deps| deps.keys().cloned().collect())
+        .unwrap_or_default();
+
+    Ok(Header {
+        line: Some(start + 1),
+        manifest,
+        dependencies,
+    })
+}
+
+/// Texto de um comentário de linha, sem o `//` e sem espaços nas pontas.
+fn comment(line: &str) -> Option<&str> {
+    line.trim_start().strip_prefix("//").map(str::trim)
+}
@@ -0,0 +1,175 @@
+//! Lexer mínimo de Rust: separa comentários e literais (strings e chars) do
+//! resto do código, o suficiente para achar macros e comentários sem se
+//! confundir com `//` dentro de uma string ou `println!` dentro de um
+//! comentário.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// `// ...` até o fim da linha (sem o `\n`), incluindo `///` e `//!`.
+    LineComment,
+    /// `/* ... */`, com aninhamento.
+    BlockComment,
+    /// `"..."`, `r#"..."#`, `b"..."` e afins, a partir das aspas (ou do `r`).
+    Str,
+    /// `'a'`, `'\n'`, `b'x'` (sem o `b`).
+    Char,
+}
+
+/// Trecho que não é código.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub kind: Kind,
+    pub range: Range<usize>,
+}
+
+/// Comentários e literais de `source`, em ordem. Tudo fora deles é código.
+pub fn scan(source: &str) -> Vec<Span> {
+    let bytes = source.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                Kind::LineComment
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = block_comment_end(bytes, i);
+                Kind::BlockComment
+            }
+            b'"' => {
+                i = string_end(bytes, i + 1);
+                Kind::Str
+            }
+            b'r' if !is_ident_byte(prev(bytes, i), true) => match raw_string_end(bytes, i + 1) {
+                Some(end) => {
+                    i = end;
+                    Kind::Str
+                }
+                None => {
+                    i += 1;
+                    continue;
+                }
+            },
+            b'\'' => match char_end(source, i) {
+                Some(end) => {
+                    i = end;
+                    Kind::Char
+                }
+                // Lifetime ou label
+                None => {
+                    i += 1;
+                    continue;
+                }
+            },
+            _ => {
+                i += source[i..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            }
+        };
+        spans.push(Span {
+            kind,
+            range: start..i,
+        });
+    }
+
+    spans
+}
+
+/// `true` para cada byte que é código (fora de comentários e literais).
+pub fn code_mask(source: &str, spans: &[Span]) -> Vec<bool> {
+    let mut mask = vec![true; source.len()];
+    for span in spans {
+        mask[span.range.clone()].fill(false);
+    }
+    mask
+}
+
+pub fn is_ident_byte(byte: Option<u8>, allow_prefix: bool) -> bool {
+    match byte {
+        // `br"..."` e `cr"..."`: o `b`/`c` é prefixo, não identificador
+        Some(b'b' | b'c') if allow_prefix => false,
+        Some(b) => b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80,
+        None => false,
+    }
+}
+
+fn prev(bytes: &[u8], i: usize) -> Option<u8> {
+    i.checked_sub(1).map(|p| bytes[p])
+}
+
+fn block_comment_end(bytes: &[u8], mut i: usize) -> usize {
+    let mut depth = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// Fim (exclusivo) de uma string cujo conteúdo começa em `i`.
+fn string_end(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// `r"..."`/`r#"..."#` a partir do byte após o `r`; `None` se não for raw string.
+fn raw_string_end(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let hashes = bytes[i..].iter().take_while(|&&b| b == b'#').count();
+    i += hashes;
+    if bytes.get(i) != Some(&b'"') {
+        return None;
+    }
+    i += 1;
+    while i < bytes.len() {
+        if bytes[i] == b'"'
+            && bytes[i + 1..].len() >= hashes
+            && bytes[i + 1..i + 1 + hashes].iter().all(|&b| b == b'#')
+        {
+            return Some(i + 1 + hashes);
+        }
+        i += 1;
+    }
+    Some(bytes.len())
+}
+
+/// Fim de um literal de char começando no `'` em `i`; `None` para lifetimes.
+fn char_end(source: &str, i: usize) -> Option<usize> {
+    let rest = &source[i + 1..];
+    let mut chars = rest.char_indices();
+    match chars.next()? {
+        (_, '\\') => {
+            // Escape (`\'`, `\n`, `\u{..}`): pula o caractere escapado e vai
+            // até o próximo `'`
+            let escaped = rest[1..].chars().next()?;
+            let from = 1 + escaped.len_utf8();
+            let close = rest[from..].find('\'')?;
+            Some(i + 1 + from + close + 1)
+        }
+        (_, '\'') => None,
+        (_, c) => {
+            let after = c.len_utf8();
+            rest[after..].starts_with('\'').then(|| i + 1 + after + 1)
+        }
+    }
+}
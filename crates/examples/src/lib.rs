@@ -0,0 +1,268 @@
+//! Harness dos `examples/rust.rs`: cada exemplo vira um projeto Cargo
+//! descartável com as dependências do cabeçalho `// Cargo.toml:`, é compilado
+//! (por padrão offline) e executado com os limites do `rust-runner`.
+//! [`coverage`] cruza os `examples/` de todos os tópicos com as linguagens;
+//! [`parity`] confere se os arquivos de um tópico cobrem as mesmas seções;
+//! [`export`] empacota os exemplos como workspace Cargo e zips para download;
+//! [`annotate`] gera os selos de complexidade, posse e pânico de cada linha;
+//! [`audit`] cobra um comentário em cada `unwrap()`, `unsafe` e afins;
+//! [`review`] roda os checklists de revisão de código sobre cada exemplo.
+
+pub mod annotate;
+pub mod audit;
+pub mod coverage;
+mod error;
+pub mod expect;
+pub mod export;
+pub mod header;
+pub mod lexer;
+pub mod parity;
+pub mod project;
+pub mod review;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use themelion_content::{find_topic_files, relative_path};
+
+use crate::expect::{ClaimReport, ClaimStatus};
+
+pub use error::Error;
+pub use project::Workspace;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Nome do arquivo de exemplo em Rust dentro de `examples/`.
+pub const EXAMPLE_FILE: &str = "rust.rs";
+
+/// Um `examples/rust.rs` e o tópico a que pertence.
+#[derive(Debug, Clone)]
+pub struct Example {
+    /// Sala do tópico (primeiro diretório sob `data/content/`).
+    pub room: String,
+    /// Slug do tópico (nome do diretório acima de `examples/`).
+    pub topic: String,
+    /// Caminho relativo à raiz, com `/`.
+    pub file: String,
+    pub path: PathBuf,
+    pub source: String,
+}
+
+/// Todos os exemplos em Rust sob `data/content`, na ordem dos tópicos.
+pub fn find_examples(root: &Path) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for topic_file in find_topic_files(root)? {
+        let path = topic_file.with_file_name("examples").join(EXAMPLE_FILE);
+        if !path.is_file() {
+            continue;
+        }
+        let topic = topic_file
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        // `data/content/<sala>/<categoria>/<slug>/topic.mdx`
+        let room = topic_file
+            .strip_prefix(root.join("data/content"))
+            .ok()
+            .and_then(|rel| rel.iter().next())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let source = fs::read_to_string(&path).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+        examples.push(Example {
+            room,
+            topic,
+            file: relative_path(root, &path),
+            path,
+            source,
+        });
+    }
+    Ok(examples)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+    Passed,
+    InvalidHeader,
+    BuildFailed,
+    RunFailed,
+    TimedOut,
+}
+
+/// Resultado de um exemplo.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub topic: String,
+    pub file: String,
+    pub dependencies: Vec<String>,
+    pub status: Status,
+    /// Resumo do problema: erros do compilador, código de saída ou sinal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub build_ms: Option<u64>,
+    pub run_ms: Option<u64>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Report {
+    fn new(example: &Example, status: Status) -> Self {
+        Self {
+            topic: example.topic.clone(),
+            file: example.file.clone(),
+            dependencies: Vec::new(),
+            status,
+            message: None,
+            build_ms: None,
+            run_ms: None,
+            stdout: String::new(),
+            stderr: String::new(),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.status == Status::Passed
+    }
+}
+
+/// Gera o projeto, compila e, se `run`, executa o exemplo.
+pub fn check(workspace: &Workspace, example: &Example, run: bool) -> Result<Report> {
+    build_and_run(workspace, example, &example.topic, &example.source, run)
+}
+
+/// Afirmações de saída de um exemplo, conferidas contra a execução real.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectReport {
+    pub topic: String,
+    pub file: String,
+    /// Resultado da compilação e execução do exemplo instrumentado.
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub claims: Vec<ClaimReport>,
+}
+
+impl ExpectReport {
+    pub fn mismatches(&self) -> usize {
+        self.claims
+            .iter()
+            .filter(|c| c.status == ClaimStatus::Mismatch)
+            .count()
+    }
+}
+
+/// Executa o exemplo com os `println!` instrumentados e compara cada
+/// comentário de saída esperada com o que foi impresso.
+pub fn expect(workspace: &Workspace, example: &Example) -> Result<ExpectReport> {
+    let instrumented = expect::instrument(&example.source);
+    let name = format!("{}-expect", example.topic);
+    let run = build_and_run(workspace, example, &name, &instrumented.source, true)?;
+
+    let mut report = ExpectReport {
+        topic: example.topic.clone(),
+        file: example.file.clone(),
+        status: run.status,
+        message: run.message,
+        claims: Vec::new(),
+    };
+    // Se o programa rodou (mesmo que tenha parado no meio), o que saiu vale
+    if matches!(
+        run.status,
+        Status::Passed | Status::RunFailed | Status::TimedOut
+    ) {
+        let segments = expect::segments(&run.stdout);
+        report.claims = instrumented
+            .claims
+            .iter()
+            .map(|claim| expect::compare(claim, segments.get(&claim.id)))
+            .collect();
+    }
+    Ok(report)
+}
+
+fn build_and_run(
+    workspace: &Workspace,
+    example: &Example,
+    name: &str,
+    source: &str,
+    run: bool,
+) -> Result<Report> {
+    let header = match header::parse(&example.file, &example.source) {
+        Ok(header) => header,
+        Err(err @ Error::Header { .. }) => {
+            let mut report = Report::new(example, Status::InvalidHeader);
+            report.message = Some(err.to_string());
+            return Ok(report);
+        }
+        Err(err) => return Err(err),
+    };
+
+    let mut report = Report::new(example, Status::Passed);
+    report.dependencies = header.dependencies.clone();
+
+    let project = workspace.prepare(name, &header, source)?;
+    let build = workspace.build(&project)?;
+    report.build_ms = Some(build.duration.as_millis() as u64);
+    if !build.success {
+        report.status = Status::BuildFailed;
+        report.message = Some(compiler_errors(&build.stderr, &example.file));
+        report.stderr = build.stderr;
+        return Ok(report);
+    }
+    if !run {
+        return Ok(report);
+    }
+
+    let output = workspace.run(name)?;
+    report.run_ms = Some(output.duration.as_millis() as u64);
+    if output.timed_out || output.cpu_exceeded() {
+        report.status = Status::TimedOut;
+        report.message = Some("tempo limite excedido".to_string());
+    } else if !output.success() {
+        report.status = Status::RunFailed;
+        report.message = Some(match (output.exit_code, output.signal) {
+            (Some(code), _) => format!("saiu com código {code}"),
+            (None, Some(signal)) => format!("encerrado pelo sinal {signal}"),
+            (None, None) => "encerrado sem código de saída".to_string(),
+        });
+        // `thread 'main' panicked at src/main.rs:L:C:` e a mensagem na linha seguinte
+        let lines: Vec<&str> = output.stderr.lines().collect();
+        if let Some(at) = lines.iter().position(|l| l.contains("panicked at")) {
+            let panic = lines[at..lines.len().min(at + 2)].join(" ");
+            report.message = report.message.map(|m| {
+                format!(
+                    "{m}: {}",
+                    panic.replace("src/main.rs:", &format!("{}:", example.file))
+                )
+            });
+        }
+    }
+    report.stdout = output.stdout;
+    report.stderr = output.stderr;
+    Ok(report)
+}
+
+/// Linhas `error...` do `--message-format=short`, apontando para o arquivo do
+/// exemplo, ou o stderr inteiro se o Cargo falhou antes de chegar ao compilador.
+fn compiler_errors(stderr: &str, file: &str) -> String {
+    let errors: Vec<String> = stderr
+        .lines()
+        .filter(|line| line.starts_with("error") || line.contains(": error"))
+        .map(|line| match line.strip_prefix("src/main.rs:") {
+            Some(rest) => format!("{file}:{rest}"),
+            None => line.to_string(),
+        })
+        .collect();
+    if errors.is_empty() {
+        stderr.trim().to_string()
+    } else {
+        errors.join("\n")
+    }
+}
@@ -0,0 +1,507 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use themelion_content::Content;
+use themelion_examples::annotate::{self, ExampleAnnotations};
+use themelion_examples::audit::{self, AuditReport};
+use themelion_examples::expect::ClaimStatus;
+use themelion_examples::parity::{self, TopicParity};
+use themelion_examples::review::{self, ItemStatus, ReviewReport};
+use themelion_examples::{Example, ExpectReport, Report, Workspace, check, expect, find_examples};
+use themelion_examples::{coverage, export};
+
+/// Compila e executa os examples/rust.rs de cada tópico, mede a cobertura
+/// dos examples/ por linguagem, confere a paridade de seções entre eles,
+/// exporta os exemplos em Rust como projetos Cargo, gera as anotações por
+/// linha deles, audita os pontos de pânico e roda os checklists de revisão.
+#[derive(Parser)]
+#[command(name = "themelion-examples", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".", global = true)]
+    root: PathBuf,
+
+    /// Onde ficam os projetos gerados (padrão: <root>/target/examples)
+    #[arg(long, global = true)]
+    work: Option<PathBuf>,
+
+    /// Permite ao Cargo baixar dependências que não estão no cache local
+    #[arg(long, global = true)]
+    online: bool,
+
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Compila e executa cada exemplo; falha se algum não passar
+    Check {
+        /// Só estes tópicos (slugs); por padrão, todos
+        topics: Vec<String>,
+
+        /// Só compila, sem executar
+        #[arg(long)]
+        no_run: bool,
+    },
+    /// Confere os comentários de saída esperada (`println!(...); // 17`)
+    /// contra a saída real; falha se algum divergir
+    Expect {
+        /// Só estes tópicos (slugs); por padrão, todos
+        topics: Vec<String>,
+
+        /// Lista também as afirmações que bateram
+        #[arg(long)]
+        verbose: bool,
+    },
+    /// Matriz tópico implementado × linguagem, por sala, em Markdown (ou
+    /// JSON com --format json)
+    Coverage,
+    /// Alinha as seções (comentários-faixa) dos examples/ de cada tópico
+    /// entre as linguagens; falha se alguma faltar em algum arquivo
+    Parity {
+        /// Só estes tópicos (slugs); por padrão, todos
+        topics: Vec<String>,
+    },
+    /// Gera um workspace Cargo com um crate por exemplo e um zip avulso de
+    /// cada um para o botão "Baixar projeto Cargo" do site
+    Export {
+        /// Destino do workspace, relativo à raiz
+        #[arg(long, default_value = "target/cargo-examples")]
+        workspace: PathBuf,
+
+        /// Destino dos zips, relativo à raiz (servidos em /examples/<sala>/<slug>.zip)
+        #[arg(long, default_value = "public/examples")]
+        zips: PathBuf,
+    },
+    /// Anotações por trecho de cada exemplo (complexidade dos comentários
+    /// `O(...)`, empréstimos, moves, clones, alocações e pânicos) para os
+    /// selos na margem do código; JSON com --format json
+    Annotate {
+        /// Só estes tópicos (slugs); por padrão, todos
+        topics: Vec<String>,
+    },
+    /// Pontos de pânico, aritmética que pode transbordar e `unsafe` sem
+    /// comentário explicando; falha se algum `unwrap()` ou `unsafe` não tiver
+    Audit {
+        /// Só estes tópicos (slugs); por padrão, todos
+        topics: Vec<String>,
+
+        /// Lista também os pontos que já têm comentário
+        #[arg(long)]
+        verbose: bool,
+    },
+    /// Roda os itens de código dos checklists de data/agents/reviewer.md e
+    /// data/agents/languages/rust.md (cargo fmt, clippy, clones, laços
+    /// manuais...); falha se algum item reprovar
+    Review {
+        /// Só estes tópicos (slugs); por padrão, os com `aiGenerated: true`
+        topics: Vec<String>,
+
+        /// Revisa todos os tópicos, não só os gerados por IA
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Serialize)]
+struct ExpectSummary<'a> {
+    claims: usize,
+    mismatches: usize,
+    examples: &'a [ExpectReport],
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    passed: usize,
+    failed: usize,
+    examples: &'a [Report],
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let work = cli
+        .work
+        .clone()
+        .unwrap_or_else(|| cli.root.join("target/examples"));
+    let workspace = Workspace::new(work, !cli.online);
+
+    let examples = find_examples(&cli.root).context("Erro ao procurar os exemplos")?;
+
+    match &cli.command {
+        Command::Check { topics, no_run } => {
+            let mut reports = Vec::new();
+            for example in select(&examples, topics)? {
+                if matches!(cli.format, Format::Text) {
+                    eprintln!("{} ...", example.topic);
+                }
+                reports.push(check(&workspace, example, !no_run)?);
+            }
+            print_reports(cli.format, &reports)
+        }
+        Command::Expect { topics, verbose } => {
+            let mut reports = Vec::new();
+            for example in select(&examples, topics)? {
+                if matches!(cli.format, Format::Text) {
+                    eprintln!("{} ...", example.topic);
+                }
+                reports.push(expect(&workspace, example)?);
+            }
+            print_expect(cli.format, &reports, *verbose)
+        }
+        Command::Coverage => {
+            let content = Content::scan(&cli.root).context("Erro ao escanear o conteúdo")?;
+            let coverage = coverage::coverage(&content);
+            match cli.format {
+                Format::Text => print!("{}", coverage::markdown(&coverage)),
+                Format::Json => println!("{}", serde_json::to_string_pretty(&coverage)?),
+            }
+            let topics: Vec<_> = coverage.rooms.iter().flat_map(|r| &r.topics).collect();
+            eprintln!(
+                "{} tópicos, {} com exemplos, {} com avisos",
+                topics.len(),
+                topics.iter().filter(|t| !t.languages.is_empty()).count(),
+                topics.iter().filter(|t| t.has_problems()).count()
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Parity { topics } => {
+            let content = Content::scan(&cli.root).context("Erro ao escanear o conteúdo")?;
+            for topic in topics {
+                if !content.topics.iter().any(|t| &t.frontmatter.slug == topic) {
+                    bail!("Tópico \"{topic}\" não encontrado");
+                }
+            }
+            let languages: Vec<String> = content.languages.iter().map(|l| l.id.clone()).collect();
+            let reports: Vec<TopicParity> = content
+                .topics
+                .iter()
+                .filter(|t| topics.is_empty() || topics.contains(&t.frontmatter.slug))
+                .filter_map(|t| parity::parity(t, &languages))
+                .collect();
+            print_parity(cli.format, &reports)
+        }
+        Command::Export { workspace, zips } => {
+            let content = Content::scan(&cli.root).context("Erro ao escanear o conteúdo")?;
+            let titles: export::Titles = content
+                .topics
+                .iter()
+                .map(|t| {
+                    let meta = &t.frontmatter;
+                    ((meta.room.clone(), meta.slug.clone()), meta.title.clone())
+                })
+                .collect();
+
+            let workspace = cli.root.join(workspace);
+            let stats = export::write_workspace(&examples, &titles, &workspace)
+                .context("Erro ao gerar o workspace")?;
+            eprintln!(
+                "{} crates → {} ({} gravados, {} sem mudança, {} removidos)",
+                examples.len(),
+                workspace.display(),
+                stats.written,
+                stats.unchanged,
+                stats.removed
+            );
+
+            let zips = cli.root.join(zips);
+            let stats =
+                export::write_zips(&examples, &titles, &zips).context("Erro ao gerar os zips")?;
+            eprintln!(
+                "{} zips → {} ({} gravados, {} sem mudança, {} removidos)",
+                examples.len(),
+                zips.display(),
+                stats.written,
+                stats.unchanged,
+                stats.removed
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Annotate { topics } => {
+            let mut reports = Vec::new();
+            for example in select(&examples, topics)? {
+                reports.push(annotate::annotate(example)?);
+            }
+            print_annotations(cli.format, &reports)
+        }
+        Command::Audit { topics, verbose } => {
+            let mut reports = Vec::new();
+            for example in select(&examples, topics)? {
+                reports.push(audit::audit(example)?);
+            }
+            print_audit(cli.format, &reports, *verbose)
+        }
+        Command::Review { topics, all } => {
+            let checklist = review::checklist(&cli.root).context("Erro ao ler os checklists")?;
+            let mut selected = select(&examples, topics)?;
+            // Sem tópicos explícitos, o gate vale para o conteúdo gerado por IA
+            if topics.is_empty() && !all {
+                let content = Content::scan(&cli.root).context("Erro ao escanear o conteúdo")?;
+                selected.retain(|e| {
+                    content.topics.iter().any(|t| {
+                        t.frontmatter.slug == e.topic && t.frontmatter.ai_generated == Some(true)
+                    })
+                });
+            }
+            let mut reports = Vec::new();
+            for example in selected {
+                if matches!(cli.format, Format::Text) {
+                    eprintln!("{} ...", example.topic);
+                }
+                reports.push(review::review(&workspace, example, &checklist)?);
+            }
+            print_review(cli.format, &reports)
+        }
+    }
+}
+
+fn select<'a>(examples: &'a [Example], topics: &[String]) -> Result<Vec<&'a Example>> {
+    for topic in topics {
+        if !examples.iter().any(|e| &e.topic == topic) {
+            bail!("Tópico \"{topic}\" não tem examples/rust.rs");
+        }
+    }
+    Ok(examples
+        .iter()
+        .filter(|e| topics.is_empty() || topics.contains(&e.topic))
+        .collect())
+}
+
+fn print_expect(format: Format, reports: &[ExpectReport], verbose: bool) -> Result<ExitCode> {
+    let claims: usize = reports.iter().map(|r| r.claims.len()).sum();
+    let mismatches: usize = reports.iter().map(ExpectReport::mismatches).sum();
+    let broken = reports
+        .iter()
+        .filter(|r| r.claims.is_empty() && r.message.is_some())
+        .count();
+
+    match format {
+        Format::Text => {
+            for report in reports {
+                if let Some(message) = &report.message {
+                    println!("{}: {message}", report.file);
+                }
+                for claim in &report.claims {
+                    let label = match claim.status {
+                        ClaimStatus::Match if !verbose => continue,
+                        ClaimStatus::Match => "ok",
+                        ClaimStatus::Mismatch => "divergente",
+                        ClaimStatus::NotExecuted => "não executado",
+                    };
+                    println!("{}:{}: {label}", report.file, claim.comment_line);
+                    if claim.status == ClaimStatus::NotExecuted {
+                        continue;
+                    }
+                    for line in &claim.expected {
+                        println!("  - {line}");
+                    }
+                    for line in &claim.actual {
+                        println!("  + {line}");
+                    }
+                }
+            }
+            eprintln!("{claims} afirmações, {mismatches} divergentes");
+        }
+        Format::Json => {
+            let summary = ExpectSummary {
+                claims,
+                mismatches,
+                examples: reports,
+            };
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+    }
+
+    Ok(if mismatches > 0 || broken > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn print_parity(format: Format, reports: &[TopicParity]) -> Result<ExitCode> {
+    let gaps: usize = reports.iter().map(|r| r.gaps().count()).sum();
+
+    match format {
+        Format::Text => {
+            for report in reports.iter().filter(|r| r.gaps().next().is_some()) {
+                println!("{}", report.topic);
+                for section in report.gaps() {
+                    // Só o nome do arquivo: o tópico já está no cabeçalho
+                    let found: Vec<&str> = section
+                        .found
+                        .values()
+                        .map(|at| at.rsplit('/').next().unwrap_or(at))
+                        .collect();
+                    println!(
+                        "  {} — {}; falta em {}",
+                        section.title,
+                        found.join(", "),
+                        section.missing.join(", ")
+                    );
+                }
+            }
+            eprintln!(
+                "{} tópicos comparados, {gaps} seções sem par",
+                reports.len()
+            );
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(reports)?),
+    }
+
+    Ok(if gaps > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn print_annotations(format: Format, reports: &[ExampleAnnotations]) -> Result<ExitCode> {
+    match format {
+        Format::Text => {
+            for report in reports {
+                for annotation in &report.annotations {
+                    println!(
+                        "{}:{}:{}: {:<10} {} — {}",
+                        report.file,
+                        annotation.line,
+                        annotation.column,
+                        format!("{:?}", annotation.kind).to_lowercase(),
+                        annotation.label,
+                        annotation.detail
+                    );
+                }
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(reports)?),
+    }
+    let annotations: usize = reports.iter().map(|r| r.annotations.len()).sum();
+    eprintln!("{} exemplos, {annotations} anotações", reports.len());
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_audit(format: Format, reports: &[AuditReport], verbose: bool) -> Result<ExitCode> {
+    let findings: usize = reports.iter().map(|r| r.findings.len()).sum();
+    let unexplained: usize = reports.iter().map(|r| r.unexplained().count()).sum();
+    let blocking: usize = reports.iter().map(AuditReport::blocking).sum();
+
+    match format {
+        Format::Text => {
+            for report in reports {
+                for finding in &report.findings {
+                    let status = match &finding.explanation {
+                        Some(_) if !verbose => continue,
+                        Some(explanation) => format!("ok ({explanation})"),
+                        None if finding.blocks() => "sem justificativa".to_string(),
+                        None => "sem justificativa (aviso)".to_string(),
+                    };
+                    println!(
+                        "{}:{}:{}: {}: {status}",
+                        report.file,
+                        finding.line,
+                        finding.column,
+                        finding.kind.label()
+                    );
+                    println!("  {}", finding.code);
+                }
+            }
+            eprintln!(
+                "{findings} pontos, {unexplained} sem justificativa, {blocking} em unwrap/unsafe"
+            );
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(reports)?),
+    }
+
+    Ok(if blocking > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn print_review(format: Format, reports: &[ReviewReport]) -> Result<ExitCode> {
+    let failed = reports.iter().filter(|r| !r.passed()).count();
+
+    match format {
+        Format::Text => {
+            for report in reports {
+                println!("{}", report.file);
+                for item in &report.items {
+                    let status = match item.status {
+                        ItemStatus::Passed => "ok",
+                        ItemStatus::Failed => "FALHOU",
+                        ItemStatus::Manual => "manual",
+                        ItemStatus::Skipped => "pulado",
+                    };
+                    println!("  {status:<7} {} ({})", item.item.text, item.item.source);
+                    for detail in &item.details {
+                        println!("          {detail}");
+                    }
+                }
+            }
+            eprintln!(
+                "{} exemplos revisados, {} aprovados, {failed} reprovados",
+                reports.len(),
+                reports.len() - failed
+            );
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(reports)?),
+    }
+
+    Ok(if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn print_reports(format: Format, reports: &[Report]) -> Result<ExitCode> {
+    let passed = reports.iter().filter(|r| r.passed()).count();
+    let failed = reports.len() - passed;
+
+    match format {
+        Format::Text => {
+            for report in reports {
+                let timing = match (report.build_ms, report.run_ms) {
+                    (Some(build), Some(run)) => format!(" (build {build} ms, execução {run} ms)"),
+                    (Some(build), None) => format!(" (build {build} ms)"),
+                    _ => String::new(),
+                };
+                let label = if report.passed() { "ok" } else { "FALHOU" };
+                println!("{label:<7}{:<24}{}{timing}", report.topic, report.file);
+                if let Some(message) = &report.message {
+                    for line in message.lines() {
+                        println!("       {line}");
+                    }
+                }
+            }
+            eprintln!("{passed} passaram, {failed} falharam");
+        }
+        Format::Json => {
+            let summary = Summary {
+                passed,
+                failed,
+                examples: reports,
+            };
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+    }
+
+    Ok(if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
@@ -0,0 +1,312 @@
+//! Paridade entre as linguagens dos `examples/` de um tópico: as seções que
+//! cada arquivo anuncia nos comentários-faixa precisam existir nos outros.
+//!
+//! Duas formas de faixa, com `//`, `///` ou `#`:
+//!
+//! ```text
+//! // ============================         # --- Parsing de string XML ---
+//! // O(1) — Tempo constante
+//! // ============================
+//! ```
+//!
+//! O título muda de uma linguagem para outra ("O(1) — Acesso direto",
+//! "Armadilha C#: ..."), então o alinhamento compara só a parte antes de
+//! ` — `, `: ` ou ` (`, sem acentos, sem nomes de linguagem e sem o plural.
+//! Palavras em comum não bastam: as duas faixas precisam começar pela mesma
+//! palavra, e a classe `O(...)` citada em qualquer parte de um título precisa
+//! estar no outro (a não ser que o começo dos dois seja idêntico).
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use themelion_content::collation_key;
+use themelion_content::model::Topic;
+
+/// Semelhança mínima (Jaccard das palavras do título) para duas faixas
+/// contarem como a mesma seção.
+const MIN_SIMILARITY: f64 = 0.5;
+
+const STOPWORDS: &[&str] = &[
+    "as", "com", "como", "da", "das", "de", "do", "dos", "em", "na", "nas", "no", "nos", "os",
+    "para", "pela", "pelo", "por", "sem", "um", "uma",
+];
+
+/// Palavras que só dizem em que arquivo a faixa está.
+const LANGUAGE_WORDS: &[&str] = &[
+    "c",
+    "csharp",
+    "go",
+    "golang",
+    "javascript",
+    "js",
+    "python",
+    "rust",
+    "ts",
+    "typescript",
+];
+
+/// Um comentário-faixa e a linha (1-based) do título.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Banner {
+    pub title: String,
+    pub line: usize,
+}
+
+/// Faixas do arquivo, sem a do título do arquivo: a que abre o arquivo,
+/// só com linhas em branco antes dela.
+pub fn banners(code: &str) -> Vec<Banner> {
+    let lines: Vec<&str> = code.lines().collect();
+    let mut banners = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let banner = if let Some(title) = comment(line).and_then(inline_title) {
+            Some((title.to_string(), i + 1, 1))
+        } else if comment(line).is_some_and(is_rule) {
+            // Faixa em caixa: régua, uma ou mais linhas de texto, régua
+            let close = lines[i + 1..]
+                .iter()
+                .take_while(|l| comment(l).is_some())
+                .position(|l| comment(l).is_some_and(is_rule));
+            close.filter(|&n| n > 0).map(|n| {
+                let title = comment(lines[i + 1]).unwrap_or_default().trim();
+                (title.to_string(), i + 2, n + 2)
+            })
+        } else {
+            None
+        };
+
+        match banner {
+            Some((title, line, used)) => {
+                let at_top = lines[..i].iter().all(|l| l.trim().is_empty());
+                if !at_top && !title.is_empty() {
+                    banners.push(Banner { title, line });
+                }
+                i += used;
+            }
+            None => i += 1,
+        }
+    }
+    banners
+}
+
+/// Texto de uma linha de comentário (`//`, `///` ou `#`), sem o marcador.
+pub fn comment(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let text = line
+        .strip_prefix("///")
+        .or_else(|| line.strip_prefix("//"))
+        .or_else(|| line.strip_prefix('#'))?;
+    // `#[derive]`, `#include`, `#region`: não são comentários
+    let is_directive = line.starts_with('#') && text.starts_with(|c: char| !c.is_whitespace());
+    (!is_directive).then_some(text)
+}
+
+/// Três ou mais `=` ou `-` e nada mais.
+fn is_rule(text: &str) -> bool {
+    let text = text.trim();
+    text.len() >= 3 && (text.bytes().all(|b| b == b'=') || text.bytes().all(|b| b == b'-'))
+}
+
+/// `--- Título ---` ou `=== Título ===`.
+fn inline_title(text: &str) -> Option<&str> {
+    let text = text.trim();
+    let mark = text.chars().next().filter(|c| matches!(c, '=' | '-'))?;
+    let open = text.len() - text.trim_start_matches(mark).len();
+    let close = text.len() - text.trim_end_matches(mark).len();
+    if open < 3 || close < 3 || open + close >= text.len() {
+        return None;
+    }
+    let title = text[open..text.len() - close].trim();
+    (!title.is_empty()).then_some(title)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicParity {
+    pub topic: String,
+    /// Linguagens com exemplo, na ordem de `data/languages/`.
+    pub languages: Vec<String>,
+    pub sections: Vec<SectionParity>,
+}
+
+/// Uma seção alinhada entre os arquivos.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionParity {
+    /// Título na primeira linguagem que tem a seção.
+    pub title: String,
+    /// Linguagem → `arquivo:linha` da faixa.
+    pub found: BTreeMap<String, String>,
+    pub missing: Vec<String>,
+}
+
+impl TopicParity {
+    pub fn gaps(&self) -> impl Iterator<Item = &SectionParity> {
+        self.sections.iter().filter(|s| !s.missing.is_empty())
+    }
+}
+
+/// Alinha as faixas dos exemplos do tópico nas linguagens de `languages`.
+/// `None` quando há menos de dois exemplos para comparar.
+pub fn parity(topic: &Topic, languages: &[String]) -> Option<TopicParity> {
+    let examples: Vec<_> = languages
+        .iter()
+        .filter_map(|id| topic.examples.iter().find(|e| &e.language_id == id))
+        .collect();
+    if examples.len() < 2 {
+        return None;
+    }
+
+    // Cada seção: chave do título e, por linguagem, a faixa
+    let mut sections: Vec<(Key, String, BTreeMap<String, String>)> = Vec::new();
+    for example in &examples {
+        let banners = banners(&example.code);
+        let keys: Vec<Key> = banners.iter().map(|b| Key::new(&b.title)).collect();
+
+        // Pares faixa × seção do melhor para o pior; cada lado casa uma vez
+        let mut pairs = Vec::new();
+        for (b, banner_key) in keys.iter().enumerate() {
+            for (s, (section_key, _, found)) in sections.iter().enumerate() {
+                if found.contains_key(&example.language_id) {
+                    continue;
+                }
+                let score = banner_key.similarity(section_key);
+                if score >= MIN_SIMILARITY {
+                    pairs.push((score, b, s));
+                }
+            }
+        }
+        pairs.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let mut matched: Vec<Option<usize>> = vec![None; banners.len()];
+        let mut taken = vec![false; sections.len()];
+        for (_, b, s) in pairs {
+            if matched[b].is_none() && !taken[s] {
+                matched[b] = Some(s);
+                taken[s] = true;
+            }
+        }
+
+        for (b, banner) in banners.iter().enumerate() {
+            let location = format!("{}:{}", example.file_path, banner.line);
+            match matched[b] {
+                Some(s) => {
+                    sections[s].2.insert(example.language_id.clone(), location);
+                }
+                None => sections.push((
+                    keys[b].clone(),
+                    banner.title.clone(),
+                    BTreeMap::from([(example.language_id.clone(), location)]),
+                )),
+            }
+        }
+    }
+
+    let present: Vec<String> = examples.iter().map(|e| e.language_id.clone()).collect();
+    let sections = sections
+        .into_iter()
+        .map(|(_, title, found)| SectionParity {
+            missing: present
+                .iter()
+                .filter(|id| !found.contains_key(*id))
+                .cloned()
+                .collect(),
+            title,
+            found,
+        })
+        .collect();
+
+    Some(TopicParity {
+        topic: topic.frontmatter.slug.clone(),
+        languages: present,
+        sections,
+    })
+}
+
+/// O que o alinhamento compara de um título.
+#[derive(Debug, Clone)]
+struct Key {
+    /// Palavras do começo do título, antes de ` — `, `: ` ou ` (`.
+    words: Vec<String>,
+    /// Primeira classe `O(...)` citada em qualquer parte do título.
+    complexity: Option<String>,
+}
+
+impl Key {
+    fn new(title: &str) -> Self {
+        let head = [" — ", " - ", ": ", " ("]
+            .iter()
+            .filter_map(|sep| title.find(sep))
+            .min()
+            .map_or(title, |at| &title[..at]);
+        let words = collation_key(head)
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .filter(|w| !STOPWORDS.contains(w) && !LANGUAGE_WORDS.contains(w))
+            .map(|w| match w.strip_suffix('s') {
+                Some(singular) if singular.chars().count() >= 3 => singular.to_string(),
+                _ => w.to_string(),
+            })
+            .collect();
+        Self {
+            words,
+            complexity: complexity(title),
+        }
+    }
+
+    /// 2 para chaves iguais (inclusive a ordem: `O(log n)` ≠ `O(n log n)`)
+    /// e 1,5 se só uma cita a classe; senão, o Jaccard dos conjuntos de
+    /// palavras. 0 quando a primeira palavra não é a mesma ("Comparação
+    /// prática" e "Go na prática"), quando as classes diferem e quando só
+    /// uma das faixas cita classe e as palavras não são as mesmas
+    /// ("Armadilha C#: concatenação ... O(n²)" e "Armadilhas de performance").
+    fn similarity(&self, other: &Key) -> f64 {
+        if self.words.first() != other.words.first() {
+            return 0.0;
+        }
+        let same_class = match (&self.complexity, &other.complexity) {
+            (Some(a), Some(b)) if a != b => return 0.0,
+            (a, b) => a == b,
+        };
+        if self.words == other.words {
+            return if same_class { 2.0 } else { 1.5 };
+        }
+        if !same_class {
+            return 0.0;
+        }
+        let (a, b) = (&self.words, &other.words);
+        let common = a.iter().filter(|w| b.contains(w)).count();
+        let union = a.len() + b.len() - common;
+        if union == 0 {
+            0.0
+        } else {
+            common as f64 / union as f64
+        }
+    }
+}
+
+/// `O(n log n)` → `O(nlogn)`: a primeira classe do texto, sem espaços.
+fn complexity(title: &str) -> Option<String> {
+    let start = title.match_indices("O(").map(|(at, _)| at).find(|&at| {
+        !title[..at]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric)
+    })?;
+    let mut depth = 0;
+    for (offset, c) in title[start + 1..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let end = start + 1 + offset + 1;
+                    return Some(title[start..end].split_whitespace().collect());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
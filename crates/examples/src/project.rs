@@ -0,0 +1,165 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use themelion_runner::Limits;
+use themelion_runner::process::{self, Output};
+
+use crate::header::Header;
+use crate::{Error, Result};
+
+/// Mesma edição do `rust-runner`, que compila os exemplos no site.
+const EDITION: &str = "2021";
+
+/// Diretório com um projeto Cargo por exemplo e um `target/` compartilhado,
+/// para as dependências serem compiladas uma vez só.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    dir: PathBuf,
+    offline: bool,
+    limits: Limits,
+}
+
+/// Resultado de um comando do Cargo (`build`, `clippy`, `fmt`) num exemplo.
+#[derive(Debug)]
+pub struct Build {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+impl Workspace {
+    /// `offline` passa `--offline` ao Cargo: as dependências precisam estar no
+    /// cache local ou em um registry vendorizado configurado em `.cargo/`.
+    pub fn new(dir: impl Into<PathBuf>, offline: bool) -> Self {
+        Self {
+            dir: dir.into(),
+            offline,
+            limits: Limits::EXECUTE,
+        }
+    }
+
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Grava `<dir>/<nome>/Cargo.toml` e `src/main.rs`. Arquivos com o mesmo
+    /// conteúdo não são regravados, para o Cargo não recompilar à toa.
+    pub fn prepare(&self, name: &str, header: &Header, source: &str) -> Result<PathBuf> {
+        let project = self.dir.join(name);
+        write_if_changed(&project.join("Cargo.toml"), manifest(name, header, true))?;
+        write_if_changed(&project.join("src/main.rs"), source)?;
+        Ok(project)
+    }
+
+    pub fn build(&self, project: &Path) -> Result<Build> {
+        self.cargo(
+            project,
+            &["build", "--quiet", "--message-format=short"],
+            &[],
+        )
+    }
+
+    /// `cargo clippy` com as mensagens em JSON (uma por linha do stdout), para
+    /// ler o nome de cada lint; `lints` vão depois do `--` (`-W clippy::...`).
+    pub fn clippy(&self, project: &Path, lints: &[&str]) -> Result<Build> {
+        self.cargo(
+            project,
+            &["clippy", "--quiet", "--message-format=json"],
+            lints,
+        )
+    }
+
+    /// `cargo fmt --check`: o diff sai no stdout, sem cores.
+    pub fn fmt_check(&self, project: &Path) -> Result<Build> {
+        self.cargo(project, &["fmt", "--check"], &["--color", "never"])
+    }
+
+    fn cargo(&self, project: &Path, args: &[&str], after: &[&str]) -> Result<Build> {
+        let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+        let mut cmd = Command::new(cargo);
+        cmd.args(args)
+            .arg("--manifest-path")
+            .arg(project.join("Cargo.toml"))
+            .env("CARGO_TARGET_DIR", self.target_dir());
+        // `cargo fmt` não resolve dependências e não aceita `--offline`
+        if self.offline && args.first() != Some(&"fmt") {
+            cmd.arg("--offline");
+        }
+        if !after.is_empty() {
+            cmd.arg("--").args(after);
+        }
+
+        let started = Instant::now();
+        let output = cmd
+            .output()
+            .map_err(|err| Error::Cargo(format!("não foi possível executar o cargo: {err}")))?;
+        Ok(Build {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            duration: started.elapsed(),
+        })
+    }
+
+    /// Executa o binário já compilado com os limites do runner.
+    pub fn run(&self, name: &str) -> Result<Output> {
+        let binary = self.target_dir().join("debug").join(package_name(name));
+        let mut cmd = Command::new(&binary);
+        cmd.env_clear();
+        process::run(cmd, &self.limits, &[]).map_err(|source| Error::Io {
+            path: binary,
+            source,
+        })
+    }
+
+    fn target_dir(&self) -> PathBuf {
+        self.dir.join("target")
+    }
+}
+
+/// `Cargo.toml` do exemplo com as dependências do cabeçalho. `standalone`
+/// declara um `[workspace]` vazio, para o projeto compilar mesmo dentro de
+/// outro workspace (o do repositório, ou a pasta onde o zip foi aberto).
+pub(crate) fn manifest(name: &str, header: &Header, standalone: bool) -> String {
+    let mut manifest = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.0.0\"\nedition = \"{EDITION}\"\npublish = false\n\n",
+        package_name(name)
+    );
+    if standalone {
+        manifest.push_str("# Fora de qualquer workspace acima deste diretório\n[workspace]\n\n");
+    }
+    manifest.push_str(if header.manifest.is_empty() {
+        "[dependencies]\n"
+    } else {
+        &header.manifest
+    });
+    manifest
+}
+
+pub(crate) fn package_name(topic: &str) -> String {
+    format!("example-{topic}")
+}
+
+/// Grava `content` em `path` se for diferente do que já está lá; `true` se
+/// gravou.
+pub(crate) fn write_if_changed(path: &Path, content: impl AsRef<[u8]>) -> Result<bool> {
+    let content = content.as_ref();
+    if fs::read(path).is_ok_and(|current| current == content) {
+        return Ok(false);
+    }
+    let io = |source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(&io)?;
+    }
+    fs::write(path, content).map_err(io)?;
+    Ok(true)
+}
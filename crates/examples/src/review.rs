@@ -0,0 +1,535 @@
+//! Checklists de revisão executados contra um `rust.rs`: a parte **Código**
+//! da revisão linha por linha de `data/agents/reviewer.md` e o **Checklist**
+//! do "Workflow de Revisão" de `data/agents/languages/rust.md`.
+//!
+//! Os itens vêm dos próprios arquivos, na ordem em que estão lá. Cada um é
+//! casado pelo texto com uma verificação mecânica (`cargo fmt --check`,
+//! `cargo clippy`, a árvore do syn, a auditoria de [`crate::audit`]); os que
+//! não têm uma, como "Comentários agregam valor", ficam como manuais.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use syn::visit::{self, Visit};
+use syn::{Expr, Pat};
+
+use crate::annotate::parse;
+use crate::audit::{self, Comments, FindingKind};
+use crate::header;
+use crate::project::Build;
+use crate::{Error, Example, Result, Workspace};
+
+/// Onde ficam os checklists, relativos à raiz, e o trecho de cada um que vale
+/// para código: (arquivo, seção, marcador da lista).
+const CHECKLISTS: [(&str, &str, &str); 2] = [
+    (
+        "data/agents/reviewer.md",
+        "### 2. Revisão Linha por Linha",
+        "**Código:**",
+    ),
+    (
+        "data/agents/languages/rust.md",
+        "## Workflow de Revisão",
+        "**Checklist:**",
+    ),
+];
+
+/// Lints do clippy que respondem a itens específicos do checklist, além do
+/// item geral "clippy sem warnings". Todos são pedidos um a um: alguns ficam
+/// fora de `clippy::all` (`elidable_lifetime_names` é pedantic,
+/// `redundant_clone` é nursery) e nunca disparariam.
+const LIFETIME_LINTS: [&str; 3] = [
+    "clippy::needless_lifetimes",
+    "clippy::extra_unused_lifetimes",
+    "clippy::elidable_lifetime_names",
+];
+const CLONE_LINTS: [&str; 1] = ["clippy::redundant_clone"];
+const LOOP_LINTS: [&str; 1] = ["clippy::needless_range_loop"];
+
+/// Nomes que o `reviewer.md` pede para evitar ("não foo/bar").
+const PLACEHOLDER_NAMES: [&str; 6] = ["foo", "bar", "baz", "qux", "quux", "foobar"];
+
+/// Um item de checklist, como está no arquivo.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+    /// Nome do arquivo de onde o item veio (`rust.md`).
+    pub source: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemStatus {
+    Passed,
+    Failed,
+    /// Sem verificação mecânica: fica com quem revisa.
+    Manual,
+    /// A verificação não pôde rodar (o exemplo não compila, por exemplo).
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemReport {
+    #[serde(flatten)]
+    pub item: ChecklistItem,
+    pub status: ItemStatus,
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReport {
+    pub topic: String,
+    pub file: String,
+    pub items: Vec<ItemReport>,
+}
+
+impl ReviewReport {
+    pub fn passed(&self) -> bool {
+        self.items.iter().all(|i| i.status != ItemStatus::Failed)
+    }
+}
+
+/// Os itens de código dos checklists, na ordem dos arquivos.
+pub fn checklist(root: &Path) -> Result<Vec<ChecklistItem>> {
+    let mut items = Vec::new();
+    for (file, section, marker) in CHECKLISTS {
+        let path = root.join(file);
+        let markdown = fs::read_to_string(&path).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let source = file.rsplit('/').next().unwrap_or(file);
+        let found = list_after(&markdown, section, marker);
+        if found.is_empty() {
+            return Err(Error::Checklist {
+                file: file.to_string(),
+                marker: marker.to_string(),
+            });
+        }
+        items.extend(found.into_iter().map(|text| ChecklistItem {
+            source: source.to_string(),
+            text,
+        }));
+    }
+    Ok(items)
+}
+
+/// Itens `- [ ]` logo depois de `marker`, dentro da seção `section`.
+fn list_after(markdown: &str, section: &str, marker: &str) -> Vec<String> {
+    markdown
+        .lines()
+        .skip_while(|line| line.trim() != section)
+        .skip_while(|line| line.trim() != marker)
+        .skip(1)
+        .map_while(|line| line.trim().strip_prefix("- [ ]"))
+        .map(|text| text.trim().to_string())
+        .collect()
+}
+
+/// Verificação mecânica de um item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Check {
+    Compiles,
+    RealisticNames,
+    Format,
+    Clippy,
+    Clones,
+    Lifetimes,
+    Unwrap,
+    Loops,
+    Unsafe,
+    SendSync,
+}
+
+impl Check {
+    fn for_item(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        [
+            ("executável", Self::Compiles),
+            ("foo/bar", Self::RealisticNames),
+            ("cargo fmt", Self::Format),
+            ("clippy", Self::Clippy),
+            ("clone", Self::Clones),
+            ("lifetime", Self::Lifetimes),
+            ("unwrap", Self::Unwrap),
+            ("iterator", Self::Loops),
+            ("unsafe", Self::Unsafe),
+            ("send/sync", Self::SendSync),
+        ]
+        .into_iter()
+        .find(|(keyword, _)| text.contains(keyword))
+        .map(|(_, check)| check)
+    }
+}
+
+/// Mensagem do compilador ou do clippy.
+#[derive(Debug, Clone)]
+struct Diagnostic {
+    level: String,
+    lint: Option<String>,
+    message: String,
+    line: usize,
+}
+
+/// O que o Cargo disse sobre o exemplo.
+struct Cargo {
+    fmt: Build,
+    clippy: Build,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Cargo {
+    fn compiled(&self) -> bool {
+        self.clippy.success
+    }
+
+    fn lints<'a>(&'a self, names: &'a [&str]) -> impl Iterator<Item = &'a Diagnostic> {
+        self.diagnostics.iter().filter(move |d| {
+            d.lint
+                .as_deref()
+                .is_some_and(|lint| names.is_empty() || names.contains(&lint))
+        })
+    }
+}
+
+/// `clippy::all` mais cada lint dos itens específicos.
+fn clippy_args() -> Vec<&'static str> {
+    let specific = CLONE_LINTS.iter().chain(&LIFETIME_LINTS).chain(&LOOP_LINTS);
+    ["-W", "clippy::all"]
+        .into_iter()
+        .chain(specific.flat_map(|lint| ["-W", lint]))
+        .collect()
+}
+
+pub fn review(
+    workspace: &Workspace,
+    example: &Example,
+    checklist: &[ChecklistItem],
+) -> Result<ReviewReport> {
+    let file = example.file.as_str();
+    let at = |line: usize| format!("{file}:{line}");
+
+    // Sem cabeçalho válido não há projeto Cargo para rodar
+    let cargo = match header::parse(file, &example.source) {
+        Ok(header) => {
+            let project = workspace.prepare(&example.topic, &header, &example.source)?;
+            let clippy = workspace.clippy(&project, &clippy_args())?;
+            Ok(Cargo {
+                fmt: workspace.fmt_check(&project)?,
+                diagnostics: diagnostics(&clippy.stdout),
+                clippy,
+            })
+        }
+        Err(err @ Error::Header { .. }) => Err(err.to_string()),
+        Err(err) => return Err(err),
+    };
+    let ast = parse(example).ok();
+    let findings = match &ast {
+        Some(_) => audit::audit(example)?.findings,
+        None => Vec::new(),
+    };
+
+    let items = checklist
+        .iter()
+        .map(|item| {
+            let Some(check) = Check::for_item(&item.text) else {
+                return report(item, ItemStatus::Manual, Vec::new());
+            };
+            // Verificações que dependem do Cargo ou da árvore sintática
+            let cargo = match (&cargo, check) {
+                (_, Check::RealisticNames | Check::Unwrap | Check::Unsafe) => None,
+                (Ok(cargo), _) => Some(cargo),
+                (Err(message), Check::Compiles) => {
+                    return report(item, ItemStatus::Failed, vec![message.clone()]);
+                }
+                (Err(_), _) => {
+                    let details = vec!["cabeçalho Cargo.toml inválido".to_string()];
+                    return report(item, ItemStatus::Skipped, details);
+                }
+            };
+            let Some(ast) = &ast else {
+                let details = vec!["o arquivo não é Rust válido".to_string()];
+                let status = if check == Check::Compiles {
+                    ItemStatus::Failed
+                } else {
+                    ItemStatus::Skipped
+                };
+                return report(item, status, details);
+            };
+            let not_compiled = || {
+                let details = vec!["o exemplo não compila".to_string()];
+                report(item, ItemStatus::Skipped, details)
+            };
+
+            let lint_details = |cargo: &Cargo, names: &[&str]| -> Vec<String> {
+                cargo
+                    .lints(names)
+                    .map(|d| {
+                        let lint = d.lint.as_deref().unwrap_or_default();
+                        format!("{}: {lint}: {}", at(d.line), d.message)
+                    })
+                    .collect()
+            };
+
+            match check {
+                Check::Compiles => {
+                    let cargo = cargo.expect("checagens do Cargo têm o resultado do Cargo");
+                    if cargo.compiled() {
+                        return report(item, ItemStatus::Passed, Vec::new());
+                    }
+                    let mut errors: Vec<String> = cargo
+                        .diagnostics
+                        .iter()
+                        .filter(|d| d.level == "error")
+                        .map(|d| format!("{}: {}", at(d.line), d.message))
+                        .collect();
+                    if errors.is_empty() {
+                        errors.push(cargo.clippy.stderr.trim().to_string());
+                    }
+                    report(item, ItemStatus::Failed, errors)
+                }
+                Check::RealisticNames => {
+                    let mut names = Placeholders::default();
+                    names.visit_file(ast);
+                    let details: Vec<String> = names
+                        .found
+                        .iter()
+                        .map(|(line, name)| format!("{}: `{name}`", at(*line)))
+                        .collect();
+                    status_by(item, details)
+                }
+                Check::Format => {
+                    let cargo = cargo.expect("checagens do Cargo têm o resultado do Cargo");
+                    if cargo.fmt.success {
+                        return report(item, ItemStatus::Passed, Vec::new());
+                    }
+                    report(item, ItemStatus::Failed, format_diff(&cargo.fmt, file))
+                }
+                Check::Clippy => {
+                    let cargo = cargo.expect("checagens do Cargo têm o resultado do Cargo");
+                    if !cargo.compiled() {
+                        return not_compiled();
+                    }
+                    status_by(item, lint_details(cargo, &[]))
+                }
+                Check::Clones => {
+                    let cargo = cargo.expect("checagens do Cargo têm o resultado do Cargo");
+                    let comments = Comments::new(&example.source);
+                    let mut clones = Clones::default();
+                    clones.visit_file(ast);
+                    let mut details: Vec<String> = clones
+                        .lines
+                        .iter()
+                        .filter(|&&line| comments.near(line, line).is_none())
+                        .map(|&line| format!("{}: clone sem `// clone:` explicando", at(line)))
+                        .collect();
+                    details.extend(lint_details(cargo, &CLONE_LINTS));
+                    status_by(item, details)
+                }
+                Check::Lifetimes => {
+                    let cargo = cargo.expect("checagens do Cargo têm o resultado do Cargo");
+                    if !cargo.compiled() {
+                        return not_compiled();
+                    }
+                    status_by(item, lint_details(cargo, &LIFETIME_LINTS))
+                }
+                Check::Unwrap | Check::Unsafe => {
+                    let kind = if check == Check::Unwrap {
+                        FindingKind::Unwrap
+                    } else {
+                        FindingKind::Unsafe
+                    };
+                    let details = findings
+                        .iter()
+                        .filter(|f| f.kind == kind && f.blocks())
+                        .map(|f| format!("{}: sem justificativa: {}", at(f.line), f.code))
+                        .collect();
+                    status_by(item, details)
+                }
+                Check::Loops => {
+                    let cargo = cargo.expect("checagens do Cargo têm o resultado do Cargo");
+                    let mut loops = IndexLoops::default();
+                    loops.visit_file(ast);
+                    let mut details: Vec<String> = loops
+                        .lines
+                        .iter()
+                        .map(|&line| {
+                            format!("{}: `for i in 0..v.len()` indexando `v[i]`", at(line))
+                        })
+                        .collect();
+                    // O clippy acha os mesmos laços; só os que o syn não viu
+                    details.extend(
+                        cargo
+                            .lints(&LOOP_LINTS)
+                            .filter(|d| !loops.lines.contains(&d.line))
+                            .map(|d| format!("{}: {}", at(d.line), d.message)),
+                    );
+                    status_by(item, details)
+                }
+                Check::SendSync => {
+                    let cargo = cargo.expect("checagens do Cargo têm o resultado do Cargo");
+                    if !cargo.compiled() {
+                        return not_compiled();
+                    }
+                    let details = vec!["o compilador confere Send/Sync; o exemplo compila".into()];
+                    report(item, ItemStatus::Passed, details)
+                }
+            }
+        })
+        .collect();
+
+    Ok(ReviewReport {
+        topic: example.topic.clone(),
+        file: example.file.clone(),
+        items,
+    })
+}
+
+fn report(item: &ChecklistItem, status: ItemStatus, details: Vec<String>) -> ItemReport {
+    ItemReport {
+        item: item.clone(),
+        status,
+        details,
+    }
+}
+
+/// Passa sem nada a apontar; falha com os apontamentos.
+fn status_by(item: &ChecklistItem, details: Vec<String>) -> ItemReport {
+    let status = if details.is_empty() {
+        ItemStatus::Passed
+    } else {
+        ItemStatus::Failed
+    };
+    report(item, status, details)
+}
+
+/// Mensagens do `--message-format=json`, só as que apontam uma linha.
+fn diagnostics(stdout: &str) -> Vec<Diagnostic> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|value| value["reason"] == "compiler-message")
+        .filter_map(|value| {
+            let message = &value["message"];
+            let line = message["spans"]
+                .as_array()?
+                .iter()
+                .find(|span| span["is_primary"] == true)?["line_start"]
+                .as_u64()?;
+            Some(Diagnostic {
+                level: message["level"].as_str()?.to_string(),
+                lint: message["code"]["code"].as_str().map(String::from),
+                message: message["message"].as_str()?.to_string(),
+                line: line as usize,
+            })
+        })
+        .collect()
+}
+
+/// Até 20 linhas do diff do `cargo fmt --check`, com o caminho do exemplo.
+fn format_diff(fmt: &Build, file: &str) -> Vec<String> {
+    const MAX: usize = 20;
+    let mut lines: Vec<String> = fmt
+        .stdout
+        .lines()
+        .map(|line| match line.strip_prefix("Diff in ") {
+            Some(rest) => {
+                let at = rest.rsplit_once("src/main.rs:").map_or(rest, |(_, at)| at);
+                format!("Diff em {file}:{}", at.trim_end_matches(':'))
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(fmt.stderr.trim().to_string());
+    }
+    if lines.len() > MAX {
+        let rest = lines.len() - MAX;
+        lines.truncate(MAX);
+        lines.push(format!("... mais {rest} linhas"));
+    }
+    lines
+}
+
+#[derive(Default)]
+struct Placeholders {
+    found: Vec<(usize, String)>,
+}
+
+impl<'ast> Visit<'ast> for Placeholders {
+    fn visit_ident(&mut self, ident: &'ast proc_macro2::Ident) {
+        let name = ident.to_string();
+        if PLACEHOLDER_NAMES.contains(&name.to_lowercase().as_str()) {
+            self.found.push((ident.span().start().line, name));
+        }
+    }
+}
+
+/// Linhas com `.clone()`/`.cloned()`. `Rc::clone(&x)` e `Arc::clone(&x)` só
+/// contam referências e ficam de fora.
+#[derive(Default)]
+struct Clones {
+    lines: Vec<usize>,
+}
+
+impl<'ast> Visit<'ast> for Clones {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "clone" || call.method == "cloned" {
+            self.lines.push(call.method.span().start().line);
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+}
+
+/// `for i in 0..v.len()` cujo corpo indexa com `i`: o laço que um iterador
+/// (`iter()`, `enumerate()`, `windows()`) substitui.
+#[derive(Default)]
+struct IndexLoops {
+    lines: Vec<usize>,
+}
+
+impl<'ast> Visit<'ast> for IndexLoops {
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast syn::ExprForLoop) {
+        let index = match &*for_loop.pat {
+            Pat::Ident(pat) => Some(pat.ident.to_string()),
+            _ => None,
+        };
+        let over_len = match &*for_loop.expr {
+            Expr::Range(range) => range
+                .end
+                .as_deref()
+                .is_some_and(|end| matches!(end, Expr::MethodCall(call) if call.method == "len")),
+            _ => false,
+        };
+        if let (Some(index), true) = (index, over_len) {
+            let mut uses = IndexedBy {
+                name: index,
+                found: false,
+            };
+            uses.visit_block(&for_loop.body);
+            if uses.found {
+                self.lines.push(for_loop.for_token.span.start().line);
+            }
+        }
+        visit::visit_expr_for_loop(self, for_loop);
+    }
+}
+
+/// Procura `x[name]` num bloco.
+struct IndexedBy {
+    name: String,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for IndexedBy {
+    fn visit_expr_index(&mut self, index: &'ast syn::ExprIndex) {
+        if let Expr::Path(path) = &*index.index {
+            self.found |= path.path.is_ident(&self.name);
+        }
+        visit::visit_expr_index(self, index);
+    }
+}
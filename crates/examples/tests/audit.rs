@@ -0,0 +1,108 @@
+mod common;
+
+use themelion_examples::audit::{self, FindingKind};
+
+use common::example;
+
+/// (tipo, linha, justificado) de cada ponto.
+fn findings(source: &str) -> Vec<(FindingKind, usize, bool)> {
+    audit::audit(&example("fundamentos", "teste", source))
+        .unwrap()
+        .findings
+        .iter()
+        .map(|f| (f.kind, f.line, f.explanation.is_some()))
+        .collect()
+}
+
+#[test]
+fn a_comment_describing_the_risk_is_not_a_justification() {
+    // character-encoding/examples/rust.rs
+    let source = r#"
+fn main() {
+    let bytes: &[u8] = "Olá".as_bytes();
+    // bytes → String (pode falhar se não for UTF-8 válido)
+    let de_volta = std::str::from_utf8(bytes).unwrap();
+    println!("{}", de_volta); // "Olá"
+}
+"#;
+    let report = audit::audit(&example("fundamentos", "teste", source)).unwrap();
+    assert_eq!(findings(source), [(FindingKind::Unwrap, 5, false)]);
+    assert_eq!(report.blocking(), 1);
+}
+
+#[test]
+fn markers_justify() {
+    let source = r#"
+fn main() {
+    let bytes: &[u8] = "Olá".as_bytes();
+    // unwrap: os bytes vêm de um &str, então são UTF-8 válido
+    let a = std::str::from_utf8(bytes).unwrap();
+    let b = std::str::from_utf8(bytes).unwrap(); // PANIC: nunca, veio de &str
+    // SAFETY: a invariante de &str garante UTF-8 válido
+    let c = unsafe { std::str::from_utf8_unchecked(bytes) };
+    let d = std::str::from_utf8(bytes).unwrap(); // decodifica
+    println!("{a}{b}{c}{d}");
+}
+"#;
+    assert_eq!(
+        findings(source),
+        [
+            (FindingKind::Unwrap, 5, true),
+            (FindingKind::Unwrap, 6, true),
+            (FindingKind::Unsafe, 8, true),
+            (FindingKind::Unwrap, 9, false),
+        ]
+    );
+}
+
+#[test]
+fn negated_and_unmarked_comments_do_not_justify() {
+    let source = r#"
+fn main() {
+    let bytes: &[u8] = "Olá".as_bytes();
+    // isto NÃO garante nada
+    let a = std::str::from_utf8(bytes).unwrap();
+    let b = std::str::from_utf8(bytes).unwrap(); // sem invariante aqui
+    // pode falhar, nunca falha em teste
+    let c = std::str::from_utf8(bytes).unwrap();
+    // A invariante de &str garante UTF-8 válido
+    let d = std::str::from_utf8(bytes).unwrap();
+    println!("{a}{b}{c}{d}");
+}
+"#;
+    let report = audit::audit(&example("fundamentos", "teste", source)).unwrap();
+    assert_eq!(
+        findings(source),
+        [
+            (FindingKind::Unwrap, 5, false),
+            (FindingKind::Unwrap, 6, false),
+            (FindingKind::Unwrap, 8, false),
+            (FindingKind::Unwrap, 10, false),
+        ]
+    );
+    assert_eq!(report.blocking(), 4);
+}
+
+#[test]
+fn flags_addition_and_subtraction_between_variables() {
+    let source = r#"
+fn main() {
+    let (a, b): (u8, u8) = (200, 100);
+    let soma = a + b;
+    let mut total = a;
+    total += b;
+    let diferenca = b - a;
+    let proximo = a + 1;
+    let texto = String::from("a") + &String::from("b");
+    println!("{soma} {total} {diferenca} {proximo} {texto}");
+}
+"#;
+    assert_eq!(
+        findings(source),
+        [
+            (FindingKind::Overflow, 4, false),
+            (FindingKind::Overflow, 6, false),
+            (FindingKind::Overflow, 7, false),
+        ]
+    );
+}
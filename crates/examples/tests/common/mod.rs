@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+use themelion_examples::Example;
+
+/// `data/content/<sala>/<categoria>/<slug>/examples/rust.rs` com `source`.
+pub fn example(room: &str, topic: &str, source: &str) -> Example {
+    let file = format!("data/content/{room}/geral/{topic}/examples/rust.rs");
+    Example {
+        room: room.into(),
+        topic: topic.into(),
+        path: PathBuf::from(&file),
+        file,
+        source: source.into(),
+    }
+}
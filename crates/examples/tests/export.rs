@@ -0,0 +1,118 @@
+mod common;
+
+use std::fs;
+use std::io::Read;
+
+use themelion_examples::{Error, export};
+
+use common::example;
+
+const MAIN: &str = "fn main() {}\n";
+
+#[test]
+fn shared_slugs_get_one_crate_and_zip_per_room() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = dir.path().join("workspace");
+    let zips = dir.path().join("zips");
+    let examples = [
+        example(
+            "fundamentos",
+            "hash-tables",
+            "fn main() { /* memória */ }\n",
+        ),
+        example("backend", "hash-tables", "fn main() { /* cache */ }\n"),
+    ];
+    let titles = export::Titles::from([(
+        ("backend".to_string(), "hash-tables".to_string()),
+        "Hash Tables distribuídas".to_string(),
+    )]);
+
+    export::write_workspace(&examples, &titles, &workspace).unwrap();
+    let root = fs::read_to_string(workspace.join("Cargo.toml")).unwrap();
+    assert!(root.contains("\"fundamentos/hash-tables\","));
+    assert!(root.contains("\"backend/hash-tables\","));
+    let manifest = fs::read_to_string(workspace.join("backend/hash-tables/Cargo.toml")).unwrap();
+    assert!(manifest.contains("name = \"example-backend-hash-tables\""));
+    let readme = fs::read_to_string(workspace.join("backend/hash-tables/README.md")).unwrap();
+    assert!(readme.starts_with("# Hash Tables distribuídas\n"));
+
+    export::write_zips(&examples, &titles, &zips).unwrap();
+    for (room, source) in [("fundamentos", "memória"), ("backend", "cache")] {
+        let file = fs::File::open(zips.join(format!("{room}/hash-tables.zip"))).unwrap();
+        let mut archive = zip::ZipArchive::new(file).unwrap();
+        let mut main = String::new();
+        archive
+            .by_name("hash-tables/src/main.rs")
+            .unwrap()
+            .read_to_string(&mut main)
+            .unwrap();
+        assert!(main.contains(source), "{room}: {main}");
+    }
+}
+
+#[test]
+fn prunes_zips_of_removed_examples() {
+    let dir = tempfile::tempdir().unwrap();
+    let titles = export::Titles::new();
+    let both = [
+        example("fundamentos", "arrays", MAIN),
+        example("backend", "filas", MAIN),
+    ];
+    export::write_zips(&both, &titles, dir.path()).unwrap();
+    // Zip do layout antigo, sem sala
+    fs::write(dir.path().join("arrays.zip"), b"").unwrap();
+
+    let stats = export::write_zips(&both[..1], &titles, dir.path()).unwrap();
+    assert_eq!((stats.unchanged, stats.removed), (1, 2));
+    assert!(dir.path().join("fundamentos/arrays.zip").is_file());
+    assert!(!dir.path().join("backend").exists());
+    assert!(!dir.path().join("arrays.zip").exists());
+}
+
+#[test]
+fn prunes_only_generated_crates() {
+    let dir = tempfile::tempdir().unwrap();
+    let titles = export::Titles::new();
+    let arrays = example("fundamentos", "arrays", MAIN);
+    let pilha = example("fundamentos", "pilha", MAIN);
+    export::write_workspace(&[arrays.clone(), pilha], &titles, dir.path()).unwrap();
+
+    // Pasta que não é de um exemplo, com Cargo.toml próprio
+    let other = dir.path().join("fundamentos/ferramenta");
+    fs::create_dir_all(other.join("src")).unwrap();
+    fs::write(
+        other.join("Cargo.toml"),
+        "[package]\nname = \"ferramenta\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let stats = export::write_workspace(&[arrays], &titles, dir.path()).unwrap();
+    assert_eq!(stats.removed, 1);
+    assert!(dir.path().join("fundamentos/arrays/Cargo.toml").is_file());
+    assert!(!dir.path().join("fundamentos/pilha").exists());
+    assert!(other.join("Cargo.toml").is_file());
+}
+
+#[test]
+fn refuses_a_directory_that_was_not_exported() {
+    let dir = tempfile::tempdir().unwrap();
+    let crates = dir.path().join("crates");
+    let real = crates.join("content");
+    fs::create_dir_all(&real).unwrap();
+    fs::write(
+        real.join("Cargo.toml"),
+        "[package]\nname = \"example-content\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        crates.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"*\"]\n",
+    )
+    .unwrap();
+
+    let examples = [example("fundamentos", "arrays", MAIN)];
+    let err = export::write_workspace(&examples, &export::Titles::new(), &crates).unwrap_err();
+    assert!(matches!(err, Error::NotExported { .. }), "{err}");
+    assert!(real.join("Cargo.toml").is_file());
+    assert!(!crates.join("fundamentos").exists());
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use themelion_content::Content;
+use themelion_examples::parity::{self, Banner};
+
+fn titles(code: &str) -> Vec<String> {
+    parity::banners(code).into_iter().map(|b| b.title).collect()
+}
+
+#[test]
+fn skips_only_the_banner_that_opens_the_file() {
+    let titled = "\n// ====\n// Pilha em Rust\n// ====\n\n// --- Push e pop ---\nfn main() {}\n";
+    assert_eq!(
+        parity::banners(titled),
+        [Banner {
+            title: "Push e pop".into(),
+            line: 6,
+        }]
+    );
+
+    // Sem título: a primeira faixa vem depois de um comentário comum
+    let untitled = "// Cargo.toml:\n// [dependencies]\n\n// --- Push e pop ---\n// --- Peek ---\n";
+    assert_eq!(titles(untitled), ["Push e pop", "Peek"]);
+}
+
+#[test]
+fn pairs_sections_by_first_word_and_complexity_class() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let content = Content::scan(&root).unwrap();
+    let languages: Vec<String> = content.languages.iter().map(|l| l.id.clone()).collect();
+    let topic = content
+        .topics
+        .iter()
+        .find(|t| t.frontmatter.slug == "big-o-notation")
+        .unwrap();
+    let report = parity::parity(topic, &languages).unwrap();
+    let section = |title: &str| {
+        report
+            .sections
+            .iter()
+            .find(|s| s.title.starts_with(title))
+            .unwrap_or_else(|| panic!("seção \"{title}\" ausente"))
+    };
+
+    // O mesmo O(1) com títulos diferentes em cada linguagem
+    let constant = section("O(1)");
+    assert!(
+        ["csharp", "go", "python", "rust"]
+            .iter()
+            .all(|l| constant.found.contains_key(*l))
+    );
+
+    // "Armadilhas de performance em Python" não cita O(n²)
+    let concat = section("Armadilha C#: concatenação");
+    assert_eq!(concat.found.keys().collect::<Vec<_>>(), ["csharp"]);
+
+    // "Comparação prática" começa por outra palavra
+    let go = section("Go na prática");
+    assert_eq!(go.found.keys().collect::<Vec<_>>(), ["go"]);
+}
@@ -0,0 +1,177 @@
+use crate::graph::Graph;
+
+/// Ciclos e profundidades do grafo.
+///
+/// A profundidade de um tópico é o número de pré-requisitos na trilha mais
+/// longa até ele (0 para quem não tem pré-requisitos). Tópicos dentro de um
+/// ciclo, ou que dependem de um, ficam sem profundidade.
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    /// Componentes fortemente conexos com mais de um nó ou com laço próprio.
+    pub cycles: Vec<Vec<usize>>,
+    pub depth: Vec<Option<usize>>,
+    /// Todos os nós, pré-requisitos antes de quem depende deles. Dentro de um
+    /// ciclo a ordem é arbitrária.
+    pub order: Vec<usize>,
+    /// Pré-requisito anterior na trilha mais longa.
+    via: Vec<Option<usize>>,
+    /// Índice em `cycles` de cada nó que está em um ciclo.
+    cycle: Vec<Option<usize>>,
+}
+
+impl Analysis {
+    pub fn new(graph: &Graph) -> Self {
+        let n = graph.nodes.len();
+        let components = Tarjan::run(graph);
+
+        let mut analysis = Self {
+            cycles: Vec::new(),
+            depth: vec![None; n],
+            order: Vec::with_capacity(n),
+            via: vec![None; n],
+            cycle: vec![None; n],
+        };
+
+        // Tarjan fecha um componente só depois de todos os que ele alcança,
+        // ou seja, pré-requisitos saem primeiro
+        for component in components {
+            analysis.order.extend(&component);
+            let cyclic =
+                component.len() > 1 || graph.prerequisites(component[0]).contains(&component[0]);
+            if cyclic {
+                analysis.cycles.push(component);
+                continue;
+            }
+
+            let node = component[0];
+            let mut depth = Some(0);
+            let mut via = None;
+            for prerequisite in graph.prerequisites(node) {
+                match (depth, analysis.depth[prerequisite]) {
+                    (Some(current), Some(d)) if via.is_none() || d + 1 > current => {
+                        depth = Some(d + 1);
+                        via = Some(prerequisite);
+                    }
+                    (_, None) => {
+                        depth = None;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            analysis.depth[node] = depth;
+            analysis.via[node] = via;
+        }
+
+        for cycle in &mut analysis.cycles {
+            cycle.sort_unstable();
+        }
+        analysis.cycles.sort();
+        for (index, cycle) in analysis.cycles.iter().enumerate() {
+            for &node in cycle {
+                analysis.cycle[node] = Some(index);
+            }
+        }
+        analysis
+    }
+
+    pub fn in_cycle(&self, node: usize) -> bool {
+        self.cycle[node].is_some()
+    }
+
+    /// Índice em `cycles` do ciclo que contém `node`.
+    pub fn cycle_of(&self, node: usize) -> Option<usize> {
+        self.cycle[node]
+    }
+
+    /// Trilha mais longa até `node`, terminando nele. Vazia quando a
+    /// profundidade é indefinida.
+    pub fn path(&self, node: usize) -> Vec<usize> {
+        if self.depth[node].is_none() {
+            return Vec::new();
+        }
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(previous) = self.via[current] {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Nós em ordem decrescente de profundidade (desempate pela ordem do grafo).
+    pub fn deepest(&self) -> Vec<usize> {
+        let mut nodes: Vec<usize> = (0..self.depth.len())
+            .filter(|&n| self.depth[n].is_some())
+            .collect();
+        nodes.sort_by(|&a, &b| self.depth[b].cmp(&self.depth[a]).then(a.cmp(&b)));
+        nodes
+    }
+}
+
+/// Componentes fortemente conexos (Tarjan), seguindo as arestas do tópico para
+/// os pré-requisitos.
+struct Tarjan<'a> {
+    graph: &'a Graph,
+    index: Vec<Option<usize>>,
+    lowlink: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn run(graph: &'a Graph) -> Vec<Vec<usize>> {
+        let n = graph.nodes.len();
+        let mut tarjan = Self {
+            graph,
+            index: vec![None; n],
+            lowlink: vec![0; n],
+            on_stack: vec![false; n],
+            stack: Vec::new(),
+            next: 0,
+            components: Vec::new(),
+        };
+        for node in 0..n {
+            if tarjan.index[node].is_none() {
+                tarjan.visit(node);
+            }
+        }
+        tarjan.components
+    }
+
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next);
+        self.lowlink[node] = self.next;
+        self.next += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for prerequisite in self.graph.prerequisites(node) {
+            match self.index[prerequisite] {
+                None => {
+                    self.visit(prerequisite);
+                    self.lowlink[node] = self.lowlink[node].min(self.lowlink[prerequisite]);
+                }
+                Some(index) if self.on_stack[prerequisite] => {
+                    self.lowlink[node] = self.lowlink[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.lowlink[node]) == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
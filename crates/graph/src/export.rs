@@ -0,0 +1,177 @@
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use themelion_content::model::Difficulty;
+
+use crate::analysis::Analysis;
+use crate::graph::{Dangling, Duplicate, Graph, RoomInfo, SharedSlug};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report<'a> {
+    pub rooms: &'a [RoomInfo],
+    pub nodes: Vec<NodeReport<'a>>,
+    pub edges: Vec<EdgeReport<'a>>,
+    pub cycles: Vec<Vec<&'a str>>,
+    pub dangling: &'a [Dangling],
+    pub duplicates: &'a [Duplicate],
+    pub shared_slugs: Vec<SharedSlug>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeReport<'a> {
+    pub id: &'a str,
+    pub room: &'a str,
+    pub category: &'a str,
+    pub slug: &'a str,
+    pub title: &'a str,
+    pub difficulty: Difficulty,
+    pub file: &'a str,
+    pub line: usize,
+    /// `null` quando o tópico está em um ciclo ou depende de um.
+    pub depth: Option<usize>,
+    pub in_cycle: bool,
+    /// Trilha mais longa até o tópico, do primeiro pré-requisito até ele.
+    pub longest_path: Vec<&'a str>,
+}
+
+#[derive(Serialize)]
+pub struct EdgeReport<'a> {
+    pub topic: &'a str,
+    pub prerequisite: &'a str,
+    pub ambiguous: bool,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(graph: &'a Graph, analysis: &Analysis) -> Self {
+        let id = |n: usize| graph.nodes[n].id.as_str();
+        Self {
+            rooms: &graph.rooms,
+            nodes: graph
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(n, node)| NodeReport {
+                    id: &node.id,
+                    room: &node.room,
+                    category: &node.category,
+                    slug: &node.slug,
+                    title: &node.title,
+                    difficulty: node.difficulty,
+                    file: &node.file,
+                    line: node.line,
+                    depth: analysis.depth[n],
+                    in_cycle: analysis.in_cycle(n),
+                    longest_path: analysis.path(n).into_iter().map(id).collect(),
+                })
+                .collect(),
+            edges: graph
+                .edges
+                .iter()
+                .map(|e| EdgeReport {
+                    topic: id(e.from),
+                    prerequisite: id(e.to),
+                    ambiguous: e.ambiguous,
+                })
+                .collect(),
+            cycles: analysis
+                .cycles
+                .iter()
+                .map(|c| c.iter().map(|&n| id(n)).collect())
+                .collect(),
+            dangling: &graph.dangling,
+            duplicates: &graph.duplicates,
+            shared_slugs: graph.shared_slugs(),
+        }
+    }
+}
+
+/// Graphviz DOT, um cluster por sala e setas do pré-requisito para o tópico.
+/// Com `room`, só os tópicos da sala e os pré-requisitos diretos de fora dela.
+/// Arestas de ciclo saem em vermelho; as ambíguas, tracejadas.
+pub fn to_dot(graph: &Graph, analysis: &Analysis, room: Option<&str>) -> String {
+    let included: Vec<bool> = match room {
+        None => vec![true; graph.nodes.len()],
+        Some(room) => {
+            let mut included: Vec<bool> = graph.nodes.iter().map(|n| n.room == room).collect();
+            for edge in &graph.edges {
+                if graph.nodes[edge.from].room == room {
+                    included[edge.to] = true;
+                }
+            }
+            included
+        }
+    };
+
+    let mut out = String::new();
+    out.push_str("digraph prerequisites {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=rounded, fontsize=10];\n");
+
+    for (index, info) in graph.rooms.iter().enumerate() {
+        let members: Vec<usize> = (0..graph.nodes.len())
+            .filter(|&n| included[n] && graph.nodes[n].room == info.slug)
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "  subgraph cluster_{index} {{");
+        let _ = writeln!(out, "    label={};", quote(&info.name));
+        for n in members {
+            let node = &graph.nodes[n];
+            let label = match analysis.depth[n] {
+                Some(depth) => format!("{}\n({depth})", node.title),
+                None => node.title.clone(),
+            };
+            let color = if analysis.in_cycle(n) {
+                ", color=red"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "    {} [label={}{color}];",
+                quote(&node.id),
+                quote(&label)
+            );
+        }
+        out.push_str("  }\n");
+    }
+
+    for edge in &graph.edges {
+        if !included[edge.from] || !included[edge.to] {
+            continue;
+        }
+        let mut attrs = Vec::new();
+        let cycle = analysis.cycle_of(edge.from);
+        if cycle.is_some() && cycle == analysis.cycle_of(edge.to) {
+            attrs.push("color=red");
+        }
+        if edge.ambiguous {
+            attrs.push("style=dashed");
+        }
+        let attrs = if attrs.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attrs.join(", "))
+        };
+        let _ = writeln!(
+            out,
+            "  {} -> {}{attrs};",
+            quote(&graph.nodes[edge.to].id),
+            quote(&graph.nodes[edge.from].id)
+        );
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use themelion_content::model::Difficulty;
+use themelion_content::registry::RoomLines;
+use themelion_content::{Error, Result, load_rooms, relative_path};
+
+/// Um tópico do registry. O mesmo slug pode existir em mais de uma sala, então
+/// a identidade de um nó é `<sala>/<slug>`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Node {
+    pub id: String,
+    pub room: String,
+    pub category: String,
+    pub slug: String,
+    pub title: String,
+    pub difficulty: Difficulty,
+    pub order: u32,
+    pub prerequisites: Vec<String>,
+    /// `data/registry/rooms/<sala>.yaml`
+    pub file: String,
+    pub line: usize,
+    /// Linha do campo `prerequisites` (ou da entrada, se ausente).
+    pub prerequisites_line: usize,
+}
+
+/// Aresta do tópico para um pré-requisito. `ambiguous` marca slugs que não
+/// existem na sala do tópico e aparecem em mais de uma outra sala: nesse caso
+/// há uma aresta para cada candidato.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub ambiguous: bool,
+}
+
+/// Sala com nome e ordem, para agrupar a exportação.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomInfo {
+    pub slug: String,
+    pub name: String,
+    pub icon: String,
+    pub order: u32,
+}
+
+/// Pré-requisito que não existe em nenhuma sala.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dangling {
+    pub topic: String,
+    pub prerequisite: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// Entrada repetida dentro da mesma sala; só a primeira vira nó.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Duplicate {
+    pub room: String,
+    pub slug: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// Grafo de pré-requisitos de todas as salas de `data/registry/rooms`.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    pub rooms: Vec<RoomInfo>,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub dangling: Vec<Dangling>,
+    pub duplicates: Vec<Duplicate>,
+    /// Arestas de saída (pré-requisitos) de cada nó, índices em `edges`.
+    outgoing: Vec<Vec<usize>>,
+    /// slug → nós com esse slug, na ordem das salas.
+    by_slug: HashMap<String, Vec<usize>>,
+}
+
+impl Graph {
+    pub fn load(root: &Path) -> Result<Self> {
+        let mut rooms = load_rooms(root)?;
+        rooms.sort_by_key(|(_, file)| file.order);
+
+        let mut graph = Self::default();
+        let mut ids: HashMap<String, usize> = HashMap::new();
+
+        for (path, data) in &rooms {
+            let raw = fs::read_to_string(path).map_err(|source| Error::Io {
+                path: path.clone(),
+                source,
+            })?;
+            let lines = RoomLines::parse(&raw);
+            let file = relative_path(root, path);
+
+            graph.rooms.push(RoomInfo {
+                slug: data.room.clone(),
+                name: data.name.clone(),
+                icon: data.icon.clone(),
+                order: data.order,
+            });
+
+            for category in &data.categories {
+                for topic in &category.topics {
+                    let id = format!("{}/{}", data.room, topic.slug);
+                    let entry = lines.topic(&topic.slug);
+                    let line = entry.map_or(1, |t| t.slug);
+                    if ids.contains_key(&id) {
+                        graph.duplicates.push(Duplicate {
+                            room: data.room.clone(),
+                            slug: topic.slug.clone(),
+                            file: file.clone(),
+                            line,
+                        });
+                        continue;
+                    }
+                    let index = graph.nodes.len();
+                    ids.insert(id.clone(), index);
+                    graph
+                        .by_slug
+                        .entry(topic.slug.clone())
+                        .or_default()
+                        .push(index);
+                    graph.nodes.push(Node {
+                        id,
+                        room: data.room.clone(),
+                        category: category.slug.clone(),
+                        slug: topic.slug.clone(),
+                        title: topic.title.clone(),
+                        difficulty: topic.difficulty,
+                        order: topic.order,
+                        prerequisites: topic.prerequisites.clone(),
+                        file: file.clone(),
+                        line,
+                        prerequisites_line: entry.map_or(line, |t| t.field("prerequisites")),
+                    });
+                }
+            }
+        }
+
+        graph.outgoing = vec![Vec::new(); graph.nodes.len()];
+        for from in 0..graph.nodes.len() {
+            for prerequisite in graph.nodes[from].prerequisites.clone() {
+                let targets = graph.resolve(&prerequisite, &graph.nodes[from].room);
+                if targets.is_empty() {
+                    let node = &graph.nodes[from];
+                    graph.dangling.push(Dangling {
+                        topic: node.id.clone(),
+                        prerequisite,
+                        file: node.file.clone(),
+                        line: node.prerequisites_line,
+                    });
+                    continue;
+                }
+                let ambiguous = targets.len() > 1;
+                for to in targets {
+                    graph.outgoing[from].push(graph.edges.len());
+                    graph.edges.push(Edge {
+                        from,
+                        to,
+                        ambiguous,
+                    });
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Nós que um pré-requisito `slug` citado em `room` pode significar: o da
+    /// própria sala, se existir; senão todas as outras ocorrências.
+    pub fn resolve(&self, slug: &str, room: &str) -> Vec<usize> {
+        let Some(candidates) = self.by_slug.get(slug) else {
+            return Vec::new();
+        };
+        match candidates.iter().find(|&&n| self.nodes[n].room == room) {
+            Some(&local) => vec![local],
+            None => candidates.clone(),
+        }
+    }
+
+    /// Nós com o slug, em qualquer sala.
+    pub fn find(&self, slug: &str) -> &[usize] {
+        self.by_slug.get(slug).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn node_by_id(&self, id: &str) -> Option<usize> {
+        self.nodes.iter().position(|n| n.id == id)
+    }
+
+    /// Pré-requisitos diretos de `node`, sem repetição.
+    pub fn prerequisites(&self, node: usize) -> Vec<usize> {
+        let mut out: Vec<usize> = Vec::new();
+        for &edge in &self.outgoing[node] {
+            let to = self.edges[edge].to;
+            if !out.contains(&to) {
+                out.push(to);
+            }
+        }
+        out
+    }
+
+    /// Slugs presentes em mais de uma sala, com as salas e quem os cita.
+    pub fn shared_slugs(&self) -> Vec<SharedSlug> {
+        let mut shared: Vec<SharedSlug> = self
+            .by_slug
+            .iter()
+            .filter(|(_, nodes)| nodes.len() > 1)
+            .map(|(slug, nodes)| {
+                let mut referenced_by: Vec<usize> = Vec::new();
+                let mut ambiguous_in: Vec<String> = Vec::new();
+                for edge in self.edges.iter().filter(|e| nodes.contains(&e.to)) {
+                    if !referenced_by.contains(&edge.from) {
+                        referenced_by.push(edge.from);
+                        if edge.ambiguous {
+                            ambiguous_in.push(self.nodes[edge.from].id.clone());
+                        }
+                    }
+                }
+                SharedSlug {
+                    slug: slug.clone(),
+                    rooms: nodes.iter().map(|&n| self.nodes[n].room.clone()).collect(),
+                    references: referenced_by.len(),
+                    ambiguous_in,
+                }
+            })
+            .collect();
+        shared.sort_by(|a, b| a.slug.cmp(&b.slug));
+        shared
+    }
+}
+
+/// Slug repetido entre salas. `ambiguous_in` lista os tópicos cujo
+/// pré-requisito não tem como ser resolvido para uma única sala.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedSlug {
+    pub slug: String,
+    pub rooms: Vec<String>,
+    pub references: usize,
+    pub ambiguous_in: Vec<String>,
+}
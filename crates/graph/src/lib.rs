@@ -0,0 +1,14 @@
+//! Grafo de pré-requisitos de `data/registry/rooms`.
+//!
+//! Cada tópico do registry vira um nó `<sala>/<slug>`. Um pré-requisito é
+//! resolvido primeiro na sala do próprio tópico; se não existir lá, aponta
+//! para todas as salas que têm o slug (e a aresta fica marcada como ambígua).
+//! [`Analysis`] encontra ciclos e a trilha mais longa até cada tópico, e
+//! [`export`] gera Graphviz DOT e JSON.
+
+mod analysis;
+pub mod export;
+mod graph;
+
+pub use analysis::Analysis;
+pub use graph::{Dangling, Duplicate, Edge, Graph, Node, RoomInfo, SharedSlug};
@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand};
+use themelion_graph::export::{self, Report};
+use themelion_graph::{Analysis, Graph};
+
+/// Analisa o grafo de pré-requisitos de data/registry/rooms.
+#[derive(Parser)]
+#[command(name = "themelion-graph", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Resumo: ciclos, pré-requisitos inexistentes, slugs repetidos e trilhas
+    /// mais longas. Falha se houver ciclos ou pré-requisitos inexistentes.
+    Report {
+        /// Quantas trilhas mais longas listar
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Graphviz DOT (pré-requisito → tópico)
+    Dot {
+        /// Só os tópicos desta sala e seus pré-requisitos diretos
+        #[arg(long)]
+        room: Option<String>,
+    },
+    /// Nós, arestas, ciclos e profundidades em JSON
+    Json,
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let graph = Graph::load(&cli.root).context("Erro ao carregar o registry")?;
+    let analysis = Analysis::new(&graph);
+
+    match cli.command.unwrap_or(Command::Report { top: 10 }) {
+        Command::Report { top } => return Ok(report(&graph, &analysis, top)),
+        Command::Dot { room } => {
+            if let Some(room) = &room
+                && !graph.rooms.iter().any(|r| &r.slug == room)
+            {
+                bail!("Sala \"{room}\" não existe em data/registry/rooms/");
+            }
+            print!("{}", export::to_dot(&graph, &analysis, room.as_deref()));
+        }
+        Command::Json => {
+            let report = Report::new(&graph, &analysis);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn report(graph: &Graph, analysis: &Analysis, top: usize) -> ExitCode {
+    let id = |n: usize| graph.nodes[n].id.as_str();
+    let ambiguous = graph.edges.iter().filter(|e| e.ambiguous).count();
+    println!(
+        "{} salas, {} tópicos, {} arestas ({ambiguous} ambíguas)",
+        graph.rooms.len(),
+        graph.nodes.len(),
+        graph.edges.len()
+    );
+
+    println!("\nCiclos ({}):", analysis.cycles.len());
+    for cycle in &analysis.cycles {
+        let members: Vec<&str> = cycle.iter().map(|&n| id(n)).collect();
+        println!("  {}", members.join(", "));
+    }
+
+    println!("\nPré-requisitos inexistentes ({}):", graph.dangling.len());
+    for dangling in &graph.dangling {
+        println!(
+            "  {}:{}: {} cita \"{}\"",
+            dangling.file, dangling.line, dangling.topic, dangling.prerequisite
+        );
+    }
+
+    if !graph.duplicates.is_empty() {
+        println!(
+            "\nEntradas repetidas na mesma sala ({}):",
+            graph.duplicates.len()
+        );
+        for duplicate in &graph.duplicates {
+            println!(
+                "  {}:{}: {}",
+                duplicate.file, duplicate.line, duplicate.slug
+            );
+        }
+    }
+
+    let shared = graph.shared_slugs();
+    println!("\nSlugs repetidos entre salas ({}):", shared.len());
+    for slug in &shared {
+        println!(
+            "  {}: {} ({} referência(s))",
+            slug.slug,
+            slug.rooms.join(", "),
+            slug.references
+        );
+        for topic in &slug.ambiguous_in {
+            println!("    ambíguo em {topic}");
+        }
+    }
+
+    println!("\nTrilhas mais longas:");
+    for node in analysis.deepest().into_iter().take(top) {
+        let path: Vec<&str> = analysis.path(node).into_iter().map(id).collect();
+        println!(
+            "  {:>3}  {}",
+            analysis.depth[node].unwrap_or_default(),
+            path.join(" → ")
+        );
+    }
+
+    if analysis.cycles.is_empty() && graph.dangling.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,170 @@
+use std::fs;
+use std::path::Path;
+
+use themelion_graph::{Analysis, Graph};
+
+/// `(slug, pré-requisitos)` de cada tópico, todos na categoria `geral`.
+type Topics<'a> = &'a [(&'a str, &'a [&'a str])];
+
+fn write_room(root: &Path, room: &str, order: u32, topics: Topics) {
+    let mut yaml = format!(
+        "room: {room}\nname: \"{room}\"\ndescription: \"\"\nicon: \"brain\"\norder: {order}\n\n\
+         categories:\n  - slug: \"geral\"\n    name: \"Geral\"\n    order: 1\n    topics:\n"
+    );
+    for (i, (slug, prerequisites)) in topics.iter().enumerate() {
+        let prerequisites: Vec<String> = prerequisites.iter().map(|p| format!("\"{p}\"")).collect();
+        yaml.push_str(&format!(
+            "      - slug: \"{slug}\"\n        title: \"{slug}\"\n        difficulty: beginner\n        \
+             order: {}\n        prerequisites: [{}]\n",
+            i + 1,
+            prerequisites.join(", ")
+        ));
+    }
+    let dir = root.join("data/registry/rooms");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(format!("{room}.yaml")), yaml).unwrap();
+}
+
+fn ids(graph: &Graph, nodes: &[usize]) -> Vec<String> {
+    nodes.iter().map(|&n| graph.nodes[n].id.clone()).collect()
+}
+
+fn node(graph: &Graph, id: &str) -> usize {
+    graph.node_by_id(id).unwrap()
+}
+
+#[test]
+fn detects_cycles_and_leaves_their_dependents_without_depth() {
+    let root = tempfile::tempdir().unwrap();
+    write_room(
+        root.path(),
+        "fundamentos",
+        1,
+        &[
+            ("arrays", &[]),
+            ("a", &["c", "arrays"]),
+            ("b", &["a"]),
+            ("c", &["b"]),
+            ("depois", &["a"]),
+            ("laco", &["laco"]),
+        ],
+    );
+    let graph = Graph::load(root.path()).unwrap();
+    let analysis = Analysis::new(&graph);
+
+    let cycles: Vec<Vec<String>> = analysis.cycles.iter().map(|c| ids(&graph, c)).collect();
+    assert_eq!(
+        cycles,
+        [
+            vec!["fundamentos/a", "fundamentos/b", "fundamentos/c"],
+            vec!["fundamentos/laco"],
+        ]
+    );
+    assert!(analysis.in_cycle(node(&graph, "fundamentos/b")));
+    assert!(!analysis.in_cycle(node(&graph, "fundamentos/depois")));
+    assert_eq!(analysis.depth[node(&graph, "fundamentos/arrays")], Some(0));
+    assert_eq!(analysis.depth[node(&graph, "fundamentos/depois")], None);
+    assert!(analysis.path(node(&graph, "fundamentos/depois")).is_empty());
+}
+
+#[test]
+fn reports_dangling_prerequisites_at_their_line() {
+    let root = tempfile::tempdir().unwrap();
+    write_room(
+        root.path(),
+        "fundamentos",
+        1,
+        &[("arrays", &[]), ("stacks", &["arrays", "nao-existe"])],
+    );
+    let graph = Graph::load(root.path()).unwrap();
+
+    let dangling: Vec<_> = graph
+        .dangling
+        .iter()
+        .map(|d| {
+            (
+                d.topic.as_str(),
+                d.prerequisite.as_str(),
+                d.file.as_str(),
+                d.line,
+            )
+        })
+        .collect();
+    assert_eq!(
+        dangling,
+        [(
+            "fundamentos/stacks",
+            "nao-existe",
+            "data/registry/rooms/fundamentos.yaml",
+            21
+        )]
+    );
+    // O pré-requisito que existe continua virando aresta
+    assert_eq!(
+        ids(
+            &graph,
+            &graph.prerequisites(node(&graph, "fundamentos/stacks"))
+        ),
+        ["fundamentos/arrays"]
+    );
+}
+
+#[test]
+fn resolves_shared_slugs_in_the_same_room_first() {
+    let root = tempfile::tempdir().unwrap();
+    write_room(
+        root.path(),
+        "backend",
+        1,
+        &[("sql-injection", &[]), ("orm", &["sql-injection"])],
+    );
+    write_room(root.path(), "cybersecurity", 2, &[("sql-injection", &[])]);
+    write_room(root.path(), "frontend", 3, &[("forms", &["sql-injection"])]);
+    let graph = Graph::load(root.path()).unwrap();
+
+    assert_eq!(
+        ids(&graph, &graph.resolve("sql-injection", "backend")),
+        ["backend/sql-injection"]
+    );
+    assert_eq!(
+        ids(&graph, &graph.prerequisites(node(&graph, "frontend/forms"))),
+        ["backend/sql-injection", "cybersecurity/sql-injection"]
+    );
+
+    let shared = graph.shared_slugs();
+    assert_eq!(shared.len(), 1);
+    assert_eq!(shared[0].rooms, ["backend", "cybersecurity"]);
+    assert_eq!(shared[0].references, 2);
+    assert_eq!(shared[0].ambiguous_in, ["frontend/forms"]);
+}
+
+#[test]
+fn follows_the_longest_path() {
+    let root = tempfile::tempdir().unwrap();
+    write_room(
+        root.path(),
+        "fundamentos",
+        1,
+        &[
+            ("arrays", &[]),
+            ("linked-lists", &["arrays"]),
+            ("stacks", &["linked-lists"]),
+            ("queues", &["arrays", "stacks"]),
+        ],
+    );
+    let graph = Graph::load(root.path()).unwrap();
+    let analysis = Analysis::new(&graph);
+
+    let queues = node(&graph, "fundamentos/queues");
+    assert_eq!(analysis.depth[queues], Some(3));
+    assert_eq!(
+        ids(&graph, &analysis.path(queues)),
+        [
+            "fundamentos/arrays",
+            "fundamentos/linked-lists",
+            "fundamentos/stacks",
+            "fundamentos/queues"
+        ]
+    );
+    assert_eq!(analysis.deepest()[0], queues);
+}
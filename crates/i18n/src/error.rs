@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Content(#[from] themelion_content::Error),
+
+    #[error(transparent)]
+    Examples(#[from] themelion_examples::Error),
+
+    #[error("Erro ao acessar {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("YAML inválido em {}: {source}", path.display())]
+    Yaml {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+
+    #[error("{}:{line}: {message}", path.display())]
+    Po {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+
+    #[error(
+        "{file}: {found} seções na tradução e {expected} no original; alinhe as duas antes de registrar"
+    )]
+    Misaligned {
+        file: String,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("Tópico \"{slug}\" não tem tradução em {locale}")]
+    NotTranslated { slug: String, locale: String },
+
+    #[error("Tópico \"{slug}\" não tem examples/rust.rs")]
+    NoRustExample { slug: String },
+
+    #[error("{file}:{line}: tradução recusada: {message}")]
+    Translation {
+        file: String,
+        line: usize,
+        message: String,
+    },
+
+    #[error("{file}: a tradução mudaria o código fora dos comentários e das strings")]
+    CodeChanged { file: String },
+}
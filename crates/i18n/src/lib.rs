@@ -0,0 +1,559 @@
+//! Edições traduzidas do conteúdo, em arquivos paralelos ao original em pt-BR:
+//!
+//! ```text
+//! <tópico>/topic.mdx               original
+//! <tópico>/topic.en.mdx            tradução (frontmatter próprio, mesmas seções `##`)
+//! <tópico>/examples/rust.rs
+//! <tópico>/examples/rust.en.rs     comentários e textos traduzidos
+//! <tópico>/translation.en.yaml     o que a tradução traduziu ([`lock`])
+//! ```
+//!
+//! A varredura do conteúdo ignora os arquivos com locale no nome, então o site
+//! continua mostrando só o original. [`stamp`] registra as impressões digitais
+//! das seções do original quando a tradução é sincronizada; [`check`] compara
+//! o registro com o original de agora e aponta as seções traduzidas que ficaram
+//! desatualizadas, as que faltam e as que não correspondem a mais nada.
+//!
+//! O `rust.<locale>.rs` não precisa ser editado à mão: [`extract_catalog`]
+//! junta os comentários e os textos de exibição do `rust.rs` em
+//! `examples/rust.<locale>.po` ([`po`]), e [`apply_catalog`] grava a cópia
+//! traduzida, com o código idêntico ao original ([`messages`]).
+
+mod error;
+pub mod lock;
+pub mod messages;
+pub mod po;
+pub mod units;
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use themelion_content::{find_topic_files, frontmatter, locale_of, relative_path};
+
+use crate::lock::{Entry, Lock};
+use crate::po::Catalog;
+use crate::units::{Unit, example_units, fingerprint, topic_units};
+
+pub use error::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueKind {
+    /// O original mudou depois da tradução.
+    Stale,
+    /// Seção ou arquivo do original sem tradução.
+    Missing,
+    /// Tradução de algo que não existe mais no original.
+    Orphaned,
+    /// Tradução sem `translation.<locale>.yaml` (ou sem a entrada do arquivo).
+    Unstamped,
+    /// A tradução tem outro número de seções que o registro.
+    Misaligned,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.kind {
+            IssueKind::Stale => "desatualizada",
+            IssueKind::Missing => "faltando",
+            IssueKind::Orphaned => "órfã",
+            IssueKind::Unstamped => "sem registro",
+            IssueKind::Misaligned => "desalinhada",
+        };
+        write!(f, "{}:{}: {label}: {}", self.file, self.line, self.message)
+    }
+}
+
+/// Situação da tradução de um tópico em um locale.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicReport {
+    pub topic: String,
+    pub locale: String,
+    /// Há `topic.<locale>.mdx` ou algum exemplo traduzido.
+    pub translated: bool,
+    pub issues: Vec<Issue>,
+}
+
+/// Locales com pelo menos um arquivo traduzido sob `data/content`.
+pub fn locales(root: &Path) -> Result<BTreeSet<String>> {
+    let mut found = BTreeSet::new();
+    for topic_file in find_topic_files(root)? {
+        let dir = topic_dir(&topic_file);
+        for path in files(dir)?.into_iter().chain(files(&dir.join("examples"))?) {
+            if let Some(locale) = locale_of(&path) {
+                found.insert(locale.to_string());
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Compara a tradução do tópico de `topic_file` com o original.
+pub fn check(root: &Path, topic_file: &Path, locale: &str) -> Result<TopicReport> {
+    let files = TopicFiles::new(topic_file, locale)?;
+    let mut report = TopicReport {
+        topic: files.slug.clone(),
+        locale: locale.to_string(),
+        translated: files.translated(),
+        issues: Vec::new(),
+    };
+    if !report.translated {
+        return Ok(report);
+    }
+    let issues = &mut report.issues;
+    let stamp_hint = format!(
+        "rode `themelion-i18n stamp {} --locale {locale}`",
+        files.slug
+    );
+
+    let Some(lock) = lock::load(&lock::path(files.dir, locale))? else {
+        let first = files.first_translated();
+        issues.push(Issue {
+            kind: IssueKind::Unstamped,
+            file: relative_path(root, first),
+            line: 1,
+            message: format!("Tradução sem registro do original; {stamp_hint}."),
+        });
+        return Ok(report);
+    };
+
+    let original_file = relative_path(root, topic_file);
+    if files.topic.is_file() {
+        let translated_file = relative_path(root, &files.topic);
+        let original = TopicSource::read(topic_file)?;
+        let translated = TopicSource::read(&files.topic)?;
+        let title = original.title.as_deref().map(fingerprint);
+        if lock.title.is_some() && lock.title != title {
+            issues.push(Issue {
+                kind: IssueKind::Stale,
+                file: translated_file.clone(),
+                line: translated.title_line,
+                message: "O título do original mudou.".to_string(),
+            });
+        }
+        compare(
+            (&original_file, &original.units),
+            (&translated_file, &translated.units),
+            &lock.topic,
+            issues,
+        );
+    } else {
+        issues.push(Issue {
+            kind: IssueKind::Missing,
+            file: original_file,
+            line: 1,
+            message: format!("Sem {}.", file_name(&files.topic)),
+        });
+    }
+
+    for example in &files.examples {
+        match (&example.original, &example.translated) {
+            (Some(original), None) => issues.push(Issue {
+                kind: IssueKind::Missing,
+                file: relative_path(root, original),
+                line: 1,
+                message: format!("Exemplo sem tradução em {locale}."),
+            }),
+            (None, Some(translated)) => issues.push(Issue {
+                kind: IssueKind::Orphaned,
+                file: relative_path(root, translated),
+                line: 1,
+                message: format!("Não existe mais o original `{}`.", example.name),
+            }),
+            (Some(original), Some(translated)) => {
+                let translated_file = relative_path(root, translated);
+                let Some(entries) = lock.examples.get(&example.name) else {
+                    issues.push(Issue {
+                        kind: IssueKind::Unstamped,
+                        file: translated_file,
+                        line: 1,
+                        message: format!("Exemplo sem registro do original; {stamp_hint}."),
+                    });
+                    continue;
+                };
+                compare(
+                    (
+                        &relative_path(root, original),
+                        &example_units(&read(original)?),
+                    ),
+                    (&translated_file, &example_units(&read(translated)?)),
+                    entries,
+                    issues,
+                );
+            }
+            (None, None) => {}
+        }
+    }
+    Ok(report)
+}
+
+/// Registra o original atual como a versão traduzida. As traduções precisam
+/// ter as mesmas seções (e os exemplos, as mesmas faixas) que o original.
+pub fn stamp(root: &Path, topic_file: &Path, locale: &str) -> Result<Lock> {
+    let files = TopicFiles::new(topic_file, locale)?;
+    if !files.translated() {
+        return Err(Error::NotTranslated {
+            slug: files.slug,
+            locale: locale.to_string(),
+        });
+    }
+
+    let aligned = |translated: &Path, original: &[Unit], units: &[Unit]| -> Result<Vec<Entry>> {
+        if original.len() != units.len() {
+            return Err(Error::Misaligned {
+                file: relative_path(root, translated),
+                expected: original.len(),
+                found: units.len(),
+            });
+        }
+        Ok(original.iter().map(Entry::from).collect())
+    };
+
+    let mut lock = Lock::default();
+    if files.topic.is_file() {
+        let original = TopicSource::read(topic_file)?;
+        let translated = TopicSource::read(&files.topic)?;
+        lock.title = original.title.as_deref().map(fingerprint);
+        lock.topic = aligned(&files.topic, &original.units, &translated.units)?;
+    }
+    for example in &files.examples {
+        if let (Some(original), Some(translated)) = (&example.original, &example.translated) {
+            let entries = aligned(
+                translated,
+                &example_units(&read(original)?),
+                &example_units(&read(translated)?),
+            )?;
+            lock.examples.insert(example.name.clone(), entries);
+        }
+    }
+
+    lock::save(&lock::path(files.dir, locale), &lock)?;
+    Ok(lock)
+}
+
+/// Resultado de [`extract_catalog`].
+#[derive(Debug, Clone)]
+pub struct CatalogUpdate {
+    pub path: PathBuf,
+    pub entries: usize,
+    pub translated: usize,
+    /// Traduções guardadas de textos que saíram do original.
+    pub obsolete: usize,
+}
+
+/// Resultado de [`apply_catalog`].
+#[derive(Debug, Clone)]
+pub struct CatalogApplied {
+    pub path: PathBuf,
+    pub translated: usize,
+    pub total: usize,
+}
+
+/// Gera (ou atualiza) `examples/rust.<locale>.po` com os textos do
+/// `rust.rs`, mantendo as traduções que já estavam no catálogo.
+pub fn extract_catalog(root: &Path, topic_file: &Path, locale: &str) -> Result<CatalogUpdate> {
+    let (slug, original) = rust_example(topic_file)?;
+    let mut catalog = messages::extract(&relative_path(root, &original), &read(&original)?)?;
+    catalog.language = locale.to_string();
+    catalog.header_comments = vec![
+        format!("Comentários e textos de {slug}/examples/rust.rs para {locale}."),
+        "Gerado por `themelion-i18n extract`; os msgstr já preenchidos são mantidos.".to_string(),
+    ];
+
+    let path = catalog_path(topic_file, locale);
+    if path.is_file() {
+        catalog.merge(read_catalog(&path)?);
+    }
+    fs::write(&path, catalog.to_po()).map_err(|source| Error::Io {
+        path: path.clone(),
+        source,
+    })?;
+
+    let active = || catalog.entries.iter().filter(|e| !e.obsolete);
+    Ok(CatalogUpdate {
+        entries: active().count(),
+        translated: active().filter(|e| e.translation().is_some()).count(),
+        obsolete: catalog.entries.iter().filter(|e| e.obsolete).count(),
+        path,
+    })
+}
+
+/// Grava `examples/rust.<locale>.rs`: o `rust.rs` com as traduções do
+/// catálogo no lugar dos textos.
+pub fn apply_catalog(root: &Path, topic_file: &Path, locale: &str) -> Result<CatalogApplied> {
+    let (_, original) = rust_example(topic_file)?;
+    let catalog = read_catalog(&catalog_path(topic_file, locale))?;
+    let injected = messages::inject(&relative_path(root, &original), &read(&original)?, &catalog)?;
+
+    let path = original.with_file_name(format!("rust.{locale}.rs"));
+    fs::write(&path, &injected.source).map_err(|source| Error::Io {
+        path: path.clone(),
+        source,
+    })?;
+    Ok(CatalogApplied {
+        path,
+        translated: injected.translated,
+        total: injected.total,
+    })
+}
+
+fn rust_example(topic_file: &Path) -> Result<(String, PathBuf)> {
+    let dir = topic_dir(topic_file);
+    let original = dir.join("examples").join("rust.rs");
+    if !original.is_file() {
+        return Err(Error::NoRustExample {
+            slug: file_name(dir),
+        });
+    }
+    Ok((file_name(dir), original))
+}
+
+fn catalog_path(topic_file: &Path, locale: &str) -> PathBuf {
+    topic_dir(topic_file)
+        .join("examples")
+        .join(format!("rust.{locale}.po"))
+}
+
+fn read_catalog(path: &Path) -> Result<Catalog> {
+    po::parse(&read(path)?).map_err(|err| Error::Po {
+        path: path.to_path_buf(),
+        line: err.line,
+        message: err.message,
+    })
+}
+
+/// Confere as entradas do registro, na ordem das seções da tradução, contra
+/// as seções atuais do original.
+fn compare(
+    (original_file, original): (&str, &[Unit]),
+    (translated_file, translated): (&str, &[Unit]),
+    entries: &[Entry],
+    issues: &mut Vec<Issue>,
+) {
+    if translated.len() != entries.len() {
+        issues.push(Issue {
+            kind: IssueKind::Misaligned,
+            file: translated_file.to_string(),
+            line: 1,
+            message: format!(
+                "{} seções na tradução e {} no registro; as correspondências abaixo podem estar deslocadas.",
+                translated.len(),
+                entries.len()
+            ),
+        });
+    }
+
+    let mut matched = vec![false; original.len()];
+    for (i, entry) in entries.iter().enumerate() {
+        let line = translated.get(i).map_or(1, |unit| unit.line);
+        let source = label(&entry.source);
+        // Títulos repetidos casam na ordem em que aparecem
+        let found = original
+            .iter()
+            .enumerate()
+            .position(|(at, unit)| !matched[at] && unit.id == entry.source);
+        let Some(at) = found else {
+            issues.push(Issue {
+                kind: IssueKind::Orphaned,
+                file: translated_file.to_string(),
+                line,
+                message: format!("{source} não existe mais no original."),
+            });
+            continue;
+        };
+        matched[at] = true;
+
+        let unit = &original[at];
+        let changed = match (unit.text != entry.text, unit.code != entry.code) {
+            (true, true) => "texto e código",
+            (true, false) => "texto",
+            (false, true) => "código",
+            (false, false) => continue,
+        };
+        issues.push(Issue {
+            kind: IssueKind::Stale,
+            file: translated_file.to_string(),
+            line,
+            message: format!(
+                "{source} mudou no original depois da tradução ({changed}; {original_file}:{}).",
+                unit.line
+            ),
+        });
+    }
+
+    let translation = translated_file
+        .rsplit('/')
+        .next()
+        .unwrap_or(translated_file);
+    for (unit, _) in original.iter().zip(&matched).filter(|(_, m)| !**m) {
+        issues.push(Issue {
+            kind: IssueKind::Missing,
+            file: original_file.to_string(),
+            line: unit.line,
+            message: format!("{} sem tradução em {translation}.", label(&unit.id)),
+        });
+    }
+}
+
+fn label(id: &str) -> String {
+    if id.is_empty() {
+        "O trecho antes do primeiro título".to_string()
+    } else {
+        format!("\"{id}\"")
+    }
+}
+
+/// Os arquivos de um tópico num locale.
+struct TopicFiles<'a> {
+    slug: String,
+    dir: &'a Path,
+    /// `topic.<locale>.mdx`, exista ou não.
+    topic: PathBuf,
+    examples: Vec<ExamplePair>,
+}
+
+/// Um arquivo de `examples/` e a tradução dele; pelo menos um dos dois existe.
+struct ExamplePair {
+    /// Nome do original (`rust.rs`).
+    name: String,
+    original: Option<PathBuf>,
+    translated: Option<PathBuf>,
+}
+
+impl<'a> TopicFiles<'a> {
+    fn new(topic_file: &'a Path, locale: &str) -> Result<Self> {
+        let dir = topic_dir(topic_file);
+        let mut examples: Vec<ExamplePair> = Vec::new();
+        for path in files(&dir.join("examples"))? {
+            // O catálogo é insumo da tradução, não um exemplo
+            if path.extension().is_some_and(|ext| ext == "po") {
+                continue;
+            }
+            let (name, translated) = match locale_of(&path) {
+                None => (file_name(&path), false),
+                Some(l) if l == locale => {
+                    (file_name(&path).replacen(&format!(".{l}."), ".", 1), true)
+                }
+                Some(_) => continue,
+            };
+            let at = match examples.iter().position(|e| e.name == name) {
+                Some(at) => at,
+                None => {
+                    examples.push(ExamplePair {
+                        name,
+                        original: None,
+                        translated: None,
+                    });
+                    examples.len() - 1
+                }
+            };
+            if translated {
+                examples[at].translated = Some(path);
+            } else {
+                examples[at].original = Some(path);
+            }
+        }
+
+        Ok(Self {
+            slug: file_name(dir),
+            dir,
+            topic: dir.join(format!("topic.{locale}.mdx")),
+            examples,
+        })
+    }
+
+    fn translated(&self) -> bool {
+        self.topic.is_file() || self.examples.iter().any(|e| e.translated.is_some())
+    }
+
+    fn first_translated(&self) -> &Path {
+        if self.topic.is_file() {
+            return &self.topic;
+        }
+        self.examples
+            .iter()
+            .find_map(|e| e.translated.as_deref())
+            .unwrap_or(&self.topic)
+    }
+}
+
+/// Um `topic.mdx` (original ou tradução) dividido em seções.
+struct TopicSource {
+    title: Option<String>,
+    title_line: usize,
+    units: Vec<Unit>,
+}
+
+impl TopicSource {
+    fn read(path: &Path) -> Result<Self> {
+        let raw = read(path)?;
+        let doc = frontmatter::split(&raw).ok_or_else(|| {
+            Error::Content(themelion_content::Error::MissingFrontmatter {
+                path: path.to_path_buf(),
+            })
+        })?;
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(doc.yaml).map_err(|source| Error::Yaml {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Ok(Self {
+            title: yaml.get("title").and_then(|t| t.as_str()).map(String::from),
+            title_line: frontmatter::key_line(&doc, "title").unwrap_or(doc.yaml_line),
+            units: topic_units(doc.body.trim_end(), doc.body_line),
+        })
+    }
+}
+
+fn topic_dir(topic_file: &Path) -> &Path {
+    topic_file.parent().unwrap_or(Path::new("."))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Arquivos de `dir`, ordenados; nenhum se ele não existe.
+fn files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|source| Error::Io {
+                path: dir.to_path_buf(),
+                source,
+            })?
+            .path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
@@ -0,0 +1,97 @@
+//! `translation.<locale>.yaml`: o que a tradução de um tópico traduziu, na
+//! ordem das seções da tradução.
+//!
+//! ```yaml
+//! title: 5f0c2a9e61b7
+//! topic:
+//!   - source: O que é
+//!     text: 9a1d44c0e2f3
+//!   - source: Na prática
+//!     text: 3c7b1f0a9d22
+//!     code: e81f6a2c4b90
+//! examples:
+//!   rust.rs:
+//!     - source: ''
+//!       text: 0b4e7d19a6c5
+//!       code: 77a0c3e5f1d8
+//! ```
+//!
+//! A entrada `i` corresponde à `i`-ésima seção da tradução; `source` é o
+//! título da seção no original, porque o da tradução está em outra língua.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::units::Unit;
+use crate::{Error, Result};
+
+const HEADER: &str = "# Gerado por `themelion-i18n stamp`: impressões digitais do original em pt-BR\n\
+                      # no momento em que a tradução foi sincronizada. Não edite à mão.\n";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Lock {
+    /// Impressão do `title` do frontmatter original.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topic: Vec<Entry>,
+    /// Por arquivo original de `examples/` (`rust.rs`, não `rust.en.rs`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub examples: BTreeMap<String, Vec<Entry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+    pub source: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+impl From<&Unit> for Entry {
+    fn from(unit: &Unit) -> Self {
+        Self {
+            source: unit.id.clone(),
+            text: unit.text.clone(),
+            code: unit.code.clone(),
+        }
+    }
+}
+
+/// `<diretório do tópico>/translation.<locale>.yaml`
+pub fn path(topic_dir: &Path, locale: &str) -> PathBuf {
+    topic_dir.join(format!("translation.{locale}.yaml"))
+}
+
+/// `None` se a tradução nunca foi sincronizada.
+pub fn load(path: &Path) -> Result<Option<Lock>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_yaml::from_str(&raw)
+        .map(Some)
+        .map_err(|source| Error::Yaml {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+pub fn save(path: &Path, lock: &Lock) -> Result<()> {
+    let yaml = serde_yaml::to_string(lock).map_err(|source| Error::Yaml {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    fs::write(path, format!("{HEADER}{yaml}")).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
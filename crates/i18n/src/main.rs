@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use themelion_content::{find_topic_files, relative_path};
+use themelion_i18n::{
+    IssueKind, TopicReport, apply_catalog, check, extract_catalog, locales, stamp,
+};
+
+/// Acompanha as traduções do conteúdo (topic.<locale>.mdx,
+/// examples/<arquivo>.<locale>.<ext>) contra o original em pt-BR.
+#[derive(Parser)]
+#[command(name = "themelion-i18n", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".", global = true)]
+    root: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Seções traduzidas desatualizadas, faltando ou órfãs; falha se houver
+    Status {
+        /// Só estes locales; por padrão, todos os que têm algum arquivo
+        #[arg(long)]
+        locale: Vec<String>,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Registra o original atual como a versão que a tradução do tópico
+    /// acompanha (grava translation.<locale>.yaml)
+    Stamp {
+        /// Slug do tópico
+        topic: String,
+
+        #[arg(long)]
+        locale: String,
+    },
+    /// Junta os comentários e textos de exibição do examples/rust.rs do
+    /// tópico em examples/rust.<locale>.po, mantendo o que já foi traduzido
+    Extract {
+        /// Slug do tópico
+        topic: String,
+
+        #[arg(long, default_value = "en")]
+        locale: String,
+    },
+    /// Grava examples/rust.<locale>.rs com as traduções do catálogo; o
+    /// código fica idêntico ao do original
+    Inject {
+        /// Slug do tópico
+        topic: String,
+
+        #[arg(long, default_value = "en")]
+        locale: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    issues: usize,
+    /// Só os tópicos com alguma tradução.
+    topics: &'a [&'a TopicReport],
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let topic_files = find_topic_files(&cli.root).context("Erro ao procurar os tópicos")?;
+
+    match cli.command {
+        Command::Status { locale, format } => {
+            let locales = if locale.is_empty() {
+                locales(&cli.root)
+                    .context("Erro ao procurar as traduções")?
+                    .into_iter()
+                    .collect()
+            } else {
+                locale
+            };
+
+            let mut reports = Vec::new();
+            for locale in &locales {
+                for file in &topic_files {
+                    reports.push(check(&cli.root, file, locale)?);
+                }
+            }
+            let issues: usize = reports.iter().map(|r| r.issues.len()).sum();
+
+            match format {
+                Format::Text => {
+                    for issue in reports.iter().flat_map(|r| &r.issues) {
+                        println!("{issue}");
+                    }
+                    for locale in &locales {
+                        let of_locale: Vec<_> =
+                            reports.iter().filter(|r| &r.locale == locale).collect();
+                        let count = |kind| {
+                            of_locale
+                                .iter()
+                                .flat_map(|r| &r.issues)
+                                .filter(|i| i.kind == kind)
+                                .count()
+                        };
+                        eprintln!(
+                            "{locale}: {} de {} tópicos traduzidos; {} desatualizadas, {} faltando, {} órfãs",
+                            of_locale.iter().filter(|r| r.translated).count(),
+                            of_locale.len(),
+                            count(IssueKind::Stale),
+                            count(IssueKind::Missing),
+                            count(IssueKind::Orphaned),
+                        );
+                    }
+                    if locales.is_empty() {
+                        eprintln!("Nenhuma tradução encontrada");
+                    }
+                }
+                Format::Json => {
+                    let translated: Vec<&TopicReport> =
+                        reports.iter().filter(|r| r.translated).collect();
+                    let summary = Summary {
+                        issues,
+                        topics: &translated,
+                    };
+                    println!("{}", serde_json::to_string_pretty(&summary)?);
+                }
+            }
+
+            Ok(if issues > 0 {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            })
+        }
+        Command::Stamp { topic, locale } => {
+            let lock = stamp(&cli.root, find_topic(&topic_files, &topic)?, &locale)?;
+            let sections: usize = lock.examples.values().map(Vec::len).sum();
+            eprintln!(
+                "{topic} ({locale}): {} seções do tópico e {sections} trechos de {} exemplo(s) registrados",
+                lock.topic.len(),
+                lock.examples.len()
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Extract { topic, locale } => {
+            let update = extract_catalog(&cli.root, find_topic(&topic_files, &topic)?, &locale)?;
+            eprintln!(
+                "{}: {} textos, {} traduzidos, {} obsoletos",
+                relative_path(&cli.root, &update.path),
+                update.entries,
+                update.translated,
+                update.obsolete
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Inject { topic, locale } => {
+            let applied = apply_catalog(&cli.root, find_topic(&topic_files, &topic)?, &locale)?;
+            eprintln!(
+                "{}: {} de {} trechos traduzidos",
+                relative_path(&cli.root, &applied.path),
+                applied.translated,
+                applied.total
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn find_topic<'a>(topic_files: &'a [PathBuf], slug: &str) -> Result<&'a PathBuf> {
+    match topic_files
+        .iter()
+        .find(|f| f.parent().and_then(|d| d.file_name()) == Some(slug.as_ref()))
+    {
+        Some(file) => Ok(file),
+        None => bail!("Tópico \"{slug}\" não encontrado"),
+    }
+}
@@ -0,0 +1,473 @@
+//! Textos traduzíveis de um exemplo em Rust: os comentários e as strings que
+//! o programa mostra (`println!`, `format!`, `panic!`, `.expect()`...).
+//!
+//! O arquivo é lido com o lexer do próprio rustc, então uma string com `//`
+//! dentro ou um `println!` dentro de um comentário não confundem a extração.
+//! A tradução só troca o miolo desses tokens; [`inject`] confere, token a
+//! token, que o resto do arquivo continua idêntico ao original.
+
+use std::ops::Range;
+
+use rustc_lexer::unescape::unescape_str;
+use rustc_lexer::{LiteralKind, TokenKind};
+use themelion_examples::header;
+
+use crate::po::{Catalog, Entry};
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    LineComment,
+    BlockComment,
+    Str,
+    RawStr { hashes: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub kind: Kind,
+    /// Linha (1-based) do começo do token.
+    pub line: usize,
+    /// Bytes trocados pela tradução: o comentário sem o `//` e sem as bordas
+    /// em branco, ou o conteúdo entre as aspas.
+    pub range: Range<usize>,
+    /// O texto como quem lê o vê, já sem os escapes da string.
+    pub text: String,
+    /// Macro ou método que mostra a string; `None` nos comentários.
+    pub context: Option<&'static str>,
+    /// A string é de formatação: os `{}` precisam sobreviver à tradução.
+    pub format: bool,
+}
+
+impl Message {
+    /// `#.` do catálogo, para quem traduz saber de onde o texto vem.
+    pub fn note(&self) -> String {
+        match self.context {
+            None => "comentário".to_string(),
+            Some(context) => format!("texto de {context}"),
+        }
+    }
+
+    /// O que entra no lugar de [`Message::range`]; `Err` com o motivo quando a
+    /// tradução não cabe no token.
+    fn replacement(&self, translation: &str) -> std::result::Result<String, String> {
+        if self.format {
+            let (mut original, mut translated) =
+                (placeholders(&self.text), placeholders(translation));
+            original.sort_unstable();
+            translated.sort_unstable();
+            if original != translated {
+                return Err(format!(
+                    "os marcadores de formatação mudaram ({} no original, {} na tradução)",
+                    list(&original),
+                    list(&translated)
+                ));
+            }
+        }
+        match self.kind {
+            Kind::LineComment if translation.contains('\n') => {
+                Err("comentário de linha não pode ter quebra de linha".to_string())
+            }
+            Kind::BlockComment if translation.contains("*/") || translation.contains("/*") => {
+                Err("`/*` e `*/` fechariam ou abririam outro comentário".to_string())
+            }
+            Kind::RawStr { hashes }
+                if translation.contains(&format!("\"{}", "#".repeat(hashes))) =>
+            {
+                Err("as aspas fechariam a raw string".to_string())
+            }
+            Kind::Str => Ok(escape(translation)),
+            _ => Ok(translation.to_string()),
+        }
+    }
+}
+
+/// Comentários e strings de exibição de `source`, na ordem do arquivo. Ficam
+/// de fora as réguas (`// ====`), que não têm letras, e o bloco
+/// `// Cargo.toml:`, que é código.
+pub fn messages(file: &str, source: &str) -> Result<Vec<Message>> {
+    let header = header::parse(file, source)?;
+    let manifest = header
+        .line
+        .map(|line| line..=line + header.manifest.lines().count());
+
+    let tokens = tokenize(source);
+    let significant: Vec<&Token> = tokens
+        .iter()
+        .filter(|t| {
+            !matches!(
+                t.kind,
+                TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment { .. }
+            )
+        })
+        .collect();
+    let line_of = |offset: usize| source[..offset].matches('\n').count() + 1;
+
+    let mut messages = Vec::new();
+    for token in &tokens {
+        let range = match token.kind {
+            TokenKind::LineComment => comment_text(source, token.range.start + 2, token.range.end),
+            TokenKind::BlockComment { terminated: true } => {
+                comment_text(source, token.range.start + 2, token.range.end - 2)
+            }
+            _ => continue,
+        };
+        let line = line_of(token.range.start);
+        let text = &source[range.clone()];
+        if !text.chars().any(char::is_alphabetic)
+            || manifest.as_ref().is_some_and(|m| m.contains(&line))
+        {
+            continue;
+        }
+        messages.push(Message {
+            kind: if token.kind == TokenKind::LineComment {
+                Kind::LineComment
+            } else {
+                Kind::BlockComment
+            },
+            line,
+            text: text.to_string(),
+            range,
+            context: None,
+            format: false,
+        });
+    }
+
+    // Chamadas abertas, da mais externa para a mais interna, com o argumento
+    // em que a leitura está
+    let mut calls: Vec<Call> = Vec::new();
+    for (at, token) in significant.iter().enumerate() {
+        let before = |n: usize| at.checked_sub(n).map(|i| significant[i]);
+        match token.kind {
+            TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace => {
+                calls.push(Call {
+                    display: display_context(source, before(2), before(1)),
+                    arg: 0,
+                });
+            }
+            TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace => {
+                calls.pop();
+            }
+            TokenKind::Comma => {
+                if let Some(call) = calls.last_mut() {
+                    call.arg += 1;
+                }
+            }
+            TokenKind::Literal { kind, suffix_start } if suffix_start == token.range.len() => {
+                let Some(call) = calls.last() else { continue };
+                let Some(display) = call.display.filter(|d| d.arg == call.arg) else {
+                    continue;
+                };
+                // A string precisa ser o argumento inteiro, não parte de uma
+                // expressão
+                let alone = |neighbour: Option<&&Token>, delimiters: &[TokenKind]| {
+                    neighbour.is_some_and(|t| delimiters.contains(&t.kind))
+                };
+                let opening = [
+                    TokenKind::Comma,
+                    TokenKind::OpenParen,
+                    TokenKind::OpenBracket,
+                    TokenKind::OpenBrace,
+                ];
+                let closing = [
+                    TokenKind::Comma,
+                    TokenKind::CloseParen,
+                    TokenKind::CloseBracket,
+                    TokenKind::CloseBrace,
+                ];
+                if !alone(before(1).as_ref(), &opening) || !alone(significant.get(at + 1), &closing)
+                {
+                    continue;
+                }
+                let line = line_of(token.range.start);
+                messages.extend(string(source, token, kind, display, line));
+            }
+            _ => {}
+        }
+    }
+
+    messages.sort_by_key(|m| m.range.start);
+    Ok(messages)
+}
+
+/// Catálogo com os textos de `source`, um por texto distinto. As referências
+/// `#:` levam só o nome do arquivo, que fica ao lado do catálogo.
+pub fn extract(file: &str, source: &str) -> Result<Catalog> {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    let mut catalog = Catalog::default();
+    for message in messages(file, source)? {
+        let reference = format!("{name}:{}", message.line);
+        let note = message.note();
+        match catalog.entries.iter_mut().find(|e| e.msgid == message.text) {
+            Some(entry) => {
+                entry.references.push(reference);
+                if !entry.notes.contains(&note) {
+                    entry.notes.push(note);
+                }
+            }
+            None => catalog.entries.push(Entry {
+                notes: vec![note],
+                references: vec![reference],
+                msgid: message.text,
+                ..Entry::default()
+            }),
+        }
+    }
+    Ok(catalog)
+}
+
+/// Resultado de [`inject`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Injected {
+    pub source: String,
+    /// Textos com tradução no catálogo.
+    pub translated: usize,
+    pub total: usize,
+}
+
+/// Troca os textos de `source` pelas traduções de `catalog`; os sem tradução
+/// (ou marcados `fuzzy`) ficam como no original.
+pub fn inject(file: &str, source: &str, catalog: &Catalog) -> Result<Injected> {
+    let messages = messages(file, source)?;
+    let mut output = String::with_capacity(source.len());
+    let mut copied = 0;
+    let mut translated = 0;
+
+    for message in &messages {
+        let Some(translation) = catalog.get(&message.text).and_then(Entry::translation) else {
+            continue;
+        };
+        translated += 1;
+        if translation == message.text {
+            continue;
+        }
+        let replacement =
+            message
+                .replacement(translation)
+                .map_err(|reason| Error::Translation {
+                    file: file.to_string(),
+                    line: message.line,
+                    message: reason,
+                })?;
+        output.push_str(&source[copied..message.range.start]);
+        output.push_str(&replacement);
+        copied = message.range.end;
+    }
+    output.push_str(&source[copied..]);
+
+    if code(source) != code(&output) {
+        return Err(Error::CodeChanged {
+            file: file.to_string(),
+        });
+    }
+    Ok(Injected {
+        source: output,
+        translated,
+        total: messages.len(),
+    })
+}
+
+struct Token {
+    kind: TokenKind,
+    range: Range<usize>,
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut offset = 0;
+    rustc_lexer::tokenize(source)
+        .map(|token| {
+            let range = offset..offset + token.len;
+            offset = range.end;
+            Token {
+                kind: token.kind,
+                range,
+            }
+        })
+        .collect()
+}
+
+/// O arquivo sem o conteúdo dos comentários e das strings: o que a tradução
+/// não pode mudar.
+fn code(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    for token in tokenize(source) {
+        code.push_str(match token.kind {
+            TokenKind::LineComment | TokenKind::BlockComment { .. } => "/**/",
+            TokenKind::Literal {
+                kind: LiteralKind::Str { .. } | LiteralKind::RawStr { .. },
+                ..
+            } => "\"\"",
+            _ => &source[token.range],
+        });
+    }
+    code
+}
+
+/// Chamada aberta na leitura dos tokens.
+struct Call {
+    display: Option<Display>,
+    /// Índice do argumento atual.
+    arg: usize,
+}
+
+/// Argumento de uma chamada que é mostrado a quem roda o programa.
+#[derive(Clone, Copy)]
+struct Display {
+    context: &'static str,
+    arg: usize,
+    format: bool,
+}
+
+/// `println!(`, `writeln!(f, `, `.expect(`...: o que vem antes do delimitador
+/// diz se algum argumento é texto de exibição.
+fn display_context(source: &str, first: Option<&Token>, second: Option<&Token>) -> Option<Display> {
+    let (first, second) = (first?, second?);
+    let name = &source[first.range.clone()];
+    if first.kind == TokenKind::Dot && second.kind == TokenKind::Ident {
+        let method = &source[second.range.clone()];
+        return (method == "expect").then_some(Display {
+            context: ".expect()",
+            arg: 0,
+            format: false,
+        });
+    }
+    if first.kind != TokenKind::Ident || second.kind != TokenKind::Not {
+        return None;
+    }
+    let (context, arg) = match name {
+        "print" => ("print!", 0),
+        "println" => ("println!", 0),
+        "eprint" => ("eprint!", 0),
+        "eprintln" => ("eprintln!", 0),
+        "format" => ("format!", 0),
+        "format_args" => ("format_args!", 0),
+        "panic" => ("panic!", 0),
+        "todo" => ("todo!", 0),
+        "unimplemented" => ("unimplemented!", 0),
+        "unreachable" => ("unreachable!", 0),
+        "write" => ("write!", 1),
+        "writeln" => ("writeln!", 1),
+        "assert" => ("assert!", 1),
+        "debug_assert" => ("debug_assert!", 1),
+        "assert_eq" => ("assert_eq!", 2),
+        "assert_ne" => ("assert_ne!", 2),
+        "debug_assert_eq" => ("debug_assert_eq!", 2),
+        "debug_assert_ne" => ("debug_assert_ne!", 2),
+        _ => return None,
+    };
+    Some(Display {
+        context,
+        arg,
+        format: true,
+    })
+}
+
+/// Miolo de um comentário entre `start` (depois do `//` ou `/*`) e `end`: sem o
+/// `/` ou `!` dos doc comments e sem as bordas em branco.
+fn comment_text(source: &str, start: usize, end: usize) -> Range<usize> {
+    let inner = &source[start..end];
+    let marker = usize::from(inner.starts_with(['/', '!', '*']) && inner.len() > 1);
+    let text = &inner[marker..];
+    let from = start + marker + (text.len() - text.trim_start().len());
+    let to = (start + marker + text.trim_end().len()).max(from);
+    from..to
+}
+
+/// String de exibição; `None` para byte strings, `c"..."` e strings com escape
+/// inválido.
+fn string(
+    source: &str,
+    token: &Token,
+    kind: LiteralKind,
+    display: Display,
+    line: usize,
+) -> Option<Message> {
+    let Range { start, end } = token.range;
+    // `c"..."` chega do lexer como o identificador `c` colado numa string
+    if source[..start].ends_with('c')
+        && !source[..start - 1]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    let (kind, range) = match kind {
+        LiteralKind::Str { terminated: true } => (Kind::Str, start + 1..end - 1),
+        LiteralKind::RawStr {
+            n_hashes,
+            started: true,
+            terminated: true,
+        } => (
+            Kind::RawStr { hashes: n_hashes },
+            start + 2 + n_hashes..end - 1 - n_hashes,
+        ),
+        _ => return None,
+    };
+
+    let inner = &source[range.clone()];
+    let text = match kind {
+        Kind::Str => {
+            let mut text = String::with_capacity(inner.len());
+            let mut valid = true;
+            unescape_str(inner, &mut |_, c| match c {
+                Ok(c) => text.push(c),
+                Err(_) => valid = false,
+            });
+            valid.then_some(text)?
+        }
+        _ => inner.to_string(),
+    };
+    text.chars().any(char::is_alphabetic).then_some(Message {
+        kind,
+        line,
+        range,
+        text,
+        context: Some(display.context),
+        format: display.format,
+    })
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `{}`, `{0}`, `{nome:>8}`... de uma string de formatação; `{{` e `}}` são
+/// chaves literais.
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some(at) = rest.find(['{', '}']) {
+        if rest[at..].starts_with("{{") || rest[at..].starts_with("}}") {
+            rest = &rest[at + 2..];
+        } else if rest[at..].starts_with('{') {
+            let Some(close) = rest[at..].find('}') else {
+                break;
+            };
+            found.push(&rest[at..at + close + 1]);
+            rest = &rest[at + close + 1..];
+        } else {
+            rest = &rest[at + 1..];
+        }
+    }
+    found
+}
+
+fn list(placeholders: &[&str]) -> String {
+    if placeholders.is_empty() {
+        "nenhum".to_string()
+    } else {
+        placeholders.join(" ")
+    }
+}
@@ -0,0 +1,255 @@
+//! Catálogos gettext (`.po`): o formato que ferramentas de tradução como
+//! Poedit e Weblate já abrem.
+//!
+//! Só o necessário para os exemplos: `msgid`/`msgstr` de uma forma só (sem
+//! plural), comentários extraídos (`#.`), referências (`#:`), a marca
+//! `#, fuzzy` e entradas obsoletas (`#~`), guardadas para não perder uma
+//! tradução quando o texto some do original e depois volta.
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Entry {
+    /// `#.`: de onde o texto vem, para quem traduz.
+    pub notes: Vec<String>,
+    /// `#:`: `arquivo:linha` de cada ocorrência.
+    pub references: Vec<String>,
+    pub fuzzy: bool,
+    pub msgid: String,
+    pub msgstr: String,
+    pub obsolete: bool,
+}
+
+impl Entry {
+    /// Tradução pronta para usar: preenchida e sem `fuzzy`.
+    pub fn translation(&self) -> Option<&str> {
+        (!self.fuzzy && !self.obsolete && !self.msgstr.is_empty()).then_some(self.msgstr.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    /// Linhas de comentário do topo, sem o `# `.
+    pub header_comments: Vec<String>,
+    pub language: String,
+    pub entries: Vec<Entry>,
+}
+
+impl Catalog {
+    pub fn get(&self, msgid: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|e| !e.obsolete && e.msgid == msgid)
+    }
+
+    /// Traz as traduções de `previous` para as entradas de mesmo `msgid`. As
+    /// traduzidas que não existem mais viram obsoletas (`#~`) e voltam se o
+    /// texto voltar.
+    pub fn merge(&mut self, previous: Catalog) {
+        let mut previous = previous.entries;
+        for entry in &mut self.entries {
+            let found = previous
+                .iter()
+                .position(|p| p.msgid == entry.msgid && !p.obsolete)
+                .or_else(|| previous.iter().position(|p| p.msgid == entry.msgid));
+            if let Some(at) = found {
+                let old = previous.remove(at);
+                entry.msgstr = old.msgstr;
+                entry.fuzzy = old.fuzzy;
+            }
+        }
+        for old in previous {
+            let current = self.entries.iter().any(|e| e.msgid == old.msgid);
+            if !current && !old.msgstr.is_empty() {
+                self.entries.push(Entry {
+                    msgid: old.msgid,
+                    msgstr: old.msgstr,
+                    obsolete: true,
+                    ..Entry::default()
+                });
+            }
+        }
+    }
+
+    /// A entrada de `msgid` vazio é o cabeçalho; dela só o `Language`.
+    fn push(&mut self, entry: Entry) {
+        if entry.msgid.is_empty() && !entry.obsolete {
+            self.language = entry
+                .msgstr
+                .lines()
+                .find_map(|l| l.strip_prefix("Language:"))
+                .map(|l| l.trim().to_string())
+                .unwrap_or_default();
+        } else {
+            self.entries.push(entry);
+        }
+    }
+
+    pub fn to_po(&self) -> String {
+        let mut po = String::new();
+        for line in &self.header_comments {
+            po.push_str(format!("# {line}").trim_end());
+            po.push('\n');
+        }
+        po.push_str("msgid \"\"\nmsgstr \"\"\n");
+        po.push_str("\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+        let _ = writeln!(po, "\"Language: {}\\n\"", escape(&self.language));
+
+        for entry in &self.entries {
+            po.push('\n');
+            if entry.obsolete {
+                write_string(&mut po, "#~ msgid", &entry.msgid);
+                write_string(&mut po, "#~ msgstr", &entry.msgstr);
+                continue;
+            }
+            for note in &entry.notes {
+                let _ = writeln!(po, "#. {note}");
+            }
+            for reference in &entry.references {
+                let _ = writeln!(po, "#: {reference}");
+            }
+            if entry.fuzzy {
+                po.push_str("#, fuzzy\n");
+            }
+            write_string(&mut po, "msgid", &entry.msgid);
+            write_string(&mut po, "msgstr", &entry.msgstr);
+        }
+        po
+    }
+}
+
+/// Texto com quebras vira várias linhas, uma por `\n`, como o `msgmerge` faz.
+fn write_string(po: &mut String, keyword: &str, text: &str) {
+    let prefix = keyword
+        .strip_suffix("msgid")
+        .or_else(|| keyword.strip_suffix("msgstr"));
+    let continuation = prefix.unwrap_or_default();
+    if !text.contains('\n') || text.trim_end_matches('\n').is_empty() {
+        let _ = writeln!(po, "{keyword} \"{}\"", escape(text));
+        return;
+    }
+    let _ = writeln!(po, "{keyword} \"\"");
+    for line in text.split_inclusive('\n') {
+        let _ = writeln!(po, "{continuation}\"{}\"", escape(line));
+    }
+}
+
+/// Erro de leitura com a linha (1-based) do `.po`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+pub fn parse(po: &str) -> Result<Catalog, ParseError> {
+    let mut catalog = Catalog::default();
+    let mut entry = Entry::default();
+    // Campo que recebe as linhas `"..."` de continuação
+    let mut field: Option<Field> = None;
+    let mut seen_msgid = false;
+
+    for (number, raw) in (1..).zip(po.lines()) {
+        let error = |message: &str| ParseError {
+            line: number,
+            message: message.to_string(),
+        };
+        let (obsolete, line) = match raw.trim().strip_prefix("#~") {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, raw.trim()),
+        };
+        if line.is_empty() {
+            continue;
+        }
+
+        // Um comentário ou um msgid depois de um msgstr abre a próxima entrada
+        let opens = line.starts_with('#') || line.starts_with("msgid");
+        if opens && field == Some(Field::Str) {
+            catalog.push(std::mem::take(&mut entry));
+            field = None;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            if !seen_msgid {
+                catalog
+                    .header_comments
+                    .push(comment.strip_prefix(' ').unwrap_or(comment).to_string());
+            } else if let Some(note) = comment.strip_prefix('.') {
+                entry.notes.push(note.trim_start().to_string());
+            } else if let Some(reference) = comment.strip_prefix(':') {
+                entry.references.push(reference.trim_start().to_string());
+            } else if let Some(flags) = comment.strip_prefix(',') {
+                entry.fuzzy |= flags.split(',').any(|f| f.trim() == "fuzzy");
+            }
+        } else if let Some(rest) = line.strip_prefix("msgid") {
+            seen_msgid = true;
+            entry.obsolete = obsolete;
+            entry.msgid = unquote(rest.trim()).ok_or_else(|| error("msgid sem aspas"))?;
+            field = Some(Field::Id);
+        } else if let Some(rest) = line.strip_prefix("msgstr") {
+            if field != Some(Field::Id) {
+                return Err(error("msgstr sem msgid"));
+            }
+            if rest.starts_with('[') {
+                return Err(error("formas de plural não são suportadas"));
+            }
+            entry.msgstr = unquote(rest.trim()).ok_or_else(|| error("msgstr sem aspas"))?;
+            field = Some(Field::Str);
+        } else if line.starts_with('"') {
+            let text = unquote(line).ok_or_else(|| error("linha de continuação inválida"))?;
+            match field {
+                Some(Field::Id) => entry.msgid.push_str(&text),
+                Some(Field::Str) => entry.msgstr.push_str(&text),
+                None => return Err(error("texto fora de um msgid ou msgstr")),
+            }
+        } else if line.starts_with("msgctxt") {
+            return Err(error("msgctxt não é suportado"));
+        } else {
+            return Err(error("linha não reconhecida"));
+        }
+    }
+    if field == Some(Field::Str) {
+        catalog.push(entry);
+    }
+    Ok(catalog)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Id,
+    Str,
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// `"texto"` → `texto`, sem os escapes do PO.
+fn unquote(quoted: &str) -> Option<String> {
+    let inner = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            other => out.push(other),
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,124 @@
+//! Trechos comparáveis de um original, cada um com a impressão digital do
+//! texto e a do código: as seções `##` do `topic.mdx` e, nos exemplos, o que
+//! fica entre um comentário-faixa e o seguinte.
+//!
+//! O texto é comparado sem a quebra das linhas, então reformatar um parágrafo
+//! não desatualiza a tradução; o código é comparado linha a linha, sem a
+//! indentação.
+
+use sha2::{Digest, Sha256};
+use themelion_examples::parity;
+use themelion_mdx::Block;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unit {
+    /// Título da seção ou da faixa; vazio no trecho antes do primeiro.
+    pub id: String,
+    /// Linha (1-based) no arquivo.
+    pub line: usize,
+    pub text: String,
+    /// `None` quando o trecho não tem código.
+    pub code: Option<String>,
+}
+
+/// Seções do corpo de um `topic.mdx`; `body_line` é a linha do arquivo onde o
+/// corpo começa.
+pub fn topic_units(body: &str, body_line: usize) -> Vec<Unit> {
+    themelion_mdx::parse(body)
+        .sections
+        .into_iter()
+        // Linhas em branco antes do primeiro `##` não são uma seção
+        .filter(|section| !section.heading.is_empty() || !section.blocks.is_empty())
+        .map(|section| {
+            let mut text = Vec::new();
+            let mut code = Vec::new();
+            for block in &section.blocks {
+                match block {
+                    Block::Code {
+                        language,
+                        code: source,
+                    } => {
+                        code.push(format!("{}\n{source}", language.as_deref().unwrap_or("")));
+                    }
+                    Block::Heading {
+                        level,
+                        text: heading,
+                    } => {
+                        text.push(format!("{} {heading}", "#".repeat((*level).into())));
+                    }
+                    Block::Paragraph { text: paragraph } => text.push(paragraph.clone()),
+                    Block::List { items, .. } => text.extend(items.iter().cloned()),
+                    Block::Table(table) => text.extend(
+                        table
+                            .header
+                            .iter()
+                            .chain(table.rows.iter().flatten())
+                            .cloned(),
+                    ),
+                    Block::Callout {
+                        kind,
+                        text: callout,
+                    } => {
+                        text.push(format!("{kind:?} {callout}"));
+                    }
+                    Block::CodeTabs => text.push("<CodeTabs />".to_string()),
+                    Block::Jsx { source } => text.push(source.clone()),
+                }
+            }
+            Unit {
+                id: section.heading,
+                line: body_line + section.line - 1,
+                text: fingerprint(&collapse(&text.join("\n"))),
+                code: (!code.is_empty()).then(|| fingerprint(&code.join("\0"))),
+            }
+        })
+        .collect()
+}
+
+/// Trechos de um arquivo de `examples/`, delimitados pelas faixas de
+/// [`parity::banners`]; comentários contam como texto, o resto como código.
+pub fn example_units(source: &str) -> Vec<Unit> {
+    let banners = parity::banners(source);
+    let lines: Vec<&str> = source.lines().collect();
+
+    // Antes da primeira faixa: título do arquivo e cabeçalho `// Cargo.toml:`
+    let mut starts = vec![(String::new(), 1)];
+    starts.extend(banners.into_iter().map(|b| (b.title, b.line)));
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, (id, line))| {
+            let end = starts.get(i + 1).map_or(lines.len(), |(_, next)| next - 1);
+            let mut text = Vec::new();
+            let mut code = Vec::new();
+            for source_line in &lines[line - 1..end.max(line - 1)] {
+                match parity::comment(source_line) {
+                    Some(comment) => text.push(comment),
+                    None if !source_line.trim().is_empty() => code.push(source_line.trim()),
+                    None => {}
+                }
+            }
+            Unit {
+                id: id.clone(),
+                line: *line,
+                text: fingerprint(&collapse(&text.join("\n"))),
+                code: (!code.is_empty()).then(|| fingerprint(&code.join("\n"))),
+            }
+        })
+        .collect()
+}
+
+/// 12 dígitos hexadecimais do SHA-256: curto o bastante para o YAML e sem
+/// colisão prática entre as seções de um tópico.
+pub fn fingerprint(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .take(6)
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::Path;
+
+use rustc_lexer::{LiteralKind, TokenKind};
+use themelion_i18n::{apply_catalog, extract_catalog, po};
+
+/// Tokens que não são comentário nem string, com o texto de cada um.
+fn code_tokens(source: &str) -> Vec<(String, &str)> {
+    let mut offset = 0;
+    let mut tokens = Vec::new();
+    for token in rustc_lexer::tokenize(source) {
+        let text = &source[offset..offset + token.len];
+        offset += token.len;
+        let translatable = matches!(
+            token.kind,
+            TokenKind::LineComment
+                | TokenKind::BlockComment { .. }
+                | TokenKind::Literal {
+                    kind: LiteralKind::Str { .. } | LiteralKind::RawStr { .. },
+                    ..
+                }
+        );
+        if !translatable {
+            tokens.push((format!("{:?}", token.kind), text));
+        }
+    }
+    tokens
+}
+
+#[test]
+fn extract_translate_and_inject_keep_the_code_byte_identical() {
+    let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let original = fs::read_to_string(
+        repo.join("data/content/fundamentos/complexidade/big-o-notation/examples/rust.rs"),
+    )
+    .unwrap();
+
+    let root = tempfile::tempdir().unwrap();
+    let dir = root
+        .path()
+        .join("data/content/fundamentos/complexidade/big-o-notation");
+    fs::create_dir_all(dir.join("examples")).unwrap();
+    fs::write(dir.join("examples/rust.rs"), &original).unwrap();
+    let topic_file = dir.join("topic.mdx");
+
+    let update = extract_catalog(root.path(), &topic_file, "en").unwrap();
+    assert!(update.entries > 0);
+    assert_eq!(update.translated, 0);
+
+    // Traduz tudo, mantendo os marcadores de formatação
+    let mut catalog = po::parse(&fs::read_to_string(&update.path).unwrap()).unwrap();
+    for entry in &mut catalog.entries {
+        entry.msgstr = format!("EN {}", entry.msgid);
+    }
+    fs::write(&update.path, catalog.to_po()).unwrap();
+
+    let applied = apply_catalog(root.path(), &topic_file, "en").unwrap();
+    assert_eq!(applied.path, dir.join("examples/rust.en.rs"));
+    assert_eq!(applied.translated, applied.total);
+
+    let translated = fs::read_to_string(&applied.path).unwrap();
+    assert_ne!(translated, original);
+    assert!(translated.contains("// EN "));
+    assert_eq!(code_tokens(&translated), code_tokens(&original));
+
+    // Extrair de novo mantém as traduções
+    let again = extract_catalog(root.path(), &topic_file, "en").unwrap();
+    assert_eq!(again.translated, again.entries);
+    assert_eq!(again.obsolete, 0);
+}
@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use themelion_i18n::{Error, IssueKind, check, stamp};
+
+const TOPIC: &str = "data/content/fundamentos/geral/teste";
+
+fn write(root: &Path, file: &str, contents: &str) {
+    let path = root.join(TOPIC).join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+/// Tópico com duas seções, traduzido e registrado.
+fn translated() -> (tempfile::TempDir, PathBuf) {
+    let root = tempfile::tempdir().unwrap();
+    write(
+        root.path(),
+        "topic.mdx",
+        "---\ntitle: Teste\n---\n\n## O que é\n\nUm teste.\n\n## Por que importa\n\nPorque sim.\n",
+    );
+    write(
+        root.path(),
+        "topic.en.mdx",
+        "---\ntitle: Test\n---\n\n## What it is\n\nA test.\n\n## Why it matters\n\nBecause.\n",
+    );
+    let topic_file = root.path().join(TOPIC).join("topic.mdx");
+    stamp(root.path(), &topic_file, "en").unwrap();
+    (root, topic_file)
+}
+
+/// (tipo, arquivo, linha) de cada problema.
+fn issues(root: &Path, topic_file: &Path) -> Vec<(IssueKind, String, usize)> {
+    check(root, topic_file, "en")
+        .unwrap()
+        .issues
+        .into_iter()
+        .map(|i| (i.kind, i.file, i.line))
+        .collect()
+}
+
+fn file(name: &str) -> String {
+    format!("{TOPIC}/{name}")
+}
+
+#[test]
+fn a_stamped_translation_has_no_issues() {
+    let (root, topic_file) = translated();
+    let report = check(root.path(), &topic_file, "en").unwrap();
+    assert!(report.translated);
+    assert!(report.issues.is_empty(), "{:?}", report.issues);
+}
+
+#[test]
+fn an_untranslated_topic_is_not_checked() {
+    let root = tempfile::tempdir().unwrap();
+    write(
+        root.path(),
+        "topic.mdx",
+        "---\ntitle: Teste\n---\n\n## O que é\n\nUm teste.\n",
+    );
+    let topic_file = root.path().join(TOPIC).join("topic.mdx");
+    let report = check(root.path(), &topic_file, "en").unwrap();
+    assert!(!report.translated);
+    assert!(report.issues.is_empty());
+    assert!(matches!(
+        stamp(root.path(), &topic_file, "en"),
+        Err(Error::NotTranslated { .. })
+    ));
+}
+
+#[test]
+fn detects_stale_sections() {
+    let (root, topic_file) = translated();
+    write(
+        root.path(),
+        "topic.mdx",
+        "---\ntitle: Teste\n---\n\n## O que é\n\nUm teste, agora maior.\n\n## Por que importa\n\nPorque sim.\n",
+    );
+    assert_eq!(
+        issues(root.path(), &topic_file),
+        [(IssueKind::Stale, file("topic.en.mdx"), 5)]
+    );
+
+    // Reformatar o parágrafo não desatualiza a tradução
+    write(
+        root.path(),
+        "topic.mdx",
+        "---\ntitle: Teste\n---\n\n## O que é\n\nUm\nteste.\n\n## Por que importa\n\nPorque sim.\n",
+    );
+    assert!(issues(root.path(), &topic_file).is_empty());
+}
+
+#[test]
+fn detects_missing_and_orphaned_sections() {
+    let (root, topic_file) = translated();
+    write(
+        root.path(),
+        "topic.mdx",
+        "---\ntitle: Teste\n---\n\n## O que é\n\nUm teste.\n\n## Quando usar\n\nSempre.\n\n## Na prática\n\nAssim.\n",
+    );
+    assert_eq!(
+        issues(root.path(), &topic_file),
+        [
+            (IssueKind::Orphaned, file("topic.en.mdx"), 9),
+            (IssueKind::Missing, file("topic.mdx"), 9),
+            (IssueKind::Missing, file("topic.mdx"), 13),
+        ]
+    );
+}
+
+#[test]
+fn detects_missing_and_orphaned_examples() {
+    let (root, topic_file) = translated();
+    write(root.path(), "examples/go.go", "package main\n");
+    write(root.path(), "examples/rust.en.rs", "fn main() {}\n");
+    assert_eq!(
+        issues(root.path(), &topic_file),
+        [
+            (IssueKind::Missing, file("examples/go.go"), 1),
+            (IssueKind::Orphaned, file("examples/rust.en.rs"), 1),
+        ]
+    );
+}
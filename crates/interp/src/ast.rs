@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::lexer::Pos;
+use crate::value::IntTy;
+
+#[derive(Debug, Default)]
+pub struct Program {
+    pub functions: HashMap<String, Rc<Function>>,
+    pub consts: Vec<(String, Option<Ty>, Expr)>,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    pub params: Vec<(Pattern, Ty)>,
+    pub ret: Option<Ty>,
+    pub body: Block,
+    pub pos: Pos,
+}
+
+/// Tipo como escrito no código; só o nome importa para conversões
+/// (`let x: f64 = 1`, `parse::<u8>()`, `collect::<String>()`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ty {
+    pub name: String,
+    pub args: Vec<Ty>,
+}
+
+#[derive(Debug, Default)]
+pub struct Block {
+    pub stmts: Vec<Stmt>,
+    pub tail: Option<Box<Expr>>,
+}
+
+#[derive(Debug)]
+pub enum Stmt {
+    Let {
+        pattern: Pattern,
+        ty: Option<Ty>,
+        init: Option<Expr>,
+    },
+    Expr(Expr),
+}
+
+#[derive(Debug)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub pos: Pos,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    And,
+    Or,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroKind {
+    Print,
+    Println,
+    Eprint,
+    Eprintln,
+    Format,
+    Panic,
+    Assert,
+    AssertEq,
+    AssertNe,
+    Unreachable,
+    Todo,
+    Unimplemented,
+}
+
+#[derive(Debug)]
+pub enum ExprKind {
+    Unit,
+    Bool(bool),
+    Int(i128, Option<IntTy>),
+    Float(f64),
+    Str(String),
+    Char(char),
+    /// `x`, `i64::MAX`, `Ordering::Less`.
+    Path(Vec<String>),
+    Unary(UnOp, Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Assign(Box<Expr>, Box<Expr>),
+    CompoundAssign(BinOp, Box<Expr>, Box<Expr>),
+    /// `f(x)`, `Some(x)`, `String::from(s)`.
+    Call(Vec<String>, Vec<Expr>),
+    /// Chamada de variável que guarda uma closure: `(f)(x)` ou `f(x)`.
+    CallValue(Box<Expr>, Vec<Expr>),
+    MethodCall {
+        receiver: Box<Expr>,
+        method: String,
+        turbofish: Option<Ty>,
+        args: Vec<Expr>,
+        /// Posição do nome do método, usada nas mensagens de pânico.
+        method_pos: Pos,
+    },
+    TupleField(Box<Expr>, usize),
+    Index(Box<Expr>, Box<Expr>),
+    Macro {
+        kind: MacroKind,
+        /// Argumentos; nos de formatação, o primeiro é o template.
+        args: Vec<Expr>,
+        template: Option<String>,
+        /// Nomes dos últimos argumentos (`width = 6`), na ordem.
+        named: Vec<String>,
+        /// Código da condição de `assert!`, citado na mensagem de pânico.
+        source: Option<String>,
+    },
+    /// `vec![...]` e `[...]`.
+    Array(Vec<Expr>),
+    /// `vec![x; n]` e `[x; n]`.
+    ArrayRepeat(Box<Expr>, Box<Expr>),
+    Tuple(Vec<Expr>),
+    Range {
+        start: Option<Box<Expr>>,
+        end: Option<Box<Expr>>,
+        inclusive: bool,
+    },
+    Ref(Box<Expr>, bool),
+    Deref(Box<Expr>),
+    Cast(Box<Expr>, Ty),
+    Try(Box<Expr>),
+    If {
+        cond: Box<Expr>,
+        then: Block,
+        otherwise: Option<Box<Expr>>,
+    },
+    IfLet {
+        pattern: Pattern,
+        value: Box<Expr>,
+        then: Block,
+        otherwise: Option<Box<Expr>>,
+    },
+    Matches(Box<Expr>, Pattern),
+    While {
+        cond: Box<Expr>,
+        body: Block,
+        label: Option<String>,
+    },
+    WhileLet {
+        pattern: Pattern,
+        value: Box<Expr>,
+        body: Block,
+        label: Option<String>,
+    },
+    Loop {
+        body: Block,
+        label: Option<String>,
+    },
+    For {
+        pattern: Pattern,
+        iter: Box<Expr>,
+        body: Block,
+        label: Option<String>,
+    },
+    Block(Block),
+    Match(Box<Expr>, Vec<Arm>),
+    Break(Option<String>, Option<Box<Expr>>),
+    Continue(Option<String>),
+    Return(Option<Box<Expr>>),
+    Closure(Rc<Closure>),
+}
+
+#[derive(Debug)]
+pub struct Closure {
+    pub params: Vec<Pattern>,
+    pub body: Expr,
+}
+
+#[derive(Debug)]
+pub struct Arm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Wild,
+    Rest,
+    Binding(String),
+    /// Literal (inclui negativos); comparado com `==`.
+    Lit(Literal),
+    Range(Literal, Literal, bool),
+    Tuple(Vec<Pattern>),
+    /// `Some(p)`, `None`, `Ok(p)`, `Err(p)`, `Ordering::Less`.
+    Variant(String, Option<Box<Pattern>>),
+    Or(Vec<Pattern>),
+    /// `[a, b, rest @ ..]` de slices.
+    Slice(Vec<Pattern>),
+    At(String, Box<Pattern>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Int(i128),
+    Float(f64),
+    Bool(bool),
+    Char(char),
+    Str(String),
+}
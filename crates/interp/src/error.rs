@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::lexer::Pos;
+
+/// Problemas encontrados antes de executar: o programa não chega a rodar.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Error {
+    #[error("main.rs:{}:{}: erro de sintaxe: {message}", pos.line, pos.column)]
+    Syntax { pos: Pos, message: String },
+
+    #[error("main.rs:{}:{}: não suportado pelo interpretador: {message}", pos.line, pos.column)]
+    Unsupported { pos: Pos, message: String },
+}
+
+impl Error {
+    pub fn pos(&self) -> Pos {
+        match self {
+            Error::Syntax { pos, .. } | Error::Unsupported { pos, .. } => *pos,
+        }
+    }
+}
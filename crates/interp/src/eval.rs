@@ -0,0 +1,1943 @@
+//! Avaliação da árvore: escopos, controle de fluxo, padrões e chamadas.
+
+use std::cmp::Ordering;
+use std::mem;
+use std::rc::Rc;
+
+use crate::ast::{
+    BinOp, Block, Closure, Expr, ExprKind, Function, Literal, MacroKind, Pattern, Program, Stmt,
+    Ty, UnOp,
+};
+use crate::format::{self, ArgRef, Count, Piece};
+use crate::lexer::Pos;
+use crate::value::{Capture, IntTy, Value, Variant};
+use crate::{Config, Exit, Outcome};
+
+/// Saídas não locais de uma avaliação.
+pub(crate) enum Flow {
+    Break(Option<String>, Value),
+    Continue(Option<String>),
+    Return(Value),
+    Exit(Exit),
+}
+
+pub(crate) type Eval<T = Value> = std::result::Result<T, Flow>;
+
+enum LoopStep {
+    Next,
+    Stop(Value),
+}
+
+/// Escopos de uma chamada, do mais externo ao mais interno.
+type Scopes = Vec<Vec<(String, Value)>>;
+
+pub fn run(program: &Program, stdin: &str, config: &Config) -> Outcome {
+    let mut interpreter = Interpreter {
+        program,
+        config: *config,
+        stdin,
+        stdin_pos: 0,
+        stdout: String::new(),
+        stderr: String::new(),
+        steps: 0,
+        depth: 0,
+        frame: 0,
+        next_frame: 0,
+        scopes: vec![Vec::new()],
+        suspended: Vec::new(),
+        globals: Vec::new(),
+        hint: None,
+    };
+
+    let exit = match interpreter.main() {
+        Ok(()) => Exit::Success,
+        Err(Flow::Exit(exit)) => exit,
+        Err(Flow::Return(_)) => Exit::Success,
+        Err(Flow::Break(..) | Flow::Continue(_)) => Exit::Invalid {
+            message: "`break`/`continue` fora de um laço".to_string(),
+            pos: Pos::default(),
+        },
+    };
+    match &exit {
+        Exit::Panicked { message, pos } => interpreter.stderr.push_str(&format!(
+            "thread 'main' panicked at main.rs:{}:{}:\n{message}\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n",
+            pos.line, pos.column
+        )),
+        Exit::StackOverflow { .. } => interpreter
+            .stderr
+            .push_str("\nthread 'main' has overflowed its stack\nfatal runtime error: stack overflow\n"),
+        _ => {}
+    }
+
+    Outcome {
+        stdout: interpreter.stdout,
+        stderr: interpreter.stderr,
+        exit,
+        steps: interpreter.steps,
+    }
+}
+
+pub(crate) struct Interpreter<'p> {
+    program: &'p Program,
+    pub(crate) config: Config,
+    stdin: &'p str,
+    stdin_pos: usize,
+    stdout: String,
+    stderr: String,
+    steps: u64,
+    depth: usize,
+    frame: usize,
+    next_frame: usize,
+    /// Escopos da chamada atual; cada um com seus nomes em ordem de criação.
+    scopes: Scopes,
+    /// Escopos das chamadas em andamento abaixo da atual, com o `frame` de
+    /// cada uma; uma closure passada adiante roda nos escopos de quem a criou.
+    suspended: Vec<(usize, Scopes)>,
+    globals: Vec<(String, Value)>,
+    /// Tipo escalar anotado no `let` em avaliação; decide `parse()`, `sum()`
+    /// e `unwrap_or_default()` sem turbofish, como a inferência do rustc.
+    pub(crate) hint: Option<String>,
+}
+
+impl Interpreter<'_> {
+    fn main(&mut self) -> Eval<()> {
+        for (name, ty, expr) in &self.program.consts {
+            let value = self.eval(expr)?;
+            let value = self.annotate(value, ty.as_ref(), expr.pos)?;
+            self.globals.push((name.clone(), value));
+        }
+        let Some(main) = self.program.functions.get("main").cloned() else {
+            return Ok(());
+        };
+        match self.call_function(&main, Vec::new(), main.pos)? {
+            Value::Variant(Variant::Err, Some(error)) => {
+                self.stderr
+                    .push_str(&format!("Error: {}\n", error.debug(false)));
+                Err(Flow::Exit(Exit::Exited { code: 1 }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // --- erros e limites ---------------------------------------------------
+
+    pub(crate) fn panic<T>(&self, pos: Pos, message: impl Into<String>) -> Eval<T> {
+        Err(Flow::Exit(Exit::Panicked {
+            message: message.into(),
+            pos,
+        }))
+    }
+
+    pub(crate) fn invalid<T>(&self, pos: Pos, message: impl Into<String>) -> Eval<T> {
+        Err(Flow::Exit(Exit::Invalid {
+            message: message.into(),
+            pos,
+        }))
+    }
+
+    pub(crate) fn unsupported<T>(&self, pos: Pos, message: impl Into<String>) -> Eval<T> {
+        Err(Flow::Exit(Exit::Unsupported {
+            message: message.into(),
+            pos,
+        }))
+    }
+
+    pub(crate) fn tick(&mut self) -> Eval<()> {
+        self.steps += 1;
+        if self.steps > self.config.max_steps {
+            return Err(Flow::Exit(Exit::StepLimit {
+                limit: self.config.max_steps,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Cobra `n` passos (trabalho proporcional ao tamanho) e confere o limite
+    /// de elementos.
+    pub(crate) fn charge(&mut self, n: usize) -> Eval<()> {
+        self.fits(n)?;
+        self.steps += n as u64;
+        if self.steps > self.config.max_steps {
+            return Err(Flow::Exit(Exit::StepLimit {
+                limit: self.config.max_steps,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Confere o limite de elementos sem cobrar passos: strings que crescem
+    /// no lugar (`push_str`, `+`) contam os bytes do resultado.
+    pub(crate) fn fits(&self, n: usize) -> Eval<()> {
+        if n > self.config.max_elements {
+            return Err(Flow::Exit(Exit::MemoryLimit {
+                limit_elements: self.config.max_elements,
+            }));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn emit(&mut self, text: &str, stderr: bool) -> Eval<()> {
+        let limit = self.config.max_output_bytes;
+        let used = self.stdout.len() + self.stderr.len();
+        let buffer = if stderr {
+            &mut self.stderr
+        } else {
+            &mut self.stdout
+        };
+        if used + text.len() > limit {
+            let mut end = limit.saturating_sub(used).min(text.len());
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            buffer.push_str(&text[..end]);
+            return Err(Flow::Exit(Exit::OutputLimit { limit_bytes: limit }));
+        }
+        buffer.push_str(text);
+        Ok(())
+    }
+
+    // --- stdin -------------------------------------------------------------
+
+    /// Próxima linha do stdin, com o `\n`; vazia no fim.
+    pub(crate) fn read_line(&mut self) -> &str {
+        let rest = &self.stdin[self.stdin_pos..];
+        let len = rest.find('\n').map_or(rest.len(), |i| i + 1);
+        self.stdin_pos += len;
+        &rest[..len]
+    }
+
+    pub(crate) fn read_rest(&mut self) -> &str {
+        let rest = &self.stdin[self.stdin_pos..];
+        self.stdin_pos = self.stdin.len();
+        rest
+    }
+
+    // --- escopos -------------------------------------------------------------
+
+    fn bind(&mut self, name: &str, value: Value) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push((name.to_string(), value));
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Value> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .chain(self.globals.iter().rev())
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value)
+    }
+
+    fn lookup_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.iter_mut().rev())
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value)
+    }
+
+    fn scoped<T>(&mut self, body: impl FnOnce(&mut Self) -> Eval<T>) -> Eval<T> {
+        self.scopes.push(Vec::new());
+        let result = body(self);
+        self.scopes.pop();
+        result
+    }
+
+    // --- blocos e instruções -----------------------------------------------
+
+    fn block(&mut self, block: &Block) -> Eval {
+        self.scoped(|this| {
+            for stmt in &block.stmts {
+                this.stmt(stmt)?;
+            }
+            match &block.tail {
+                Some(tail) => this.eval(tail),
+                None => Ok(Value::Unit),
+            }
+        })
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> Eval<()> {
+        match stmt {
+            Stmt::Let { pattern, ty, init } => {
+                let value = match init {
+                    Some(init) => {
+                        let hint = mem::replace(&mut self.hint, ty.as_ref().and_then(scalar));
+                        let value = self.eval_owned(init);
+                        self.hint = hint;
+                        self.annotate(value?, ty.as_ref(), init.pos)?
+                    }
+                    None => Value::Unit,
+                };
+                let pos = init.as_ref().map_or_else(Pos::default, |init| init.pos);
+                self.bind_pattern(pattern, value, pos)
+            }
+            Stmt::Expr(expr) => self.eval(expr).map(drop),
+        }
+    }
+
+    /// Valor movido ou copiado de um lugar (`let b = a;`, `f(a)`): arrays são
+    /// `Copy` no Rust, então o destino ganha sua própria cópia.
+    fn eval_owned(&mut self, expr: &Expr) -> Eval {
+        let value = self.eval(expr)?;
+        let is_place = matches!(
+            expr.kind,
+            ExprKind::Path(_) | ExprKind::Index(..) | ExprKind::TupleField(..)
+        );
+        Ok(match value {
+            Value::Vec(_) | Value::Tuple(_) if is_place => value.deep_clone(),
+            value => value,
+        })
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern, value: Value, pos: Pos) -> Eval<()> {
+        let mut bindings = Vec::new();
+        if !self.matches(pattern, &value, &mut bindings, pos)? {
+            return self.invalid(pos, "padrão refutável onde só cabe um irrefutável");
+        }
+        for (name, value) in bindings {
+            self.bind(&name, value);
+        }
+        Ok(())
+    }
+
+    // --- padrões -------------------------------------------------------------
+
+    fn matches(
+        &mut self,
+        pattern: &Pattern,
+        value: &Value,
+        bindings: &mut Vec<(String, Value)>,
+        pos: Pos,
+    ) -> Eval<bool> {
+        Ok(match (pattern, value) {
+            (Pattern::Wild | Pattern::Rest, _) => true,
+            (Pattern::Binding(name), value) => {
+                bindings.push((name.clone(), value.clone()));
+                true
+            }
+            (Pattern::At(name, inner), value) => {
+                if !self.matches(inner, value, bindings, pos)? {
+                    return Ok(false);
+                }
+                bindings.push((name.clone(), value.clone()));
+                true
+            }
+            (Pattern::Lit(literal), value) => {
+                let literal = self.literal(literal, pos)?;
+                literal.equals(value)
+            }
+            (Pattern::Range(low, high, inclusive), value) => {
+                let low = self.literal(low, pos)?;
+                let high = self.literal(high, pos)?;
+                let above = matches!(
+                    value.compare(&low),
+                    Some(Ordering::Greater | Ordering::Equal)
+                );
+                let below = match value.compare(&high) {
+                    Some(Ordering::Less) => true,
+                    Some(Ordering::Equal) => *inclusive,
+                    _ => false,
+                };
+                above && below
+            }
+            (Pattern::Or(alternatives), value) => {
+                for alternative in alternatives {
+                    let mut found = Vec::new();
+                    if self.matches(alternative, value, &mut found, pos)? {
+                        bindings.extend(found);
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            (Pattern::Tuple(patterns), Value::Tuple(items)) => {
+                self.match_sequence(patterns, items, bindings, pos, false)?
+            }
+            (Pattern::Tuple(patterns), Value::Unit) => patterns.is_empty(),
+            (Pattern::Slice(patterns), Value::Vec(items)) => {
+                let items = items.borrow().clone();
+                self.match_sequence(patterns, &items, bindings, pos, true)?
+            }
+            (Pattern::Variant(name, inner), Value::Variant(variant, payload)) => {
+                if variant.name() != name {
+                    return Ok(false);
+                }
+                match (inner, payload) {
+                    (Some(inner), Some(payload)) => self.matches(inner, payload, bindings, pos)?,
+                    (None, None) => true,
+                    _ => false,
+                }
+            }
+            (Pattern::Variant(..), _) | (Pattern::Tuple(_), _) | (Pattern::Slice(_), _) => {
+                return self.invalid(
+                    pos,
+                    format!(
+                        "padrão incompatível com valor do tipo {}",
+                        value.type_name()
+                    ),
+                );
+            }
+        })
+    }
+
+    /// Tuplas e slices, com `..` (e `resto @ ..` em slices) no meio.
+    fn match_sequence(
+        &mut self,
+        patterns: &[Pattern],
+        items: &[Value],
+        bindings: &mut Vec<(String, Value)>,
+        pos: Pos,
+        slice: bool,
+    ) -> Eval<bool> {
+        let is_rest = |p: &Pattern| match p {
+            Pattern::Rest => true,
+            Pattern::At(_, inner) => matches!(**inner, Pattern::Rest),
+            _ => false,
+        };
+        let Some(rest) = patterns.iter().position(is_rest) else {
+            if patterns.len() != items.len() {
+                return Ok(false);
+            }
+            for (pattern, item) in patterns.iter().zip(items) {
+                if !self.matches(pattern, item, bindings, pos)? {
+                    return Ok(false);
+                }
+            }
+            return Ok(true);
+        };
+
+        let before = &patterns[..rest];
+        let after = &patterns[rest + 1..];
+        if items.len() < before.len() + after.len() {
+            return Ok(false);
+        }
+        for (pattern, item) in before.iter().zip(items) {
+            if !self.matches(pattern, item, bindings, pos)? {
+                return Ok(false);
+            }
+        }
+        let tail = &items[items.len() - after.len()..];
+        for (pattern, item) in after.iter().zip(tail) {
+            if !self.matches(pattern, item, bindings, pos)? {
+                return Ok(false);
+            }
+        }
+        if let Pattern::At(name, _) = &patterns[rest] {
+            if !slice {
+                return self.unsupported(pos, "`nome @ ..` em tupla");
+            }
+            let middle = items[before.len()..items.len() - after.len()].to_vec();
+            bindings.push((name.clone(), Value::vec(middle)));
+        }
+        Ok(true)
+    }
+
+    fn literal(&self, literal: &Literal, pos: Pos) -> Eval {
+        Ok(match literal {
+            Literal::Int(value) => self.int_literal(*value, None, pos)?,
+            Literal::Float(value) => Value::Float(*value),
+            Literal::Bool(value) => Value::Bool(*value),
+            Literal::Char(value) => Value::Char(*value),
+            Literal::Str(value) => Value::str(value.clone()),
+        })
+    }
+
+    fn int_literal(&self, value: i128, ty: Option<IntTy>, pos: Pos) -> Eval {
+        match ty {
+            Some(ty) if !ty.contains(value) => {
+                self.invalid(pos, format!("literal fora do intervalo de `{}`", ty.name()))
+            }
+            _ => self.int_value(value, ty, pos),
+        }
+    }
+
+    pub(crate) fn int_value(&self, value: i128, ty: Option<IntTy>, pos: Pos) -> Eval {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::Int(value, ty)),
+            Err(_) => self.unsupported(pos, format!("inteiro fora do intervalo de i64 ({value})")),
+        }
+    }
+
+    /// [`coerce`] que recusa inteiros fora da faixa do tipo anotado: em Rust,
+    /// a conta que os produziu já teria estourado nesse tipo.
+    fn annotate(&self, value: Value, ty: Option<&Ty>, pos: Pos) -> Eval {
+        let int = ty.and_then(|ty| IntTy::from_name(&ty.name));
+        if let (Value::Int(n, _), Some(int)) = (&value, int)
+            && !int.contains((*n).into())
+        {
+            return self.panic(
+                pos,
+                format!(
+                    "valor {n} não cabe em {}; a conta que o produziu estouraria em Rust",
+                    int.name()
+                ),
+            );
+        }
+        Ok(coerce(value, ty))
+    }
+
+    // --- expressões -------------------------------------------------------------
+
+    pub(crate) fn eval(&mut self, expr: &Expr) -> Eval {
+        self.tick()?;
+        let pos = expr.pos;
+        match &expr.kind {
+            ExprKind::Unit => Ok(Value::Unit),
+            ExprKind::Bool(value) => Ok(Value::Bool(*value)),
+            ExprKind::Int(value, ty) => self.int_literal(*value, *ty, pos),
+            ExprKind::Float(value) => Ok(Value::Float(*value)),
+            ExprKind::Str(value) => Ok(Value::str(value.clone())),
+            ExprKind::Char(value) => Ok(Value::Char(*value)),
+            ExprKind::Path(path) => self.path(path, pos),
+            ExprKind::Unary(op, operand) => {
+                let value = self.eval(operand)?;
+                self.unary(*op, value, pos)
+            }
+            ExprKind::Binary(BinOp::And, lhs, rhs) => match self.eval(lhs)? {
+                Value::Bool(false) => Ok(Value::Bool(false)),
+                Value::Bool(true) => self.eval(rhs),
+                other => self.invalid(pos, format!("`&&` com {}", other.type_name())),
+            },
+            ExprKind::Binary(BinOp::Or, lhs, rhs) => match self.eval(lhs)? {
+                Value::Bool(true) => Ok(Value::Bool(true)),
+                Value::Bool(false) => self.eval(rhs),
+                other => self.invalid(pos, format!("`||` com {}", other.type_name())),
+            },
+            ExprKind::Binary(op, lhs, rhs) => {
+                let lhs = self.eval(lhs)?;
+                let rhs = self.eval(rhs)?;
+                self.binary(*op, lhs, rhs, pos)
+            }
+            ExprKind::Assign(place, value) => {
+                let value = self.eval_owned(value)?;
+                self.assign(place, value)?;
+                Ok(Value::Unit)
+            }
+            ExprKind::CompoundAssign(op, place, value) => {
+                let rhs = self.eval(value)?;
+                let current = self.eval(place)?;
+                if let (BinOp::Add, Value::Str(target)) = (op, &current) {
+                    let text = match &rhs {
+                        Value::Str(text) => text.borrow().clone(),
+                        Value::Char(c) => c.to_string(),
+                        other => {
+                            return self.invalid(pos, format!("`String += {}`", other.type_name()));
+                        }
+                    };
+                    self.fits(target.borrow().len() + text.len())?;
+                    target.borrow_mut().push_str(&text);
+                    return Ok(Value::Unit);
+                }
+                let updated = self.binary(*op, current, rhs, pos)?;
+                self.assign(place, updated)?;
+                Ok(Value::Unit)
+            }
+            ExprKind::Call(path, args) => self.call_path(path, args, pos),
+            ExprKind::CallValue(callee, args) => {
+                let callee = self.eval(callee)?;
+                let args = self.eval_args(args)?;
+                self.call_value(&callee, args, pos)
+            }
+            ExprKind::MethodCall {
+                receiver,
+                method,
+                turbofish,
+                args,
+                method_pos,
+            } => {
+                let receiver = self.eval(receiver)?;
+                let args = self.eval_args(args)?;
+                self.method(receiver, method, turbofish.as_ref(), args, *method_pos)
+            }
+            ExprKind::TupleField(base, index) => match self.eval(base)? {
+                Value::Tuple(items) => match items.get(*index) {
+                    Some(item) => Ok(item.clone()),
+                    None => self.invalid(pos, format!("tupla sem o campo {index}")),
+                },
+                other => self.invalid(pos, format!("`.{index}` em {}", other.type_name())),
+            },
+            ExprKind::Index(base, index) => {
+                let base = self.eval(base)?;
+                self.index(base, index, pos)
+            }
+            ExprKind::Macro {
+                kind,
+                args,
+                template,
+                named,
+                source,
+            } => self.macro_call(
+                *kind,
+                args,
+                template
+                    .as_deref()
+                    .map(|template| (template, named.as_slice())),
+                source.as_deref(),
+                pos,
+            ),
+            ExprKind::Array(items) => {
+                self.charge(items.len())?;
+                let items = self.eval_args(items)?;
+                Ok(Value::vec(items))
+            }
+            ExprKind::ArrayRepeat(item, count) => {
+                let item = self.eval(item)?;
+                let count = self.eval(count)?;
+                let count = self.usize_arg(&count, pos)?;
+                self.charge(count)?;
+                Ok(Value::vec((0..count).map(|_| item.deep_clone()).collect()))
+            }
+            ExprKind::Tuple(items) => {
+                let items = self.eval_args(items)?;
+                Ok(Value::tuple(items))
+            }
+            ExprKind::Range {
+                start,
+                end,
+                inclusive,
+            } => {
+                let Some(start) = start else {
+                    return self.unsupported(pos, "faixa sem início fora de indexação");
+                };
+                let start = self.eval(start)?;
+                // `a..` não tem fim; i64::MAX faz as vezes de infinito
+                let end = match end {
+                    Some(end) => self.eval(end)?,
+                    None => Value::int(i64::MAX),
+                };
+                match (start, end) {
+                    (Value::Int(start, a), Value::Int(end, b)) => {
+                        let end = if *inclusive {
+                            match end.checked_add(1) {
+                                Some(end) => end,
+                                None => {
+                                    return self.unsupported(pos, "faixa até i64::MAX inclusive");
+                                }
+                            }
+                        } else {
+                            end
+                        };
+                        Ok(Value::Range(start, end, a.or(b)))
+                    }
+                    (Value::Char(_), Value::Char(_)) => self.unsupported(pos, "faixas de char"),
+                    (start, _) => self.invalid(pos, format!("faixa de {}", start.type_name())),
+                }
+            }
+            ExprKind::Ref(inner, mutable) => {
+                let value = self.eval(inner)?;
+                if *mutable && is_copy(&value) {
+                    return self
+                        .unsupported(pos, format!("`&mut` de valor {} (Copy)", value.type_name()));
+                }
+                Ok(value)
+            }
+            ExprKind::Deref(inner) => self.eval(inner),
+            ExprKind::Cast(inner, ty) => {
+                let value = self.eval(inner)?;
+                self.cast(value, ty, pos)
+            }
+            ExprKind::Try(inner) => match self.eval(inner)? {
+                Value::Variant(Variant::Some | Variant::Ok, Some(value)) => Ok((*value).clone()),
+                Value::Variant(Variant::None, _) => Err(Flow::Return(Value::none())),
+                error @ Value::Variant(Variant::Err, _) => Err(Flow::Return(error)),
+                other => self.invalid(pos, format!("`?` em {}", other.type_name())),
+            },
+            ExprKind::If {
+                cond,
+                then,
+                otherwise,
+            } => {
+                if self.condition(cond)? {
+                    self.block(then)
+                } else if let Some(otherwise) = otherwise {
+                    self.eval(otherwise)
+                } else {
+                    Ok(Value::Unit)
+                }
+            }
+            ExprKind::IfLet {
+                pattern,
+                value,
+                then,
+                otherwise,
+            } => {
+                let value = self.eval(value)?;
+                let mut bindings = Vec::new();
+                if self.matches(pattern, &value, &mut bindings, pos)? {
+                    self.scoped(|this| {
+                        for (name, value) in bindings {
+                            this.bind(&name, value);
+                        }
+                        this.block(then)
+                    })
+                } else if let Some(otherwise) = otherwise {
+                    self.eval(otherwise)
+                } else {
+                    Ok(Value::Unit)
+                }
+            }
+            ExprKind::Matches(value, pattern) => {
+                let value = self.eval(value)?;
+                let matched = self.matches(pattern, &value, &mut Vec::new(), pos)?;
+                Ok(Value::Bool(matched))
+            }
+            ExprKind::While { cond, body, label } => {
+                while self.condition(cond)? {
+                    if let LoopStep::Stop(_) = self.loop_body(body, label)? {
+                        break;
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            ExprKind::WhileLet {
+                pattern,
+                value,
+                body,
+                label,
+            } => {
+                loop {
+                    let value = self.eval(value)?;
+                    let mut bindings = Vec::new();
+                    if !self.matches(pattern, &value, &mut bindings, pos)? {
+                        break;
+                    }
+                    let step = self.scoped(|this| {
+                        for (name, value) in bindings {
+                            this.bind(&name, value);
+                        }
+                        this.loop_body(body, label)
+                    })?;
+                    if let LoopStep::Stop(_) = step {
+                        break;
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            ExprKind::Loop { body, label } => loop {
+                self.tick()?;
+                if let LoopStep::Stop(value) = self.loop_body(body, label)? {
+                    return Ok(value);
+                }
+            },
+            ExprKind::For {
+                pattern,
+                iter,
+                body,
+                label,
+            } => {
+                let source = self.eval(iter)?;
+                if let Value::Range(start, end, ty) = source {
+                    let mut i = start;
+                    while i < end {
+                        self.tick()?;
+                        let step = self.scoped(|this| {
+                            this.bind_pattern(pattern, Value::Int(i, ty), pos)?;
+                            this.loop_body(body, label)
+                        })?;
+                        if let LoopStep::Stop(_) = step {
+                            break;
+                        }
+                        i += 1;
+                    }
+                    return Ok(Value::Unit);
+                }
+                let state = self.iterator(source, pos)?;
+                while let Some(item) = self.next_item(&state, pos)? {
+                    let step = self.scoped(|this| {
+                        this.bind_pattern(pattern, item, pos)?;
+                        this.loop_body(body, label)
+                    })?;
+                    if let LoopStep::Stop(_) = step {
+                        break;
+                    }
+                }
+                Ok(Value::Unit)
+            }
+            ExprKind::Block(block) => self.block(block),
+            ExprKind::Match(value, arms) => {
+                let value = self.eval(value)?;
+                for arm in arms {
+                    let mut bindings = Vec::new();
+                    if !self.matches(&arm.pattern, &value, &mut bindings, pos)? {
+                        continue;
+                    }
+                    let result = self.scoped(|this| {
+                        for (name, value) in bindings {
+                            this.bind(&name, value);
+                        }
+                        if let Some(guard) = &arm.guard
+                            && !this.condition(guard)?
+                        {
+                            return Ok(None);
+                        }
+                        this.eval(&arm.body).map(Some)
+                    })?;
+                    if let Some(result) = result {
+                        return Ok(result);
+                    }
+                }
+                self.invalid(
+                    pos,
+                    format!("`match` não cobre o valor {}", value.debug(false)),
+                )
+            }
+            ExprKind::Break(label, value) => {
+                let value = match value {
+                    Some(value) => self.eval(value)?,
+                    None => Value::Unit,
+                };
+                Err(Flow::Break(label.clone(), value))
+            }
+            ExprKind::Continue(label) => Err(Flow::Continue(label.clone())),
+            ExprKind::Return(value) => {
+                let value = match value {
+                    Some(value) => self.eval(value)?,
+                    None => Value::Unit,
+                };
+                Err(Flow::Return(value))
+            }
+            ExprKind::Closure(closure) => Ok(Value::Closure(
+                closure.clone(),
+                Capture {
+                    frame: self.frame,
+                    depth: self.scopes.len(),
+                    count: self.scopes.last().map_or(0, Vec::len),
+                },
+            )),
+        }
+    }
+
+    fn eval_args(&mut self, args: &[Expr]) -> Eval<Vec<Value>> {
+        args.iter().map(|arg| self.eval_owned(arg)).collect()
+    }
+
+    fn condition(&mut self, cond: &Expr) -> Eval<bool> {
+        match self.eval(cond)? {
+            Value::Bool(value) => Ok(value),
+            other => self.invalid(
+                cond.pos,
+                format!("condição precisa ser bool, recebeu {}", other.type_name()),
+            ),
+        }
+    }
+
+    fn loop_body(&mut self, body: &Block, label: &Option<String>) -> Eval<LoopStep> {
+        match self.block(body) {
+            Ok(_) => Ok(LoopStep::Next),
+            Err(Flow::Break(target, value)) if target.is_none() || target == *label => {
+                Ok(LoopStep::Stop(value))
+            }
+            Err(Flow::Continue(target)) if target.is_none() || target == *label => {
+                Ok(LoopStep::Next)
+            }
+            Err(flow) => Err(flow),
+        }
+    }
+
+    fn path(&mut self, path: &[String], pos: Pos) -> Eval {
+        if let [name] = path
+            && let Some(value) = self.lookup(name)
+        {
+            return Ok(value.clone());
+        }
+        let last = path.last().map(String::as_str).unwrap_or_default();
+        if let Some(variant) = Variant::from_name(last)
+            && !matches!(variant, Variant::Some | Variant::Ok | Variant::Err)
+        {
+            return Ok(Value::Variant(variant, None));
+        }
+        if path.len() >= 2
+            && let Some(value) = constant(&path[path.len() - 2], last)
+        {
+            return Ok(value);
+        }
+        if let [name] = path
+            && self.program.functions.contains_key(name)
+        {
+            return self.unsupported(pos, "função usada como valor");
+        }
+        self.invalid(pos, format!("nome desconhecido `{}`", path.join("::")))
+    }
+
+    fn unary(&self, op: UnOp, value: Value, pos: Pos) -> Eval {
+        match (op, value) {
+            (UnOp::Neg, Value::Int(n, ty)) => {
+                let n = -i128::from(n);
+                if ty.map_or(n > i64::MAX.into(), |ty| !ty.contains(n)) {
+                    return self.panic(pos, "attempt to negate with overflow");
+                }
+                self.int_value(n, ty, pos)
+            }
+            (UnOp::Neg, Value::Float(f)) => Ok(Value::Float(-f)),
+            (UnOp::Not, Value::Bool(b)) => Ok(Value::Bool(!b)),
+            (UnOp::Not, Value::Int(n, None)) => Ok(Value::Int(!n, None)),
+            (UnOp::Not, Value::Int(n, Some(ty))) => match ty.wrap((!n).into()) {
+                Some(n) => Ok(Value::Int(n, Some(ty))),
+                None => self.unsupported(
+                    pos,
+                    format!("`!` em {} fora do intervalo de i64", ty.name()),
+                ),
+            },
+            (op, value) => self.invalid(
+                pos,
+                format!(
+                    "operador `{}` não se aplica a {}",
+                    if op == UnOp::Neg { "-" } else { "!" },
+                    value.type_name()
+                ),
+            ),
+        }
+    }
+
+    pub(crate) fn binary(&self, op: BinOp, lhs: Value, rhs: Value, pos: Pos) -> Eval {
+        if let Some(ordering) = comparison(op) {
+            let result = match lhs.compare(&rhs) {
+                Some(found) => ordering(found),
+                None if matches!(lhs, Value::Float(_)) || matches!(rhs, Value::Float(_)) => {
+                    op == BinOp::Ne
+                }
+                None => {
+                    return self.invalid(
+                        pos,
+                        format!("comparação entre {} e {}", lhs.type_name(), rhs.type_name()),
+                    );
+                }
+            };
+            return Ok(Value::Bool(result));
+        }
+
+        match (lhs, rhs) {
+            // Em deslocamentos o tipo do resultado é o da esquerda
+            (Value::Int(a, ta), Value::Int(b, tb)) => {
+                let ty = if matches!(op, BinOp::Shl | BinOp::Shr) {
+                    ta
+                } else {
+                    ta.or(tb)
+                };
+                self.int_op(op, a, b, ty, pos)
+            }
+            (Value::Float(a), Value::Float(b)) => self.float_op(op, a, b, pos),
+            (Value::Int(a, _), Value::Float(b)) => self.float_op(op, a as f64, b, pos),
+            (Value::Float(a), Value::Int(b, _)) => self.float_op(op, a, b as f64, pos),
+            (Value::Bool(a), Value::Bool(b)) => match op {
+                BinOp::BitAnd => Ok(Value::Bool(a & b)),
+                BinOp::BitOr => Ok(Value::Bool(a | b)),
+                BinOp::BitXor => Ok(Value::Bool(a ^ b)),
+                _ => self.invalid(pos, format!("operador {op:?} com bool")),
+            },
+            (Value::Str(a), Value::Str(b)) if op == BinOp::Add => {
+                self.fits(a.borrow().len() + b.borrow().len())?;
+                let mut text = a.borrow().clone();
+                text.push_str(&b.borrow());
+                Ok(Value::str(text))
+            }
+            (lhs, rhs) => self.invalid(
+                pos,
+                format!(
+                    "operador {op:?} entre {} e {}",
+                    lhs.type_name(),
+                    rhs.type_name()
+                ),
+            ),
+        }
+    }
+
+    /// Aritmética inteira com os pânicos de overflow do Rust na faixa de
+    /// `ty` (a de `i64` quando o tipo não é conhecido).
+    fn int_op(&self, op: BinOp, a: i64, b: i64, ty: Option<IntTy>, pos: Pos) -> Eval {
+        let (min, max) = ty.map_or((i64::MIN.into(), i64::MAX.into()), IntTy::bounds);
+        let bits = ty.map_or(64, IntTy::bits);
+        let (a, b) = (i128::from(a), i128::from(b));
+        let (result, overflow) = match op {
+            BinOp::Add => (a + b, "attempt to add with overflow"),
+            BinOp::Sub => (a - b, "attempt to subtract with overflow"),
+            BinOp::Mul => (a * b, "attempt to multiply with overflow"),
+            BinOp::Div if b == 0 => return self.panic(pos, "attempt to divide by zero"),
+            BinOp::Div => (a / b, "attempt to divide with overflow"),
+            BinOp::Rem if b == 0 => {
+                return self.panic(
+                    pos,
+                    "attempt to calculate the remainder with a divisor of zero",
+                );
+            }
+            BinOp::Rem if b == -1 && a == min => {
+                return self.panic(pos, "attempt to calculate the remainder with overflow");
+            }
+            BinOp::Rem => (a % b, ""),
+            BinOp::BitAnd => (a & b, ""),
+            BinOp::BitOr => (a | b, ""),
+            BinOp::BitXor => (a ^ b, ""),
+            BinOp::Shl if !(0..bits.into()).contains(&b) => {
+                return self.panic(pos, "attempt to shift left with overflow");
+            }
+            BinOp::Shr if !(0..bits.into()).contains(&b) => {
+                return self.panic(pos, "attempt to shift right with overflow");
+            }
+            // `<<` descarta os bits que saem da largura, sem pânico
+            BinOp::Shl => {
+                let shifted = a.wrapping_shl(b as u32);
+                return match ty {
+                    None => Ok(Value::Int(shifted as i64, None)),
+                    Some(ty) => match ty.wrap(shifted) {
+                        Some(n) => Ok(Value::Int(n, Some(ty))),
+                        None => self.unsupported(
+                            pos,
+                            format!("`<<` em {} fora do intervalo de i64", ty.name()),
+                        ),
+                    },
+                };
+            }
+            BinOp::Shr => (a >> b, ""),
+            _ => return self.invalid(pos, format!("operador {op:?} com inteiros")),
+        };
+        if !(min..=max).contains(&result) {
+            return self.panic(pos, overflow);
+        }
+        self.int_value(result, ty, pos)
+    }
+
+    fn float_op(&self, op: BinOp, a: f64, b: f64, pos: Pos) -> Eval {
+        Ok(Value::Float(match op {
+            BinOp::Add => a + b,
+            BinOp::Sub => a - b,
+            BinOp::Mul => a * b,
+            BinOp::Div => a / b,
+            BinOp::Rem => a % b,
+            _ => return self.invalid(pos, format!("operador {op:?} com f64")),
+        }))
+    }
+
+    fn assign(&mut self, place: &Expr, value: Value) -> Eval<()> {
+        let pos = place.pos;
+        match &place.kind {
+            ExprKind::Path(path) if path.len() == 1 => match self.lookup_mut(&path[0]) {
+                Some(slot) => {
+                    *slot = value;
+                    Ok(())
+                }
+                None => self.invalid(pos, format!("nome desconhecido `{}`", path[0])),
+            },
+            ExprKind::Index(base, index) => {
+                let container = self.eval(base)?;
+                let Value::Vec(items) = container else {
+                    return self.unsupported(
+                        pos,
+                        format!("atribuição por índice em {}", container.type_name()),
+                    );
+                };
+                let index = self.eval(index)?;
+                let index = self.index_arg(&index, pos)?;
+                let len = items.borrow().len();
+                if index >= len {
+                    return self.panic(
+                        pos,
+                        format!("index out of bounds: the len is {len} but the index is {index}"),
+                    );
+                }
+                items.borrow_mut()[index] = value;
+                Ok(())
+            }
+            ExprKind::TupleField(base, index) => {
+                let Value::Tuple(items) = self.eval(base)? else {
+                    return self.invalid(pos, format!("`.{index}` fora de tupla"));
+                };
+                let mut items = (*items).clone();
+                if *index >= items.len() {
+                    return self.invalid(pos, format!("tupla sem o campo {index}"));
+                }
+                items[*index] = value;
+                self.assign(base, Value::tuple(items))
+            }
+            ExprKind::Deref(inner) => match (self.eval(inner)?, value) {
+                (Value::Str(target), Value::Str(source)) => {
+                    let text = source.borrow().clone();
+                    *target.borrow_mut() = text;
+                    Ok(())
+                }
+                (Value::Vec(target), Value::Vec(source)) => {
+                    let items = source.borrow().clone();
+                    *target.borrow_mut() = items;
+                    Ok(())
+                }
+                (_, value) => self.assign(inner, value),
+            },
+            _ => self.invalid(pos, "lado esquerdo inválido em atribuição"),
+        }
+    }
+
+    // --- indexação -------------------------------------------------------------
+
+    pub(crate) fn index_arg(&self, value: &Value, pos: Pos) -> Eval<usize> {
+        match value {
+            Value::Int(n, _) if *n >= 0 => Ok(*n as usize),
+            Value::Int(n, _) => self.panic(
+                pos,
+                format!("índice negativo ({n}); em Rust, um usize teria dado overflow antes"),
+            ),
+            other => self.invalid(
+                pos,
+                format!("índice precisa ser inteiro, recebeu {}", other.type_name()),
+            ),
+        }
+    }
+
+    pub(crate) fn usize_arg(&self, value: &Value, pos: Pos) -> Eval<usize> {
+        self.index_arg(value, pos)
+    }
+
+    fn index(&mut self, base: Value, index: &Expr, pos: Pos) -> Eval {
+        let len = match &base {
+            Value::Vec(items) => items.borrow().len(),
+            Value::Str(text) => text.borrow().len(),
+            other => {
+                return self.invalid(pos, format!("indexação em {}", other.type_name()));
+            }
+        };
+
+        let range = if let ExprKind::Range {
+            start,
+            end,
+            inclusive,
+        } = &index.kind
+        {
+            let start = match start {
+                Some(start) => {
+                    let start = self.eval(start)?;
+                    self.index_arg(&start, pos)?
+                }
+                None => 0,
+            };
+            let end = match end {
+                Some(end) => {
+                    let end = self.eval(end)?;
+                    self.index_arg(&end, pos)? + usize::from(*inclusive)
+                }
+                None => len,
+            };
+            Some((start, end))
+        } else {
+            None
+        };
+
+        match (base, range) {
+            (Value::Vec(items), None) => {
+                let index = self.eval(index)?;
+                let index = self.index_arg(&index, pos)?;
+                match items.borrow().get(index) {
+                    Some(item) => Ok(item.clone()),
+                    None => self.panic(
+                        pos,
+                        format!("index out of bounds: the len is {len} but the index is {index}"),
+                    ),
+                }
+            }
+            (Value::Vec(items), Some((start, end))) => {
+                self.check_range(start, end, len, pos)?;
+                Ok(Value::vec(items.borrow()[start..end].to_vec()))
+            }
+            (Value::Str(text), Some((start, end))) => {
+                let text = text.borrow();
+                if let Some(message) = str_range_error(&text, start, end) {
+                    return self.panic(pos, message);
+                }
+                Ok(Value::str(&text[start..end]))
+            }
+            (Value::Str(_), None) => self.invalid(
+                pos,
+                "String não pode ser indexada por inteiro; use .chars().nth(i) ou .as_bytes()[i]",
+            ),
+            _ => unreachable!("tipos conferidos acima"),
+        }
+    }
+
+    fn check_range(&self, start: usize, end: usize, len: usize, pos: Pos) -> Eval<()> {
+        if start > len && end == len {
+            return self.panic(
+                pos,
+                format!("range start index {start} out of range for slice of length {len}"),
+            );
+        }
+        if start > end {
+            return self.panic(
+                pos,
+                format!("slice index starts at {start} but ends at {end}"),
+            );
+        }
+        if end > len {
+            return self.panic(
+                pos,
+                format!("range end index {end} out of range for slice of length {len}"),
+            );
+        }
+        Ok(())
+    }
+
+    /// Todos os elementos de algo iterável (`collect`, `extend`, `Vec::from`).
+    pub(crate) fn items(&mut self, value: Value, pos: Pos) -> Eval<Vec<Value>> {
+        if let Value::Vec(items) = &value {
+            let items = items.borrow().clone();
+            self.charge(items.len())?;
+            return Ok(items);
+        }
+        let state = self.iterator(value, pos)?;
+        self.drain(&state, pos)
+    }
+
+    // --- chamadas ----------------------------------------------------------------
+
+    fn call_path(&mut self, path: &[String], args: &[Expr], pos: Pos) -> Eval {
+        if let [name] = path {
+            if let Some(callee @ Value::Closure(..)) = self.lookup(name).cloned() {
+                let args = self.eval_args(args)?;
+                return self.call_value(&callee, args, pos);
+            }
+            if let Some(function) = self.program.functions.get(name).cloned() {
+                let args = self.eval_args(args)?;
+                return self.call_function(&function, args, pos);
+            }
+        }
+
+        let name = path.last().map(String::as_str).unwrap_or_default();
+        let owner = path.len().checked_sub(2).map(|i| path[i].as_str());
+        if name == "swap" && matches!(owner, None | Some("mem")) {
+            return self.swap(args, pos);
+        }
+        let values = self.eval_args(args)?;
+        self.builtin(owner, name, values, path, pos)
+    }
+
+    /// `std::mem::swap(&mut a, &mut b)`: troca os lugares.
+    fn swap(&mut self, args: &[Expr], pos: Pos) -> Eval {
+        let [a, b] = args else {
+            return self.invalid(pos, "swap recebe dois argumentos");
+        };
+        let (ExprKind::Ref(a, true), ExprKind::Ref(b, true)) = (&a.kind, &b.kind) else {
+            return self.invalid(pos, "swap recebe `&mut a, &mut b`");
+        };
+        let va = self.eval(a)?;
+        let vb = self.eval(b)?;
+        self.assign(a, vb)?;
+        self.assign(b, va)?;
+        Ok(Value::Unit)
+    }
+
+    fn builtin(
+        &mut self,
+        owner: Option<&str>,
+        name: &str,
+        mut args: Vec<Value>,
+        path: &[String],
+        pos: Pos,
+    ) -> Eval {
+        let arity = |n: usize| args.len() == n;
+        match (owner, name) {
+            (None | Some("Option" | "Result"), "Some" | "Ok" | "Err") if arity(1) => {
+                let variant = Variant::from_name(name).unwrap_or(Variant::Some);
+                Ok(Value::Variant(variant, Some(Rc::new(args.remove(0)))))
+            }
+            (Some("String"), "new" | "with_capacity") => Ok(Value::str("")),
+            (Some("String"), "from") if arity(1) => match &args[0] {
+                Value::Str(text) => Ok(Value::str(text.borrow().clone())),
+                Value::Char(c) => Ok(Value::str(c.to_string())),
+                other => self.invalid(pos, format!("String::from({})", other.type_name())),
+            },
+            (Some("str" | "String"), "from_utf8" | "from_utf8_lossy") if arity(1) => {
+                let mut bytes = Vec::new();
+                for item in self.items(args.remove(0), pos)? {
+                    match item {
+                        Value::Int(n, _) if (0..=255).contains(&n) => bytes.push(n as u8),
+                        other => {
+                            return self.invalid(pos, format!("{name} de {}", other.type_name()));
+                        }
+                    }
+                }
+                // Os erros vêm da própria biblioteca padrão, com `{:?}` e `{}`
+                Ok(match (owner, name) {
+                    (_, "from_utf8_lossy") => Value::str(String::from_utf8_lossy(&bytes)),
+                    (Some("str"), _) => match std::str::from_utf8(&bytes) {
+                        Ok(text) => Value::ok(Value::str(text)),
+                        Err(err) => Value::error(format!("{err:?}"), err.to_string()),
+                    },
+                    _ => match String::from_utf8(bytes) {
+                        Ok(text) => Value::ok(Value::str(text)),
+                        Err(err) => Value::error(format!("{err:?}"), err.to_string()),
+                    },
+                })
+            }
+            (Some("Vec"), "new" | "with_capacity") => Ok(Value::vec(Vec::new())),
+            (Some("Vec"), "from") if arity(1) => {
+                let items = self.items(args.remove(0), pos)?;
+                Ok(Value::vec(items))
+            }
+            (Some("Box" | "Rc"), "new") if arity(1) => Ok(args.remove(0)),
+            (Some("io") | None, "stdin") if arity(0) => Ok(Value::Stdin),
+            (Some("io"), "read_to_string") if matches!(args[..], [Value::Stdin]) => {
+                let text = self.read_rest().to_string();
+                Ok(Value::ok(Value::str(text)))
+            }
+            (Some("BufReader"), "new") if matches!(args[..], [Value::Stdin]) => Ok(Value::Stdin),
+            (Some("cmp") | None, "max" | "min") if arity(2) => {
+                let b = args.pop().unwrap_or(Value::Unit);
+                let a = args.pop().unwrap_or(Value::Unit);
+                self.method(a, name, None, vec![b], pos)
+            }
+            (Some("process"), "exit") if arity(1) => match args[0] {
+                Value::Int(code, _) => Err(Flow::Exit(Exit::Exited { code: code as i32 })),
+                _ => self.invalid(pos, "process::exit recebe um inteiro"),
+            },
+            (Some("char"), "from_u32") if arity(1) => match args[0] {
+                Value::Int(n, _) => Ok(Value::option(
+                    u32::try_from(n)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .map(Value::Char),
+                )),
+                _ => self.invalid(pos, "char::from_u32 recebe um inteiro"),
+            },
+            (Some("char"), "from_digit") if arity(2) => match (&args[0], &args[1]) {
+                (Value::Int(d, _), Value::Int(radix, _)) if (2..=36).contains(radix) => {
+                    Ok(Value::option(
+                        u32::try_from(*d)
+                            .ok()
+                            .and_then(|d| char::from_digit(d, *radix as u32))
+                            .map(Value::Char),
+                    ))
+                }
+                _ => self.invalid(pos, "char::from_digit(dígito, base)"),
+            },
+            (Some("char"), "from") if arity(1) => match args[0] {
+                Value::Int(n @ 0..=255, _) => Ok(Value::Char(char::from(n as u8))),
+                Value::Char(c) => Ok(Value::Char(c)),
+                _ => self.invalid(pos, "char::from recebe um u8"),
+            },
+            (Some(ty), "from_str_radix") if is_int_type(ty) && arity(2) => {
+                match (&args[0], &args[1]) {
+                    (Value::Str(text), Value::Int(radix, _)) if (2..=36).contains(radix) => {
+                        let ty = IntTy::from_name(ty).unwrap_or(IntTy::I64);
+                        Ok(parse_int(&text.borrow(), *radix as u32, ty))
+                    }
+                    _ => self.invalid(pos, "from_str_radix(texto, base)"),
+                }
+            }
+            (Some(ty), "from") if arity(1) && (is_int_type(ty) || is_float_type(ty)) => {
+                let target = Ty {
+                    name: ty.to_string(),
+                    args: Vec::new(),
+                };
+                self.cast(args.remove(0), &target, pos)
+            }
+            // `i64::max(a, b)`, `f64::sqrt(x)`: o método com o primeiro argumento
+            (Some(ty), method)
+                if (is_int_type(ty) || is_float_type(ty) || ty == "char" || ty == "str")
+                    && !args.is_empty() =>
+            {
+                let receiver = args.remove(0);
+                self.method(receiver, method, None, args, pos)
+            }
+            (None, _) => self.invalid(pos, format!("função `{name}` não encontrada")),
+            _ => self.unsupported(pos, format!("`{}`", path.join("::"))),
+        }
+    }
+
+    fn call_function(&mut self, function: &Rc<Function>, args: Vec<Value>, pos: Pos) -> Eval {
+        if args.len() != function.params.len() {
+            return self.invalid(
+                pos,
+                format!(
+                    "`{}` recebe {} argumento(s), mas recebeu {}",
+                    function.name,
+                    function.params.len(),
+                    args.len()
+                ),
+            );
+        }
+        if self.depth >= self.config.max_depth {
+            return Err(Flow::Exit(Exit::StackOverflow {
+                limit_depth: self.config.max_depth,
+            }));
+        }
+
+        let saved_scopes = mem::replace(&mut self.scopes, vec![Vec::new()]);
+        self.suspended.push((self.frame, saved_scopes));
+        let saved_hint = mem::replace(&mut self.hint, function.ret.as_ref().and_then(scalar));
+        self.next_frame += 1;
+        self.frame = self.next_frame;
+        self.depth += 1;
+
+        let result = self.invoke(function, args);
+
+        self.depth -= 1;
+        self.hint = saved_hint;
+        if let Some((frame, scopes)) = self.suspended.pop() {
+            self.frame = frame;
+            self.scopes = scopes;
+        }
+
+        let value = match result {
+            Ok(value) | Err(Flow::Return(value)) => value,
+            Err(Flow::Break(..) | Flow::Continue(_)) => {
+                return self.invalid(function.pos, "`break`/`continue` fora de um laço");
+            }
+            Err(flow) => return Err(flow),
+        };
+        self.annotate(value, function.ret.as_ref(), function.pos)
+    }
+
+    fn invoke(&mut self, function: &Function, args: Vec<Value>) -> Eval {
+        for ((pattern, ty), arg) in function.params.iter().zip(args) {
+            let arg = self.annotate(arg, Some(ty), function.pos)?;
+            self.bind_pattern(pattern, arg, function.pos)?;
+        }
+        self.block(&function.body)
+    }
+
+    pub(crate) fn call_value(&mut self, callee: &Value, args: Vec<Value>, pos: Pos) -> Eval {
+        let Value::Closure(closure, capture) = callee else {
+            return self.invalid(pos, format!("{} não é chamável", callee.type_name()));
+        };
+        self.call_closure(closure, *capture, args, pos)
+    }
+
+    fn call_closure(
+        &mut self,
+        closure: &Closure,
+        capture: Capture,
+        args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        if capture.frame != self.frame {
+            // Criada numa chamada abaixo desta: troca de escopos com ela
+            let Some(index) = self
+                .suspended
+                .iter()
+                .position(|(frame, _)| *frame == capture.frame)
+            else {
+                return self.unsupported(
+                    pos,
+                    "closure chamada depois que a função que a criou retornou",
+                );
+            };
+            let scopes = mem::take(&mut self.suspended[index].1);
+            let current = mem::replace(&mut self.scopes, scopes);
+            self.suspended[index] = (self.frame, current);
+            self.frame = capture.frame;
+            let result = self.call_closure(closure, capture, args, pos);
+            let (frame, scopes) = mem::take(&mut self.suspended[index]);
+            self.suspended[index] = (self.frame, mem::replace(&mut self.scopes, scopes));
+            self.frame = frame;
+            return result;
+        }
+        if self.scopes.len() < capture.depth {
+            return self.unsupported(pos, "closure chamada fora do escopo em que foi criada");
+        }
+        if args.len() != closure.params.len() {
+            return self.invalid(
+                pos,
+                format!(
+                    "closure recebe {} argumento(s), mas recebeu {}",
+                    closure.params.len(),
+                    args.len()
+                ),
+            );
+        }
+
+        // Esconde o que foi declarado depois da closure, como na captura
+        let hidden_scopes = self.scopes.split_off(capture.depth);
+        let hidden_names = match self.scopes.last_mut() {
+            Some(scope) if scope.len() >= capture.count => scope.split_off(capture.count),
+            _ => Vec::new(),
+        };
+
+        self.scopes.push(Vec::new());
+        let mut result = Ok(Value::Unit);
+        for (pattern, arg) in closure.params.iter().zip(args) {
+            if let Err(flow) = self.bind_pattern(pattern, arg, pos) {
+                result = Err(flow);
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = self.eval(&closure.body);
+        }
+        self.scopes.pop();
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.extend(hidden_names);
+        }
+        self.scopes.extend(hidden_scopes);
+
+        match result {
+            Err(Flow::Return(value)) => Ok(value),
+            other => other,
+        }
+    }
+
+    // --- macros --------------------------------------------------------------------
+
+    fn macro_call(
+        &mut self,
+        kind: MacroKind,
+        args: &[Expr],
+        template: Option<(&str, &[String])>,
+        source: Option<&str>,
+        pos: Pos,
+    ) -> Eval {
+        let leading = match kind {
+            MacroKind::Assert => 1,
+            MacroKind::AssertEq | MacroKind::AssertNe => 2,
+            _ => 0,
+        };
+        let (leading_args, format_args) = args.split_at(leading.min(args.len()));
+        let mut leading_values = Vec::new();
+        for arg in leading_args {
+            leading_values.push(self.eval(arg)?);
+        }
+        let message = match template {
+            Some((template, named)) => Some(self.format(template, format_args, named, pos)?),
+            None => None,
+        };
+
+        match kind {
+            MacroKind::Print | MacroKind::Println | MacroKind::Eprint | MacroKind::Eprintln => {
+                let mut text = message.unwrap_or_default();
+                if matches!(kind, MacroKind::Println | MacroKind::Eprintln) {
+                    text.push('\n');
+                }
+                self.emit(
+                    &text,
+                    matches!(kind, MacroKind::Eprint | MacroKind::Eprintln),
+                )?;
+                Ok(Value::Unit)
+            }
+            MacroKind::Format => Ok(Value::str(message.unwrap_or_default())),
+            MacroKind::Panic => {
+                self.panic(pos, message.unwrap_or_else(|| "explicit panic".to_string()))
+            }
+            MacroKind::Unreachable => self.panic(
+                pos,
+                match message {
+                    Some(message) => format!("internal error: entered unreachable code: {message}"),
+                    None => "internal error: entered unreachable code".to_string(),
+                },
+            ),
+            MacroKind::Todo | MacroKind::Unimplemented => {
+                let base = if kind == MacroKind::Todo {
+                    "not yet implemented"
+                } else {
+                    "not implemented"
+                };
+                self.panic(
+                    pos,
+                    match message {
+                        Some(message) => format!("{base}: {message}"),
+                        None => base.to_string(),
+                    },
+                )
+            }
+            MacroKind::Assert => match leading_values.first() {
+                Some(Value::Bool(true)) => Ok(Value::Unit),
+                Some(Value::Bool(false)) => self.panic(
+                    pos,
+                    message.unwrap_or_else(|| {
+                        format!("assertion failed: {}", source.unwrap_or_default())
+                    }),
+                ),
+                _ => self.invalid(pos, "assert! precisa de uma condição bool"),
+            },
+            MacroKind::AssertEq | MacroKind::AssertNe => {
+                let [left, right] = &leading_values[..] else {
+                    return self.invalid(pos, "assert_eq! recebe dois valores");
+                };
+                let equal = left.equals(right);
+                let (expected, symbol) = if kind == MacroKind::AssertEq {
+                    (true, "==")
+                } else {
+                    (false, "!=")
+                };
+                if equal == expected {
+                    return Ok(Value::Unit);
+                }
+                let head = match message {
+                    Some(message) => format!("assertion `left {symbol} right` failed: {message}"),
+                    None => format!("assertion `left {symbol} right` failed"),
+                };
+                self.panic(
+                    pos,
+                    format!(
+                        "{head}\n  left: {}\n right: {}",
+                        left.debug(false),
+                        right.debug(false)
+                    ),
+                )
+            }
+        }
+    }
+
+    fn format(
+        &mut self,
+        template: &str,
+        args: &[Expr],
+        named: &[String],
+        pos: Pos,
+    ) -> Eval<String> {
+        let pieces = match format::parse(template) {
+            Ok(pieces) => pieces,
+            Err(message) => return self.invalid(pos, message),
+        };
+        let values = self.eval_args(args)?;
+        let first_named = values.len().saturating_sub(named.len());
+        // Argumento nomeado primeiro; senão, captura implícita do escopo
+        let by_name = |this: &Self, name: &str| match named.iter().position(|n| n == name) {
+            Some(index) => values.get(first_named + index).cloned(),
+            None => this.lookup(name).cloned(),
+        };
+        let mut next = 0;
+        let mut out = String::new();
+        for piece in pieces {
+            let (arg, spec) = match piece {
+                Piece::Text(text) => {
+                    out.push_str(&text);
+                    continue;
+                }
+                Piece::Arg { arg, spec } => (arg, spec),
+            };
+            let precision = match &spec.precision {
+                Some(Count::Star) => {
+                    let value = values.get(next).cloned();
+                    next += 1;
+                    Some(self.count_value(value, pos)?)
+                }
+                Some(Count::Name(name)) => Some(self.count_value(by_name(self, name), pos)?),
+                Some(count) => Some(self.count(count, &values, pos)?),
+                None => None,
+            };
+            let width = match &spec.width {
+                Some(Count::Name(name)) => Some(self.count_value(by_name(self, name), pos)?),
+                Some(count) => Some(self.count(count, &values, pos)?),
+                None => None,
+            };
+            let value = match arg {
+                ArgRef::Next => {
+                    next += 1;
+                    values.get(next - 1).cloned()
+                }
+                ArgRef::Index(index) => values.get(index).cloned(),
+                ArgRef::Name(name) => match by_name(self, &name) {
+                    Some(value) => Some(value),
+                    None => return self.invalid(pos, format!("nome desconhecido `{name}`")),
+                },
+            };
+            let Some(value) = value else {
+                return self.invalid(pos, "faltam argumentos para o formato");
+            };
+            // O preenchimento e os dígitos da precisão são alocados de uma vez
+            self.charge(width.unwrap_or(0).max(precision.unwrap_or(0)))?;
+            match format::render(&value, &spec, width, precision) {
+                Ok(text) => {
+                    self.fits(out.len() + text.len())?;
+                    out.push_str(&text);
+                }
+                Err(message) => return self.invalid(pos, message),
+            }
+        }
+        Ok(out)
+    }
+
+    fn count(&self, count: &Count, values: &[Value], pos: Pos) -> Eval<usize> {
+        match count {
+            Count::Fixed(n) => Ok(*n),
+            Count::Index(index) => self.count_value(values.get(*index).cloned(), pos),
+            Count::Name(name) => self.count_value(self.lookup(name).cloned(), pos),
+            Count::Star => self.invalid(pos, "`*` só vale na precisão"),
+        }
+    }
+
+    fn count_value(&self, value: Option<Value>, pos: Pos) -> Eval<usize> {
+        match value {
+            Some(Value::Int(n, _)) if n >= 0 => Ok(n as usize),
+            _ => self.invalid(pos, "largura/precisão precisa ser um usize"),
+        }
+    }
+
+    // --- conversões ------------------------------------------------------------
+
+    pub(crate) fn cast(&self, value: Value, ty: &Ty, pos: Pos) -> Eval {
+        let target = ty.name.as_str();
+        let int = IntTy::from_name(target);
+        match (value, int) {
+            (Value::Int(n, _), Some(int)) => self.wrap_int(n.into(), int, pos),
+            (Value::Int(n, _), None) if is_float_type(target) => Ok(Value::Float(n as f64)),
+            (Value::Int(n, _), None) if target == "char" => match u8::try_from(n) {
+                Ok(byte) => Ok(Value::Char(char::from(byte))),
+                Err(_) => self.invalid(pos, "só u8 pode ser convertido para char com `as`"),
+            },
+            (Value::Float(f), None) if is_float_type(target) => {
+                Ok(Value::Float(if target == "f32" {
+                    f as f32 as f64
+                } else {
+                    f
+                }))
+            }
+            // `as` satura floats na faixa do tipo; NaN vira 0
+            (Value::Float(f), Some(int)) => {
+                let (min, max) = int.bounds();
+                let clamped = if f.is_nan() {
+                    0
+                } else {
+                    (f as i128).clamp(min, max)
+                };
+                self.wrap_int(clamped, int, pos)
+            }
+            (Value::Char(c), Some(int)) => self.wrap_int(u32::from(c).into(), int, pos),
+            (Value::Char(c), None) if target == "char" => Ok(Value::Char(c)),
+            (Value::Bool(b), Some(int)) => Ok(Value::Int(i64::from(b), Some(int))),
+            (value, _) => self.invalid(
+                pos,
+                format!("conversão de {} para {target} com `as`", value.type_name()),
+            ),
+        }
+    }
+
+    /// Trunca `n` para a largura de `target` (como `as`).
+    fn wrap_int(&self, n: i128, target: IntTy, pos: Pos) -> Eval {
+        match target.wrap(n) {
+            Some(value) => Ok(Value::Int(value, Some(target))),
+            None => self.unsupported(
+                pos,
+                format!("{n} convertido para {} não cabe em i64", target.name()),
+            ),
+        }
+    }
+}
+
+/// Conversões implícitas pela anotação de tipo: `let x: f64 = 1;` e
+/// `let s: String = chars.into_iter().collect();`.
+pub(crate) fn coerce(value: Value, ty: Option<&Ty>) -> Value {
+    let Some(ty) = ty else {
+        return value;
+    };
+    let int = IntTy::from_name(&ty.name);
+    match (value, ty.name.as_str()) {
+        (Value::Int(n, _), "f64" | "f32") => Value::Float(n as f64),
+        (Value::Int(n, _), _) if int.is_some_and(|int| int.contains(n.into())) => {
+            Value::Int(n, int)
+        }
+        (Value::Vec(items), "String") => {
+            let text: String = items
+                .borrow()
+                .iter()
+                .map(|item| item.display().unwrap_or_default())
+                .collect();
+            Value::str(text)
+        }
+        // `collect::<Result<Vec<_>, _>>()` escrito como anotação
+        (Value::Vec(items), wrapper @ ("Result" | "Option")) => {
+            let mut inner = Vec::new();
+            for item in items.borrow().iter() {
+                match item {
+                    Value::Variant(Variant::Ok | Variant::Some, Some(value)) => {
+                        inner.push((**value).clone());
+                    }
+                    Value::Variant(Variant::Err | Variant::None, _) => return item.clone(),
+                    _ => return Value::Vec(items.clone()),
+                }
+            }
+            let inner = coerce(Value::vec(inner), ty.args.first());
+            if wrapper == "Result" {
+                Value::ok(inner)
+            } else {
+                Value::some(inner)
+            }
+        }
+        // No lugar, para não desligar um `&mut Vec<_>` de quem o passou
+        (Value::Vec(items), "Vec" | "[]") => {
+            let element = ty.args.first().map_or("", |t| t.name.as_str());
+            let int = IntTy::from_name(element);
+            if is_float_type(element) || int.is_some() {
+                for item in items.borrow_mut().iter_mut() {
+                    if let Value::Int(n, _) = *item {
+                        *item = coerce(Value::Int(n, None), ty.args.first());
+                    }
+                }
+            }
+            Value::Vec(items)
+        }
+        (value, _) => value,
+    }
+}
+
+/// Tipo escalar mais interno de uma anotação: `Vec<u8>` → `u8`,
+/// `Result<Vec<f64>, _>` → `f64`.
+pub(crate) fn scalar(ty: &Ty) -> Option<String> {
+    match ty.name.as_str() {
+        "Vec" | "Option" | "Result" | "Box" | "[]" => ty.args.first().and_then(scalar),
+        name if is_int_type(name) || is_float_type(name) => Some(name.to_string()),
+        name @ ("bool" | "char" | "String" | "str") => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+fn is_copy(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Unit
+            | Value::Bool(_)
+            | Value::Int(..)
+            | Value::Float(_)
+            | Value::Char(_)
+            | Value::Tuple(_)
+            | Value::Variant(..)
+    )
+}
+
+pub(crate) fn is_int_type(name: &str) -> bool {
+    IntTy::from_name(name).is_some()
+}
+
+pub(crate) fn is_float_type(name: &str) -> bool {
+    matches!(name, "f32" | "f64")
+}
+
+/// `str::parse` para inteiros, com os erros da biblioteca padrão.
+pub(crate) fn parse_int(text: &str, radix: u32, ty: IntTy) -> Value {
+    let error = |debug: &str, display: &str| Value::error(debug, display);
+    if text.is_empty() {
+        return error(
+            "ParseIntError { kind: Empty }",
+            "cannot parse integer from empty string",
+        );
+    }
+    let (min, max) = ty.bounds();
+    let unsigned = min == 0;
+    let digits = text
+        .strip_prefix('+')
+        .or_else(|| text.strip_prefix('-').filter(|_| !unsigned))
+        .unwrap_or(text);
+    let negative = !unsigned && text.starts_with('-');
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return error(
+            "ParseIntError { kind: InvalidDigit }",
+            "invalid digit found in string",
+        );
+    }
+    let magnitude = digits
+        .chars()
+        .try_fold(0i128, |acc, c| {
+            acc.checked_mul(radix as i128)?
+                .checked_add(c.to_digit(radix)? as i128)
+        })
+        .unwrap_or(i128::MAX);
+    let value = if negative { -magnitude } else { magnitude };
+    if value > max {
+        return error(
+            "ParseIntError { kind: PosOverflow }",
+            "number too large to fit in target type",
+        );
+    }
+    if value < min {
+        return error(
+            "ParseIntError { kind: NegOverflow }",
+            "number too small to fit in target type",
+        );
+    }
+    match i64::try_from(value) {
+        Ok(value) => Value::ok(Value::Int(value, Some(ty))),
+        // Cabe em u64/u128 mas não no i64 do interpretador
+        Err(_) => error(
+            "ParseIntError { kind: PosOverflow }",
+            "number too large to fit in target type",
+        ),
+    }
+}
+
+/// Mensagem de pânico do Rust ao fatiar `text[start..end]`, se houver.
+pub(crate) fn str_range_error(text: &str, start: usize, end: usize) -> Option<String> {
+    for index in [start, end] {
+        if index > text.len() {
+            return Some(format!("byte index {index} is out of bounds of `{text}`"));
+        }
+    }
+    if start > end {
+        return Some(format!(
+            "begin <= end ({start} <= {end}) when slicing `{text}`"
+        ));
+    }
+    for index in [start, end] {
+        if !text.is_char_boundary(index) {
+            return Some(char_boundary_error(text, index));
+        }
+    }
+    None
+}
+
+pub(crate) fn char_boundary_error(text: &str, index: usize) -> String {
+    let (char_start, c) = text
+        .char_indices()
+        .take_while(|(i, _)| *i < index)
+        .last()
+        .unwrap_or((0, ' '));
+    format!(
+        "byte index {index} is not a char boundary; it is inside {c:?} (bytes {char_start}..{}) of `{text}`",
+        char_start + c.len_utf8()
+    )
+}
+
+fn comparison(op: BinOp) -> Option<fn(Ordering) -> bool> {
+    Some(match op {
+        BinOp::Eq => |o| o == Ordering::Equal,
+        BinOp::Ne => |o| o != Ordering::Equal,
+        BinOp::Lt => |o| o == Ordering::Less,
+        BinOp::Le => |o| o != Ordering::Greater,
+        BinOp::Gt => |o| o == Ordering::Greater,
+        BinOp::Ge => |o| o != Ordering::Less,
+        _ => return None,
+    })
+}
+
+fn constant(owner: &str, name: &str) -> Option<Value> {
+    if let Some(int) = IntTy::from_name(owner) {
+        // `u64::MAX` e afins não cabem em i64; o subconjunto usa i64::MAX
+        // como "infinito"
+        let (min, max) = int.bounds();
+        let clamp = |n: i128| n.clamp(i64::MIN.into(), i64::MAX.into()) as i64;
+        return match name {
+            "MIN" => Some(Value::Int(clamp(min), Some(int))),
+            "MAX" => Some(Value::Int(clamp(max), Some(int))),
+            "BITS" => Some(Value::Int(int.bits().into(), Some(IntTy::U32))),
+            _ => None,
+        };
+    }
+    match owner {
+        "f64" | "f32" => {
+            let narrow = owner == "f32";
+            Some(Value::Float(match name {
+                "MAX" if narrow => f32::MAX as f64,
+                "MIN" if narrow => f32::MIN as f64,
+                "EPSILON" if narrow => f32::EPSILON as f64,
+                "MAX" => f64::MAX,
+                "MIN" => f64::MIN,
+                "EPSILON" => f64::EPSILON,
+                "MIN_POSITIVE" => f64::MIN_POSITIVE,
+                "INFINITY" => f64::INFINITY,
+                "NEG_INFINITY" => f64::NEG_INFINITY,
+                "NAN" => f64::NAN,
+                _ => return None,
+            }))
+        }
+        "consts" => Some(Value::Float(match name {
+            "PI" => std::f64::consts::PI,
+            "E" => std::f64::consts::E,
+            "TAU" => std::f64::consts::TAU,
+            "SQRT_2" => std::f64::consts::SQRT_2,
+            "LN_2" => std::f64::consts::LN_2,
+            "LN_10" => std::f64::consts::LN_10,
+            _ => return None,
+        })),
+        "char" if name == "MAX" => Some(Value::Char(char::MAX)),
+        _ => None,
+    }
+}
@@ -0,0 +1,281 @@
+//! `format!` e família: `{}`, `{:?}`, `{nome}`, `{0}`, largura, alinhamento,
+//! preenchimento, sinal e precisão (`{:>8.2}`, `{:05}`, `{:+}`, `{:#?}`).
+
+use crate::value::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Piece {
+    Text(String),
+    Arg { arg: ArgRef, spec: Spec },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgRef {
+    Next,
+    Index(usize),
+    Name(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Spec {
+    pub fill: Option<char>,
+    pub align: Option<char>,
+    pub plus: bool,
+    pub alternate: bool,
+    pub zero: bool,
+    pub width: Option<Count>,
+    pub precision: Option<Count>,
+    pub debug: bool,
+    /// `x`, `X`, `b`, `o`, `e`.
+    pub radix: Option<char>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Count {
+    Fixed(usize),
+    /// `{:1$}`: o argumento posicional 1.
+    Index(usize),
+    /// `{:width$}` / `{:.prec$}`.
+    Name(String),
+    /// `{:.*}` consome um argumento.
+    Star,
+}
+
+pub fn parse(template: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '}' => return Err("`}` sem par no formato (use `}}`)".to_string()),
+            '{' => {
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => inner.push(c),
+                        None => return Err("`{` sem fechamento no formato".to_string()),
+                    }
+                }
+                if !text.is_empty() {
+                    pieces.push(Piece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(parse_placeholder(&inner)?);
+            }
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    Ok(pieces)
+}
+
+fn parse_placeholder(inner: &str) -> Result<Piece, String> {
+    let (name, spec) = match inner.split_once(':') {
+        Some((name, spec)) => (name.trim(), spec),
+        None => (inner.trim(), ""),
+    };
+    let arg = if name.is_empty() {
+        ArgRef::Next
+    } else if let Ok(index) = name.parse() {
+        ArgRef::Index(index)
+    } else if name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        ArgRef::Name(name.to_string())
+    } else {
+        return Err(format!("argumento de formato inválido: `{name}`"));
+    };
+    Ok(Piece::Arg {
+        arg,
+        spec: parse_spec(spec)?,
+    })
+}
+
+fn parse_spec(spec: &str) -> Result<Spec, String> {
+    let chars: Vec<char> = spec.chars().collect();
+    let mut i = 0;
+    let mut result = Spec::default();
+
+    let is_align = |c: char| matches!(c, '<' | '>' | '^');
+    if chars.len() >= 2 && is_align(chars[1]) {
+        result.fill = Some(chars[0]);
+        result.align = Some(chars[1]);
+        i = 2;
+    } else if chars.first().copied().is_some_and(is_align) {
+        result.align = Some(chars[0]);
+        i = 1;
+    }
+    if chars.get(i) == Some(&'+') {
+        result.plus = true;
+        i += 1;
+    } else if chars.get(i) == Some(&'-') {
+        i += 1;
+    }
+    if chars.get(i) == Some(&'#') {
+        result.alternate = true;
+        i += 1;
+    }
+    if chars.get(i) == Some(&'0') {
+        result.zero = true;
+        i += 1;
+    }
+    let (width, next) = parse_count(&chars, i);
+    result.width = width;
+    i = next;
+    if chars.get(i) == Some(&'.') {
+        i += 1;
+        if chars.get(i) == Some(&'*') {
+            result.precision = Some(Count::Star);
+            i += 1;
+        } else {
+            let (precision, next) = parse_count(&chars, i);
+            if precision.is_none() {
+                return Err(format!("precisão inválida em `{{:{spec}}}`"));
+            }
+            result.precision = precision;
+            i = next;
+        }
+    }
+    match chars.get(i..).unwrap_or_default() {
+        [] => {}
+        ['?'] => result.debug = true,
+        [c @ ('x' | 'X' | 'b' | 'o' | 'e' | 'E')] => result.radix = Some(*c),
+        [c @ ('x' | 'X'), '?'] => {
+            result.radix = Some(*c);
+            result.debug = true;
+        }
+        _ => {
+            return Err(format!(
+                "especificação de formato não suportada: `{{:{spec}}}`"
+            ));
+        }
+    }
+    Ok(result)
+}
+
+fn parse_count(chars: &[char], start: usize) -> (Option<Count>, usize) {
+    let mut end = start;
+    while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+        end += 1;
+    }
+    if end == start {
+        return (None, start);
+    }
+    let word: String = chars[start..end].iter().collect();
+    if chars.get(end) == Some(&'$') {
+        return match word.parse() {
+            Ok(index) => (Some(Count::Index(index)), end + 1),
+            Err(_) => (Some(Count::Name(word)), end + 1),
+        };
+    }
+    let digits: String = word.chars().take_while(char::is_ascii_digit).collect();
+    if digits.is_empty() {
+        return (None, start);
+    }
+    let end = start + digits.len();
+    (digits.parse().ok().map(Count::Fixed), end)
+}
+
+/// Formata um valor já resolvido com largura e precisão numéricas.
+pub fn render(
+    value: &Value,
+    spec: &Spec,
+    width: Option<usize>,
+    precision: Option<usize>,
+) -> Result<String, String> {
+    let mut body = if let (Some(radix @ ('e' | 'E')), Value::Float(f)) = (spec.radix, value) {
+        if radix == 'e' {
+            format!("{f:e}")
+        } else {
+            format!("{f:E}")
+        }
+    } else if let Some(radix) = spec.radix {
+        let Value::Int(n, ty) = value else {
+            return Err(format!(
+                "`{{:{radix}}}` exige inteiro, recebeu {}",
+                value.type_name()
+            ));
+        };
+        // Negativos saem em complemento de dois na largura do tipo.
+        let bits = ty.map_or(64, |ty| ty.bits().min(64));
+        let n = if bits == 64 {
+            *n as u64
+        } else {
+            (*n as u64) & ((1u64 << bits) - 1)
+        };
+        let digits = match radix {
+            'x' => format!("{n:x}"),
+            'X' => format!("{n:X}"),
+            'b' => format!("{n:b}"),
+            'o' => format!("{n:o}"),
+            'e' => format!("{:e}", n as i64),
+            _ => format!("{:E}", n as i64),
+        };
+        let prefix = match (spec.alternate, radix) {
+            (true, 'x' | 'X') => "0x",
+            (true, 'b') => "0b",
+            (true, 'o') => "0o",
+            _ => "",
+        };
+        format!("{prefix}{digits}")
+    } else if spec.debug {
+        match (value, precision) {
+            (Value::Float(f), Some(p)) => format!("{f:.p$}"),
+            _ => value.debug(spec.alternate),
+        }
+    } else {
+        match (value, precision) {
+            (Value::Float(f), Some(p)) => format!("{f:.p$}"),
+            (Value::Int(i, _), Some(_)) => i.to_string(),
+            (Value::Str(s), Some(p)) => s.borrow().chars().take(p).collect(),
+            _ => value.display().ok_or_else(|| {
+                format!("{} não implementa Display; use {{:?}}", value.type_name())
+            })?,
+        }
+    };
+
+    let numeric = matches!(value, Value::Int(..) | Value::Float(_));
+    if spec.plus && numeric && !body.starts_with('-') {
+        body.insert(0, '+');
+    }
+
+    let Some(width) = width else {
+        return Ok(body);
+    };
+    let len = body.chars().count();
+    if len >= width {
+        return Ok(body);
+    }
+    let pad = width - len;
+    if spec.zero && numeric && spec.align.is_none() {
+        let sign = body.starts_with(['-', '+']);
+        let split = if sign { 1 } else { 0 };
+        let split = if body[split..].starts_with("0x") || body[split..].starts_with("0b") {
+            split + 2
+        } else {
+            split
+        };
+        body.insert_str(split, &"0".repeat(pad));
+        return Ok(body);
+    }
+    let fill = spec.fill.unwrap_or(' ');
+    let align = spec.align.unwrap_or(if numeric { '>' } else { '<' });
+    let (left, right) = match align {
+        '>' => (pad, 0),
+        '^' => (pad / 2, pad - pad / 2),
+        _ => (0, pad),
+    };
+    let mut out = String::new();
+    out.extend(std::iter::repeat_n(fill, left));
+    out.push_str(&body);
+    out.extend(std::iter::repeat_n(fill, right));
+    Ok(out)
+}
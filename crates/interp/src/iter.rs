@@ -0,0 +1,413 @@
+//! Iteradores preguiçosos: uma fonte e uma pilha de adaptadores, puxados um
+//! item por vez como no Rust. A closure de um `map` só roda quando alguém
+//! pede o próximo item, então `take`, `next` e pânicos saem na mesma ordem.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::mem;
+use std::rc::Rc;
+
+use crate::eval::{Eval, Interpreter};
+use crate::lexer::Pos;
+use crate::value::{IntTy, Value, Variant};
+
+#[derive(Debug, Clone)]
+pub struct IterState {
+    pub source: Source,
+    /// Adaptadores na ordem em que foram encadeados.
+    pub stages: Vec<Stage>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Source {
+    Items(VecDeque<Value>),
+    /// `start..end` (semiaberta); `rev` anda de `end - 1` até `start`.
+    Range {
+        start: i64,
+        end: i64,
+        ty: Option<IntTy>,
+        rev: bool,
+    },
+    /// `stdin().lines()`: lê sob demanda, como o `BufRead` real.
+    StdinLines,
+    Zip(Value, Value),
+    Chain(VecDeque<Value>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Stage {
+    Map(Value),
+    Filter(Value),
+    FilterMap(Value),
+    MapWhile(Value, bool),
+    Inspect(Value),
+    Enumerate(i64),
+    Skip(usize),
+    Take(usize),
+    StepBy {
+        step: usize,
+        started: bool,
+    },
+    SkipWhile(Value, bool),
+    TakeWhile(Value, bool),
+    FlatMap(Value, Option<Value>),
+    Flatten(Option<Value>),
+    Cloned,
+    /// `peekable()`: o item já espiado por `peek()`.
+    Peek(Option<Option<Value>>),
+}
+
+impl IterState {
+    pub fn new(source: Source) -> Self {
+        IterState {
+            source,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Itens ainda por vir, quando dá para saber sem rodar closures.
+    pub fn pending_items(&self) -> Option<Vec<Value>> {
+        if !self.stages.is_empty() {
+            return None;
+        }
+        match &self.source {
+            Source::Items(items) => Some(items.iter().cloned().collect()),
+            _ => None,
+        }
+    }
+
+    /// `ExactSizeIterator::len` para fontes sem adaptadores.
+    pub fn len(&self) -> Option<usize> {
+        if !self.stages.is_empty() {
+            return None;
+        }
+        match &self.source {
+            Source::Items(items) => Some(items.len()),
+            Source::Range { start, end, .. } => usize::try_from(end.saturating_sub(*start)).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn deep_clone(&self) -> IterState {
+        let source = match &self.source {
+            Source::Items(items) => Source::Items(items.iter().map(Value::deep_clone).collect()),
+            Source::Zip(a, b) => Source::Zip(a.deep_clone(), b.deep_clone()),
+            Source::Chain(parts) => Source::Chain(parts.iter().map(Value::deep_clone).collect()),
+            other => other.clone(),
+        };
+        let stages = self
+            .stages
+            .iter()
+            .map(|stage| match stage {
+                Stage::FlatMap(f, Some(current)) => {
+                    Stage::FlatMap(f.clone(), Some(current.deep_clone()))
+                }
+                Stage::Flatten(Some(current)) => Stage::Flatten(Some(current.deep_clone())),
+                other => other.clone(),
+            })
+            .collect();
+        IterState { source, stages }
+    }
+}
+
+impl Interpreter<'_> {
+    /// Converte algo iterável num iterador (`into_iter()` implícito do `for`).
+    pub(crate) fn iterator(&mut self, value: Value, pos: Pos) -> Eval<Rc<RefCell<IterState>>> {
+        let source = match value {
+            Value::Iter(state) => return Ok(state),
+            Value::Vec(items) => {
+                let items = items.borrow();
+                self.charge(items.len())?;
+                Source::Items(items.iter().cloned().collect())
+            }
+            Value::Range(start, end, ty) => Source::Range {
+                start,
+                end,
+                ty,
+                rev: false,
+            },
+            Value::Variant(Variant::Some | Variant::Ok, Some(inner)) => {
+                Source::Items(VecDeque::from([(*inner).clone()]))
+            }
+            Value::Variant(Variant::None | Variant::Err, _) => Source::Items(VecDeque::new()),
+            Value::Str(_) => {
+                return self.invalid(pos, "String não é iterável; use .chars() ou .bytes()");
+            }
+            other => return self.invalid(pos, format!("{} não é iterável", other.type_name())),
+        };
+        Ok(Rc::new(RefCell::new(IterState::new(source))))
+    }
+
+    /// Adiciona um adaptador, devolvendo o mesmo iterador.
+    pub(crate) fn adapt(&mut self, value: Value, stage: Stage, pos: Pos) -> Eval {
+        let state = self.iterator(value, pos)?;
+        state.borrow_mut().stages.push(stage);
+        Ok(Value::Iter(state))
+    }
+
+    pub(crate) fn next_item(
+        &mut self,
+        state: &Rc<RefCell<IterState>>,
+        pos: Pos,
+    ) -> Eval<Option<Value>> {
+        let level = state.borrow().stages.len();
+        self.pull(state, level, pos)
+    }
+
+    /// Consome o restante do iterador.
+    pub(crate) fn drain(&mut self, state: &Rc<RefCell<IterState>>, pos: Pos) -> Eval<Vec<Value>> {
+        let mut items = Vec::new();
+        while let Some(item) = self.next_item(state, pos)? {
+            items.push(item);
+            if items.len() > self.config.max_elements {
+                self.charge(items.len())?;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Próximo item depois dos `level` primeiros adaptadores.
+    fn pull(
+        &mut self,
+        state: &Rc<RefCell<IterState>>,
+        level: usize,
+        pos: Pos,
+    ) -> Eval<Option<Value>> {
+        self.tick()?;
+        if level == 0 {
+            return self.pull_source(state, pos);
+        }
+        let index = level - 1;
+        // O adaptador sai do lugar enquanto roda: closures podem reentrar
+        let mut stage = mem::replace(&mut state.borrow_mut().stages[index], Stage::Cloned);
+        let result = self.apply(state, index, &mut stage, pos);
+        state.borrow_mut().stages[index] = stage;
+        result
+    }
+
+    fn apply(
+        &mut self,
+        state: &Rc<RefCell<IterState>>,
+        level: usize,
+        stage: &mut Stage,
+        pos: Pos,
+    ) -> Eval<Option<Value>> {
+        match stage {
+            Stage::Map(f) => match self.pull(state, level, pos)? {
+                Some(item) => self.call_value(f, vec![item], pos).map(Some),
+                None => Ok(None),
+            },
+            Stage::Filter(f) => {
+                while let Some(item) = self.pull(state, level, pos)? {
+                    if self.predicate(f, item.clone(), pos)? {
+                        return Ok(Some(item));
+                    }
+                }
+                Ok(None)
+            }
+            Stage::FilterMap(f) => {
+                while let Some(item) = self.pull(state, level, pos)? {
+                    match self.call_value(f, vec![item], pos)? {
+                        Value::Variant(Variant::Some, Some(inner)) => {
+                            return Ok(Some((*inner).clone()));
+                        }
+                        Value::Variant(Variant::None, _) => {}
+                        other => {
+                            return self.invalid(
+                                pos,
+                                format!("filter_map espera Option, recebeu {}", other.type_name()),
+                            );
+                        }
+                    }
+                }
+                Ok(None)
+            }
+            Stage::MapWhile(f, done) => {
+                if *done {
+                    return Ok(None);
+                }
+                let Some(item) = self.pull(state, level, pos)? else {
+                    return Ok(None);
+                };
+                match self.call_value(f, vec![item], pos)? {
+                    Value::Variant(Variant::Some, Some(inner)) => Ok(Some((*inner).clone())),
+                    _ => {
+                        *done = true;
+                        Ok(None)
+                    }
+                }
+            }
+            Stage::Inspect(f) => match self.pull(state, level, pos)? {
+                Some(item) => {
+                    self.call_value(f, vec![item.clone()], pos)?;
+                    Ok(Some(item))
+                }
+                None => Ok(None),
+            },
+            Stage::Enumerate(count) => match self.pull(state, level, pos)? {
+                Some(item) => {
+                    let index = *count;
+                    *count += 1;
+                    Ok(Some(Value::tuple(vec![
+                        Value::Int(index, Some(IntTy::Usize)),
+                        item,
+                    ])))
+                }
+                None => Ok(None),
+            },
+            Stage::Skip(remaining) => {
+                while *remaining > 0 {
+                    *remaining -= 1;
+                    if self.pull(state, level, pos)?.is_none() {
+                        return Ok(None);
+                    }
+                }
+                self.pull(state, level, pos)
+            }
+            Stage::Take(remaining) => {
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                *remaining -= 1;
+                self.pull(state, level, pos)
+            }
+            Stage::StepBy { step, started } => {
+                if *started {
+                    for _ in 1..*step {
+                        if self.pull(state, level, pos)?.is_none() {
+                            return Ok(None);
+                        }
+                    }
+                }
+                *started = true;
+                self.pull(state, level, pos)
+            }
+            Stage::SkipWhile(f, done) => {
+                while let Some(item) = self.pull(state, level, pos)? {
+                    if *done || !self.predicate(f, item.clone(), pos)? {
+                        *done = true;
+                        return Ok(Some(item));
+                    }
+                }
+                Ok(None)
+            }
+            Stage::TakeWhile(f, done) => {
+                if *done {
+                    return Ok(None);
+                }
+                match self.pull(state, level, pos)? {
+                    Some(item) if self.predicate(f, item.clone(), pos)? => Ok(Some(item)),
+                    _ => {
+                        *done = true;
+                        Ok(None)
+                    }
+                }
+            }
+            Stage::FlatMap(f, current) => loop {
+                if let Some(Value::Iter(inner)) = current {
+                    let inner = inner.clone();
+                    if let Some(item) = self.next_item(&inner, pos)? {
+                        return Ok(Some(item));
+                    }
+                }
+                let Some(item) = self.pull(state, level, pos)? else {
+                    return Ok(None);
+                };
+                let mapped = self.call_value(f, vec![item], pos)?;
+                *current = Some(Value::Iter(self.iterator(mapped, pos)?));
+            },
+            Stage::Flatten(current) => loop {
+                if let Some(Value::Iter(inner)) = current {
+                    let inner = inner.clone();
+                    if let Some(item) = self.next_item(&inner, pos)? {
+                        return Ok(Some(item));
+                    }
+                }
+                let Some(item) = self.pull(state, level, pos)? else {
+                    return Ok(None);
+                };
+                *current = Some(Value::Iter(self.iterator(item, pos)?));
+            },
+            Stage::Cloned => Ok(self.pull(state, level, pos)?.map(|item| item.deep_clone())),
+            Stage::Peek(peeked) => match peeked.take() {
+                Some(item) => Ok(item),
+                None => self.pull(state, level, pos),
+            },
+        }
+    }
+
+    fn pull_source(&mut self, state: &Rc<RefCell<IterState>>, pos: Pos) -> Eval<Option<Value>> {
+        let (a, b) = {
+            let mut state = state.borrow_mut();
+            match &mut state.source {
+                Source::Items(items) => return Ok(items.pop_front()),
+                Source::Range {
+                    start,
+                    end,
+                    ty,
+                    rev,
+                } => {
+                    if start >= end {
+                        return Ok(None);
+                    }
+                    let item = if *rev {
+                        *end -= 1;
+                        *end
+                    } else {
+                        *start += 1;
+                        *start - 1
+                    };
+                    return Ok(Some(Value::Int(item, *ty)));
+                }
+                Source::StdinLines => (None, None),
+                Source::Zip(a, b) => (Some(a.clone()), Some(b.clone())),
+                Source::Chain(parts) => (parts.front().cloned(), None),
+            }
+        };
+
+        match (a, b) {
+            (None, _) if matches!(state.borrow().source, Source::StdinLines) => {
+                let line = self.read_line();
+                if line.is_empty() {
+                    return Ok(None);
+                }
+                let line = line.strip_suffix('\n').unwrap_or(line);
+                let line = line.strip_suffix('\r').unwrap_or(line).to_string();
+                Ok(Some(Value::ok(Value::str(line))))
+            }
+            (Some(Value::Iter(a)), Some(Value::Iter(b))) => {
+                let Some(x) = self.next_item(&a, pos)? else {
+                    return Ok(None);
+                };
+                let Some(y) = self.next_item(&b, pos)? else {
+                    return Ok(None);
+                };
+                Ok(Some(Value::tuple(vec![x, y])))
+            }
+            (Some(Value::Iter(part)), None) => {
+                if let Some(item) = self.next_item(&part, pos)? {
+                    return Ok(Some(item));
+                }
+                if let Source::Chain(parts) = &mut state.borrow_mut().source {
+                    parts.pop_front();
+                }
+                self.pull_source(state, pos)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Chama uma closure que precisa devolver `bool`.
+    pub(crate) fn predicate(&mut self, f: &Value, item: Value, pos: Pos) -> Eval<bool> {
+        match self.call_value(f, vec![item], pos)? {
+            Value::Bool(value) => Ok(value),
+            other => self.invalid(
+                pos,
+                format!(
+                    "closure precisa devolver bool, devolveu {}",
+                    other.type_name()
+                ),
+            ),
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Tokens do subconjunto de Rust aceito pelo interpretador.
+
+use serde::Serialize;
+
+use crate::value::IntTy;
+use crate::{Error, Result};
+
+/// Posição 1-based no código, em caracteres.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Pos {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tok {
+    Ident(String),
+    /// Literal inteiro com o sufixo de tipo, se houver (`f32`/`f64` viram
+    /// [`Tok::Float`]).
+    Int(i128, Option<IntTy>),
+    Float(f64),
+    Str(String),
+    Char(char),
+    /// `'a`, usado em rótulos de laço.
+    Lifetime(String),
+    Punct(&'static str),
+    Eof,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub tok: Tok,
+    pub pos: Pos,
+    /// Há espaço (ou comentário) antes do token; distingue `> >` de `>>`.
+    pub spaced: bool,
+    /// Índices (em caracteres) do trecho no código, para citar expressões.
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Do mais longo para o mais curto. `<` e `>` nunca se juntam (exceto em
+/// `<=`, `>=`, `->` e `=>`) para não quebrar `Vec<Vec<i64>>`; o parser
+/// reconhece `<<` e `>>` por adjacência.
+const PUNCTS: &[&str] = &[
+    "..=", "...", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=",
+    "%=", "^=", "&=", "|=", "..", "+", "-", "*", "/", "%", "=", "<", ">", "!", "&", "|", "^", ".",
+    ",", ";", ":", "#", "(", ")", "[", "]", "{", "}", "?", "@", "$",
+];
+
+pub fn tokenize(source: &str) -> Result<Vec<Token>> {
+    Lexer {
+        chars: source.chars().collect(),
+        index: 0,
+        line: 1,
+        column: 1,
+        tokens: Vec::new(),
+    }
+    .run()
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    index: usize,
+    line: u32,
+    column: u32,
+    tokens: Vec<Token>,
+}
+
+impl Lexer {
+    fn run(mut self) -> Result<Vec<Token>> {
+        let mut spaced = true;
+        loop {
+            if self.skip_trivia()? {
+                spaced = true;
+            }
+            let pos = self.pos();
+            let start = self.index;
+            let Some(c) = self.peek(0) else {
+                self.tokens.push(Token {
+                    tok: Tok::Eof,
+                    pos,
+                    spaced: true,
+                    start: self.index,
+                    end: self.index,
+                });
+                return Ok(self.tokens);
+            };
+
+            let tok = if c == 'r' && matches!(self.peek(1), Some('"' | '#')) && self.raw_ahead() {
+                self.bump();
+                self.raw_string(pos)?
+            } else if c == 'b' && self.peek(1) == Some('\'') {
+                self.bump();
+                match self.char_or_lifetime(pos)? {
+                    Tok::Char(c) => Tok::Int(c as i128, Some(IntTy::U8)),
+                    _ => return Err(syntax(pos, "literal de byte inválido")),
+                }
+            } else if c == 'b' && self.peek(1) == Some('"') {
+                return Err(Error::Unsupported {
+                    pos,
+                    message: "literais de bytes (b\"...\")".to_string(),
+                });
+            } else if c.is_alphabetic() || c == '_' {
+                let mut ident = String::new();
+                while let Some(c) = self.peek(0).filter(|c| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                    self.bump();
+                }
+                Tok::Ident(ident)
+            } else if c.is_ascii_digit() {
+                self.number(pos)?
+            } else if c == '"' {
+                self.bump();
+                self.string(pos)?
+            } else if c == '\'' {
+                self.char_or_lifetime(pos)?
+            } else {
+                let punct = PUNCTS
+                    .iter()
+                    .find(|p| {
+                        p.chars()
+                            .enumerate()
+                            .all(|(i, pc)| self.peek(i) == Some(pc))
+                    })
+                    .ok_or_else(|| syntax(pos, &format!("caractere inesperado '{c}'")))?;
+                for _ in 0..punct.chars().count() {
+                    self.bump();
+                }
+                Tok::Punct(punct)
+            };
+            self.tokens.push(Token {
+                tok,
+                pos,
+                spaced,
+                start,
+                end: self.index,
+            });
+            spaced = false;
+        }
+    }
+
+    fn pos(&self) -> Pos {
+        Pos {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.index += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Pula espaços e comentários; devolve se pulou algo.
+    fn skip_trivia(&mut self) -> Result<bool> {
+        let start = self.index;
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('/'), Some('/')) => {
+                    while self.peek(0).is_some_and(|c| c != '\n') {
+                        self.bump();
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let pos = self.pos();
+                    let mut depth = 0;
+                    loop {
+                        match (self.peek(0), self.peek(1)) {
+                            (Some('/'), Some('*')) => {
+                                depth += 1;
+                                self.bump();
+                                self.bump();
+                            }
+                            (Some('*'), Some('/')) => {
+                                depth -= 1;
+                                self.bump();
+                                self.bump();
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            (Some(_), _) => {
+                                self.bump();
+                            }
+                            (None, _) => return Err(syntax(pos, "comentário de bloco sem fim")),
+                        }
+                    }
+                }
+                _ => return Ok(self.index > start),
+            }
+        }
+    }
+
+    /// `r"..."` ou `r#"..."#`, a partir do `r` já consumido.
+    fn raw_ahead(&self) -> bool {
+        let mut offset = 1;
+        while self.peek(offset) == Some('#') {
+            offset += 1;
+        }
+        self.peek(offset) == Some('"')
+    }
+
+    fn raw_string(&mut self, pos: Pos) -> Result<Tok> {
+        let mut hashes = 0;
+        while self.peek(0) == Some('#') {
+            hashes += 1;
+            self.bump();
+        }
+        self.bump();
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some('"') if (0..hashes).all(|i| self.peek(i) == Some('#')) => {
+                    for _ in 0..hashes {
+                        self.bump();
+                    }
+                    return Ok(Tok::Str(text));
+                }
+                Some(c) => text.push(c),
+                None => return Err(syntax(pos, "string sem fim")),
+            }
+        }
+    }
+
+    fn string(&mut self, pos: Pos) -> Result<Tok> {
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(Tok::Str(text)),
+                Some('\\') if self.peek(0) == Some('\n') => {
+                    while self.peek(0).is_some_and(char::is_whitespace) {
+                        self.bump();
+                    }
+                }
+                Some('\\') => text.push(self.escape(pos)?),
+                Some(c) => text.push(c),
+                None => return Err(syntax(pos, "string sem fim")),
+            }
+        }
+    }
+
+    fn escape(&mut self, pos: Pos) -> Result<char> {
+        Ok(match self.bump() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('\'') => '\'',
+            Some('"') => '"',
+            Some('x') => {
+                let hex: String = (0..2).filter_map(|_| self.bump()).collect();
+                u8::from_str_radix(&hex, 16)
+                    .ok()
+                    .filter(u8::is_ascii)
+                    .map(char::from)
+                    .ok_or_else(|| syntax(pos, "escape \\x inválido"))?
+            }
+            Some('u') if self.peek(0) == Some('{') => {
+                self.bump();
+                let mut hex = String::new();
+                while let Some(c) = self.bump().filter(|c| *c != '}') {
+                    hex.push(c);
+                }
+                u32::from_str_radix(&hex.replace('_', ""), 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| syntax(pos, "escape \\u{...} inválido"))?
+            }
+            _ => return Err(syntax(pos, "escape desconhecido")),
+        })
+    }
+
+    fn char_or_lifetime(&mut self, pos: Pos) -> Result<Tok> {
+        self.bump();
+        let is_lifetime = self.peek(0).is_some_and(|c| c.is_alphabetic() || c == '_')
+            && self.peek(1) != Some('\'');
+        if is_lifetime {
+            let mut name = String::new();
+            while let Some(c) = self.peek(0).filter(|c| c.is_alphanumeric() || *c == '_') {
+                name.push(c);
+                self.bump();
+            }
+            return Ok(Tok::Lifetime(name));
+        }
+        let c = match self.bump() {
+            Some('\\') => self.escape(pos)?,
+            Some(c) => c,
+            None => return Err(syntax(pos, "caractere sem fim")),
+        };
+        if self.bump() != Some('\'') {
+            return Err(syntax(pos, "literal de caractere inválido"));
+        }
+        Ok(Tok::Char(c))
+    }
+
+    fn number(&mut self, pos: Pos) -> Result<Tok> {
+        let radix = match (self.peek(0), self.peek(1)) {
+            (Some('0'), Some('x')) => 16,
+            (Some('0'), Some('o')) => 8,
+            (Some('0'), Some('b')) => 2,
+            _ => 10,
+        };
+        if radix != 10 {
+            self.bump();
+            self.bump();
+        }
+
+        let mut digits = String::new();
+        let mut float = false;
+        // Depois de `.`, `t.0.1` são dois acessos de tupla, não o float 0.1
+        let after_dot = matches!(
+            self.tokens.last(),
+            Some(Token {
+                tok: Tok::Punct("."),
+                ..
+            })
+        );
+        loop {
+            match self.peek(0) {
+                Some(c) if c.is_digit(radix) => digits.push(c),
+                Some('_') => {}
+                Some('.')
+                    if radix == 10
+                        && !float
+                        && !after_dot
+                        && self.peek(1).is_some_and(|c| c.is_ascii_digit()) =>
+                {
+                    float = true;
+                    digits.push('.');
+                }
+                Some('.')
+                    if radix == 10
+                        && !float
+                        && !after_dot
+                        && !matches!(self.peek(1), Some('.'))
+                        && !self.peek(1).is_some_and(|c| c.is_alphabetic() || c == '_') =>
+                {
+                    // `1.` é um float
+                    float = true;
+                }
+                Some('e' | 'E')
+                    if radix == 10
+                        && (self.peek(1).is_some_and(|c| c.is_ascii_digit())
+                            || (matches!(self.peek(1), Some('+' | '-'))
+                                && self.peek(2).is_some_and(|c| c.is_ascii_digit()))) =>
+                {
+                    float = true;
+                    digits.push('e');
+                    self.bump();
+                    digits.push(self.peek(0).unwrap_or('0'));
+                }
+                _ => break,
+            }
+            self.bump();
+        }
+
+        let mut suffix = String::new();
+        while let Some(c) = self.peek(0).filter(|c| c.is_alphanumeric() || *c == '_') {
+            suffix.push(c);
+            self.bump();
+        }
+        if suffix.starts_with('f') {
+            float = true;
+        }
+        let ty = IntTy::from_name(&suffix);
+        let valid_suffix =
+            suffix.is_empty() || ty.is_some() || matches!(suffix.as_str(), "f32" | "f64");
+        if !valid_suffix {
+            return Err(syntax(pos, &format!("sufixo numérico inválido: {suffix}")));
+        }
+
+        if float {
+            return digits
+                .parse()
+                .map(Tok::Float)
+                .map_err(|_| syntax(pos, "número inválido"));
+        }
+        i128::from_str_radix(&digits, radix)
+            .map(|value| Tok::Int(value, ty))
+            .map_err(|_| syntax(pos, "número inválido"))
+    }
+}
+
+fn syntax(pos: Pos, message: &str) -> Error {
+    Error::Syntax {
+        pos,
+        message: message.to_string(),
+    }
+}
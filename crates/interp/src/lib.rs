@@ -0,0 +1,198 @@
+//! Interpretador de um subconjunto de Rust, sem compilador nem processo.
+//!
+//! Existe para o Modo Prática e os exemplos rodarem no navegador (via
+//! `themelion-wasm`) sem backend. Não substitui o `rustc`: não há checagem
+//! de tipos nem de empréstimos, e o que rustc rejeitaria só é apontado
+//! quando a execução chega lá ([`Exit::Invalid`]). O subconjunto:
+//!
+//! - funções (genéricos ignorados), `const`/`static`, `use` (ignorado);
+//! - `let` com padrões, `if`/`if let`, `match` (literais, faixas, tuplas,
+//!   slices, `Some`/`None`/`Ok`/`Err`, `Ordering`, `|`, `@`, guardas),
+//!   `while`/`while let`, `loop`, `for`, rótulos, `?` e closures (também
+//!   passadas a funções, mas não devolvidas por elas);
+//! - inteiros guardados em `i64` com o tipo que se sabe (sufixo, anotação,
+//!   `as`, `len()`), com os pânicos de overflow desse tipo; sem tipo
+//!   conhecido, como `let x = 5;`, vale a faixa de `i64`, e não a de `i32`;
+//! - `f64`, `bool`, `char`, `String`/`&str`, `Vec`/arrays/slices, tuplas,
+//!   `Option`, `Result`, faixas e os métodos comuns desses tipos e de
+//!   iteradores (preguiçosos, como na biblioteca padrão);
+//! - `println!`/`print!`/`eprintln!`/`format!` com largura, alinhamento e
+//!   precisão, `vec!`, `assert!`/`assert_eq!`, `panic!`, `matches!`;
+//! - stdin por `io::stdin()`: `read_line`, `read_to_string` e `lines()`.
+//!
+//! Ficam de fora structs, enums, traits, `impl`, `HashMap`, referências a
+//! valores `Copy` (`&mut i64` como argumento) e `iter_mut()`; chegar a
+//! qualquer um deles dá [`Error::Unsupported`] ou [`Exit::Unsupported`],
+//! nunca um resultado errado em silêncio.
+
+mod ast;
+mod error;
+mod eval;
+mod format;
+mod iter;
+mod lexer;
+mod methods;
+mod parser;
+mod value;
+
+use std::fmt;
+
+use serde::Serialize;
+
+pub use error::Error;
+pub use lexer::Pos;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Limites da execução. Sem relógio no wasm, tempo é contado em passos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// Expressões avaliadas antes de [`Exit::StepLimit`].
+    pub max_steps: u64,
+    pub max_output_bytes: usize,
+    /// Chamadas de função aninhadas (a pilha do wasm é pequena).
+    pub max_depth: usize,
+    /// Elementos num único `Vec`, iterador ou faixa materializada, e bytes
+    /// numa única `String`.
+    pub max_elements: usize,
+}
+
+impl Config {
+    pub const DEFAULT: Config = Config {
+        max_steps: 50_000_000,
+        max_output_bytes: 1024 * 1024,
+        max_depth: 200,
+        max_elements: 4_000_000,
+    };
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::DEFAULT
+    }
+}
+
+/// Como a execução terminou.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum Exit {
+    Success,
+    /// `std::process::exit` ou `main` devolvendo `Err`.
+    Exited {
+        code: i32,
+    },
+    Panicked {
+        message: String,
+        pos: Pos,
+    },
+    StepLimit {
+        limit: u64,
+    },
+    OutputLimit {
+        limit_bytes: usize,
+    },
+    MemoryLimit {
+        limit_elements: usize,
+    },
+    StackOverflow {
+        limit_depth: usize,
+    },
+    /// Erro que o rustc pegaria na compilação (nome desconhecido, tipos).
+    Invalid {
+        message: String,
+        pos: Pos,
+    },
+    Unsupported {
+        message: String,
+        pos: Pos,
+    },
+}
+
+impl Exit {
+    pub fn success(&self) -> bool {
+        matches!(self, Exit::Success | Exit::Exited { code: 0 })
+    }
+
+    /// Código de saída equivalente ao do binário compilado.
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            Exit::Success => Some(0),
+            Exit::Exited { code } => Some(*code),
+            Exit::Panicked { .. } => Some(101),
+            _ => None,
+        }
+    }
+
+    /// O programa não pôde ser julgado por limitação do interpretador ou
+    /// por um erro que seria de compilação.
+    pub fn rejected(&self) -> bool {
+        matches!(self, Exit::Invalid { .. } | Exit::Unsupported { .. })
+    }
+}
+
+impl fmt::Display for Exit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exit::Success => write!(f, "saiu com código 0"),
+            Exit::Exited { code } => write!(f, "saiu com código {code}"),
+            Exit::Panicked { .. } => write!(f, "saiu com código 101"),
+            Exit::StepLimit { limit } => {
+                write!(f, "tempo limite excedido ({limit} passos do interpretador)")
+            }
+            Exit::OutputLimit { limit_bytes } => {
+                write!(f, "saída grande demais (mais de {limit_bytes} bytes)")
+            }
+            Exit::MemoryLimit { limit_elements } => {
+                write!(f, "memória excedida (mais de {limit_elements} elementos)")
+            }
+            Exit::StackOverflow { limit_depth } => {
+                write!(
+                    f,
+                    "estouro de pilha (mais de {limit_depth} chamadas aninhadas)"
+                )
+            }
+            Exit::Invalid { message, pos } => {
+                write!(f, "main.rs:{}:{}: {message}", pos.line, pos.column)
+            }
+            Exit::Unsupported { message, pos } => write!(
+                f,
+                "main.rs:{}:{}: não suportado pelo interpretador: {message}",
+                pos.line, pos.column
+            ),
+        }
+    }
+}
+
+/// Resultado de uma execução.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Outcome {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit: Exit,
+    pub steps: u64,
+}
+
+/// Programa analisado, pronto para rodar com várias entradas.
+#[derive(Debug)]
+pub struct Program(ast::Program);
+
+impl Program {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = lexer::tokenize(source)?;
+        parser::parse(source, tokens).map(Program)
+    }
+
+    pub fn run(&self, stdin: &str, config: &Config) -> Outcome {
+        eval::run(&self.0, stdin, config)
+    }
+}
+
+/// Analisa e executa `source` com `stdin`.
+pub fn run(source: &str, stdin: &str, config: &Config) -> Result<Outcome> {
+    Ok(Program::parse(source)?.run(stdin, config))
+}
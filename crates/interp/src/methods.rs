@@ -0,0 +1,2059 @@
+//! Métodos dos tipos da biblioteca padrão cobertos pelo subconjunto.
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::ast::{BinOp, Ty};
+use crate::eval::{
+    Eval, Interpreter, char_boundary_error, is_float_type, is_int_type, parse_int, str_range_error,
+};
+use crate::iter::{Source, Stage};
+use crate::lexer::Pos;
+use crate::value::{IntTy, Value, Variant};
+
+/// Padrão de busca em strings: `&str`, `char`, `[char]` ou closure.
+enum Pat {
+    Str(String),
+    Chars(Vec<char>),
+    Fn(Value),
+}
+
+impl Interpreter<'_> {
+    pub(crate) fn method(
+        &mut self,
+        receiver: Value,
+        name: &str,
+        turbofish: Option<&Ty>,
+        args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        self.tick()?;
+        if let Some(value) = self.common_method(&receiver, name, &args, pos)? {
+            return Ok(value);
+        }
+        match receiver {
+            Value::Int(n, ty) => self.int_method(n, ty, name, args, pos),
+            Value::Float(f) => self.float_method(f, name, args, pos),
+            Value::Char(c) => self.char_method(c, name, args, pos),
+            Value::Str(text) => self.str_method(&text, name, turbofish, args, pos),
+            Value::Vec(items) => self.vec_method(&items, name, args, pos),
+            Value::Range(start, end, ty) => {
+                self.range_method((start, end, ty), name, turbofish, args, pos)
+            }
+            Value::Iter(_) => self.iter_method(receiver, name, turbofish, args, pos),
+            Value::Variant(variant, payload) => {
+                self.variant_method(variant, payload, name, args, pos)
+            }
+            Value::Stdin => self.stdin_method(name, args, pos),
+            other => self.unknown(&other, name, pos),
+        }
+    }
+
+    /// Sem checagem de tipos, não dá para saber se o método não existe ou só
+    /// não foi coberto; na dúvida, o programa não é culpado.
+    fn unknown<T>(&self, receiver: &Value, name: &str, pos: Pos) -> Eval<T> {
+        if name == "capacity" {
+            return self.unsupported(pos, "`capacity()` depende do alocador");
+        }
+        self.unsupported(
+            pos,
+            format!(
+                "método `{name}` de {} (inexistente ou fora do subconjunto)",
+                receiver.type_name()
+            ),
+        )
+    }
+
+    fn arity(&self, name: &str, args: &[Value], n: usize, pos: Pos) -> Eval<()> {
+        if args.len() != n {
+            return self.invalid(
+                pos,
+                format!(
+                    "`{name}` recebe {n} argumento(s), mas recebeu {}",
+                    args.len()
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    fn int_arg(&self, value: &Value, pos: Pos) -> Eval<i64> {
+        match value {
+            Value::Int(n, _) => Ok(*n),
+            other => self.invalid(
+                pos,
+                format!("esperava inteiro, recebeu {}", other.type_name()),
+            ),
+        }
+    }
+
+    fn float_arg(&self, value: &Value, pos: Pos) -> Eval<f64> {
+        match value {
+            Value::Float(f) => Ok(*f),
+            Value::Int(n, _) => Ok(*n as f64),
+            other => self.invalid(pos, format!("esperava f64, recebeu {}", other.type_name())),
+        }
+    }
+
+    fn ordering_arg(&self, value: &Value, pos: Pos) -> Eval<Ordering> {
+        match value {
+            Value::Variant(Variant::Less, _) => Ok(Ordering::Less),
+            Value::Variant(Variant::Equal, _) => Ok(Ordering::Equal),
+            Value::Variant(Variant::Greater, _) => Ok(Ordering::Greater),
+            other => self.invalid(
+                pos,
+                format!("esperava Ordering, recebeu {}", other.type_name()),
+            ),
+        }
+    }
+
+    fn compare_values(&self, a: &Value, b: &Value, pos: Pos) -> Eval<Ordering> {
+        match a.compare(b) {
+            Some(ordering) => Ok(ordering),
+            None if matches!(a, Value::Float(_)) => {
+                self.invalid(pos, "f64 não implementa Ord; use partial_cmp ou total_cmp")
+            }
+            None => self.invalid(
+                pos,
+                format!("comparação entre {} e {}", a.type_name(), b.type_name()),
+            ),
+        }
+    }
+
+    // --- comuns a vários tipos ---------------------------------------------
+
+    fn common_method(
+        &mut self,
+        receiver: &Value,
+        name: &str,
+        args: &[Value],
+        pos: Pos,
+    ) -> Eval<Option<Value>> {
+        let iterator = matches!(receiver, Value::Iter(_));
+        Ok(Some(match (name, args) {
+            ("clone" | "to_owned" | "to_vec", []) if !iterator => receiver.deep_clone(),
+            ("clone", []) => receiver.deep_clone(),
+            ("to_string", []) => match receiver.display() {
+                Some(text) => Value::str(text),
+                None => {
+                    return self.invalid(
+                        pos,
+                        format!("{} não implementa Display", receiver.type_name()),
+                    );
+                }
+            },
+            // `Chars::as_str`: o que falta percorrer
+            ("as_str", []) if iterator => match receiver.display() {
+                Some(text) => Value::str(text),
+                None => return self.unsupported(pos, "as_str() de iterador com adaptadores"),
+            },
+            (
+                "as_str" | "as_ref" | "as_mut" | "borrow" | "borrow_mut" | "as_slice"
+                | "as_mut_slice" | "as_deref" | "by_ref" | "into" | "lock" | "into_boxed_slice",
+                [],
+            ) if !matches!(receiver, Value::Stdin) || name == "lock" => receiver.clone(),
+            ("cmp", [other]) if !iterator => {
+                Value::ordering(self.compare_values(receiver, other, pos)?)
+            }
+            ("partial_cmp", [other]) if !iterator => {
+                Value::option(receiver.compare(other).map(Value::ordering))
+            }
+            ("eq" | "ne" | "lt" | "le" | "gt" | "ge", [other]) if !iterator => {
+                let op = match name {
+                    "eq" => BinOp::Eq,
+                    "ne" => BinOp::Ne,
+                    "lt" => BinOp::Lt,
+                    "le" => BinOp::Le,
+                    "gt" => BinOp::Gt,
+                    _ => BinOp::Ge,
+                };
+                self.binary(op, receiver.clone(), other.clone(), pos)?
+            }
+            ("max" | "min", [other])
+                if matches!(
+                    receiver,
+                    Value::Int(..)
+                        | Value::Char(_)
+                        | Value::Str(_)
+                        | Value::Bool(_)
+                        | Value::Tuple(_)
+                ) =>
+            {
+                let ordering = self.compare_values(receiver, other, pos)?;
+                // `max` devolve o segundo nos empates, `min` o primeiro
+                let pick_other = match name {
+                    "max" => ordering != Ordering::Greater,
+                    _ => ordering == Ordering::Greater,
+                };
+                if pick_other {
+                    other.clone()
+                } else {
+                    receiver.clone()
+                }
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    // --- inteiros ------------------------------------------------------------
+
+    /// Métodos de inteiro na faixa de `ty` (a de `i64` quando o tipo não é
+    /// conhecido); os que dependem da largura exigem o tipo.
+    fn int_method(
+        &mut self,
+        n: i64,
+        ty: Option<IntTy>,
+        name: &str,
+        args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        let overflow = |what: &str| format!("attempt to {what} with overflow");
+        let arg = |this: &Self, i: usize| -> Eval<i128> {
+            match args.get(i) {
+                Some(value) => this.int_arg(value, pos).map(i128::from),
+                None => this.invalid(pos, format!("`{name}` precisa de argumento")),
+            }
+        };
+        let (min, max) = ty.map_or((i64::MIN.into(), i64::MAX.into()), IntTy::bounds);
+        let fits = |value: &i128| (min..=max).contains(value);
+        let bits = ty.map(IntTy::bits).filter(|bits| *bits <= 64);
+        // Bits do valor na largura do tipo (complemento de dois)
+        let raw = |bits: u32| (n as u64) & (u64::MAX >> (64 - bits));
+        let n = i128::from(n);
+        let unsigned = ty.map(IntTy::unsigned);
+        let u32_ty = Some(IntTy::U32);
+
+        let (value, ty) = match name {
+            "abs" => match n.abs() {
+                abs if fits(&abs) => (abs, ty),
+                _ => return self.panic(pos, overflow("negate")),
+            },
+            "unsigned_abs" => (n.abs(), unsigned),
+            "pow" | "checked_pow" | "saturating_pow" | "wrapping_pow" => {
+                let exp = u32::try_from(arg(self, 0)?).unwrap_or(u32::MAX);
+                let power = match n {
+                    -1..=1 if exp == 0 => Some(1),
+                    -1 if exp % 2 == 0 => Some(1),
+                    -1..=1 => Some(n),
+                    // |n| >= 2 estoura em no máximo 127 multiplicações
+                    _ => (0..exp).try_fold(1i128, |p, _| p.checked_mul(n).filter(fits)),
+                };
+                match (name, power) {
+                    ("checked_pow", power) => {
+                        return self.checked_int(power, ty, pos);
+                    }
+                    (_, Some(power)) => (power, ty),
+                    ("saturating_pow", None) => (if n < 0 && exp % 2 == 1 { min } else { max }, ty),
+                    ("wrapping_pow", None) => {
+                        return self.unsupported(pos, "wrapping_pow com overflow");
+                    }
+                    _ => return self.panic(pos, overflow("multiply")),
+                }
+            }
+            "signum" => (n.signum(), ty),
+            "is_positive" => return Ok(Value::Bool(n > 0)),
+            "is_negative" => return Ok(Value::Bool(n < 0)),
+            "rem_euclid" | "div_euclid" => {
+                let d = arg(self, 0)?;
+                if d == 0 {
+                    return self.panic(
+                        pos,
+                        if name == "rem_euclid" {
+                            "attempt to calculate the remainder with a divisor of zero"
+                        } else {
+                            "attempt to divide by zero"
+                        },
+                    );
+                }
+                if d == -1 && n == min {
+                    return self.panic(
+                        pos,
+                        if name == "rem_euclid" {
+                            "attempt to calculate the remainder with overflow"
+                        } else {
+                            "attempt to divide with overflow"
+                        },
+                    );
+                }
+                let value = if name == "rem_euclid" {
+                    n.rem_euclid(d)
+                } else {
+                    n.div_euclid(d)
+                };
+                (value, ty)
+            }
+            "clamp" => {
+                let (low, high) = (arg(self, 0)?, arg(self, 1)?);
+                if low > high {
+                    return self.panic(pos, "assertion failed: min <= max");
+                }
+                (n.clamp(low, high), ty)
+            }
+            "abs_diff" => ((n - arg(self, 0)?).abs(), unsigned),
+            "checked_add" | "checked_sub" | "checked_mul" | "checked_div" | "checked_rem"
+            | "checked_neg" => {
+                let value = match name {
+                    "checked_neg" => Some(-n),
+                    "checked_add" => Some(n + arg(self, 0)?),
+                    "checked_sub" => Some(n - arg(self, 0)?),
+                    "checked_mul" => Some(n * arg(self, 0)?),
+                    "checked_div" => n.checked_div(arg(self, 0)?),
+                    _ => match arg(self, 0)? {
+                        -1 if n == min => None,
+                        d => n.checked_rem(d),
+                    },
+                };
+                return self.checked_int(value.filter(fits), ty, pos);
+            }
+            "saturating_add" => ((n + arg(self, 0)?).clamp(min, max), ty),
+            "saturating_sub" => ((n - arg(self, 0)?).clamp(min, max), ty),
+            "saturating_mul" => ((n * arg(self, 0)?).clamp(min, max), ty),
+            "wrapping_add" | "wrapping_sub" | "wrapping_mul" | "wrapping_neg" => {
+                let value = match name {
+                    "wrapping_neg" => -n,
+                    "wrapping_add" => n + arg(self, 0)?,
+                    "wrapping_sub" => n - arg(self, 0)?,
+                    _ => n * arg(self, 0)?,
+                };
+                return match ty {
+                    Some(ty) => match ty.wrap(value) {
+                        Some(value) => Ok(Value::Int(value, Some(ty))),
+                        None => self.unsupported(
+                            pos,
+                            format!("`{name}` em {} fora do intervalo de i64", ty.name()),
+                        ),
+                    },
+                    None => Ok(Value::Int(value as i64, None)),
+                };
+            }
+            "count_ones" => match bits {
+                Some(bits) => (raw(bits).count_ones().into(), u32_ty),
+                None if n >= 0 => (n.count_ones().into(), u32_ty),
+                None => return self.width_unknown(name, pos),
+            },
+            "count_zeros" | "leading_zeros" => match bits {
+                Some(bits) => {
+                    let raw = raw(bits);
+                    let zeros = if name == "count_zeros" {
+                        bits - raw.count_ones()
+                    } else {
+                        raw.leading_zeros() - (64 - bits)
+                    };
+                    (zeros.into(), u32_ty)
+                }
+                None => return self.width_unknown(name, pos),
+            },
+            "trailing_zeros" => match bits {
+                _ if n != 0 => (n.trailing_zeros().into(), u32_ty),
+                Some(bits) => (bits.into(), u32_ty),
+                None => return self.width_unknown(name, pos),
+            },
+            "to_le_bytes" | "to_be_bytes" => match bits {
+                Some(bits) => {
+                    let bytes = raw(bits).to_le_bytes();
+                    let mut bytes = bytes[..bits as usize / 8].to_vec();
+                    if name == "to_be_bytes" {
+                        bytes.reverse();
+                    }
+                    let bytes = bytes
+                        .into_iter()
+                        .map(|b| Value::Int(b.into(), Some(IntTy::U8)))
+                        .collect();
+                    return Ok(Value::vec(bytes));
+                }
+                None => return self.width_unknown(name, pos),
+            },
+            "is_power_of_two" => return Ok(Value::Bool(n > 0 && (n & (n - 1)) == 0)),
+            "next_power_of_two" if n >= 0 => {
+                let mut power = 1;
+                while power < n {
+                    power *= 2;
+                }
+                if !fits(&power) {
+                    return self.panic(pos, overflow("add"));
+                }
+                (power, ty)
+            }
+            "isqrt" if n >= 0 => ((n as u128).isqrt() as i128, ty),
+            "ilog2" | "ilog10" if n > 0 => {
+                let log = if name == "ilog2" {
+                    n.ilog2()
+                } else {
+                    n.ilog10()
+                };
+                (log.into(), u32_ty)
+            }
+            "ilog2" | "ilog10" => {
+                return self.panic(pos, "argument of integer logarithm must be positive");
+            }
+            _ => return self.unknown(&Value::Int(n as i64, ty), name, pos),
+        };
+        self.int_value(value, ty, pos)
+    }
+
+    fn checked_int(&self, value: Option<i128>, ty: Option<IntTy>, pos: Pos) -> Eval {
+        match value {
+            Some(value) => Ok(Value::some(self.int_value(value, ty, pos)?)),
+            None => Ok(Value::none()),
+        }
+    }
+
+    fn width_unknown<T>(&self, name: &str, pos: Pos) -> Eval<T> {
+        self.unsupported(
+            pos,
+            format!("`{name}` depende da largura do inteiro; anote o tipo (ex.: `let x: u32`)"),
+        )
+    }
+
+    // --- f64 -----------------------------------------------------------------
+
+    fn float_method(&mut self, f: f64, name: &str, args: Vec<Value>, pos: Pos) -> Eval {
+        let arg = |this: &Self, i: usize| -> Eval<f64> {
+            match args.get(i) {
+                Some(value) => this.float_arg(value, pos),
+                None => this.invalid(pos, format!("`{name}` precisa de argumento")),
+            }
+        };
+        Ok(match name {
+            "abs" => Value::Float(f.abs()),
+            "sqrt" => Value::Float(f.sqrt()),
+            "cbrt" => Value::Float(f.cbrt()),
+            "powi" => Value::Float(f.powi(arg(self, 0)? as i32)),
+            "powf" => Value::Float(f.powf(arg(self, 0)?)),
+            "floor" => Value::Float(f.floor()),
+            "ceil" => Value::Float(f.ceil()),
+            "round" => Value::Float(f.round()),
+            "trunc" => Value::Float(f.trunc()),
+            "fract" => Value::Float(f.fract()),
+            "min" => Value::Float(f.min(arg(self, 0)?)),
+            "max" => Value::Float(f.max(arg(self, 0)?)),
+            "clamp" => Value::Float(f.clamp(arg(self, 0)?, arg(self, 1)?)),
+            "signum" => Value::Float(f.signum()),
+            "recip" => Value::Float(f.recip()),
+            "sin" => Value::Float(f.sin()),
+            "cos" => Value::Float(f.cos()),
+            "tan" => Value::Float(f.tan()),
+            "asin" => Value::Float(f.asin()),
+            "acos" => Value::Float(f.acos()),
+            "atan" => Value::Float(f.atan()),
+            "atan2" => Value::Float(f.atan2(arg(self, 0)?)),
+            "sinh" => Value::Float(f.sinh()),
+            "cosh" => Value::Float(f.cosh()),
+            "tanh" => Value::Float(f.tanh()),
+            "exp" => Value::Float(f.exp()),
+            "exp2" => Value::Float(f.exp2()),
+            "ln" => Value::Float(f.ln()),
+            "log10" => Value::Float(f.log10()),
+            "log2" => Value::Float(f.log2()),
+            "log" => Value::Float(f.log(arg(self, 0)?)),
+            "hypot" => Value::Float(f.hypot(arg(self, 0)?)),
+            "mul_add" => Value::Float(f.mul_add(arg(self, 0)?, arg(self, 1)?)),
+            "rem_euclid" => Value::Float(f.rem_euclid(arg(self, 0)?)),
+            "div_euclid" => Value::Float(f.div_euclid(arg(self, 0)?)),
+            "to_degrees" => Value::Float(f.to_degrees()),
+            "to_radians" => Value::Float(f.to_radians()),
+            "is_nan" => Value::Bool(f.is_nan()),
+            "is_finite" => Value::Bool(f.is_finite()),
+            "is_infinite" => Value::Bool(f.is_infinite()),
+            "is_sign_negative" => Value::Bool(f.is_sign_negative()),
+            "is_sign_positive" => Value::Bool(f.is_sign_positive()),
+            "total_cmp" => Value::ordering(f.total_cmp(&arg(self, 0)?)),
+            _ => return self.unknown(&Value::Float(f), name, pos),
+        })
+    }
+
+    // --- char ----------------------------------------------------------------
+
+    fn char_method(&mut self, c: char, name: &str, args: Vec<Value>, pos: Pos) -> Eval {
+        let radix = |this: &Self| -> Eval<u32> {
+            match args.first() {
+                Some(Value::Int(radix @ 2..=36, _)) => Ok(*radix as u32),
+                Some(Value::Int(radix, _)) => this.panic(
+                    pos,
+                    format!("to_digit: invalid radix -- radix must be in the range 2 to 36 inclusive (got {radix})"),
+                ),
+                _ => this.invalid(pos, format!("`{name}` recebe a base")),
+            }
+        };
+        Ok(match name {
+            "is_alphabetic" => Value::Bool(c.is_alphabetic()),
+            "is_numeric" => Value::Bool(c.is_numeric()),
+            "is_alphanumeric" => Value::Bool(c.is_alphanumeric()),
+            "is_whitespace" => Value::Bool(c.is_whitespace()),
+            "is_uppercase" => Value::Bool(c.is_uppercase()),
+            "is_lowercase" => Value::Bool(c.is_lowercase()),
+            "is_control" => Value::Bool(c.is_control()),
+            "is_ascii" => Value::Bool(c.is_ascii()),
+            "is_ascii_alphabetic" => Value::Bool(c.is_ascii_alphabetic()),
+            "is_ascii_digit" => Value::Bool(c.is_ascii_digit()),
+            "is_ascii_alphanumeric" => Value::Bool(c.is_ascii_alphanumeric()),
+            "is_ascii_uppercase" => Value::Bool(c.is_ascii_uppercase()),
+            "is_ascii_lowercase" => Value::Bool(c.is_ascii_lowercase()),
+            "is_ascii_whitespace" => Value::Bool(c.is_ascii_whitespace()),
+            "is_ascii_punctuation" => Value::Bool(c.is_ascii_punctuation()),
+            "is_ascii_hexdigit" => Value::Bool(c.is_ascii_hexdigit()),
+            "is_ascii_graphic" => Value::Bool(c.is_ascii_graphic()),
+            "is_digit" => Value::Bool(c.is_digit(radix(self)?)),
+            "to_digit" => Value::option(
+                c.to_digit(radix(self)?)
+                    .map(|d| Value::Int(d.into(), Some(IntTy::U32))),
+            ),
+            "to_ascii_uppercase" => Value::Char(c.to_ascii_uppercase()),
+            "to_ascii_lowercase" => Value::Char(c.to_ascii_lowercase()),
+            "to_uppercase" => Value::iter(c.to_uppercase().map(Value::Char)),
+            "to_lowercase" => Value::iter(c.to_lowercase().map(Value::Char)),
+            "len_utf8" => Value::usize(c.len_utf8()),
+            "len_utf16" => Value::usize(c.len_utf16()),
+            "eq_ignore_ascii_case" => match args.first() {
+                Some(Value::Char(other)) => Value::Bool(c.eq_ignore_ascii_case(other)),
+                _ => return self.invalid(pos, "eq_ignore_ascii_case recebe um char"),
+            },
+            _ => return self.unknown(&Value::Char(c), name, pos),
+        })
+    }
+
+    // --- String / &str -------------------------------------------------------
+
+    fn pattern(&self, value: &Value, pos: Pos) -> Eval<Pat> {
+        Ok(match value {
+            Value::Str(text) => Pat::Str(text.borrow().clone()),
+            Value::Char(c) => Pat::Chars(vec![*c]),
+            Value::Vec(items) => {
+                let chars = items
+                    .borrow()
+                    .iter()
+                    .map(|item| match item {
+                        Value::Char(c) => Some(*c),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<char>>>();
+                match chars {
+                    Some(chars) => Pat::Chars(chars),
+                    None => {
+                        return self
+                            .invalid(pos, "padrão de busca precisa ser &str, char ou [char]");
+                    }
+                }
+            }
+            Value::Closure(..) => Pat::Fn(value.clone()),
+            other => {
+                return self.invalid(
+                    pos,
+                    format!("{} não serve de padrão de busca", other.type_name()),
+                );
+            }
+        })
+    }
+
+    fn char_matches(&mut self, pat: &Pat, c: char, pos: Pos) -> Eval<bool> {
+        match pat {
+            Pat::Str(_) => unreachable!("padrões de string são tratados à parte"),
+            Pat::Chars(chars) => Ok(chars.contains(&c)),
+            Pat::Fn(f) => self.predicate(f, Value::Char(c), pos),
+        }
+    }
+
+    /// Ocorrências (não sobrepostas, da esquerda) como faixas de bytes.
+    fn match_ranges(&mut self, text: &str, pat: &Pat, pos: Pos) -> Eval<Vec<(usize, usize)>> {
+        if let Pat::Str(needle) = pat {
+            return Ok(text
+                .match_indices(needle.as_str())
+                .map(|(i, m)| (i, i + m.len()))
+                .collect());
+        }
+        let mut ranges = Vec::new();
+        for (i, c) in text.char_indices() {
+            self.tick()?;
+            if self.char_matches(pat, c, pos)? {
+                ranges.push((i, i + c.len_utf8()));
+            }
+        }
+        Ok(ranges)
+    }
+
+    fn split_by(
+        &mut self,
+        text: &str,
+        pat: &Pat,
+        limit: Option<usize>,
+        pos: Pos,
+    ) -> Eval<Vec<String>> {
+        let mut pieces = Vec::new();
+        let mut last = 0;
+        for (start, end) in self.match_ranges(text, pat, pos)? {
+            if limit.is_some_and(|limit| pieces.len() + 1 >= limit) {
+                break;
+            }
+            pieces.push(text[last..start].to_string());
+            last = end;
+        }
+        pieces.push(text[last..].to_string());
+        Ok(pieces)
+    }
+
+    fn strs(items: impl IntoIterator<Item = impl Into<String>>) -> Value {
+        Value::iter(items.into_iter().map(Value::str))
+    }
+
+    fn str_method(
+        &mut self,
+        target: &Rc<RefCell<String>>,
+        name: &str,
+        turbofish: Option<&Ty>,
+        args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        let text = target.borrow().clone();
+        let str_arg = |this: &Self, i: usize| -> Eval<String> {
+            match args.get(i) {
+                Some(Value::Str(s)) => Ok(s.borrow().clone()),
+                Some(Value::Char(c)) if name == "push_str" => {
+                    this.invalid(pos, "push_str recebe &str; para char use push")
+                }
+                _ => this.invalid(pos, format!("`{name}` recebe &str")),
+            }
+        };
+        let index_arg = |this: &Self, i: usize| -> Eval<usize> {
+            match args.get(i) {
+                Some(value) => this.index_arg(value, pos),
+                None => this.invalid(pos, format!("`{name}` precisa de argumento")),
+            }
+        };
+        let pat_arg = |this: &Self, i: usize| -> Eval<Pat> {
+            match args.get(i) {
+                Some(value) => this.pattern(value, pos),
+                None => this.invalid(pos, format!("`{name}` precisa de um padrão")),
+            }
+        };
+
+        Ok(match name {
+            "len" => Value::usize(text.len()),
+            "is_empty" => Value::Bool(text.is_empty()),
+            "trim" => Value::str(text.trim()),
+            "trim_start" => Value::str(text.trim_start()),
+            "trim_end" => Value::str(text.trim_end()),
+            "chars" => Value::iter(text.chars().map(Value::Char)),
+            "bytes" => Value::iter(text.bytes().map(byte)),
+            "as_bytes" | "into_bytes" => Value::vec(text.bytes().map(byte).collect()),
+            "char_indices" => Value::iter(
+                text.char_indices()
+                    .map(|(i, c)| Value::tuple(vec![Value::usize(i), Value::Char(c)])),
+            ),
+            "split_whitespace" => Self::strs(text.split_whitespace()),
+            "split_ascii_whitespace" => Self::strs(text.split_ascii_whitespace()),
+            "lines" => Self::strs(text.lines()),
+            "split" | "split_terminator" => {
+                let pat = pat_arg(self, 0)?;
+                let mut pieces = self.split_by(&text, &pat, None, pos)?;
+                if name == "split_terminator" && pieces.last().is_some_and(String::is_empty) {
+                    pieces.pop();
+                }
+                Self::strs(pieces)
+            }
+            "splitn" => {
+                let n = index_arg(self, 0)?;
+                let pat = pat_arg(self, 1)?;
+                if n == 0 {
+                    Value::iter(Vec::new())
+                } else {
+                    Self::strs(self.split_by(&text, &pat, Some(n), pos)?)
+                }
+            }
+            "rsplit" => match pat_arg(self, 0)? {
+                Pat::Str(needle) => Self::strs(text.rsplit(needle.as_str())),
+                pat => {
+                    let mut pieces = self.split_by(&text, &pat, None, pos)?;
+                    pieces.reverse();
+                    Self::strs(pieces)
+                }
+            },
+            "split_once" | "rsplit_once" => {
+                let pat = pat_arg(self, 0)?;
+                let found = match (&pat, name) {
+                    (Pat::Str(needle), "rsplit_once") => {
+                        text.rfind(needle.as_str()).map(|i| (i, i + needle.len()))
+                    }
+                    (_, "rsplit_once") => self.match_ranges(&text, &pat, pos)?.last().copied(),
+                    _ => self.match_ranges(&text, &pat, pos)?.first().copied(),
+                };
+                Value::option(found.map(|(start, end)| {
+                    Value::tuple(vec![Value::str(&text[..start]), Value::str(&text[end..])])
+                }))
+            }
+            "matches" => {
+                let pat = pat_arg(self, 0)?;
+                let ranges = self.match_ranges(&text, &pat, pos)?;
+                Self::strs(ranges.into_iter().map(|(start, end)| &text[start..end]))
+            }
+            "match_indices" => {
+                let pat = pat_arg(self, 0)?;
+                let ranges = self.match_ranges(&text, &pat, pos)?;
+                Value::iter(ranges.into_iter().map(|(start, end)| {
+                    Value::tuple(vec![Value::usize(start), Value::str(&text[start..end])])
+                }))
+            }
+            "contains" => {
+                let pat = pat_arg(self, 0)?;
+                Value::Bool(match &pat {
+                    Pat::Str(needle) => text.contains(needle.as_str()),
+                    _ => !self.match_ranges(&text, &pat, pos)?.is_empty(),
+                })
+            }
+            "find" => {
+                let pat = pat_arg(self, 0)?;
+                let found = match &pat {
+                    Pat::Str(needle) => text.find(needle.as_str()),
+                    _ => self.match_ranges(&text, &pat, pos)?.first().map(|r| r.0),
+                };
+                Value::option(found.map(Value::usize))
+            }
+            "rfind" => {
+                let pat = pat_arg(self, 0)?;
+                let found = match &pat {
+                    Pat::Str(needle) => text.rfind(needle.as_str()),
+                    _ => self.match_ranges(&text, &pat, pos)?.last().map(|r| r.0),
+                };
+                Value::option(found.map(Value::usize))
+            }
+            "starts_with" | "ends_with" | "strip_prefix" | "strip_suffix" => {
+                let pat = pat_arg(self, 0)?;
+                let prefix = matches!(name, "starts_with" | "strip_prefix");
+                let rest = match &pat {
+                    Pat::Str(needle) if prefix => text.strip_prefix(needle.as_str()),
+                    Pat::Str(needle) => text.strip_suffix(needle.as_str()),
+                    _ => {
+                        let c = if prefix {
+                            text.chars().next()
+                        } else {
+                            text.chars().next_back()
+                        };
+                        match c {
+                            Some(c) if self.char_matches(&pat, c, pos)? => Some(if prefix {
+                                &text[c.len_utf8()..]
+                            } else {
+                                &text[..text.len() - c.len_utf8()]
+                            }),
+                            _ => None,
+                        }
+                    }
+                };
+                if name.ends_with("_with") {
+                    Value::Bool(rest.is_some())
+                } else {
+                    Value::option(rest.map(Value::str))
+                }
+            }
+            "trim_matches" | "trim_start_matches" | "trim_end_matches" => {
+                let pat = pat_arg(self, 0)?;
+                let (start, end) = (name != "trim_end_matches", name != "trim_start_matches");
+                let trimmed = match &pat {
+                    Pat::Str(needle) => {
+                        let mut rest = text.as_str();
+                        while start && !needle.is_empty() && rest.starts_with(needle.as_str()) {
+                            rest = &rest[needle.len()..];
+                        }
+                        while end && !needle.is_empty() && rest.ends_with(needle.as_str()) {
+                            rest = &rest[..rest.len() - needle.len()];
+                        }
+                        rest.to_string()
+                    }
+                    _ => {
+                        let chars: Vec<char> = text.chars().collect();
+                        let mut low = 0;
+                        let mut high = chars.len();
+                        while start && low < high && self.char_matches(&pat, chars[low], pos)? {
+                            low += 1;
+                        }
+                        while end && high > low && self.char_matches(&pat, chars[high - 1], pos)? {
+                            high -= 1;
+                        }
+                        chars[low..high].iter().collect()
+                    }
+                };
+                Value::str(trimmed)
+            }
+            "replace" | "replacen" => {
+                let pat = pat_arg(self, 0)?;
+                let with = str_arg(self, 1)?;
+                let limit = if name == "replacen" {
+                    Some(index_arg(self, 2)?)
+                } else {
+                    None
+                };
+                let mut out = String::new();
+                let mut last = 0;
+                for (i, (start, end)) in
+                    self.match_ranges(&text, &pat, pos)?.into_iter().enumerate()
+                {
+                    if limit.is_some_and(|limit| i >= limit) {
+                        break;
+                    }
+                    self.fits(out.len() + (start - last) + with.len())?;
+                    out.push_str(&text[last..start]);
+                    out.push_str(&with);
+                    last = end;
+                }
+                out.push_str(&text[last..]);
+                Value::str(out)
+            }
+            "to_uppercase" => Value::str(text.to_uppercase()),
+            "to_lowercase" => Value::str(text.to_lowercase()),
+            "to_ascii_uppercase" => Value::str(text.to_ascii_uppercase()),
+            "to_ascii_lowercase" => Value::str(text.to_ascii_lowercase()),
+            "eq_ignore_ascii_case" => Value::Bool(text.eq_ignore_ascii_case(&str_arg(self, 0)?)),
+            "repeat" => {
+                let n = index_arg(self, 0)?;
+                self.charge(text.len().saturating_mul(n))?;
+                Value::str(text.repeat(n))
+            }
+            "parse" => self.parse_str(&text, turbofish, pos)?,
+            "is_char_boundary" => Value::Bool(text.is_char_boundary(index_arg(self, 0)?)),
+            "split_at" => {
+                let mid = index_arg(self, 0)?;
+                if let Some(message) = str_range_error(&text, 0, mid) {
+                    let message = if mid > text.len() {
+                        "failed to slice string".to_string()
+                    } else {
+                        message
+                    };
+                    return self.panic(pos, message);
+                }
+                let (a, b) = text.split_at(mid);
+                Value::tuple(vec![Value::str(a), Value::str(b)])
+            }
+            "get" => return self.unsupported(pos, "String::get; use fatias text[a..b]"),
+            // Mutação no lugar: o receptor é a própria String
+            "push_str" => {
+                let extra = str_arg(self, 0)?;
+                self.fits(text.len() + extra.len())?;
+                target.borrow_mut().push_str(&extra);
+                Value::Unit
+            }
+            "push" => match args.first() {
+                Some(Value::Char(c)) => {
+                    self.fits(text.len() + c.len_utf8())?;
+                    target.borrow_mut().push(*c);
+                    Value::Unit
+                }
+                _ => return self.invalid(pos, "push recebe um char; para &str use push_str"),
+            },
+            "pop" => Value::option(target.borrow_mut().pop().map(Value::Char)),
+            "clear" => {
+                target.borrow_mut().clear();
+                Value::Unit
+            }
+            "truncate" => {
+                let len = index_arg(self, 0)?;
+                if len < text.len() && !text.is_char_boundary(len) {
+                    return self.panic(pos, "assertion failed: self.is_char_boundary(new_len)");
+                }
+                target.borrow_mut().truncate(len);
+                Value::Unit
+            }
+            "insert" | "insert_str" => {
+                let index = index_arg(self, 0)?;
+                if !text.is_char_boundary(index) {
+                    return self.panic(pos, "assertion failed: self.is_char_boundary(idx)");
+                }
+                match args.get(1) {
+                    Some(Value::Char(c)) if name == "insert" => {
+                        target.borrow_mut().insert(index, *c)
+                    }
+                    Some(Value::Str(s)) if name == "insert_str" => {
+                        let s = s.borrow().clone();
+                        self.fits(text.len() + s.len())?;
+                        target.borrow_mut().insert_str(index, &s);
+                    }
+                    _ => return self.invalid(pos, format!("`{name}` recebe índice e valor")),
+                }
+                Value::Unit
+            }
+            "remove" => {
+                let index = index_arg(self, 0)?;
+                if index >= text.len() {
+                    return self.panic(pos, "cannot remove a char from the end of a string");
+                }
+                if !text.is_char_boundary(index) {
+                    return self.panic(pos, char_boundary_error(&text, index));
+                }
+                Value::Char(target.borrow_mut().remove(index))
+            }
+            "retain" => {
+                let f = args.first().cloned().unwrap_or(Value::Unit);
+                let mut kept = String::new();
+                for c in text.chars() {
+                    if self.predicate(&f, Value::Char(c), pos)? {
+                        kept.push(c);
+                    }
+                }
+                *target.borrow_mut() = kept;
+                Value::Unit
+            }
+            "iter" | "into_iter" => {
+                return self.invalid(pos, "String não é iterável; use .chars() ou .bytes()");
+            }
+            _ => return self.unknown(&Value::Str(target.clone()), name, pos),
+        })
+    }
+
+    /// `str::parse`, pelo turbofish, pela anotação do `let` ou, sem nada,
+    /// tentando inteiro e depois f64.
+    fn parse_str(&mut self, text: &str, turbofish: Option<&Ty>, pos: Pos) -> Eval {
+        let target = turbofish
+            .map(|ty| ty.name.clone())
+            .filter(|name| name != "_")
+            .or_else(|| self.hint.clone());
+        let float_error = || {
+            if text.is_empty() {
+                Value::error(
+                    "ParseFloatError { kind: Empty }",
+                    "cannot parse float from empty string",
+                )
+            } else {
+                Value::error("ParseFloatError { kind: Invalid }", "invalid float literal")
+            }
+        };
+        Ok(match target.as_deref() {
+            Some(ty) if is_int_type(ty) => {
+                parse_int(text, 10, IntTy::from_name(ty).unwrap_or(IntTy::I64))
+            }
+            Some(ty) if is_float_type(ty) => match text.parse::<f64>() {
+                Ok(f) => Value::ok(Value::Float(f)),
+                Err(_) => float_error(),
+            },
+            Some("bool") => match text {
+                "true" => Value::ok(Value::Bool(true)),
+                "false" => Value::ok(Value::Bool(false)),
+                _ => Value::error(
+                    "ParseBoolError",
+                    "provided string was not `true` or `false`",
+                ),
+            },
+            Some("char") => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Value::ok(Value::Char(c)),
+                    (None, _) => Value::error(
+                        "ParseCharError { kind: EmptyString }",
+                        "cannot parse char from empty string",
+                    ),
+                    _ => Value::error(
+                        "ParseCharError { kind: TooManyChars }",
+                        "too many characters in string",
+                    ),
+                }
+            }
+            Some("String" | "str") => Value::ok(Value::str(text)),
+            Some(other) => {
+                return self.unsupported(pos, format!("parse::<{other}>()"));
+            }
+            // Sem tipo, o valor fica sem largura conhecida
+            None => match parse_int(text, 10, IntTy::I64) {
+                Value::Variant(Variant::Ok, Some(value)) => match &*value {
+                    Value::Int(n, _) => Value::ok(Value::int(*n)),
+                    other => Value::ok(other.clone()),
+                },
+                error => match text.parse::<f64>() {
+                    Ok(f) if !text.is_empty() && text.contains(['.', 'e', 'E']) => {
+                        Value::ok(Value::Float(f))
+                    }
+                    _ => error,
+                },
+            },
+        })
+    }
+
+    // --- Vec / arrays / slices ---------------------------------------------
+
+    fn vec_method(
+        &mut self,
+        target: &Rc<RefCell<Vec<Value>>>,
+        name: &str,
+        mut args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        let len = target.borrow().len();
+        let index_arg = |this: &Self, args: &[Value], i: usize| -> Eval<usize> {
+            match args.get(i) {
+                Some(value) => this.index_arg(value, pos),
+                None => this.invalid(pos, format!("`{name}` precisa de argumento")),
+            }
+        };
+        let out_of_bounds = |index: usize| {
+            format!("index out of bounds: the len is {len} but the index is {index}")
+        };
+
+        Ok(match name {
+            "len" => Value::usize(len),
+            "is_empty" => Value::Bool(len == 0),
+            "push" => {
+                self.arity(name, &args, 1, pos)?;
+                if len >= self.config.max_elements {
+                    self.charge(len + 1)?;
+                }
+                target.borrow_mut().push(args.remove(0));
+                Value::Unit
+            }
+            "pop" => Value::option(target.borrow_mut().pop()),
+            "insert" => {
+                self.arity(name, &args, 2, pos)?;
+                let index = index_arg(self, &args, 0)?;
+                if index > len {
+                    return self.panic(
+                        pos,
+                        format!("insertion index (is {index}) should be <= len (is {len})"),
+                    );
+                }
+                self.charge(len - index)?;
+                target.borrow_mut().insert(index, args.remove(1));
+                Value::Unit
+            }
+            "remove" | "swap_remove" => {
+                let index = index_arg(self, &args, 0)?;
+                if index >= len {
+                    return self.panic(
+                        pos,
+                        format!("{name} index (is {index}) should be < len (is {len})"),
+                    );
+                }
+                if name == "remove" {
+                    self.charge(len - index)?;
+                    target.borrow_mut().remove(index)
+                } else {
+                    target.borrow_mut().swap_remove(index)
+                }
+            }
+            "clear" => {
+                target.borrow_mut().clear();
+                Value::Unit
+            }
+            "truncate" => {
+                let n = index_arg(self, &args, 0)?;
+                target.borrow_mut().truncate(n);
+                Value::Unit
+            }
+            "contains" => {
+                self.arity(name, &args, 1, pos)?;
+                Value::Bool(target.borrow().iter().any(|item| item.equals(&args[0])))
+            }
+            "starts_with" | "ends_with" => {
+                let Some(Value::Vec(other)) = args.first() else {
+                    return self.invalid(pos, format!("`{name}` recebe uma slice"));
+                };
+                let other = other.borrow().clone();
+                let items = target.borrow();
+                let Some(window) = (if name == "starts_with" {
+                    items.get(..other.len())
+                } else {
+                    items
+                        .get(len.saturating_sub(other.len())..)
+                        .filter(|_| other.len() <= len)
+                }) else {
+                    return Ok(Value::Bool(false));
+                };
+                Value::Bool(window.iter().zip(&other).all(|(a, b)| a.equals(b)))
+            }
+            "sort" | "sort_unstable" => {
+                let mut compare =
+                    |this: &mut Self, a: &Value, b: &Value| this.compare_values(a, b, pos);
+                self.sort_in_place(target, &mut compare)?;
+                Value::Unit
+            }
+            "sort_by" | "sort_unstable_by" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                let mut compare = |this: &mut Self, a: &Value, b: &Value| {
+                    let ordering = this.call_value(&f, vec![a.clone(), b.clone()], pos)?;
+                    this.ordering_arg(&ordering, pos)
+                };
+                self.sort_in_place(target, &mut compare)?;
+                Value::Unit
+            }
+            "sort_by_key" | "sort_unstable_by_key" | "sort_by_cached_key" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                let mut compare = |this: &mut Self, a: &Value, b: &Value| {
+                    let ka = this.call_value(&f, vec![a.clone()], pos)?;
+                    let kb = this.call_value(&f, vec![b.clone()], pos)?;
+                    this.compare_values(&ka, &kb, pos)
+                };
+                self.sort_in_place(target, &mut compare)?;
+                Value::Unit
+            }
+            "is_sorted" => Value::Bool(
+                target
+                    .borrow()
+                    .windows(2)
+                    .all(|w| w[0].compare(&w[1]) != Some(Ordering::Greater)),
+            ),
+            "dedup" => {
+                target.borrow_mut().dedup_by(|a, b| a.equals(b));
+                Value::Unit
+            }
+            "dedup_by_key" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                let items = target.borrow().clone();
+                let mut kept: Vec<Value> = Vec::new();
+                let mut last_key: Option<Value> = None;
+                for item in items {
+                    let key = self.call_value(&f, vec![item.clone()], pos)?;
+                    if last_key.as_ref().is_some_and(|last| last.equals(&key)) {
+                        continue;
+                    }
+                    last_key = Some(key);
+                    kept.push(item);
+                }
+                *target.borrow_mut() = kept;
+                Value::Unit
+            }
+            "retain" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                let items = target.borrow().clone();
+                let mut kept = Vec::new();
+                for item in items {
+                    if self.predicate(&f, item.clone(), pos)? {
+                        kept.push(item);
+                    }
+                }
+                *target.borrow_mut() = kept;
+                Value::Unit
+            }
+            "reverse" => {
+                target.borrow_mut().reverse();
+                Value::Unit
+            }
+            "swap" => {
+                let a = index_arg(self, &args, 0)?;
+                let b = index_arg(self, &args, 1)?;
+                for index in [a, b] {
+                    if index >= len {
+                        return self.panic(pos, out_of_bounds(index));
+                    }
+                }
+                target.borrow_mut().swap(a, b);
+                Value::Unit
+            }
+            "rotate_left" | "rotate_right" => {
+                let n = index_arg(self, &args, 0)?;
+                if n > len {
+                    return self.panic(pos, "assertion failed: mid <= self.len()");
+                }
+                if name == "rotate_left" {
+                    target.borrow_mut().rotate_left(n);
+                } else {
+                    target.borrow_mut().rotate_right(n);
+                }
+                Value::Unit
+            }
+            "fill" => {
+                self.arity(name, &args, 1, pos)?;
+                for item in target.borrow_mut().iter_mut() {
+                    *item = args[0].deep_clone();
+                }
+                Value::Unit
+            }
+            "resize" => {
+                let n = index_arg(self, &args, 0)?;
+                let Some(fill) = args.get(1) else {
+                    return self.invalid(pos, "resize recebe tamanho e valor");
+                };
+                self.charge(n)?;
+                let fill = fill.clone();
+                target.borrow_mut().resize_with(n, || fill.deep_clone());
+                Value::Unit
+            }
+            "extend" | "extend_from_slice" | "append" => {
+                self.arity(name, &args, 1, pos)?;
+                let source = args.remove(0);
+                let items = self.items(source.clone(), pos)?;
+                if name == "append"
+                    && let Value::Vec(other) = &source
+                {
+                    other.borrow_mut().clear();
+                }
+                self.charge(len + items.len())?;
+                target.borrow_mut().extend(items);
+                Value::Unit
+            }
+            "iter" | "into_iter" | "drain" => {
+                let items = if name == "drain" {
+                    std::mem::take(&mut *target.borrow_mut())
+                } else {
+                    target.borrow().clone()
+                };
+                Value::iter(items)
+            }
+            "iter_mut" | "get_mut" | "first_mut" | "last_mut" | "split_at_mut" | "chunks_mut" => {
+                return self.unsupported(
+                    pos,
+                    format!("`{name}`; percorra por índice (`for i in 0..v.len()`) e use v[i]"),
+                );
+            }
+            "first" => Value::option(target.borrow().first().cloned()),
+            "last" => Value::option(target.borrow().last().cloned()),
+            "get" => match args.first() {
+                Some(Value::Int(index, _)) => Value::option(
+                    usize::try_from(*index)
+                        .ok()
+                        .and_then(|index| target.borrow().get(index).cloned()),
+                ),
+                _ => return self.invalid(pos, "get recebe um índice"),
+            },
+            "split_first" | "split_last" => {
+                let items = target.borrow();
+                let split = if name == "split_first" {
+                    items.split_first()
+                } else {
+                    items.split_last()
+                };
+                Value::option(split.map(|(item, rest)| {
+                    Value::tuple(vec![item.clone(), Value::vec(rest.to_vec())])
+                }))
+            }
+            "split_at" => {
+                let mid = index_arg(self, &args, 0)?;
+                if mid > len {
+                    return self.panic(pos, "mid > len");
+                }
+                let items = target.borrow();
+                let (a, b) = items.split_at(mid);
+                Value::tuple(vec![Value::vec(a.to_vec()), Value::vec(b.to_vec())])
+            }
+            "windows" | "chunks" | "chunks_exact" => {
+                let size = index_arg(self, &args, 0)?;
+                if size == 0 {
+                    return self.panic(
+                        pos,
+                        if name == "windows" {
+                            "window size must be non-zero"
+                        } else {
+                            "chunk size must be non-zero"
+                        },
+                    );
+                }
+                let items = target.borrow();
+                self.charge(len)?;
+                let parts: Vec<Value> = match name {
+                    "windows" => items
+                        .windows(size)
+                        .map(|w| Value::vec(w.to_vec()))
+                        .collect(),
+                    "chunks" => items.chunks(size).map(|c| Value::vec(c.to_vec())).collect(),
+                    _ => items
+                        .chunks_exact(size)
+                        .map(|c| Value::vec(c.to_vec()))
+                        .collect(),
+                };
+                Value::iter(parts)
+            }
+            "join" | "concat" => {
+                let separator = match (name, args.first()) {
+                    ("concat", _) => String::new(),
+                    (_, Some(Value::Str(s))) => s.borrow().clone(),
+                    (_, Some(Value::Char(c))) => c.to_string(),
+                    _ => return self.invalid(pos, "join recebe o separador (&str)"),
+                };
+                let items = target.borrow();
+                if let Some(Value::Vec(_)) = items.first() {
+                    let mut joined = Vec::new();
+                    for item in items.iter() {
+                        if let Value::Vec(inner) = item {
+                            joined.extend(inner.borrow().iter().cloned());
+                        }
+                    }
+                    return Ok(Value::vec(joined));
+                }
+                let mut parts = Vec::new();
+                for item in items.iter() {
+                    match item {
+                        Value::Str(s) => parts.push(s.borrow().clone()),
+                        Value::Char(c) if name == "concat" => parts.push(c.to_string()),
+                        other => {
+                            return self
+                                .invalid(pos, format!("`{name}` em Vec de {}", other.type_name()));
+                        }
+                    }
+                }
+                let total = parts.iter().map(String::len).sum::<usize>()
+                    + separator.len() * parts.len().saturating_sub(1);
+                self.charge(total)?;
+                Value::str(parts.join(&separator))
+            }
+            "binary_search" => {
+                self.arity(name, &args, 1, pos)?;
+                let items = target.borrow();
+                let found = items
+                    .binary_search_by(|item| item.compare(&args[0]).unwrap_or(Ordering::Equal));
+                match found {
+                    Ok(i) => Value::ok(Value::usize(i)),
+                    Err(i) => Value::err(Value::usize(i)),
+                }
+            }
+            "repeat" => {
+                let n = index_arg(self, &args, 0)?;
+                self.charge(len.saturating_mul(n))?;
+                let items = target.borrow();
+                Value::vec(
+                    (0..n)
+                        .flat_map(|_| items.iter().map(Value::deep_clone))
+                        .collect(),
+                )
+            }
+            // Vec não é iterador, mas o erro mais útil é sugerir `.iter()`
+            _ if is_iterator_method(name) => {
+                return self.invalid(
+                    pos,
+                    format!("`{name}` é método de iterador; use .iter().{name}(...)"),
+                );
+            }
+            _ => return self.unknown(&Value::Vec(target.clone()), name, pos),
+        })
+    }
+
+    fn closure_arg(&self, name: &str, args: &[Value], pos: Pos) -> Eval<Value> {
+        match args {
+            [f @ Value::Closure(..)] => Ok(f.clone()),
+            _ => self.invalid(pos, format!("`{name}` recebe uma closure")),
+        }
+    }
+
+    fn sort_in_place(
+        &mut self,
+        target: &Rc<RefCell<Vec<Value>>>,
+        compare: &mut dyn FnMut(&mut Self, &Value, &Value) -> Eval<Ordering>,
+    ) -> Eval<()> {
+        let items = std::mem::take(&mut *target.borrow_mut());
+        let sorted = self.merge_sort(items.clone(), compare);
+        match sorted {
+            Ok(sorted) => {
+                *target.borrow_mut() = sorted;
+                Ok(())
+            }
+            Err(flow) => {
+                *target.borrow_mut() = items;
+                Err(flow)
+            }
+        }
+    }
+
+    /// Ordenação estável com comparador que pode falhar (closure com pânico).
+    fn merge_sort(
+        &mut self,
+        mut items: Vec<Value>,
+        compare: &mut dyn FnMut(&mut Self, &Value, &Value) -> Eval<Ordering>,
+    ) -> Eval<Vec<Value>> {
+        if items.len() <= 1 {
+            return Ok(items);
+        }
+        let right = items.split_off(items.len() / 2);
+        let left = self.merge_sort(items, compare)?;
+        let right = self.merge_sort(right, compare)?;
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        let mut left = VecDeque::from(left);
+        let mut right = VecDeque::from(right);
+        while let (Some(a), Some(b)) = (left.front(), right.front()) {
+            self.tick()?;
+            if compare(self, b, a)? == Ordering::Less {
+                merged.extend(right.pop_front());
+            } else {
+                merged.extend(left.pop_front());
+            }
+        }
+        merged.extend(left);
+        merged.extend(right);
+        Ok(merged)
+    }
+
+    // --- faixas e iteradores ---------------------------------------------------
+
+    fn range_method(
+        &mut self,
+        (start, end, ty): (i64, i64, Option<IntTy>),
+        name: &str,
+        turbofish: Option<&Ty>,
+        args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        Ok(match name {
+            "contains" => match args.first() {
+                Some(Value::Int(n, _)) => Value::Bool(start <= *n && *n < end),
+                _ => return self.invalid(pos, "contains recebe um inteiro"),
+            },
+            "len" => Value::Int(end.saturating_sub(start).max(0), Some(IntTy::Usize)),
+            "is_empty" => Value::Bool(start >= end),
+            "rev" => Value::from_source(Source::Range {
+                start,
+                end,
+                ty,
+                rev: true,
+            }),
+            _ => return self.iter_method(Value::Range(start, end, ty), name, turbofish, args, pos),
+        })
+    }
+
+    fn iter_method(
+        &mut self,
+        receiver: Value,
+        name: &str,
+        turbofish: Option<&Ty>,
+        mut args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        let state = self.iterator(receiver.clone(), pos)?;
+        let closure = |this: &Self, args: &[Value]| this.closure_arg(name, args, pos);
+        let count_arg = |this: &Self, args: &[Value]| -> Eval<usize> {
+            match args.first() {
+                Some(value) => this.index_arg(value, pos),
+                None => this.invalid(pos, format!("`{name}` precisa de argumento")),
+            }
+        };
+        let iter = Value::Iter(state.clone());
+
+        Ok(match name {
+            "iter" | "into_iter" | "copied" | "by_ref" => iter,
+            "next" => Value::option(self.next_item(&state, pos)?),
+            "map" => self.adapt(iter, Stage::Map(closure(self, &args)?), pos)?,
+            "filter" => self.adapt(iter, Stage::Filter(closure(self, &args)?), pos)?,
+            "filter_map" => self.adapt(iter, Stage::FilterMap(closure(self, &args)?), pos)?,
+            "map_while" => self.adapt(iter, Stage::MapWhile(closure(self, &args)?, false), pos)?,
+            "inspect" => self.adapt(iter, Stage::Inspect(closure(self, &args)?), pos)?,
+            "flat_map" => self.adapt(iter, Stage::FlatMap(closure(self, &args)?, None), pos)?,
+            "flatten" => self.adapt(iter, Stage::Flatten(None), pos)?,
+            "skip_while" => {
+                self.adapt(iter, Stage::SkipWhile(closure(self, &args)?, false), pos)?
+            }
+            "take_while" => {
+                self.adapt(iter, Stage::TakeWhile(closure(self, &args)?, false), pos)?
+            }
+            "enumerate" => self.adapt(iter, Stage::Enumerate(0), pos)?,
+            "cloned" => self.adapt(iter, Stage::Cloned, pos)?,
+            "peekable" => self.adapt(iter, Stage::Peek(None), pos)?,
+            "skip" => {
+                let n = count_arg(self, &args)?;
+                self.adapt(iter, Stage::Skip(n), pos)?
+            }
+            "take" => {
+                let n = count_arg(self, &args)?;
+                self.adapt(iter, Stage::Take(n), pos)?
+            }
+            "step_by" => {
+                let step = count_arg(self, &args)?;
+                if step == 0 {
+                    return self.panic(pos, "assertion failed: step != 0");
+                }
+                self.adapt(
+                    iter,
+                    Stage::StepBy {
+                        step,
+                        started: false,
+                    },
+                    pos,
+                )?
+            }
+            "peek" => {
+                let has_peek = matches!(state.borrow().stages.last(), Some(Stage::Peek(_)));
+                if !has_peek {
+                    return self.invalid(pos, "peek() exige .peekable()");
+                }
+                let peeked = match state.borrow().stages.last() {
+                    Some(Stage::Peek(Some(item))) => Some(item.clone()),
+                    _ => None,
+                };
+                let item = match peeked {
+                    Some(item) => item,
+                    None => {
+                        let item = self.next_item(&state, pos)?;
+                        if let Some(Stage::Peek(slot)) = state.borrow_mut().stages.last_mut() {
+                            *slot = Some(item.clone());
+                        }
+                        item
+                    }
+                };
+                Value::option(item)
+            }
+            "next_if" => {
+                let f = closure(self, &args)?;
+                let peeked = self.iter_method(iter.clone(), "peek", None, Vec::new(), pos)?;
+                match peeked {
+                    Value::Variant(Variant::Some, Some(item))
+                        if self.predicate(&f, (*item).clone(), pos)? =>
+                    {
+                        Value::option(self.next_item(&state, pos)?)
+                    }
+                    _ => Value::none(),
+                }
+            }
+            "rev" => {
+                let mut items = self.drain(&state, pos)?;
+                items.reverse();
+                Value::iter(items)
+            }
+            "zip" => {
+                self.arity(name, &args, 1, pos)?;
+                let other = self.iterator(args.remove(0), pos)?;
+                Value::from_source(Source::Zip(iter, Value::Iter(other)))
+            }
+            "chain" => {
+                self.arity(name, &args, 1, pos)?;
+                let other = self.iterator(args.remove(0), pos)?;
+                Value::from_source(Source::Chain(VecDeque::from([iter, Value::Iter(other)])))
+            }
+            "len" => match state.borrow().len() {
+                Some(len) => Value::usize(len),
+                None => {
+                    return self.unsupported(pos, "len() de iterador com adaptadores; use count()");
+                }
+            },
+            "count" => Value::usize(self.drain(&state, pos)?.len()),
+            "last" => Value::option(self.drain(&state, pos)?.pop()),
+            "nth" => {
+                let n = count_arg(self, &args)?;
+                for _ in 0..n {
+                    if self.next_item(&state, pos)?.is_none() {
+                        return Ok(Value::none());
+                    }
+                }
+                Value::option(self.next_item(&state, pos)?)
+            }
+            "collect" => {
+                let items = self.drain(&state, pos)?;
+                self.collect(items, turbofish, pos)?
+            }
+            "sum" | "product" => {
+                let items = self.drain(&state, pos)?;
+                self.fold_numbers(items, name, turbofish, pos)?
+            }
+            "max" | "min" => {
+                let items = self.drain(&state, pos)?;
+                let mut compare =
+                    |this: &mut Self, a: &Value, b: &Value| this.compare_values(a, b, pos);
+                self.extreme(items, name == "max", &mut compare)?
+            }
+            "max_by" | "min_by" => {
+                let f = closure(self, &args)?;
+                let items = self.drain(&state, pos)?;
+                let mut compare = |this: &mut Self, a: &Value, b: &Value| {
+                    let ordering = this.call_value(&f, vec![a.clone(), b.clone()], pos)?;
+                    this.ordering_arg(&ordering, pos)
+                };
+                self.extreme(items, name == "max_by", &mut compare)?
+            }
+            "max_by_key" | "min_by_key" => {
+                let f = closure(self, &args)?;
+                let items = self.drain(&state, pos)?;
+                let mut keyed = Vec::with_capacity(items.len());
+                for item in items {
+                    let key = self.call_value(&f, vec![item.clone()], pos)?;
+                    keyed.push(Value::tuple(vec![key, item]));
+                }
+                let mut compare = |this: &mut Self, a: &Value, b: &Value| match (a, b) {
+                    (Value::Tuple(a), Value::Tuple(b)) => this.compare_values(&a[0], &b[0], pos),
+                    _ => unreachable!("pares montados acima"),
+                };
+                match self.extreme(keyed, name == "max_by_key", &mut compare)? {
+                    Value::Variant(Variant::Some, Some(pair)) => match &*pair {
+                        Value::Tuple(pair) => Value::some(pair[1].clone()),
+                        _ => unreachable!("pares montados acima"),
+                    },
+                    none => none,
+                }
+            }
+            "any" | "all" => {
+                let f = closure(self, &args)?;
+                let want = name == "any";
+                while let Some(item) = self.next_item(&state, pos)? {
+                    if self.predicate(&f, item, pos)? == want {
+                        return Ok(Value::Bool(want));
+                    }
+                }
+                Value::Bool(!want)
+            }
+            "find" => {
+                let f = closure(self, &args)?;
+                while let Some(item) = self.next_item(&state, pos)? {
+                    if self.predicate(&f, item.clone(), pos)? {
+                        return Ok(Value::some(item));
+                    }
+                }
+                Value::none()
+            }
+            "find_map" => {
+                let f = closure(self, &args)?;
+                while let Some(item) = self.next_item(&state, pos)? {
+                    let found = self.call_value(&f, vec![item], pos)?;
+                    if matches!(found, Value::Variant(Variant::Some, _)) {
+                        return Ok(found);
+                    }
+                }
+                Value::none()
+            }
+            "position" => {
+                let f = closure(self, &args)?;
+                let mut index = 0;
+                while let Some(item) = self.next_item(&state, pos)? {
+                    if self.predicate(&f, item, pos)? {
+                        return Ok(Value::some(Value::usize(index)));
+                    }
+                    index += 1;
+                }
+                Value::none()
+            }
+            "rposition" => {
+                let f = closure(self, &args)?;
+                let items = self.drain(&state, pos)?;
+                for (index, item) in items.into_iter().enumerate().rev() {
+                    if self.predicate(&f, item, pos)? {
+                        return Ok(Value::some(Value::usize(index)));
+                    }
+                }
+                Value::none()
+            }
+            "fold" => {
+                self.arity(name, &args, 2, pos)?;
+                let f = args.pop().unwrap_or(Value::Unit);
+                let mut acc = args.pop().unwrap_or(Value::Unit);
+                while let Some(item) = self.next_item(&state, pos)? {
+                    acc = self.call_value(&f, vec![acc, item], pos)?;
+                }
+                acc
+            }
+            "reduce" => {
+                let f = closure(self, &args)?;
+                let Some(mut acc) = self.next_item(&state, pos)? else {
+                    return Ok(Value::none());
+                };
+                while let Some(item) = self.next_item(&state, pos)? {
+                    acc = self.call_value(&f, vec![acc, item], pos)?;
+                }
+                Value::some(acc)
+            }
+            "for_each" => {
+                let f = closure(self, &args)?;
+                while let Some(item) = self.next_item(&state, pos)? {
+                    self.call_value(&f, vec![item], pos)?;
+                }
+                Value::Unit
+            }
+            "partition" => {
+                let f = closure(self, &args)?;
+                let (mut yes, mut no) = (Vec::new(), Vec::new());
+                while let Some(item) = self.next_item(&state, pos)? {
+                    if self.predicate(&f, item.clone(), pos)? {
+                        yes.push(item);
+                    } else {
+                        no.push(item);
+                    }
+                }
+                Value::tuple(vec![Value::vec(yes), Value::vec(no)])
+            }
+            "unzip" => {
+                let (mut left, mut right) = (Vec::new(), Vec::new());
+                for item in self.drain(&state, pos)? {
+                    match item {
+                        Value::Tuple(pair) if pair.len() == 2 => {
+                            left.push(pair[0].clone());
+                            right.push(pair[1].clone());
+                        }
+                        other => {
+                            return self.invalid(
+                                pos,
+                                format!("unzip exige pares, recebeu {}", other.type_name()),
+                            );
+                        }
+                    }
+                }
+                Value::tuple(vec![Value::vec(left), Value::vec(right)])
+            }
+            "eq" | "ne" => {
+                self.arity(name, &args, 1, pos)?;
+                let a = self.drain(&state, pos)?;
+                let b = self.items(args.remove(0), pos)?;
+                let equal = a.len() == b.len() && a.iter().zip(&b).all(|(x, y)| x.equals(y));
+                Value::Bool(equal == (name == "eq"))
+            }
+            _ => return self.unknown(&receiver, name, pos),
+        })
+    }
+
+    /// `max`/`min` com as regras de empate do Rust: `max` fica com o
+    /// último, `min` com o primeiro.
+    fn extreme(
+        &mut self,
+        items: Vec<Value>,
+        max: bool,
+        compare: &mut dyn FnMut(&mut Self, &Value, &Value) -> Eval<Ordering>,
+    ) -> Eval {
+        let mut best: Option<Value> = None;
+        for item in items {
+            best = Some(match best {
+                None => item,
+                Some(current) => {
+                    let ordering = compare(self, &item, &current)?;
+                    let replace = if max {
+                        ordering != Ordering::Less
+                    } else {
+                        ordering == Ordering::Less
+                    };
+                    if replace { item } else { current }
+                }
+            });
+        }
+        Ok(Value::option(best))
+    }
+
+    fn fold_numbers(
+        &mut self,
+        items: Vec<Value>,
+        name: &str,
+        turbofish: Option<&Ty>,
+        pos: Pos,
+    ) -> Eval {
+        let product = name == "product";
+        let target = turbofish
+            .map(|ty| ty.name.clone())
+            .or_else(|| self.hint.clone())
+            .unwrap_or_default();
+        let int = IntTy::from_name(&target);
+        let mut acc = match (is_float_type(&target), product) {
+            (true, false) => Value::Float(0.0),
+            (true, true) => Value::Float(1.0),
+            (false, false) => Value::Int(0, int),
+            (false, true) => Value::Int(1, int),
+        };
+        for item in items {
+            if !matches!(item, Value::Int(..) | Value::Float(_)) {
+                return self.invalid(pos, format!("`{name}` de {}", item.type_name()));
+            }
+            if let (Value::Int(..), Value::Float(_)) = (&acc, &item) {
+                acc = self.float_arg(&acc, pos).map(Value::Float)?;
+            }
+            let op = if product { BinOp::Mul } else { BinOp::Add };
+            acc = self.binary(op, acc, item, pos)?;
+        }
+        Ok(acc)
+    }
+
+    fn collect(&mut self, items: Vec<Value>, turbofish: Option<&Ty>, pos: Pos) -> Eval {
+        let target = turbofish.map(|ty| ty.name.as_str()).unwrap_or("Vec");
+        match target {
+            "Vec" | "_" | "Box" => Ok(crate::eval::coerce(Value::vec(items), turbofish)),
+            "String" => {
+                let mut text = String::new();
+                for item in &items {
+                    match item {
+                        Value::Char(c) => text.push(*c),
+                        Value::Str(s) => text.push_str(&s.borrow()),
+                        other => {
+                            return self.invalid(
+                                pos,
+                                format!("collect::<String>() de {}", other.type_name()),
+                            );
+                        }
+                    }
+                }
+                Ok(Value::str(text))
+            }
+            "Result" | "Option" => Ok(crate::eval::coerce(Value::vec(items), turbofish)),
+            other => self.unsupported(pos, format!("collect::<{other}>()")),
+        }
+    }
+
+    // --- Option / Result / Ordering ------------------------------------------
+
+    fn variant_method(
+        &mut self,
+        variant: Variant,
+        payload: Option<Rc<Value>>,
+        name: &str,
+        mut args: Vec<Value>,
+        pos: Pos,
+    ) -> Eval {
+        let receiver = Value::Variant(variant, payload.clone());
+        if matches!(variant, Variant::Less | Variant::Equal | Variant::Greater) {
+            let ordering = self.ordering_arg(&receiver, pos)?;
+            return Ok(match name {
+                "reverse" => Value::ordering(ordering.reverse()),
+                "then" => {
+                    let other = args
+                        .first()
+                        .map_or(Ok(Ordering::Equal), |o| self.ordering_arg(o, pos))?;
+                    Value::ordering(ordering.then(other))
+                }
+                "then_with" => {
+                    if ordering != Ordering::Equal {
+                        return Ok(receiver);
+                    }
+                    let f = self.closure_arg(name, &args, pos)?;
+                    self.call_value(&f, Vec::new(), pos)?
+                }
+                "is_eq" => Value::Bool(ordering.is_eq()),
+                "is_ne" => Value::Bool(ordering.is_ne()),
+                "is_lt" => Value::Bool(ordering.is_lt()),
+                "is_le" => Value::Bool(ordering.is_le()),
+                "is_gt" => Value::Bool(ordering.is_gt()),
+                "is_ge" => Value::Bool(ordering.is_ge()),
+                _ => return self.unknown(&receiver, name, pos),
+            });
+        }
+
+        let is_option = matches!(variant, Variant::Some | Variant::None);
+        let inner = payload.as_deref().cloned();
+        let good = matches!(variant, Variant::Some | Variant::Ok);
+        let call = |this: &mut Self, f: &Value, args: Vec<Value>| this.call_value(f, args, pos);
+
+        Ok(match name {
+            "is_some" | "is_ok" => Value::Bool(good),
+            "is_none" | "is_err" => Value::Bool(!good),
+            "is_some_and" | "is_ok_and" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(value) if good => Value::Bool(self.predicate(&f, value, pos)?),
+                    _ => Value::Bool(false),
+                }
+            }
+            "is_none_or" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(value) => Value::Bool(self.predicate(&f, value, pos)?),
+                    None => Value::Bool(true),
+                }
+            }
+            "unwrap" => match (good, inner) {
+                (true, Some(value)) => value,
+                (_, None) => return self.panic(pos, "called `Option::unwrap()` on a `None` value"),
+                (false, Some(error)) => {
+                    return self.panic(
+                        pos,
+                        format!(
+                            "called `Result::unwrap()` on an `Err` value: {}",
+                            error.debug(false)
+                        ),
+                    );
+                }
+            },
+            "expect" => {
+                let message = match args.first() {
+                    Some(Value::Str(s)) => s.borrow().clone(),
+                    _ => return self.invalid(pos, "expect recebe a mensagem (&str)"),
+                };
+                match (good, inner) {
+                    (true, Some(value)) => value,
+                    (_, None) => return self.panic(pos, message),
+                    (false, Some(error)) => {
+                        return self.panic(pos, format!("{message}: {}", error.debug(false)));
+                    }
+                }
+            }
+            "unwrap_err" | "expect_err" => match (good, inner) {
+                (false, Some(error)) => error,
+                (_, value) => {
+                    let value = value.map(|v| v.debug(false)).unwrap_or_default();
+                    let message = match args.first() {
+                        Some(Value::Str(s)) => format!("{}: {value}", s.borrow()),
+                        _ => format!("called `Result::unwrap_err()` on an `Ok` value: {value}"),
+                    };
+                    return self.panic(pos, message);
+                }
+            },
+            "unwrap_or" => {
+                self.arity(name, &args, 1, pos)?;
+                match inner {
+                    Some(value) if good => value,
+                    _ => args.remove(0),
+                }
+            }
+            "unwrap_or_else" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(value) if good => value,
+                    error => {
+                        let args = if is_option {
+                            Vec::new()
+                        } else {
+                            error.into_iter().collect()
+                        };
+                        call(self, &f, args)?
+                    }
+                }
+            }
+            "unwrap_or_default" => match inner {
+                Some(value) if good => value,
+                _ => match self.hint.as_deref() {
+                    Some(ty) if is_int_type(ty) => Value::Int(0, IntTy::from_name(ty)),
+                    Some(ty) if is_float_type(ty) => Value::Float(0.0),
+                    Some("String" | "str") => Value::str(""),
+                    Some("bool") => Value::Bool(false),
+                    Some("char") => Value::Char('\0'),
+                    _ => {
+                        return self.unsupported(
+                            pos,
+                            "unwrap_or_default sem tipo anotado no `let`; use unwrap_or(...)",
+                        );
+                    }
+                },
+            },
+            "map" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(value) if good => {
+                        Value::Variant(variant, Some(Rc::new(call(self, &f, vec![value])?)))
+                    }
+                    _ => receiver,
+                }
+            }
+            "map_err" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(error) if !good => Value::err(call(self, &f, vec![error])?),
+                    _ => receiver,
+                }
+            }
+            "and_then" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(value) if good => call(self, &f, vec![value])?,
+                    _ => receiver,
+                }
+            }
+            "and" => {
+                self.arity(name, &args, 1, pos)?;
+                if good { args.remove(0) } else { receiver }
+            }
+            "or" => {
+                self.arity(name, &args, 1, pos)?;
+                if good { receiver } else { args.remove(0) }
+            }
+            "or_else" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    _ if good => receiver,
+                    error => {
+                        let args = if is_option {
+                            Vec::new()
+                        } else {
+                            error.into_iter().collect()
+                        };
+                        call(self, &f, args)?
+                    }
+                }
+            }
+            "filter" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(value) if self.predicate(&f, value.clone(), pos)? => Value::some(value),
+                    _ => Value::none(),
+                }
+            }
+            "ok" => Value::option(inner.filter(|_| good)),
+            "err" => Value::option(inner.filter(|_| !good)),
+            "ok_or" => {
+                self.arity(name, &args, 1, pos)?;
+                match inner {
+                    Some(value) => Value::ok(value),
+                    None => Value::err(args.remove(0)),
+                }
+            }
+            "ok_or_else" => {
+                let f = self.closure_arg(name, &args, pos)?;
+                match inner {
+                    Some(value) => Value::ok(value),
+                    None => Value::err(call(self, &f, Vec::new())?),
+                }
+            }
+            "map_or" => {
+                self.arity(name, &args, 2, pos)?;
+                let f = args.pop().unwrap_or(Value::Unit);
+                let default = args.pop().unwrap_or(Value::Unit);
+                match inner {
+                    Some(value) if good => call(self, &f, vec![value])?,
+                    _ => default,
+                }
+            }
+            "map_or_else" => {
+                self.arity(name, &args, 2, pos)?;
+                let f = args.pop().unwrap_or(Value::Unit);
+                let default = args.pop().unwrap_or(Value::Unit);
+                match inner {
+                    Some(value) if good => call(self, &f, vec![value])?,
+                    error => {
+                        let args = if is_option {
+                            Vec::new()
+                        } else {
+                            error.into_iter().collect()
+                        };
+                        call(self, &default, args)?
+                    }
+                }
+            }
+            "flatten" => match inner {
+                Some(value) if good => value,
+                _ => receiver,
+            },
+            "cloned" | "copied" | "as_deref_mut" => match inner {
+                Some(value) => Value::Variant(variant, Some(Rc::new(value.deep_clone()))),
+                None => receiver,
+            },
+            "iter" | "into_iter" => Value::iter(inner.filter(|_| good)),
+            "unwrap_unchecked" => return self.unsupported(pos, "unwrap_unchecked (unsafe)"),
+            "take" | "replace" | "insert" | "get_or_insert" | "get_or_insert_with" => {
+                return self.unsupported(
+                    pos,
+                    format!("`Option::{name}` altera a variável; reatribua (`x = None`)"),
+                );
+            }
+            _ => return self.unknown(&receiver, name, pos),
+        })
+    }
+
+    // --- stdin -------------------------------------------------------------
+
+    fn stdin_method(&mut self, name: &str, args: Vec<Value>, pos: Pos) -> Eval {
+        Ok(match (name, &args[..]) {
+            ("lock", []) => Value::Stdin,
+            ("lines", []) => Value::from_source(Source::StdinLines),
+            ("read_line", [Value::Str(buffer)]) => {
+                let line = self.read_line().to_string();
+                buffer.borrow_mut().push_str(&line);
+                Value::ok(Value::usize(line.len()))
+            }
+            ("read_to_string", [Value::Str(buffer)]) => {
+                let rest = self.read_rest().to_string();
+                buffer.borrow_mut().push_str(&rest);
+                Value::ok(Value::usize(rest.len()))
+            }
+            ("read_line" | "read_to_string", _) => {
+                return self.invalid(pos, format!("`{name}` recebe `&mut String`"));
+            }
+            _ => return self.unknown(&Value::Stdin, name, pos),
+        })
+    }
+}
+
+fn is_iterator_method(name: &str) -> bool {
+    matches!(
+        name,
+        "map"
+            | "filter"
+            | "sum"
+            | "product"
+            | "max"
+            | "min"
+            | "count"
+            | "enumerate"
+            | "fold"
+            | "any"
+            | "all"
+            | "collect"
+            | "rev"
+            | "zip"
+            | "skip"
+            | "take"
+            | "for_each"
+            | "position"
+            | "find"
+    )
+}
+
+fn byte(b: u8) -> Value {
+    Value::Int(b.into(), Some(IntTy::U8))
+}
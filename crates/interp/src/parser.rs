@@ -0,0 +1,1279 @@
+//! Parser descendente recursivo do subconjunto.
+
+use std::rc::Rc;
+
+use crate::ast::{
+    Arm, BinOp, Block, Closure, Expr, ExprKind, Function, Literal, MacroKind, Pattern, Program,
+    Stmt, Ty, UnOp,
+};
+use crate::lexer::{Pos, Tok, Token};
+use crate::{Error, Result};
+
+pub fn parse(source: &str, tokens: Vec<Token>) -> Result<Program> {
+    let mut parser = Parser {
+        source: source.chars().collect(),
+        tokens,
+        index: 0,
+        program: Program::default(),
+    };
+    parser.program()?;
+    if !parser.program.functions.contains_key("main") {
+        return Err(Error::Syntax {
+            pos: Pos { line: 1, column: 1 },
+            message: "função `main` não encontrada".to_string(),
+        });
+    }
+    Ok(parser.program)
+}
+
+struct Parser {
+    source: Vec<char>,
+    tokens: Vec<Token>,
+    index: usize,
+    program: Program,
+}
+
+/// Precedência dos operadores binários (maior liga mais forte).
+fn binary_op(tok: &Tok) -> Option<(BinOp, u8)> {
+    Some(match tok {
+        Tok::Punct("||") => (BinOp::Or, 1),
+        Tok::Punct("&&") => (BinOp::And, 2),
+        Tok::Punct("==") => (BinOp::Eq, 3),
+        Tok::Punct("!=") => (BinOp::Ne, 3),
+        Tok::Punct("<") => (BinOp::Lt, 3),
+        Tok::Punct("<=") => (BinOp::Le, 3),
+        Tok::Punct(">") => (BinOp::Gt, 3),
+        Tok::Punct(">=") => (BinOp::Ge, 3),
+        Tok::Punct("|") => (BinOp::BitOr, 4),
+        Tok::Punct("^") => (BinOp::BitXor, 5),
+        Tok::Punct("&") => (BinOp::BitAnd, 6),
+        Tok::Punct("+") => (BinOp::Add, 8),
+        Tok::Punct("-") => (BinOp::Sub, 8),
+        Tok::Punct("*") => (BinOp::Mul, 9),
+        Tok::Punct("/") => (BinOp::Div, 9),
+        Tok::Punct("%") => (BinOp::Rem, 9),
+        _ => return None,
+    })
+}
+
+fn compound_op(tok: &Tok) -> Option<BinOp> {
+    Some(match tok {
+        Tok::Punct("+=") => BinOp::Add,
+        Tok::Punct("-=") => BinOp::Sub,
+        Tok::Punct("*=") => BinOp::Mul,
+        Tok::Punct("/=") => BinOp::Div,
+        Tok::Punct("%=") => BinOp::Rem,
+        Tok::Punct("^=") => BinOp::BitXor,
+        Tok::Punct("&=") => BinOp::BitAnd,
+        Tok::Punct("|=") => BinOp::BitOr,
+        _ => return None,
+    })
+}
+
+impl Parser {
+    // --- navegação -------------------------------------------------------
+
+    fn peek(&self) -> &Tok {
+        &self.tokens[self.index].tok
+    }
+
+    fn peek_at(&self, offset: usize) -> &Tok {
+        let index = (self.index + offset).min(self.tokens.len() - 1);
+        &self.tokens[index].tok
+    }
+
+    fn pos(&self) -> Pos {
+        self.tokens[self.index].pos
+    }
+
+    fn next(&mut self) -> Tok {
+        let tok = self.tokens[self.index].tok.clone();
+        if self.index < self.tokens.len() - 1 {
+            self.index += 1;
+        }
+        tok
+    }
+
+    fn is(&self, punct: &str) -> bool {
+        matches!(self.peek(), Tok::Punct(p) if *p == punct)
+    }
+
+    fn is_keyword(&self, word: &str) -> bool {
+        matches!(self.peek(), Tok::Ident(i) if i == word)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if self.is(punct) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, word: &str) -> bool {
+        if self.is_keyword(word) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<()> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("esperado `{punct}`")))
+        }
+    }
+
+    /// `>>` e `<<` chegam como dois tokens; aqui vale só se estiverem colados.
+    fn is_double(&self, punct: &str) -> bool {
+        self.is(punct)
+            && matches!(self.peek_at(1), Tok::Punct(p) if *p == punct)
+            && !self.tokens[self.index + 1].spaced
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.next() {
+            Tok::Ident(name) => Ok(name),
+            _ => {
+                self.index -= 1;
+                Err(self.error("esperado um identificador"))
+            }
+        }
+    }
+
+    /// Código entre os tokens `start` (inclusive) e `end` (exclusive).
+    fn text(&self, start: usize, end: usize) -> String {
+        let from = self.tokens[start].start;
+        let to = self.tokens[end.max(start + 1) - 1].end;
+        self.source[from..to].iter().collect()
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let found = match self.peek() {
+            Tok::Ident(name) => format!("`{name}`"),
+            Tok::Punct(p) => format!("`{p}`"),
+            Tok::Eof => "fim do arquivo".to_string(),
+            Tok::Int(..) | Tok::Float(_) => "número".to_string(),
+            Tok::Str(_) => "string".to_string(),
+            Tok::Char(_) => "caractere".to_string(),
+            Tok::Lifetime(name) => format!("`'{name}`"),
+        };
+        Error::Syntax {
+            pos: self.pos(),
+            message: format!("{message}, encontrado {found}"),
+        }
+    }
+
+    fn unsupported(&self, message: &str) -> Error {
+        Error::Unsupported {
+            pos: self.pos(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Pula um grupo balanceado começando no delimitador atual.
+    fn skip_group(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        loop {
+            match self.next() {
+                Tok::Punct("(" | "[" | "{") => depth += 1,
+                Tok::Punct(")" | "]" | "}") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                Tok::Eof => return Err(self.error("delimitador sem fechamento")),
+                _ => {}
+            }
+        }
+    }
+
+    /// `<...>` de genéricos, sem interpretar.
+    fn skip_generics(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        loop {
+            match self.next() {
+                Tok::Punct("<") => depth += 1,
+                Tok::Punct(">") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                Tok::Punct(">=") if depth == 1 => {
+                    return Err(self.error("espaço esperado entre `>` e `=`"));
+                }
+                Tok::Eof => return Err(self.error("genérico sem fechamento")),
+                _ => {}
+            }
+        }
+    }
+
+    fn skip_attributes(&mut self) -> Result<()> {
+        while self.is("#") {
+            self.next();
+            self.eat("!");
+            if !self.is("[") {
+                return Err(self.error("esperado `[`"));
+            }
+            self.skip_group()?;
+        }
+        Ok(())
+    }
+
+    // --- itens -----------------------------------------------------------
+
+    fn program(&mut self) -> Result<()> {
+        loop {
+            self.skip_attributes()?;
+            if matches!(self.peek(), Tok::Eof) {
+                return Ok(());
+            }
+            self.item()?;
+        }
+    }
+
+    fn item(&mut self) -> Result<()> {
+        if self.eat_keyword("pub") && self.is("(") {
+            self.skip_group()?;
+        }
+        match self.peek().clone() {
+            Tok::Ident(word) if word == "use" => {
+                while !self.eat(";") {
+                    if matches!(self.next(), Tok::Eof) {
+                        return Err(self.error("esperado `;`"));
+                    }
+                }
+                Ok(())
+            }
+            Tok::Ident(word) if word == "fn" => {
+                let function = self.function()?;
+                if self.program.functions.contains_key(&function.name) {
+                    return Err(Error::Syntax {
+                        pos: function.pos,
+                        message: format!("função `{}` definida mais de uma vez", function.name),
+                    });
+                }
+                self.program
+                    .functions
+                    .insert(function.name.clone(), Rc::new(function));
+                Ok(())
+            }
+            Tok::Ident(word) if word == "const" || word == "static" => {
+                self.next();
+                self.eat_keyword("mut");
+                let name = self.ident()?;
+                let ty = if self.eat(":") {
+                    Some(self.ty()?)
+                } else {
+                    None
+                };
+                self.expect("=")?;
+                let value = self.expr()?;
+                self.expect(";")?;
+                self.program.consts.push((name, ty, value));
+                Ok(())
+            }
+            Tok::Ident(word)
+                if matches!(
+                    word.as_str(),
+                    "struct"
+                        | "enum"
+                        | "impl"
+                        | "trait"
+                        | "mod"
+                        | "type"
+                        | "union"
+                        | "macro_rules"
+                        | "extern"
+                        | "unsafe"
+                        | "async"
+                ) =>
+            {
+                Err(self.unsupported(&format!("itens `{word}`")))
+            }
+            _ => Err(self.error("esperado `fn`, `use` ou `const`")),
+        }
+    }
+
+    fn function(&mut self) -> Result<Function> {
+        self.next();
+        let pos = self.pos();
+        let name = self.ident()?;
+        if self.is("<") {
+            self.skip_generics()?;
+        }
+        self.expect("(")?;
+        let mut params = Vec::new();
+        while !self.eat(")") {
+            if self.is_keyword("self")
+                || (self.is("&") && matches!(self.peek_at(1), Tok::Ident(i) if i == "self"))
+            {
+                return Err(self.unsupported("métodos com `self`"));
+            }
+            let pattern = self.pattern()?;
+            self.expect(":")?;
+            params.push((pattern, self.ty()?));
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        let ret = if self.eat("->") {
+            Some(self.ty()?)
+        } else {
+            None
+        };
+        if self.eat_keyword("where") {
+            while !self.is("{") {
+                if matches!(self.next(), Tok::Eof) {
+                    return Err(self.error("esperado `{`"));
+                }
+            }
+        }
+        let body = self.block()?;
+        Ok(Function {
+            name,
+            params,
+            ret,
+            body,
+            pos,
+        })
+    }
+
+    fn ty(&mut self) -> Result<Ty> {
+        if self.eat("&") || self.eat("&&") {
+            if matches!(self.peek(), Tok::Lifetime(_)) {
+                self.next();
+            }
+            self.eat_keyword("mut");
+            return self.ty();
+        }
+        if self.eat("(") {
+            let mut args = Vec::new();
+            while !self.eat(")") {
+                args.push(self.ty()?);
+                if !self.eat(",") {
+                    self.expect(")")?;
+                    break;
+                }
+            }
+            return Ok(Ty {
+                name: "()".to_string(),
+                args,
+            });
+        }
+        if self.eat("[") {
+            let element = self.ty()?;
+            if self.eat(";") {
+                self.expr()?;
+            }
+            self.expect("]")?;
+            return Ok(Ty {
+                name: "[]".to_string(),
+                args: vec![element],
+            });
+        }
+        if self.eat_keyword("impl") || self.eat_keyword("dyn") {
+            let bound = self.ty()?;
+            while self.eat("+") {
+                self.ty()?;
+            }
+            return Ok(bound);
+        }
+        if self.is_keyword("fn") {
+            return Err(self.unsupported("tipos de ponteiro de função"));
+        }
+        if self.eat("!") {
+            return Ok(Ty {
+                name: "!".to_string(),
+                args: Vec::new(),
+            });
+        }
+
+        let mut name = self.ident()?;
+        let mut args = Vec::new();
+        loop {
+            if self.eat("<") {
+                args.clear();
+                while !self.eat(">") {
+                    if matches!(self.peek(), Tok::Lifetime(_)) {
+                        self.next();
+                    } else {
+                        args.push(self.ty()?);
+                    }
+                    if !self.eat(",") {
+                        self.expect(">")?;
+                        break;
+                    }
+                }
+            } else if self.is("(") && matches!(name.as_str(), "Fn" | "FnMut" | "FnOnce") {
+                self.skip_group()?;
+                if self.eat("->") {
+                    self.ty()?;
+                }
+                break;
+            }
+            if self.eat("::") {
+                name = self.ident()?;
+            } else {
+                break;
+            }
+        }
+        Ok(Ty { name, args })
+    }
+
+    // --- blocos e instruções ----------------------------------------------
+
+    fn block(&mut self) -> Result<Block> {
+        self.expect("{")?;
+        let mut block = Block::default();
+        loop {
+            self.skip_attributes()?;
+            if self.eat("}") {
+                return Ok(block);
+            }
+            if self.eat(";") {
+                continue;
+            }
+            if self.is_keyword("let") {
+                block.stmts.push(self.let_stmt()?);
+                continue;
+            }
+            if self.is_keyword("use") {
+                self.item()?;
+                continue;
+            }
+            if self.is_keyword("fn") {
+                let function = self.function()?;
+                self.program
+                    .functions
+                    .insert(function.name.clone(), Rc::new(function));
+                continue;
+            }
+            if self.is_keyword("const") || self.is_keyword("static") {
+                self.next();
+                let name = self.ident()?;
+                let ty = if self.eat(":") {
+                    Some(self.ty()?)
+                } else {
+                    None
+                };
+                self.expect("=")?;
+                let init = self.expr()?;
+                self.expect(";")?;
+                block.stmts.push(Stmt::Let {
+                    pattern: Pattern::Binding(name),
+                    ty,
+                    init: Some(init),
+                });
+                continue;
+            }
+            if matches!(
+                self.peek(),
+                Tok::Ident(word) if matches!(word.as_str(), "struct" | "enum" | "impl" | "trait" | "mod")
+            ) {
+                return Err(self.unsupported("itens dentro de funções"));
+            }
+
+            let block_like = self.starts_block_like();
+            let expr = if block_like {
+                let expr = self.block_like()?;
+                // `match x { .. }.len()` ou `if .. {} else {} as i64` em posição de valor
+                if self.is(".") || self.is("?") || self.is_keyword("as") {
+                    let expr = self.postfix_from(expr)?;
+                    self.binary_from(expr, 0)?
+                } else {
+                    expr
+                }
+            } else {
+                self.expr()?
+            };
+            if self.eat("}") {
+                block.tail = Some(Box::new(expr));
+                return Ok(block);
+            }
+            if !self.eat(";") && !block_like {
+                return Err(self.error("esperado `;`"));
+            }
+            block.stmts.push(Stmt::Expr(expr));
+        }
+    }
+
+    fn let_stmt(&mut self) -> Result<Stmt> {
+        self.next();
+        let pattern = self.pattern()?;
+        let ty = if self.eat(":") {
+            Some(self.ty()?)
+        } else {
+            None
+        };
+        let init = if self.eat("=") {
+            Some(self.expr()?)
+        } else {
+            None
+        };
+        if self.is_keyword("else") {
+            return Err(self.unsupported("`let ... else`"));
+        }
+        self.expect(";")?;
+        Ok(Stmt::Let { pattern, ty, init })
+    }
+
+    fn starts_block_like(&self) -> bool {
+        match self.peek() {
+            Tok::Punct("{") => true,
+            Tok::Ident(word) => matches!(
+                word.as_str(),
+                "if" | "match" | "loop" | "while" | "for" | "unsafe"
+            ),
+            Tok::Lifetime(_) => matches!(self.peek_at(1), Tok::Punct(":")),
+            _ => false,
+        }
+    }
+
+    fn block_like(&mut self) -> Result<Expr> {
+        let pos = self.pos();
+        let label = if let Tok::Lifetime(name) = self.peek().clone() {
+            self.next();
+            self.expect(":")?;
+            Some(name)
+        } else {
+            None
+        };
+
+        let kind = match self.next() {
+            Tok::Punct("{") => {
+                self.index -= 1;
+                ExprKind::Block(self.block()?)
+            }
+            Tok::Ident(word) if word == "unsafe" => ExprKind::Block(self.block()?),
+            Tok::Ident(word) if word == "if" => return self.if_expr(pos),
+            Tok::Ident(word) if word == "match" => {
+                let value = self.expr()?;
+                self.expect("{")?;
+                let mut arms = Vec::new();
+                while !self.eat("}") {
+                    let pattern = self.pattern()?;
+                    let guard = if self.eat_keyword("if") {
+                        Some(self.expr()?)
+                    } else {
+                        None
+                    };
+                    self.expect("=>")?;
+                    let block_body = self.starts_block_like();
+                    let body = if block_body {
+                        self.block_like()?
+                    } else {
+                        self.expr()?
+                    };
+                    arms.push(Arm {
+                        pattern,
+                        guard,
+                        body,
+                    });
+                    if !self.eat(",") && !block_body && !self.is("}") {
+                        return Err(self.error("esperado `,` ou `}`"));
+                    }
+                }
+                ExprKind::Match(Box::new(value), arms)
+            }
+            Tok::Ident(word) if word == "loop" => ExprKind::Loop {
+                body: self.block()?,
+                label,
+            },
+            Tok::Ident(word) if word == "while" => {
+                if self.eat_keyword("let") {
+                    let pattern = self.pattern()?;
+                    self.expect("=")?;
+                    let value = self.expr()?;
+                    ExprKind::WhileLet {
+                        pattern,
+                        value: Box::new(value),
+                        body: self.block()?,
+                        label,
+                    }
+                } else {
+                    let cond = self.expr()?;
+                    ExprKind::While {
+                        cond: Box::new(cond),
+                        body: self.block()?,
+                        label,
+                    }
+                }
+            }
+            Tok::Ident(word) if word == "for" => {
+                let pattern = self.pattern()?;
+                if !self.eat_keyword("in") {
+                    return Err(self.error("esperado `in`"));
+                }
+                let iter = self.expr()?;
+                ExprKind::For {
+                    pattern,
+                    iter: Box::new(iter),
+                    body: self.block()?,
+                    label,
+                }
+            }
+            _ => {
+                self.index -= 1;
+                return Err(self.error("esperado um bloco"));
+            }
+        };
+        Ok(Expr { kind, pos })
+    }
+
+    fn if_expr(&mut self, pos: Pos) -> Result<Expr> {
+        let kind = if self.eat_keyword("let") {
+            let pattern = self.pattern()?;
+            self.expect("=")?;
+            let value = self.expr()?;
+            let then = self.block()?;
+            ExprKind::IfLet {
+                pattern,
+                value: Box::new(value),
+                then,
+                otherwise: self.else_branch()?,
+            }
+        } else {
+            let cond = self.expr()?;
+            let then = self.block()?;
+            ExprKind::If {
+                cond: Box::new(cond),
+                then,
+                otherwise: self.else_branch()?,
+            }
+        };
+        Ok(Expr { kind, pos })
+    }
+
+    fn else_branch(&mut self) -> Result<Option<Box<Expr>>> {
+        if !self.eat_keyword("else") {
+            return Ok(None);
+        }
+        let pos = self.pos();
+        if self.eat_keyword("if") {
+            return Ok(Some(Box::new(self.if_expr(pos)?)));
+        }
+        Ok(Some(Box::new(Expr {
+            kind: ExprKind::Block(self.block()?),
+            pos,
+        })))
+    }
+
+    // --- expressões --------------------------------------------------------
+
+    pub fn expr(&mut self) -> Result<Expr> {
+        let pos = self.pos();
+        let lhs = self.range()?;
+        if self.eat("=") {
+            let rhs = self.expr()?;
+            return Ok(Expr {
+                kind: ExprKind::Assign(Box::new(lhs), Box::new(rhs)),
+                pos,
+            });
+        }
+        if let Some(op) = compound_op(self.peek()) {
+            self.next();
+            let rhs = self.expr()?;
+            return Ok(Expr {
+                kind: ExprKind::CompoundAssign(op, Box::new(lhs), Box::new(rhs)),
+                pos,
+            });
+        }
+        if self.is("<") && self.is_double("<") && matches!(self.peek_at(2), Tok::Punct("=")) {
+            return Err(self.unsupported("`<<=`"));
+        }
+        Ok(lhs)
+    }
+
+    fn ends_expr(&self) -> bool {
+        matches!(
+            self.peek(),
+            Tok::Punct(")" | "]" | "}" | "," | ";" | "{" | "=>" | "=") | Tok::Eof
+        )
+    }
+
+    fn range(&mut self) -> Result<Expr> {
+        let pos = self.pos();
+        if self.is("..") || self.is("..=") {
+            let inclusive = self.next() == Tok::Punct("..=");
+            let end = if self.ends_expr() {
+                None
+            } else {
+                Some(Box::new(self.binary(0)?))
+            };
+            return Ok(Expr {
+                kind: ExprKind::Range {
+                    start: None,
+                    end,
+                    inclusive,
+                },
+                pos,
+            });
+        }
+        let start = self.binary(0)?;
+        if self.is("..") || self.is("..=") {
+            let inclusive = self.next() == Tok::Punct("..=");
+            let end = if self.ends_expr() {
+                None
+            } else {
+                Some(Box::new(self.binary(0)?))
+            };
+            return Ok(Expr {
+                kind: ExprKind::Range {
+                    start: Some(Box::new(start)),
+                    end,
+                    inclusive,
+                },
+                pos,
+            });
+        }
+        Ok(start)
+    }
+
+    fn binary(&mut self, min: u8) -> Result<Expr> {
+        let lhs = self.cast()?;
+        self.binary_from(lhs, min)
+    }
+
+    fn binary_from(&mut self, mut lhs: Expr, min: u8) -> Result<Expr> {
+        loop {
+            let (op, prec, width) = if self.is_double("<") {
+                (BinOp::Shl, 7, 2)
+            } else if self.is_double(">") {
+                (BinOp::Shr, 7, 2)
+            } else if let Some((op, prec)) = binary_op(self.peek()) {
+                (op, prec, 1)
+            } else {
+                return Ok(lhs);
+            };
+            if prec <= min {
+                return Ok(lhs);
+            }
+            let pos = lhs.pos;
+            for _ in 0..width {
+                self.next();
+            }
+            let rhs = self.binary(prec)?;
+            lhs = Expr {
+                kind: ExprKind::Binary(op, Box::new(lhs), Box::new(rhs)),
+                pos,
+            };
+        }
+    }
+
+    fn cast(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.eat_keyword("as") {
+            let pos = expr.pos;
+            let ty = self.ty()?;
+            expr = Expr {
+                kind: ExprKind::Cast(Box::new(expr), ty),
+                pos,
+            };
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        let pos = self.pos();
+        let kind = if self.eat("-") {
+            let operand = self.unary()?;
+            match operand.kind {
+                ExprKind::Int(value, ty) => ExprKind::Int(-value, ty),
+                ExprKind::Float(value) => ExprKind::Float(-value),
+                _ => ExprKind::Unary(UnOp::Neg, Box::new(operand)),
+            }
+        } else if self.eat("!") {
+            ExprKind::Unary(UnOp::Not, Box::new(self.unary()?))
+        } else if self.eat("*") {
+            ExprKind::Deref(Box::new(self.unary()?))
+        } else if self.eat("&") || self.eat("&&") {
+            let mutable = self.eat_keyword("mut");
+            ExprKind::Ref(Box::new(self.unary()?), mutable)
+        } else {
+            return self.postfix();
+        };
+        Ok(Expr { kind, pos })
+    }
+
+    fn postfix(&mut self) -> Result<Expr> {
+        let expr = self.primary()?;
+        self.postfix_from(expr)
+    }
+
+    fn postfix_from(&mut self, mut expr: Expr) -> Result<Expr> {
+        loop {
+            let pos = expr.pos;
+            if self.eat(".") {
+                let member_pos = self.pos();
+                match self.next() {
+                    Tok::Int(index, _) => {
+                        expr = Expr {
+                            kind: ExprKind::TupleField(Box::new(expr), index as usize),
+                            pos,
+                        };
+                    }
+                    Tok::Float(_) => {
+                        self.index -= 1;
+                        return Err(self.unsupported("acesso a tupla aninhada sem parênteses"));
+                    }
+                    Tok::Ident(method) => {
+                        let turbofish = if self.eat("::") {
+                            self.expect("<")?;
+                            let ty = self.ty()?;
+                            self.expect(">")?;
+                            Some(ty)
+                        } else {
+                            None
+                        };
+                        if !self.is("(") {
+                            self.index -= 1;
+                            return Err(Error::Unsupported {
+                                pos: member_pos,
+                                message: format!("acesso a campo `.{method}` (structs)"),
+                            });
+                        }
+                        let args = self.args(")")?;
+                        expr = Expr {
+                            kind: ExprKind::MethodCall {
+                                receiver: Box::new(expr),
+                                method,
+                                turbofish,
+                                args,
+                                method_pos: member_pos,
+                            },
+                            pos,
+                        };
+                    }
+                    _ => {
+                        self.index -= 1;
+                        return Err(self.error("esperado nome de método"));
+                    }
+                }
+            } else if self.is("(") {
+                let args = self.args(")")?;
+                expr = Expr {
+                    kind: ExprKind::CallValue(Box::new(expr), args),
+                    pos,
+                };
+            } else if self.eat("[") {
+                let index = self.expr()?;
+                self.expect("]")?;
+                expr = Expr {
+                    kind: ExprKind::Index(Box::new(expr), Box::new(index)),
+                    pos,
+                };
+            } else if self.eat("?") {
+                expr = Expr {
+                    kind: ExprKind::Try(Box::new(expr)),
+                    pos,
+                };
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    /// Argumentos entre `(`/`[` e `close`.
+    fn args(&mut self, close: &str) -> Result<Vec<Expr>> {
+        self.next();
+        let mut args = Vec::new();
+        while !self.eat(close) {
+            args.push(self.expr()?);
+            if !self.eat(",") {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let pos = self.pos();
+        if self.starts_block_like() {
+            return self.block_like();
+        }
+        let kind = match self.next() {
+            Tok::Int(value, ty) => ExprKind::Int(value, ty),
+            Tok::Float(value) => ExprKind::Float(value),
+            Tok::Str(value) => ExprKind::Str(value),
+            Tok::Char(value) => ExprKind::Char(value),
+            Tok::Punct("(") => {
+                if self.eat(")") {
+                    ExprKind::Unit
+                } else {
+                    let first = self.expr()?;
+                    if self.eat(")") {
+                        return Ok(first);
+                    }
+                    let mut items = vec![first];
+                    while self.eat(",") {
+                        if self.is(")") {
+                            break;
+                        }
+                        items.push(self.expr()?);
+                    }
+                    self.expect(")")?;
+                    ExprKind::Tuple(items)
+                }
+            }
+            Tok::Punct("[") => {
+                self.index -= 1;
+                self.array("]")?
+            }
+            Tok::Punct("|") | Tok::Punct("||") => {
+                self.index -= 1;
+                self.closure()?
+            }
+            Tok::Ident(word) => match word.as_str() {
+                "true" => ExprKind::Bool(true),
+                "false" => ExprKind::Bool(false),
+                "move" => self.closure()?,
+                "return" => ExprKind::Return(self.optional_value()?),
+                "break" => {
+                    let label = match self.peek().clone() {
+                        Tok::Lifetime(name) => {
+                            self.next();
+                            Some(name)
+                        }
+                        _ => None,
+                    };
+                    ExprKind::Break(label, self.optional_value()?)
+                }
+                "continue" => {
+                    let label = match self.peek().clone() {
+                        Tok::Lifetime(name) => {
+                            self.next();
+                            Some(name)
+                        }
+                        _ => None,
+                    };
+                    ExprKind::Continue(label)
+                }
+                "self" | "Self" | "super" | "crate" => {
+                    self.index -= 1;
+                    return Err(self.unsupported(&format!("`{word}`")));
+                }
+                "async" | "await" => {
+                    self.index -= 1;
+                    return Err(self.unsupported("async/await"));
+                }
+                _ if self.is("!") => {
+                    self.next();
+                    return self.macro_call(&word, pos);
+                }
+                _ => {
+                    let mut path = vec![word];
+                    while self.eat("::") {
+                        if self.is("<") {
+                            // `Vec::<i64>::new()`: o tipo não importa
+                            self.skip_generics()?;
+                            continue;
+                        }
+                        path.push(self.ident()?);
+                    }
+                    if self.is("(") {
+                        ExprKind::Call(path, self.args(")")?)
+                    } else {
+                        ExprKind::Path(path)
+                    }
+                }
+            },
+            _ => {
+                self.index -= 1;
+                return Err(self.error("esperada uma expressão"));
+            }
+        };
+        Ok(Expr { kind, pos })
+    }
+
+    fn optional_value(&mut self) -> Result<Option<Box<Expr>>> {
+        if self.ends_expr() {
+            Ok(None)
+        } else {
+            Ok(Some(Box::new(self.expr()?)))
+        }
+    }
+
+    /// `[a, b]`, `[x; n]` (também usado por `vec![...]`).
+    fn array(&mut self, close: &str) -> Result<ExprKind> {
+        self.next();
+        if self.eat(close) {
+            return Ok(ExprKind::Array(Vec::new()));
+        }
+        let first = self.expr()?;
+        if self.eat(";") {
+            let count = self.expr()?;
+            self.expect(close)?;
+            return Ok(ExprKind::ArrayRepeat(Box::new(first), Box::new(count)));
+        }
+        let mut items = vec![first];
+        while self.eat(",") {
+            if self.is(close) {
+                break;
+            }
+            items.push(self.expr()?);
+        }
+        self.expect(close)?;
+        Ok(ExprKind::Array(items))
+    }
+
+    fn closure(&mut self) -> Result<ExprKind> {
+        let mut params = Vec::new();
+        if !self.eat("||") {
+            self.expect("|")?;
+            while !self.eat("|") {
+                params.push(self.pattern_no_alt()?);
+                if self.eat(":") {
+                    self.ty()?;
+                }
+                if !self.eat(",") {
+                    self.expect("|")?;
+                    break;
+                }
+            }
+        }
+        let body = if self.eat("->") {
+            self.ty()?;
+            let pos = self.pos();
+            Expr {
+                kind: ExprKind::Block(self.block()?),
+                pos,
+            }
+        } else {
+            self.expr()?
+        };
+        Ok(ExprKind::Closure(Rc::new(Closure { params, body })))
+    }
+
+    fn macro_call(&mut self, name: &str, pos: Pos) -> Result<Expr> {
+        let close = match self.peek() {
+            Tok::Punct("(") => ")",
+            Tok::Punct("[") => "]",
+            Tok::Punct("{") => "}",
+            _ => return Err(self.error("esperado `(`")),
+        };
+        let kind = match name {
+            "vec" => self.array(close)?,
+            "matches" => {
+                self.next();
+                let value = self.expr()?;
+                self.expect(",")?;
+                let pattern = self.pattern()?;
+                if self.is_keyword("if") {
+                    return Err(self.unsupported("guarda em `matches!`"));
+                }
+                self.eat(",");
+                self.expect(close)?;
+                ExprKind::Matches(Box::new(value), pattern)
+            }
+            _ => {
+                let kind = match name {
+                    "print" => MacroKind::Print,
+                    "println" => MacroKind::Println,
+                    "eprint" => MacroKind::Eprint,
+                    "eprintln" => MacroKind::Eprintln,
+                    "format" => MacroKind::Format,
+                    "panic" => MacroKind::Panic,
+                    "assert" => MacroKind::Assert,
+                    "assert_eq" => MacroKind::AssertEq,
+                    "assert_ne" => MacroKind::AssertNe,
+                    "unreachable" => MacroKind::Unreachable,
+                    "todo" => MacroKind::Todo,
+                    "unimplemented" => MacroKind::Unimplemented,
+                    _ => {
+                        self.index -= 2;
+                        return Err(self.unsupported(&format!("macro `{name}!`")));
+                    }
+                };
+                let leading = match kind {
+                    MacroKind::Assert => 1,
+                    MacroKind::AssertEq | MacroKind::AssertNe => 2,
+                    _ => 0,
+                };
+                self.next();
+                let mut args = Vec::new();
+                let mut source = None;
+                for i in 0..leading {
+                    if i > 0 {
+                        self.expect(",")?;
+                    }
+                    let start = self.index;
+                    args.push(self.expr()?);
+                    source = Some(self.text(start, self.index));
+                }
+                if leading > 0 && !self.eat(",") {
+                    self.expect(close)?;
+                    return Ok(Expr {
+                        kind: ExprKind::Macro {
+                            kind,
+                            args,
+                            template: None,
+                            named: Vec::new(),
+                            source,
+                        },
+                        pos,
+                    });
+                }
+                let template = match self.peek().clone() {
+                    Tok::Str(template) => {
+                        self.next();
+                        Some(template)
+                    }
+                    _ if self.is(close) => None,
+                    _ => return Err(self.error("esperado um literal de formato")),
+                };
+                let mut named = Vec::new();
+                while self.eat(",") {
+                    if self.is(close) {
+                        break;
+                    }
+                    if let (Tok::Ident(name), Tok::Punct("=")) =
+                        (self.peek().clone(), self.peek_at(1))
+                    {
+                        self.next();
+                        self.next();
+                        named.push(name);
+                    } else if !named.is_empty() {
+                        return Err(self.error("argumento posicional depois de um nomeado"));
+                    }
+                    args.push(self.expr()?);
+                }
+                self.expect(close)?;
+                ExprKind::Macro {
+                    kind,
+                    args,
+                    template,
+                    named,
+                    source,
+                }
+            }
+        };
+        Ok(Expr { kind, pos })
+    }
+
+    // --- padrões -----------------------------------------------------------
+
+    fn pattern(&mut self) -> Result<Pattern> {
+        self.eat("|");
+        let first = self.pattern_no_alt()?;
+        if !self.is("|") {
+            return Ok(first);
+        }
+        let mut alternatives = vec![first];
+        while self.eat("|") {
+            alternatives.push(self.pattern_no_alt()?);
+        }
+        Ok(Pattern::Or(alternatives))
+    }
+
+    fn literal(&mut self) -> Result<Option<Literal>> {
+        let negative = self.is("-");
+        if negative {
+            self.next();
+        }
+        let literal = match self.peek().clone() {
+            Tok::Int(value, _) => Literal::Int(if negative { -value } else { value }),
+            Tok::Float(value) => Literal::Float(if negative { -value } else { value }),
+            Tok::Char(value) if !negative => Literal::Char(value),
+            Tok::Str(value) if !negative => Literal::Str(value),
+            Tok::Ident(word) if !negative && (word == "true" || word == "false") => {
+                Literal::Bool(word == "true")
+            }
+            _ if negative => return Err(self.error("esperado um número")),
+            _ => return Ok(None),
+        };
+        self.next();
+        Ok(Some(literal))
+    }
+
+    fn pattern_no_alt(&mut self) -> Result<Pattern> {
+        if let Some(literal) = self.literal()? {
+            if self.is("..=") || self.is("...") || self.is("..") {
+                let inclusive = self.next() != Tok::Punct("..");
+                let end = self
+                    .literal()?
+                    .ok_or_else(|| self.unsupported("faixa aberta em padrão"))?;
+                return Ok(Pattern::Range(literal, end, inclusive));
+            }
+            return Ok(Pattern::Lit(literal));
+        }
+        if self.eat("&") || self.eat("&&") {
+            self.eat_keyword("mut");
+            return self.pattern_no_alt();
+        }
+        if self.eat("..") {
+            return Ok(Pattern::Rest);
+        }
+        if self.eat("(") {
+            let mut items = Vec::new();
+            while !self.eat(")") {
+                items.push(self.pattern()?);
+                if !self.eat(",") {
+                    self.expect(")")?;
+                    if items.len() == 1 {
+                        return Ok(items.pop().unwrap_or(Pattern::Wild));
+                    }
+                    break;
+                }
+            }
+            return Ok(Pattern::Tuple(items));
+        }
+        if self.eat("[") {
+            let mut items = Vec::new();
+            while !self.eat("]") {
+                items.push(self.pattern()?);
+                if !self.eat(",") {
+                    self.expect("]")?;
+                    break;
+                }
+            }
+            return Ok(Pattern::Slice(items));
+        }
+
+        let word = self.ident()?;
+        if word == "_" {
+            return Ok(Pattern::Wild);
+        }
+        if word == "ref" || word == "mut" {
+            self.eat_keyword("mut");
+            return self.binding();
+        }
+        let mut path = vec![word];
+        while self.eat("::") {
+            path.push(self.ident()?);
+        }
+        let last = path.last().cloned().unwrap_or_default();
+        let is_variant = path.len() > 1 || last.starts_with(char::is_uppercase);
+        if !is_variant {
+            self.index -= 1;
+            return self.binding();
+        }
+        match last.as_str() {
+            "None" | "Less" | "Equal" | "Greater" => Ok(Pattern::Variant(last, None)),
+            "Some" | "Ok" | "Err" => {
+                self.expect("(")?;
+                let inner = self.pattern()?;
+                self.eat(",");
+                self.expect(")")?;
+                Ok(Pattern::Variant(last, Some(Box::new(inner))))
+            }
+            _ => Err(self.unsupported(&format!("padrão `{}`", path.join("::")))),
+        }
+    }
+
+    fn binding(&mut self) -> Result<Pattern> {
+        let name = self.ident()?;
+        if self.eat("@") {
+            return Ok(Pattern::At(name, Box::new(self.pattern_no_alt()?)));
+        }
+        Ok(Pattern::Binding(name))
+    }
+}
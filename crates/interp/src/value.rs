@@ -0,0 +1,475 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::rc::Rc;
+
+use crate::ast::Closure;
+use crate::iter::{IterState, Source};
+
+/// Valor em tempo de execução. Todo inteiro vive num `i64`; `String` e
+/// `Vec` são compartilhados por referência, o que só se nota onde o Rust
+/// real também compartilharia (`&mut`), já que atribuição move o valor.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    /// Inteiro com o tipo, quando conhecido (sufixo, anotação, `as`,
+    /// `len()`); sem tipo, só o overflow de `i64` é detectado.
+    Int(i64, Option<IntTy>),
+    Float(f64),
+    Char(char),
+    Str(Rc<RefCell<String>>),
+    Vec(Rc<RefCell<Vec<Value>>>),
+    Tuple(Rc<Vec<Value>>),
+    /// `Some`, `None`, `Ok`, `Err` e as variantes de `Ordering`.
+    Variant(Variant, Option<Rc<Value>>),
+    /// Faixa semiaberta já normalizada (`a..=b` vira `a..b+1`).
+    Range(i64, i64, Option<IntTy>),
+    /// Iterador preguiçoso, compartilhado como no `it.by_ref()`.
+    Iter(Rc<RefCell<IterState>>),
+    Closure(Rc<Closure>, Capture),
+    Stdin,
+    /// Erro de `parse()` e afins: `{:?}` e `{}` como na biblioteca padrão.
+    Error {
+        debug: Rc<str>,
+        display: Rc<str>,
+    },
+}
+
+/// Onde a closure foi criada: chamada da função (`frame`), profundidade de
+/// escopo e quantos nomes o escopo tinha. Ao chamá-la, só esses nomes ficam
+/// visíveis, como na captura do Rust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capture {
+    pub frame: usize,
+    pub depth: usize,
+    pub count: usize,
+}
+
+/// Tipos inteiros. Os valores vivem num `i64`; o que passar disso em `u64`,
+/// `usize` e nos de 128 bits é recusado como não suportado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntTy {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+}
+
+impl IntTy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "i8" => IntTy::I8,
+            "i16" => IntTy::I16,
+            "i32" => IntTy::I32,
+            "i64" => IntTy::I64,
+            "i128" => IntTy::I128,
+            "isize" => IntTy::Isize,
+            "u8" => IntTy::U8,
+            "u16" => IntTy::U16,
+            "u32" => IntTy::U32,
+            "u64" => IntTy::U64,
+            "u128" => IntTy::U128,
+            "usize" => IntTy::Usize,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+            IntTy::I128 => "i128",
+            IntTy::Isize => "isize",
+            IntTy::U8 => "u8",
+            IntTy::U16 => "u16",
+            IntTy::U32 => "u32",
+            IntTy::U64 => "u64",
+            IntTy::U128 => "u128",
+            IntTy::Usize => "usize",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IntTy::I8 | IntTy::U8 => 8,
+            IntTy::I16 | IntTy::U16 => 16,
+            IntTy::I32 | IntTy::U32 => 32,
+            IntTy::I64 | IntTy::Isize | IntTy::U64 | IntTy::Usize => 64,
+            IntTy::I128 | IntTy::U128 => 128,
+        }
+    }
+
+    /// Tipo sem sinal de mesma largura (`i32::unsigned_abs` devolve `u32`).
+    pub fn unsigned(self) -> Self {
+        match self {
+            IntTy::I8 => IntTy::U8,
+            IntTy::I16 => IntTy::U16,
+            IntTy::I32 => IntTy::U32,
+            IntTy::I64 => IntTy::U64,
+            IntTy::I128 => IntTy::U128,
+            IntTy::Isize => IntTy::Usize,
+            unsigned => unsigned,
+        }
+    }
+
+    /// Faixa do tipo; a de `u128` para em `i128::MAX`.
+    pub fn bounds(self) -> (i128, i128) {
+        match self {
+            IntTy::I8 => (i8::MIN.into(), i8::MAX.into()),
+            IntTy::I16 => (i16::MIN.into(), i16::MAX.into()),
+            IntTy::I32 => (i32::MIN.into(), i32::MAX.into()),
+            IntTy::I64 | IntTy::Isize => (i64::MIN.into(), i64::MAX.into()),
+            IntTy::I128 => (i128::MIN, i128::MAX),
+            IntTy::U8 => (0, u8::MAX.into()),
+            IntTy::U16 => (0, u16::MAX.into()),
+            IntTy::U32 => (0, u32::MAX.into()),
+            IntTy::U64 | IntTy::Usize => (0, u64::MAX.into()),
+            IntTy::U128 => (0, i128::MAX),
+        }
+    }
+
+    pub fn contains(self, n: i128) -> bool {
+        let (min, max) = self.bounds();
+        (min..=max).contains(&n)
+    }
+
+    /// Trunca `n` para a largura do tipo, como `as`; `None` se o resultado
+    /// não couber no `i64` em que o interpretador guarda inteiros.
+    pub fn wrap(self, n: i128) -> Option<i64> {
+        let value = match self {
+            IntTy::I8 => (n as i8).into(),
+            IntTy::I16 => (n as i16).into(),
+            IntTy::I32 => (n as i32).into(),
+            IntTy::I64 | IntTy::Isize => (n as i64).into(),
+            IntTy::U8 => (n as u8).into(),
+            IntTy::U16 => (n as u16).into(),
+            IntTy::U32 => (n as u32).into(),
+            IntTy::U64 | IntTy::Usize => (n as u64).into(),
+            IntTy::I128 => n,
+            IntTy::U128 if n < 0 => return None,
+            IntTy::U128 => n,
+        };
+        i64::try_from(value).ok()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Some,
+    None,
+    Ok,
+    Err,
+    Less,
+    Equal,
+    Greater,
+}
+
+impl Variant {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Some" => Variant::Some,
+            "None" => Variant::None,
+            "Ok" => Variant::Ok,
+            "Err" => Variant::Err,
+            "Less" => Variant::Less,
+            "Equal" => Variant::Equal,
+            "Greater" => Variant::Greater,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Some => "Some",
+            Variant::None => "None",
+            Variant::Ok => "Ok",
+            Variant::Err => "Err",
+            Variant::Less => "Less",
+            Variant::Equal => "Equal",
+            Variant::Greater => "Greater",
+        }
+    }
+}
+
+impl Value {
+    /// Inteiro sem tipo conhecido.
+    pub fn int(n: i64) -> Self {
+        Value::Int(n, None)
+    }
+
+    pub fn usize(n: usize) -> Self {
+        Value::Int(n as i64, Some(IntTy::Usize))
+    }
+
+    pub fn str(text: impl Into<String>) -> Self {
+        Value::Str(Rc::new(RefCell::new(text.into())))
+    }
+
+    pub fn vec(items: Vec<Value>) -> Self {
+        Value::Vec(Rc::new(RefCell::new(items)))
+    }
+
+    pub fn iter(items: impl IntoIterator<Item = Value>) -> Self {
+        Value::from_source(Source::Items(items.into_iter().collect()))
+    }
+
+    pub fn from_source(source: Source) -> Self {
+        Value::Iter(Rc::new(RefCell::new(IterState::new(source))))
+    }
+
+    pub fn tuple(items: Vec<Value>) -> Self {
+        Value::Tuple(Rc::new(items))
+    }
+
+    pub fn some(value: Value) -> Self {
+        Value::Variant(Variant::Some, Some(Rc::new(value)))
+    }
+
+    pub fn none() -> Self {
+        Value::Variant(Variant::None, None)
+    }
+
+    pub fn ok(value: Value) -> Self {
+        Value::Variant(Variant::Ok, Some(Rc::new(value)))
+    }
+
+    pub fn err(value: Value) -> Self {
+        Value::Variant(Variant::Err, Some(Rc::new(value)))
+    }
+
+    /// `Err` com um erro da biblioteca padrão.
+    pub fn error(debug: impl Into<Rc<str>>, display: impl Into<Rc<str>>) -> Self {
+        Value::err(Value::Error {
+            debug: debug.into(),
+            display: display.into(),
+        })
+    }
+
+    pub fn option(value: Option<Value>) -> Self {
+        value.map_or_else(Value::none, Value::some)
+    }
+
+    pub fn ordering(ordering: Ordering) -> Self {
+        let variant = match ordering {
+            Ordering::Less => Variant::Less,
+            Ordering::Equal => Variant::Equal,
+            Ordering::Greater => Variant::Greater,
+        };
+        Value::Variant(variant, None)
+    }
+
+    /// Nome do tipo para mensagens.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Unit => "()",
+            Value::Bool(_) => "bool",
+            Value::Int(_, Some(ty)) => ty.name(),
+            Value::Int(_, None) => "inteiro",
+            Value::Float(_) => "f64",
+            Value::Char(_) => "char",
+            Value::Str(_) => "String",
+            Value::Vec(_) => "Vec",
+            Value::Tuple(_) => "tupla",
+            Value::Variant(Variant::Some | Variant::None, _) => "Option",
+            Value::Variant(Variant::Ok | Variant::Err, _) => "Result",
+            Value::Variant(_, _) => "Ordering",
+            Value::Range(..) => "Range",
+            Value::Iter(_) => "iterador",
+            Value::Closure(_, _) => "closure",
+            Value::Stdin => "Stdin",
+            Value::Error { .. } => "erro",
+        }
+    }
+
+    /// `.clone()`: cópia profunda de `String`, `Vec` e tuplas.
+    pub fn deep_clone(&self) -> Value {
+        match self {
+            Value::Str(text) => Value::str(text.borrow().clone()),
+            Value::Vec(items) => Value::vec(items.borrow().iter().map(Value::deep_clone).collect()),
+            Value::Tuple(items) => Value::tuple(items.iter().map(Value::deep_clone).collect()),
+            Value::Variant(variant, Some(inner)) => {
+                Value::Variant(*variant, Some(Rc::new(inner.deep_clone())))
+            }
+            Value::Iter(state) => Value::Iter(Rc::new(RefCell::new(state.borrow().deep_clone()))),
+            other => other.clone(),
+        }
+    }
+
+    pub fn equals(&self, other: &Value) -> bool {
+        self.compare(other) == Some(Ordering::Equal)
+    }
+
+    /// Ordem total onde o Rust tem `Ord`/`PartialOrd`; `None` para tipos
+    /// incomparáveis ou `NaN`.
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Unit, Value::Unit) => Some(Ordering::Equal),
+            (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+            (Value::Int(a, _), Value::Int(b, _)) => Some(a.cmp(b)),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::Int(a, _), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Int(b, _)) => a.partial_cmp(&(*b as f64)),
+            (Value::Char(a), Value::Char(b)) => Some(a.cmp(b)),
+            (Value::Str(a), Value::Str(b)) => Some(a.borrow().as_str().cmp(b.borrow().as_str())),
+            (Value::Vec(a), Value::Vec(b)) => compare_slices(&a.borrow(), &b.borrow()),
+            (Value::Tuple(a), Value::Tuple(b)) => compare_slices(a, b),
+            (Value::Variant(va, a), Value::Variant(vb, b)) => {
+                if va != vb {
+                    return Some(variant_rank(*va).cmp(&variant_rank(*vb)));
+                }
+                match (a, b) {
+                    (Some(a), Some(b)) => a.compare(b),
+                    _ => Some(Ordering::Equal),
+                }
+            }
+            (Value::Range(a0, a1, _), Value::Range(b0, b1, _)) => Some((a0, a1).cmp(&(b0, b1))),
+            _ => None,
+        }
+    }
+
+    /// `{}`.
+    pub fn display(&self) -> Option<String> {
+        Some(match self {
+            Value::Bool(b) => b.to_string(),
+            Value::Int(i, _) => i.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Char(c) => c.to_string(),
+            Value::Str(text) => text.borrow().clone(),
+            Value::Error { display, .. } => display.to_string(),
+            // `c.to_uppercase()` implementa Display
+            Value::Iter(state) => {
+                let items = state.borrow().pending_items()?;
+                items
+                    .iter()
+                    .map(|item| match item {
+                        Value::Char(c) => Some(*c),
+                        _ => None,
+                    })
+                    .collect::<Option<String>>()?
+            }
+            _ => return None,
+        })
+    }
+
+    /// `{:?}` (e `{:#?}` com `pretty`).
+    pub fn debug(&self, pretty: bool) -> String {
+        let mut out = String::new();
+        self.write_debug(&mut out, pretty, 0);
+        out
+    }
+
+    fn write_debug(&self, out: &mut String, pretty: bool, indent: usize) {
+        match self {
+            Value::Unit => out.push_str("()"),
+            Value::Float(f) => {
+                let _ = write!(out, "{f:?}");
+            }
+            Value::Char(c) => {
+                let _ = write!(out, "{c:?}");
+            }
+            Value::Str(text) => {
+                let _ = write!(out, "{:?}", text.borrow());
+            }
+            Value::Vec(items) => write_seq(out, "[", "]", &items.borrow(), pretty, indent),
+            Value::Iter(state) => match state.borrow().pending_items() {
+                Some(items) => {
+                    out.push_str("Iter(");
+                    write_seq(out, "[", "]", &items, pretty, indent);
+                    out.push(')');
+                }
+                None => out.push_str("Iter { .. }"),
+            },
+            Value::Tuple(items) => {
+                write_seq(out, "(", ")", items, pretty, indent);
+                if items.len() == 1 && !pretty {
+                    out.insert(out.len() - 1, ',');
+                }
+            }
+            Value::Variant(variant, None) => out.push_str(variant.name()),
+            Value::Variant(variant, Some(inner)) => {
+                out.push_str(variant.name());
+                write_seq(
+                    out,
+                    "(",
+                    ")",
+                    std::slice::from_ref(inner.as_ref()),
+                    pretty,
+                    indent,
+                );
+            }
+            Value::Range(start, end, _) => {
+                let _ = write!(out, "{start}..{end}");
+            }
+            Value::Closure(_, _) => out.push_str("<closure>"),
+            Value::Stdin => out.push_str("Stdin { .. }"),
+            Value::Error { debug, .. } => out.push_str(debug),
+            other => out.push_str(&other.display().unwrap_or_default()),
+        }
+    }
+}
+
+fn compare_slices(a: &[Value], b: &[Value]) -> Option<Ordering> {
+    for (x, y) in a.iter().zip(b) {
+        match x.compare(y)? {
+            Ordering::Equal => {}
+            ordering => return Some(ordering),
+        }
+    }
+    Some(a.len().cmp(&b.len()))
+}
+
+fn variant_rank(variant: Variant) -> u8 {
+    match variant {
+        Variant::None | Variant::Ok | Variant::Less => 0,
+        Variant::Some | Variant::Err | Variant::Equal => 1,
+        Variant::Greater => 2,
+    }
+}
+
+fn write_seq(
+    out: &mut String,
+    open: &str,
+    close: &str,
+    items: &[Value],
+    pretty: bool,
+    indent: usize,
+) {
+    out.push_str(open);
+    if pretty && !items.is_empty() {
+        out.push('\n');
+        for item in items {
+            out.push_str(&"    ".repeat(indent + 1));
+            item.write_debug(out, pretty, indent + 1);
+            out.push_str(",\n");
+        }
+        out.push_str(&"    ".repeat(indent));
+    } else {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            item.write_debug(out, pretty, indent);
+        }
+    }
+    out.push_str(close);
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.display() {
+            Some(text) => f.write_str(&text),
+            None => f.write_str(&self.debug(false)),
+        }
+    }
+}
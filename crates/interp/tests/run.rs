@@ -0,0 +1,192 @@
+use themelion_interp::{Config, Error, Exit, Outcome};
+
+fn run(source: &str) -> Outcome {
+    run_with(source, &Config::DEFAULT)
+}
+
+fn run_with(source: &str, config: &Config) -> Outcome {
+    themelion_interp::run(source, "", config).expect("programa válido")
+}
+
+fn panic_message(outcome: &Outcome) -> &str {
+    match &outcome.exit {
+        Exit::Panicked { message, .. } => message,
+        other => panic!("esperava pânico, saiu {other:?}"),
+    }
+}
+
+#[test]
+fn runs_a_program() {
+    let outcome = run(r#"
+        fn main() {
+            let v: Vec<i32> = (1..=4).map(|x| x * x).collect();
+            println!("{:?} {}", v, v.iter().sum::<i32>());
+        }
+    "#);
+    assert_eq!(outcome.exit, Exit::Success);
+    assert_eq!(outcome.stdout, "[1, 4, 9, 16] 30\n");
+}
+
+#[test]
+fn panics_on_overflow_of_the_known_type() {
+    let outcome = run(r#"
+        fn main() {
+            let x: u8 = 255;
+            println!("{}", x + 1);
+        }
+    "#);
+    assert_eq!(panic_message(&outcome), "attempt to add with overflow");
+    assert_eq!(outcome.exit.code(), Some(101));
+
+    let outcome = run(r#"
+        fn main() {
+            let x: i32 = i32::MAX;
+            println!("{}", x * 2);
+        }
+    "#);
+    assert_eq!(panic_message(&outcome), "attempt to multiply with overflow");
+
+    let outcome = run(r#"
+        fn main() {
+            let x: usize = 0;
+            println!("{}", x - 1);
+        }
+    "#);
+    assert_eq!(panic_message(&outcome), "attempt to subtract with overflow");
+}
+
+#[test]
+fn wrapping_and_checked_arithmetic_do_not_panic() {
+    let outcome = run(r#"
+        fn main() {
+            let x: u8 = 250;
+            println!("{} {:?} {:?}", x.wrapping_add(10), x.checked_add(10), x.checked_add(5));
+        }
+    "#);
+    assert_eq!(outcome.exit, Exit::Success);
+    assert_eq!(outcome.stdout, "4 None Some(255)\n");
+}
+
+#[test]
+fn slices_vecs_and_strings() {
+    let outcome = run(r#"
+        fn main() {
+            let v = vec![1, 2, 3, 4, 5];
+            let s = "olá mundo";
+            println!("{:?} {:?} {}", &v[1..3], &v[..2], &s[5..]);
+        }
+    "#);
+    assert_eq!(outcome.exit, Exit::Success);
+    assert_eq!(outcome.stdout, "[2, 3] [1, 2] mundo\n");
+}
+
+#[test]
+fn panics_on_out_of_bounds_slices() {
+    let outcome = run(r#"
+        fn main() {
+            let v = vec![1, 2, 3];
+            println!("{:?}", &v[1..5]);
+        }
+    "#);
+    assert_eq!(
+        panic_message(&outcome),
+        "range end index 5 out of range for slice of length 3"
+    );
+
+    let outcome = run(r#"
+        fn main() {
+            let s = "olá";
+            println!("{}", &s[..3]);
+        }
+    "#);
+    assert!(
+        panic_message(&outcome).starts_with("byte index 3 is not a char boundary"),
+        "{:?}",
+        outcome.exit
+    );
+}
+
+#[test]
+fn stops_at_the_step_limit() {
+    let config = Config {
+        max_steps: 10_000,
+        ..Config::DEFAULT
+    };
+    let outcome = run_with("fn main() { loop {} }", &config);
+    assert_eq!(outcome.exit, Exit::StepLimit { limit: 10_000 });
+}
+
+#[test]
+fn stops_at_the_depth_limit() {
+    // Abaixo do padrão: sem otimização, cada chamada interpretada usa bem
+    // mais pilha nativa que no build de release
+    let config = Config {
+        max_depth: 50,
+        ..Config::DEFAULT
+    };
+    let outcome = run_with(
+        r#"
+        fn f(n: u64) -> u64 { f(n + 1) }
+        fn main() { f(0); }
+    "#,
+        &config,
+    );
+    assert_eq!(outcome.exit, Exit::StackOverflow { limit_depth: 50 });
+}
+
+#[test]
+fn stops_at_the_memory_limit() {
+    let limit = Exit::MemoryLimit {
+        limit_elements: Config::DEFAULT.max_elements,
+    };
+
+    let vec = run("fn main() { let v = vec![0u8; 1_000_000_000]; }");
+    assert_eq!(vec.exit, limit);
+
+    let concat = run(r#"
+        fn main() {
+            let mut s = String::from("ab");
+            for _ in 0..40 {
+                s = s.clone() + &s;
+            }
+        }
+    "#);
+    assert_eq!(concat.exit, limit);
+
+    let push = run(r#"
+        fn main() {
+            let mut s = String::from("ab");
+            for _ in 0..40 {
+                let t = s.clone();
+                s.push_str(&t);
+            }
+        }
+    "#);
+    assert_eq!(push.exit, limit);
+
+    let padding = run(r#"fn main() { println!("{:>1000000000}", 1); }"#);
+    assert_eq!(padding.exit, limit);
+}
+
+#[test]
+fn rejects_what_it_does_not_support() {
+    let error = themelion_interp::run(
+        "struct Ponto { x: i32 }\nfn main() {}",
+        "",
+        &Config::DEFAULT,
+    )
+    .unwrap_err();
+    assert!(matches!(error, Error::Unsupported { .. }), "{error:?}");
+
+    let outcome = run(r#"
+        fn main() {
+            let f = main;
+        }
+    "#);
+    assert!(
+        matches!(outcome.exit, Exit::Unsupported { .. }),
+        "{:?}",
+        outcome.exit
+    );
+    assert!(outcome.exit.rejected());
+}
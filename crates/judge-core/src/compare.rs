@@ -0,0 +1,101 @@
+//! Comparação da saída do programa com a esperada e diff por linha.
+
+use serde::{Deserialize, Serialize};
+
+/// Como a saída é comparada com a esperada.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compare {
+    /// Byte a byte.
+    Exact,
+    /// Ignora espaços no fim de cada linha e linhas vazias no fim da saída.
+    #[default]
+    Lines,
+    /// Compara só a sequência de tokens separados por espaço em branco.
+    Tokens,
+}
+
+impl Compare {
+    pub fn matches(self, expected: &str, actual: &str) -> bool {
+        match self {
+            Compare::Exact => expected == actual,
+            Compare::Lines => normalized_lines(expected) == normalized_lines(actual),
+            Compare::Tokens => expected.split_whitespace().eq(actual.split_whitespace()),
+        }
+    }
+}
+
+fn normalized_lines(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Acima disso o diff vira custo quadrático demais; o resto é cortado.
+const MAX_DIFF_LINES: usize = 500;
+
+/// Linhas iguais mantidas em volta de cada trecho diferente.
+const CONTEXT: usize = 2;
+
+/// Diff de linhas (LCS) no estilo unificado: `  ` igual, `- ` esperado, `+ ` obtido.
+/// Trechos iguais longos viram `  …`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().take(MAX_DIFF_LINES).collect();
+    let actual: Vec<&str> = actual.lines().take(MAX_DIFF_LINES).collect();
+    let (n, m) = (expected.len(), actual.len());
+
+    // lcs[i][j] = tamanho da maior subsequência comum de expected[i..] e actual[j..]
+    let mut lcs = vec![vec![0u16; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            ops.push(('=', expected[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', expected[i]));
+            i += 1;
+        } else {
+            ops.push(('+', actual[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != '=').collect();
+    let near_change = |k: usize| changed.iter().any(|&c| c.abs_diff(k) <= CONTEXT);
+
+    let mut out = String::new();
+    let mut skipping = false;
+    for (k, (op, line)) in ops.iter().enumerate() {
+        if *op == '=' && !near_change(k) {
+            if !skipping {
+                out.push_str("  …\n");
+                skipping = true;
+            }
+            continue;
+        }
+        skipping = false;
+        let prefix = match op {
+            '-' => "- ",
+            '+' => "+ ",
+            _ => "  ",
+        };
+        out.push_str(prefix);
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
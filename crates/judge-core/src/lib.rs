@@ -0,0 +1,13 @@
+//! Núcleo do juiz do Modo Prática, sem processos nem sistema de arquivos.
+//!
+//! Comparação de saída ([`compare`]), avaliação de cada teste e placar da
+//! submissão ([`score`]) ficam aqui para que o mesmo código julgue no
+//! servidor (`themelion-judge`, via runner) e no navegador
+//! (`themelion-wasm`, via interpretador). Quem executa o código só precisa
+//! devolver uma [`Execution`] por teste.
+
+pub mod compare;
+mod score;
+
+pub use compare::Compare;
+pub use score::{Execution, Status, Submission, Termination, TestCase, TestReport, score};
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+
+use crate::compare::{Compare, diff};
+
+/// Um caso de teste: entrada no stdin e saída esperada no stdout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestCase {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub input: String,
+    pub output: String,
+}
+
+impl TestCase {
+    /// Nome para exibição: o declarado ou `teste <n>` (1-based).
+    pub fn display_name(&self, index: usize) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("teste {}", index + 1))
+    }
+}
+
+/// Veredito de um teste, com as siglas usuais de juízes online.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    #[serde(rename = "AC")]
+    Accepted,
+    #[serde(rename = "WA")]
+    WrongAnswer,
+    #[serde(rename = "TLE")]
+    TimeLimitExceeded,
+    #[serde(rename = "RE")]
+    RuntimeError,
+    #[serde(rename = "CE")]
+    CompileError,
+}
+
+impl Status {
+    pub fn code(self) -> &'static str {
+        match self {
+            Status::Accepted => "AC",
+            Status::WrongAnswer => "WA",
+            Status::TimeLimitExceeded => "TLE",
+            Status::RuntimeError => "RE",
+            Status::CompileError => "CE",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Accepted => "aceito",
+            Status::WrongAnswer => "resposta errada",
+            Status::TimeLimitExceeded => "tempo limite excedido",
+            Status::RuntimeError => "erro de execução",
+            Status::CompileError => "erro de compilação",
+        }
+    }
+}
+
+/// Como a execução de um teste terminou, com a mensagem para o aluno.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum Termination {
+    Success,
+    TimeLimit(String),
+    Error(String),
+}
+
+/// O que a execução de um teste produziu, venha do runner ou do interpretador.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Execution {
+    pub stdout: String,
+    #[serde(default)]
+    pub stderr: String,
+    #[serde(default)]
+    pub time_ms: u64,
+    pub termination: Termination,
+}
+
+/// Resultado de um teste. Entrada, saídas e diff só aparecem nos exemplos;
+/// nos testes ocultos o aluno vê apenas o veredito.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestReport {
+    pub name: String,
+    pub hidden: bool,
+    pub status: Status,
+    pub time_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+impl TestReport {
+    /// Avalia a execução de `case` contra a saída esperada.
+    pub fn evaluate(
+        case: &TestCase,
+        index: usize,
+        hidden: bool,
+        compare: Compare,
+        execution: Execution,
+    ) -> Self {
+        let (status, message) = match execution.termination {
+            Termination::TimeLimit(message) => (Status::TimeLimitExceeded, Some(message)),
+            Termination::Error(message) => (Status::RuntimeError, Some(message)),
+            Termination::Success if compare.matches(&case.output, &execution.stdout) => {
+                (Status::Accepted, None)
+            }
+            Termination::Success => (Status::WrongAnswer, None),
+        };
+
+        let mut report = TestReport {
+            name: case.display_name(index),
+            hidden,
+            status,
+            time_ms: execution.time_ms,
+            message,
+            input: None,
+            expected: None,
+            actual: None,
+            stderr: None,
+            diff: None,
+        };
+        if !hidden && status != Status::Accepted {
+            if status == Status::WrongAnswer {
+                report.diff = Some(diff(&case.output, &execution.stdout));
+            }
+            report.input = Some(case.input.clone());
+            report.expected = Some(case.output.clone());
+            report.actual = Some(execution.stdout);
+            if !execution.stderr.is_empty() {
+                report.stderr = Some(execution.stderr);
+            }
+        }
+        report
+    }
+
+    fn not_run(case: &TestCase, index: usize, hidden: bool, status: Status) -> Self {
+        TestReport {
+            name: case.display_name(index),
+            hidden,
+            status,
+            time_ms: 0,
+            message: None,
+            input: None,
+            expected: None,
+            actual: None,
+            stderr: None,
+            diff: None,
+        }
+    }
+}
+
+/// Resultado de uma submissão: o primeiro veredito diferente de AC decide.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Submission {
+    pub challenge: String,
+    pub language: String,
+    pub status: Status,
+    pub passed: usize,
+    pub total: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_output: Option<String>,
+    pub tests: Vec<TestReport>,
+}
+
+impl Submission {
+    pub fn accepted(&self) -> bool {
+        self.status == Status::Accepted
+    }
+
+    /// Submissão que não compilou: todo teste recebe CE.
+    pub fn compile_error<'a>(
+        challenge: &str,
+        language: &str,
+        cases: impl IntoIterator<Item = (&'a TestCase, bool)>,
+        compiler_output: String,
+    ) -> Self {
+        let tests: Vec<TestReport> = cases
+            .into_iter()
+            .enumerate()
+            .map(|(index, (case, hidden))| {
+                TestReport::not_run(case, index, hidden, Status::CompileError)
+            })
+            .collect();
+        Submission {
+            challenge: challenge.to_string(),
+            language: language.to_string(),
+            status: Status::CompileError,
+            passed: 0,
+            total: tests.len(),
+            compiler_output: Some(compiler_output),
+            tests,
+        }
+    }
+}
+
+/// Executa cada caso (`hidden` indica teste oculto) e monta a submissão.
+pub fn score<'a, E>(
+    challenge: &str,
+    language: &str,
+    compare: Compare,
+    cases: impl IntoIterator<Item = (&'a TestCase, bool)>,
+    mut execute: impl FnMut(&TestCase) -> Result<Execution, E>,
+) -> Result<Submission, E> {
+    let mut submission = Submission {
+        challenge: challenge.to_string(),
+        language: language.to_string(),
+        status: Status::Accepted,
+        passed: 0,
+        total: 0,
+        compiler_output: None,
+        tests: Vec::new(),
+    };
+    for (index, (case, hidden)) in cases.into_iter().enumerate() {
+        let report = TestReport::evaluate(case, index, hidden, compare, execute(case)?);
+        if report.status == Status::Accepted {
+            submission.passed += 1;
+        } else if submission.status == Status::Accepted {
+            submission.status = report.status;
+        }
+        submission.total += 1;
+        submission.tests.push(report);
+    }
+    Ok(submission)
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use themelion_content::model::Difficulty;
+use themelion_content::{find_topic_files, relative_path};
+use themelion_judge_core::{Compare, TestCase};
+use themelion_runner::RunLimits;
+
+use crate::{CHALLENGES_DIR, Error, Result};
+
+/// Formato de `challenges/<nome>.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChallengeFile {
+    title: String,
+    statement: String,
+    difficulty: Option<Difficulty>,
+    #[serde(default)]
+    compare: Compare,
+    time_limit_ms: Option<u64>,
+    memory_mb: Option<u64>,
+    /// Código inicial por `id` de linguagem (`data/languages/*.json`).
+    #[serde(default)]
+    starter: BTreeMap<String, String>,
+    /// Soluções de referência, usadas por `verify` para validar os testes.
+    #[serde(default)]
+    solution: BTreeMap<String, String>,
+    #[serde(default)]
+    examples: Vec<TestCase>,
+    #[serde(default)]
+    tests: Vec<TestCase>,
+}
+
+/// Desafio carregado, com a origem.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    /// `<slug do tópico>/<nome do arquivo sem .toml>`.
+    pub id: String,
+    pub topic: String,
+    /// Caminho relativo à raiz, com `/`.
+    pub file: String,
+    pub title: String,
+    pub statement: String,
+    pub difficulty: Option<Difficulty>,
+    pub compare: Compare,
+    pub time_limit_ms: Option<u64>,
+    pub memory_mb: Option<u64>,
+    pub starter: BTreeMap<String, String>,
+    pub solution: BTreeMap<String, String>,
+    /// Mostrados no enunciado e julgados antes dos ocultos.
+    pub examples: Vec<TestCase>,
+    /// Ocultos: o aluno só vê o veredito.
+    pub tests: Vec<TestCase>,
+}
+
+/// O que pode ir para o navegador: sem testes ocultos nem soluções.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicChallenge<'a> {
+    pub id: &'a str,
+    pub topic: &'a str,
+    pub title: &'a str,
+    pub statement: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<Difficulty>,
+    pub starter: &'a BTreeMap<String, String>,
+    pub examples: &'a [TestCase],
+    pub hidden_tests: usize,
+}
+
+impl Challenge {
+    pub fn parse(topic: &str, file: &str, source: &str) -> Result<Self> {
+        let invalid = |message: String| Error::Challenge {
+            file: file.to_string(),
+            message,
+        };
+        let raw: ChallengeFile = toml::from_str(source).map_err(|err| invalid(err.to_string()))?;
+        if raw.examples.is_empty() && raw.tests.is_empty() {
+            return Err(invalid(
+                "nenhum caso de teste em examples ou tests".to_string(),
+            ));
+        }
+        if raw.starter.is_empty() {
+            return Err(invalid("sem código inicial em [starter]".to_string()));
+        }
+
+        let name = Path::new(file)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Self {
+            id: format!("{topic}/{name}"),
+            topic: topic.to_string(),
+            file: file.to_string(),
+            title: raw.title,
+            statement: raw.statement,
+            difficulty: raw.difficulty,
+            compare: raw.compare,
+            time_limit_ms: raw.time_limit_ms,
+            memory_mb: raw.memory_mb,
+            starter: raw.starter,
+            solution: raw.solution,
+            examples: raw.examples,
+            tests: raw.tests,
+        })
+    }
+
+    /// Exemplos e testes ocultos, na ordem de julgamento.
+    pub fn cases(&self) -> impl Iterator<Item = (&TestCase, bool)> {
+        self.examples
+            .iter()
+            .map(|case| (case, false))
+            .chain(self.tests.iter().map(|case| (case, true)))
+    }
+
+    /// Limites do runner com os ajustes do desafio na fase de execução.
+    pub fn limits(&self, base: RunLimits) -> RunLimits {
+        let mut limits = base;
+        if let Some(ms) = self.time_limit_ms {
+            limits.execute.wall_time_ms = ms;
+            limits.execute.cpu_seconds = ms.div_ceil(1000).max(1);
+        }
+        if let Some(mb) = self.memory_mb {
+            limits.execute.memory_bytes = mb * 1024 * 1024;
+        }
+        limits
+    }
+
+    pub fn public(&self) -> PublicChallenge<'_> {
+        PublicChallenge {
+            id: &self.id,
+            topic: &self.topic,
+            title: &self.title,
+            statement: &self.statement,
+            difficulty: self.difficulty,
+            starter: &self.starter,
+            examples: &self.examples,
+            hidden_tests: self.tests.len(),
+        }
+    }
+}
+
+/// Todos os `challenges/*.toml` sob `data/content`, na ordem dos tópicos.
+pub fn find_challenges(root: &Path) -> Result<Vec<Challenge>> {
+    let mut challenges = Vec::new();
+    for topic_file in find_topic_files(root)? {
+        let dir = topic_file.with_file_name(CHALLENGES_DIR);
+        if !dir.is_dir() {
+            continue;
+        }
+        let topic = topic_file
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+            .map_err(|source| io_error(&dir, source))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let source = fs::read_to_string(&path).map_err(|source| io_error(&path, source))?;
+            challenges.push(Challenge::parse(
+                &topic,
+                &relative_path(root, &path),
+                &source,
+            )?);
+        }
+    }
+    Ok(challenges)
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> Error {
+    Error::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
@@ -0,0 +1,21 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Content(#[from] themelion_content::Error),
+
+    #[error(transparent)]
+    Runner(#[from] themelion_runner::Error),
+
+    #[error("Erro ao acessar {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("{file}: desafio inválido: {message}")]
+    Challenge { file: String, message: String },
+}
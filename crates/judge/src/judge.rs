@@ -0,0 +1,64 @@
+use std::fs;
+
+use themelion_judge_core::{Execution, Submission, Termination, score};
+use themelion_runner::{RunRequest, Runner, validate};
+
+use crate::Result;
+use crate::challenge::Challenge;
+
+/// Compila `source` uma vez e executa cada teste de `challenge`.
+pub fn judge(
+    runner: &dyn Runner,
+    challenge: &Challenge,
+    language: &str,
+    source: &str,
+) -> Result<Submission> {
+    validate(&RunRequest {
+        language_id: language.to_string(),
+        topic: challenge.topic.clone(),
+        source: source.to_string(),
+        stdin: String::new(),
+    })?;
+    let limits = challenge.limits(runner.limits());
+
+    let workdir = tempfile::Builder::new()
+        .prefix(&format!("{}-{}-", runner.name(), challenge.topic))
+        .tempdir()
+        .map_err(themelion_runner::Error::from)?;
+    fs::write(workdir.path().join(runner.source_file()), source)
+        .map_err(themelion_runner::Error::from)?;
+
+    if let Some(compilation) = runner.compile(workdir.path(), &limits.compile)?
+        && !compilation.success()
+    {
+        return Ok(Submission::compile_error(
+            &challenge.id,
+            language,
+            challenge.cases(),
+            compilation.rendered,
+        ));
+    }
+
+    score(
+        &challenge.id,
+        language,
+        challenge.compare,
+        challenge.cases(),
+        |case| {
+            let output = runner.execute(workdir.path(), case.input.as_bytes(), &limits.execute)?;
+            let termination = if output.verdict.timed_out() {
+                Termination::TimeLimit(output.verdict.to_string())
+            } else if output.verdict.success() {
+                Termination::Success
+            } else {
+                Termination::Error(output.verdict.to_string())
+            };
+            Ok(Execution {
+                stdout: output.stdout,
+                stderr: output.stderr,
+                time_ms: output.duration.as_millis() as u64,
+                termination,
+            })
+        },
+    )
+}
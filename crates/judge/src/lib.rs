@@ -0,0 +1,23 @@
+//! Juiz do Modo Prática.
+//!
+//! Cada tópico pode ter desafios em `challenges/*.toml`, ao lado de
+//! `topic.mdx` e `examples/`: enunciado, código inicial por linguagem,
+//! exemplos visíveis e testes ocultos. [`judge`] compila a submissão uma vez
+//! pelo [`Runner`](themelion_runner::Runner) da linguagem, executa cada teste
+//! com os limites da fase de execução e devolve um veredito por teste
+//! (AC/WA/TLE/RE/CE), com diff quando a saída não bate. A avaliação em si
+//! fica em `themelion-judge-core`, compartilhada com o juiz no navegador.
+
+mod challenge;
+mod error;
+mod judge;
+
+pub use challenge::{Challenge, PublicChallenge, find_challenges};
+pub use error::Error;
+pub use judge::judge;
+pub use themelion_judge_core::{Compare, Status, Submission, TestCase, TestReport, compare};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Diretório de desafios dentro da pasta do tópico.
+pub const CHALLENGES_DIR: &str = "challenges";
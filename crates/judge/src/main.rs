@@ -0,0 +1,235 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use themelion_judge::{Challenge, Status, Submission, find_challenges, judge};
+use themelion_runner::rust::RustRunner;
+use themelion_runner::{Limits, Registry};
+use themelion_sandbox::Sandbox;
+
+/// Julga submissões contra os desafios do Modo Prática.
+#[derive(Parser)]
+#[command(name = "themelion-judge", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".", global = true)]
+    root: PathBuf,
+
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+
+    /// Executa as submissões isoladas: namespaces, seccomp e rlimits (Linux)
+    #[arg(long, global = true)]
+    sandbox: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Lista os desafios (sem testes ocultos nem soluções em --format json)
+    List,
+    /// Julga um arquivo contra um desafio; falha se não for aceito
+    Submit {
+        /// `<slug do tópico>/<desafio>`, ex.: arrays/soma-e-maximo
+        challenge: String,
+        file: PathBuf,
+        /// Id da linguagem (padrão: inferido pela extensão do arquivo)
+        #[arg(long)]
+        language: Option<String>,
+    },
+    /// Julga as soluções de referência de cada desafio; falha se alguma
+    /// não for aceita ou se o desafio citar linguagem desconhecida
+    Verify {
+        /// Só estes desafios; por padrão, todos
+        challenges: Vec<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    accepted: usize,
+    failed: usize,
+    skipped: &'a [String],
+    submissions: &'a [Submission],
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let languages = cli.root.join("data/languages");
+    let mut registry = Registry::load(&languages)
+        .with_context(|| format!("Erro ao carregar {}", languages.display()))?;
+    match RustRunner::detect() {
+        Ok(runner) if cli.sandbox => {
+            registry.register(runner.with_sandbox(Sandbox::new(Limits::EXECUTE.into())))
+        }
+        Ok(runner) => registry.register(runner),
+        Err(err) => eprintln!("Aviso: {} desativado — {err}", RustRunner::NAME),
+    }
+
+    let challenges = find_challenges(&cli.root).context("Erro ao procurar os desafios")?;
+
+    match cli.command {
+        Command::List => print_list(cli.format, &challenges),
+        Command::Submit {
+            challenge,
+            file,
+            language,
+        } => {
+            let challenge = select(&challenges, std::slice::from_ref(&challenge))?[0];
+            let language = match language {
+                Some(id) => registry
+                    .language(&id)
+                    .with_context(|| format!("Linguagem \"{id}\" não existe em data/languages"))?,
+                None => registry.language_for_path(&file).with_context(|| {
+                    format!("Nenhuma linguagem usa a extensão de {}", file.display())
+                })?,
+            };
+            let source = fs::read_to_string(&file)
+                .with_context(|| format!("Erro ao ler {}", file.display()))?;
+            let runner = registry.runner_for(&language.id)?;
+            let submission = judge(runner, challenge, &language.id, &source)?;
+            print_submissions(cli.format, &[submission], &[])
+        }
+        Command::Verify { challenges: ids } => {
+            let mut submissions = Vec::new();
+            let mut skipped = Vec::new();
+            for challenge in select(&challenges, &ids)? {
+                for id in challenge.starter.keys().chain(challenge.solution.keys()) {
+                    if registry.language(id).is_none() {
+                        bail!(
+                            "{}: linguagem \"{id}\" não existe em data/languages",
+                            challenge.file
+                        );
+                    }
+                }
+                if challenge.solution.is_empty() {
+                    skipped.push(format!("{}: sem [solution]", challenge.id));
+                }
+                for (language, source) in &challenge.solution {
+                    let Ok(runner) = registry.runner_for(language) else {
+                        skipped.push(format!("{} ({language}): sem runner ativo", challenge.id));
+                        continue;
+                    };
+                    if matches!(cli.format, Format::Text) {
+                        eprintln!("{} ({language}) ...", challenge.id);
+                    }
+                    submissions.push(judge(runner, challenge, language, source)?);
+                }
+            }
+            print_submissions(cli.format, &submissions, &skipped)
+        }
+    }
+}
+
+fn select<'a>(challenges: &'a [Challenge], ids: &[String]) -> Result<Vec<&'a Challenge>> {
+    for id in ids {
+        if !challenges.iter().any(|c| &c.id == id) {
+            bail!("Desafio \"{id}\" não encontrado");
+        }
+    }
+    Ok(challenges
+        .iter()
+        .filter(|c| ids.is_empty() || ids.contains(&c.id))
+        .collect())
+}
+
+fn print_list(format: Format, challenges: &[Challenge]) -> Result<ExitCode> {
+    match format {
+        Format::Text => {
+            for challenge in challenges {
+                let languages: Vec<&str> = challenge.starter.keys().map(String::as_str).collect();
+                println!(
+                    "{:<32}{} [{}] ({} exemplos, {} ocultos)",
+                    challenge.id,
+                    challenge.title,
+                    languages.join(", "),
+                    challenge.examples.len(),
+                    challenge.tests.len()
+                );
+            }
+            eprintln!("{} desafios", challenges.len());
+        }
+        Format::Json => {
+            let public: Vec<_> = challenges.iter().map(Challenge::public).collect();
+            println!("{}", serde_json::to_string_pretty(&public)?);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_submissions(
+    format: Format,
+    submissions: &[Submission],
+    skipped: &[String],
+) -> Result<ExitCode> {
+    let accepted = submissions.iter().filter(|s| s.accepted()).count();
+    let failed = submissions.len() - accepted;
+
+    match format {
+        Format::Text => {
+            for submission in submissions {
+                println!(
+                    "{:<4}{} ({}): {}/{} testes",
+                    submission.status.code(),
+                    submission.challenge,
+                    submission.language,
+                    submission.passed,
+                    submission.total
+                );
+                if let Some(output) = &submission.compiler_output {
+                    for line in output.lines() {
+                        println!("    {line}");
+                    }
+                    continue;
+                }
+                for test in &submission.tests {
+                    if test.status == Status::Accepted {
+                        continue;
+                    }
+                    let hidden = if test.hidden { " (oculto)" } else { "" };
+                    println!(
+                        "  {:<4}{}{hidden}: {}",
+                        test.status.code(),
+                        test.name,
+                        test.message.as_deref().unwrap_or(test.status.label())
+                    );
+                    if let Some(diff) = &test.diff {
+                        for line in diff.lines() {
+                            println!("      {line}");
+                        }
+                    }
+                }
+            }
+            for note in skipped {
+                eprintln!("ignorado: {note}");
+            }
+            eprintln!("{accepted} aceitas, {failed} falharam");
+        }
+        Format::Json => {
+            let summary = Summary {
+                accepted,
+                failed,
+                skipped,
+                submissions,
+            };
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+    }
+
+    Ok(if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Um problema apontado em `arquivo:linha`. O `code` identifica a regra.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: usize,
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn error(file: &str, line: usize, code: &'static str, message: String) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            severity: Severity::Error,
+            code,
+            message,
+        }
+    }
+
+    pub fn warning(file: &str, line: usize, code: &'static str, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(file, line, code, message)
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Error => "erro",
+            Severity::Warning => "aviso",
+        };
+        write!(
+            f,
+            "{}:{}: {label}[{}]: {}",
+            self.file, self.line, self.code, self.message
+        )
+    }
+}
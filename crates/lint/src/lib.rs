@@ -0,0 +1,302 @@
+//! Regras do `themelion-lint`: o frontmatter de cada `topic.mdx` precisa bater
+//! com a entrada do tópico em `data/registry/rooms/<sala>.yaml`, o arquivo
+//! precisa estar em `<sala>/<categoria>/<slug>/` e todo pré-requisito precisa
+//! existir no registry. Nas salas com esquema em `data/schemas/`, o corpo
+//! também precisa seguir a estrutura de seções que o agente de escrita
+//! prescreve ([`structure`]).
+
+mod diagnostic;
+pub mod structure;
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use themelion_content::frontmatter::{self, Document};
+use themelion_content::model::{TopicFrontmatter, TopicSummary};
+use themelion_content::registry::{RoomFile, RoomLines};
+use themelion_content::{Error, Result, find_topic_files, load_rooms, relative_path};
+
+pub use diagnostic::{Diagnostic, Severity};
+use structure::Schema;
+
+/// Roda todas as regras sobre o repositório em `root`.
+pub fn lint(root: &Path) -> Result<Vec<Diagnostic>> {
+    let registry = Registry::load(root)?;
+    let mut diagnostics = Vec::new();
+    let mut seen: HashMap<String, String> = HashMap::new();
+
+    for file in find_topic_files(root)? {
+        let raw = fs::read_to_string(&file).map_err(|source| Error::Io {
+            path: file.clone(),
+            source,
+        })?;
+        let rel = relative_path(root, &file);
+        lint_topic(&rel, &raw, &registry, &mut seen, &mut diagnostics);
+    }
+
+    diagnostics.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    Ok(diagnostics)
+}
+
+struct RoomEntry {
+    file: String,
+    data: RoomFile,
+    lines: RoomLines,
+}
+
+impl RoomEntry {
+    fn find(&self, slug: &str) -> Option<(&str, &TopicSummary)> {
+        self.data.categories.iter().find_map(|category| {
+            category
+                .topics
+                .iter()
+                .find(|t| t.slug == slug)
+                .map(|t| (category.slug.as_str(), t))
+        })
+    }
+
+    /// `data/registry/rooms/<sala>.yaml:<linha>` do campo do tópico.
+    fn location(&self, slug: &str, field: &str) -> String {
+        let line = self.lines.topic(slug).map_or(1, |t| t.field(field));
+        format!("{}:{line}", self.file)
+    }
+}
+
+struct Registry {
+    rooms: HashMap<String, RoomEntry>,
+    /// slug → salas onde aparece (um slug pode se repetir entre salas).
+    slugs: HashMap<String, Vec<String>>,
+    schemas: HashMap<String, Schema>,
+}
+
+impl Registry {
+    fn load(root: &Path) -> Result<Self> {
+        let mut rooms = HashMap::new();
+        let mut slugs: HashMap<String, Vec<String>> = HashMap::new();
+
+        for (path, data) in load_rooms(root)? {
+            let raw = fs::read_to_string(&path).map_err(|source| Error::Io {
+                path: path.clone(),
+                source,
+            })?;
+            for topic in data.categories.iter().flat_map(|c| &c.topics) {
+                slugs
+                    .entry(topic.slug.clone())
+                    .or_default()
+                    .push(data.room.clone());
+            }
+            rooms.insert(
+                data.room.clone(),
+                RoomEntry {
+                    file: relative_path(root, &path),
+                    lines: RoomLines::parse(&raw),
+                    data,
+                },
+            );
+        }
+
+        Ok(Self {
+            rooms,
+            slugs,
+            schemas: structure::load_schemas(root)?,
+        })
+    }
+}
+
+fn lint_topic(
+    file: &str,
+    raw: &str,
+    registry: &Registry,
+    seen: &mut HashMap<String, String>,
+    out: &mut Vec<Diagnostic>,
+) {
+    let Some(doc) = frontmatter::split(raw) else {
+        out.push(Diagnostic::error(
+            file,
+            1,
+            "frontmatter",
+            "Frontmatter ausente ou sem o `---` de fechamento.".to_string(),
+        ));
+        return;
+    };
+    let fm: TopicFrontmatter = match serde_yaml::from_str(doc.yaml) {
+        Ok(fm) => fm,
+        Err(err) => {
+            let line = err
+                .location()
+                .map_or(doc.yaml_line, |loc| doc.yaml_line + loc.line() - 1);
+            out.push(Diagnostic::error(
+                file,
+                line,
+                "frontmatter",
+                format!("Frontmatter inválido: {err}"),
+            ));
+            return;
+        }
+    };
+    let line = |key: &str| frontmatter::key_line(&doc, key).unwrap_or(1);
+
+    check_path(file, &fm, &doc, out);
+    if let Some(schema) = registry.schemas.get(&fm.room) {
+        structure::check(file, doc.body, doc.body_line, schema, out);
+    }
+
+    if let Some(previous) = seen.insert(fm.slug.clone(), file.to_string()) {
+        out.push(Diagnostic::error(
+            file,
+            line("slug"),
+            "duplicate",
+            format!("Slug \"{}\" já usado em {previous}.", fm.slug),
+        ));
+    }
+
+    for prerequisite in &fm.prerequisites {
+        if !registry.slugs.contains_key(prerequisite) {
+            out.push(Diagnostic::error(
+                file,
+                line("prerequisites"),
+                "prerequisite",
+                format!("Pré-requisito \"{prerequisite}\" não existe no registry."),
+            ));
+        }
+    }
+
+    let Some(room) = registry.rooms.get(&fm.room) else {
+        out.push(Diagnostic::error(
+            file,
+            line("room"),
+            "room",
+            format!("Sala \"{}\" não existe em data/registry/rooms/.", fm.room),
+        ));
+        return;
+    };
+
+    let Some((category, entry)) = room.find(&fm.slug) else {
+        let message = match registry.slugs.get(&fm.slug) {
+            Some(others) => format!(
+                "Tópico \"{}\" não está registrado em {} (aparece em: {}).",
+                fm.slug,
+                room.file,
+                others.join(", ")
+            ),
+            None => format!(
+                "Tópico \"{}\" não está registrado em {}.",
+                fm.slug, room.file
+            ),
+        };
+        out.push(Diagnostic::error(file, line("slug"), "registry", message));
+        return;
+    };
+
+    if category != fm.category {
+        out.push(Diagnostic::error(
+            file,
+            line("category"),
+            "category",
+            format!(
+                "Frontmatter diz categoria \"{}\", registry diz \"{category}\" ({}).",
+                fm.category,
+                room.location(&fm.slug, "slug")
+            ),
+        ));
+    }
+
+    if entry.difficulty != fm.difficulty {
+        out.push(Diagnostic::error(
+            file,
+            line("difficulty"),
+            "difficulty",
+            format!(
+                "Frontmatter diz \"{}\", registry diz \"{}\" ({}).",
+                fm.difficulty.as_str(),
+                entry.difficulty.as_str(),
+                room.location(&fm.slug, "difficulty")
+            ),
+        ));
+    }
+
+    if entry.order != fm.order {
+        out.push(Diagnostic::error(
+            file,
+            line("order"),
+            "order",
+            format!(
+                "Frontmatter diz {}, registry diz {} ({}).",
+                fm.order,
+                entry.order,
+                room.location(&fm.slug, "order")
+            ),
+        ));
+    }
+
+    if entry.prerequisites != fm.prerequisites {
+        out.push(Diagnostic::error(
+            file,
+            line("prerequisites"),
+            "prerequisites",
+            format!(
+                "Frontmatter diz {}, registry diz {} ({}).",
+                list(&fm.prerequisites),
+                list(&entry.prerequisites),
+                room.location(&fm.slug, "prerequisites")
+            ),
+        ));
+    }
+
+    // Tags são um conjunto: ordem diferente não é divergência
+    let fm_tags: BTreeSet<_> = fm.tags.iter().collect();
+    let registry_tags: BTreeSet<_> = entry.tags.iter().collect();
+    if fm_tags != registry_tags {
+        out.push(Diagnostic::warning(
+            file,
+            line("tags"),
+            "tags",
+            format!(
+                "Frontmatter diz {}, registry diz {} ({}).",
+                list(&fm.tags),
+                list(&entry.tags),
+                room.location(&fm.slug, "tags")
+            ),
+        ));
+    }
+}
+
+/// O arquivo deve estar em `data/content/<room>/<category>/<slug>/topic.mdx`.
+fn check_path(file: &str, fm: &TopicFrontmatter, doc: &Document<'_>, out: &mut Vec<Diagnostic>) {
+    let parts: Vec<&str> = file
+        .strip_prefix("data/content/")
+        .unwrap_or(file)
+        .split('/')
+        .collect();
+    let [room, category, slug, _] = parts.as_slice() else {
+        out.push(Diagnostic::error(
+            file,
+            1,
+            "path",
+            "topic.mdx deve ficar em data/content/<sala>/<categoria>/<slug>/.".to_string(),
+        ));
+        return;
+    };
+
+    for (key, expected, actual) in [
+        ("room", *room, fm.room.as_str()),
+        ("category", *category, fm.category.as_str()),
+        ("slug", *slug, fm.slug.as_str()),
+    ] {
+        if expected != actual {
+            out.push(Diagnostic::error(
+                file,
+                frontmatter::key_line(doc, key).unwrap_or(1),
+                "path",
+                format!(
+                    "Frontmatter diz {key} \"{actual}\", mas o arquivo está em \"{expected}\"."
+                ),
+            ));
+        }
+    }
+}
+
+fn list(items: &[String]) -> String {
+    format!("[{}]", items.join(", "))
+}
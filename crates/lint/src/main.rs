@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use themelion_lint::{Diagnostic, Severity, lint};
+
+/// Confere o frontmatter de cada topic.mdx contra data/registry/rooms e a
+/// estrutura de seções contra data/schemas.
+#[derive(Parser)]
+#[command(name = "themelion-lint", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Falha também quando houver apenas avisos
+    #[arg(long)]
+    deny_warnings: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    /// Markdown por tópico, no formato de feedback de data/agents/reviewer.md
+    Review,
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    errors: usize,
+    warnings: usize,
+    diagnostics: &'a [Diagnostic],
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let diagnostics = lint(&cli.root).context("Erro ao carregar o conteúdo")?;
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    match cli.format {
+        Format::Text => {
+            for diagnostic in &diagnostics {
+                println!("{diagnostic}");
+            }
+            eprintln!("{errors} erro(s), {warnings} aviso(s)");
+        }
+        Format::Json => {
+            let report = Report {
+                errors,
+                warnings,
+                diagnostics: &diagnostics,
+            };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        Format::Review => print_review(&diagnostics),
+    }
+
+    let failed = errors > 0 || (cli.deny_warnings && warnings > 0);
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Um bloco `## Revisão automática: <arquivo>` por tópico com problema: erros em
+/// 🔴 Crítico, avisos em 🟡 Importante.
+fn print_review(diagnostics: &[Diagnostic]) {
+    let mut files: Vec<&str> = diagnostics.iter().map(|d| d.file.as_str()).collect();
+    files.dedup();
+    for file in &files {
+        println!("## Revisão automática: {file}\n");
+        for (severity, title) in [
+            (Severity::Error, "🔴 Crítico"),
+            (Severity::Warning, "🟡 Importante"),
+        ] {
+            let found: Vec<&Diagnostic> = diagnostics
+                .iter()
+                .filter(|d| d.file == *file && d.severity == severity)
+                .collect();
+            if found.is_empty() {
+                continue;
+            }
+            println!("### {title}\n");
+            for diagnostic in found {
+                println!(
+                    "**Linha {}:** {} (`{}`)\n",
+                    diagnostic.line, diagnostic.message, diagnostic.code
+                );
+            }
+        }
+    }
+    eprintln!("{} tópico(s) com problema", files.len());
+}
@@ -0,0 +1,227 @@
+//! Estrutura do corpo: as seções `##` que o agente de escrita da sala
+//! prescreve, declaradas em `data/schemas/<sala>.yaml`. Salas sem agente
+//! seguem a estrutura obrigatória de `CONTRIBUTING.md`.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use themelion_content::{Error, Result};
+use themelion_mdx::{Block, Document, Section, plain_text};
+
+use crate::Diagnostic;
+
+/// `data/schemas/<sala>.yaml`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    pub room: String,
+    /// Agente de escrita de onde a estrutura vem, só para referência.
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// As seções exigidas precisam aparecer na ordem da lista.
+    #[serde(default)]
+    pub ordered: bool,
+    /// Nível máximo de título dentro das seções (`3` = até `###`).
+    #[serde(default)]
+    pub max_heading_depth: Option<u8>,
+    pub sections: Vec<SectionRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SectionRule {
+    /// Texto exato depois do `## `, como o agente o escreve.
+    pub heading: String,
+    /// Outros títulos aceitos para a mesma seção, como o de `CONTRIBUTING.md`
+    /// que os tópicos já publicados usam.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Palavras de texto corrido (parágrafos, listas, tabelas, citações e
+    /// subtítulos); código não conta.
+    #[serde(default)]
+    pub min_words: usize,
+    /// A seção precisa ter o `<CodeTabs />` com os exemplos.
+    #[serde(default)]
+    pub code_tabs: bool,
+}
+
+impl SectionRule {
+    pub fn matches(&self, heading: &str) -> bool {
+        self.heading == heading || self.aliases.iter().any(|alias| alias == heading)
+    }
+}
+
+/// Esquemas por sala. Sem `data/schemas/`, nenhum.
+pub fn load_schemas(root: &Path) -> Result<HashMap<String, Schema>> {
+    let dir = root.join("data/schemas");
+    if !dir.is_dir() {
+        return Ok(HashMap::new());
+    }
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .map_err(|source| Error::Io {
+            path: dir.clone(),
+            source,
+        })?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "yaml"))
+        .collect();
+    files.sort();
+
+    let mut schemas = HashMap::new();
+    for path in files {
+        let raw = fs::read_to_string(&path).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let schema: Schema = serde_yaml::from_str(&raw).map_err(|source| Error::Yaml {
+            path: path.clone(),
+            source,
+        })?;
+        schemas.insert(schema.room.clone(), schema);
+    }
+    Ok(schemas)
+}
+
+/// Confere o corpo contra o esquema. `body_line` é a linha do arquivo onde o
+/// corpo começa, para os diagnósticos apontarem o título certo.
+pub fn check(file: &str, body: &str, body_line: usize, schema: &Schema, out: &mut Vec<Diagnostic>) {
+    let document = themelion_mdx::parse(body);
+    let line = |section: &Section| body_line + section.line - 1;
+
+    if schema.ordered {
+        check_order(file, &document, schema, &line, out);
+    }
+
+    for rule in &schema.sections {
+        let Some(section) = document.sections.iter().find(|s| rule.matches(&s.heading)) else {
+            out.push(Diagnostic::error(
+                file,
+                body_line,
+                "section",
+                format!("Seção obrigatória \"## {}\" ausente.", rule.heading),
+            ));
+            continue;
+        };
+
+        let has_tabs = section.blocks.contains(&Block::CodeTabs);
+        if rule.code_tabs && !has_tabs {
+            out.push(Diagnostic::warning(
+                file,
+                line(section),
+                "code-tabs",
+                format!("Seção \"{}\" sem o `<CodeTabs />`.", section.heading),
+            ));
+        }
+
+        // A seção que cita o CodeTabs é só as abas, no site e aqui
+        let words = words(section);
+        if !has_tabs && words < rule.min_words {
+            out.push(Diagnostic::warning(
+                file,
+                line(section),
+                "section-words",
+                format!(
+                    "Seção \"{}\" tem {words} palavras; o mínimo é {}.",
+                    section.heading, rule.min_words
+                ),
+            ));
+        }
+    }
+
+    if let Some(max) = schema.max_heading_depth {
+        check_depth(file, &document, max, &line, out);
+    }
+}
+
+fn check_order(
+    file: &str,
+    document: &Document,
+    schema: &Schema,
+    line: &dyn Fn(&Section) -> usize,
+    out: &mut Vec<Diagnostic>,
+) {
+    let mut last: Option<(usize, &str)> = None;
+    let mut seen = HashSet::new();
+    for section in &document.sections {
+        let Some(at) = schema
+            .sections
+            .iter()
+            .position(|rule| rule.matches(&section.heading))
+        else {
+            continue;
+        };
+        // Título repetido: vale a primeira ocorrência, como em `Document::section`
+        if !seen.insert(at) {
+            continue;
+        }
+        match last {
+            // Comparada com a seção exigida mais adiante já vista, uma seção
+            // deslocada não faz todas as seguintes parecerem fora de ordem
+            Some((previous, name)) if at < previous => out.push(Diagnostic::error(
+                file,
+                line(section),
+                "section-order",
+                format!(
+                    "Seção \"{}\" deveria vir antes de \"{name}\".",
+                    section.heading
+                ),
+            )),
+            _ => last = Some((at, &section.heading)),
+        }
+    }
+}
+
+fn check_depth(
+    file: &str,
+    document: &Document,
+    max: u8,
+    line: &dyn Fn(&Section) -> usize,
+    out: &mut Vec<Diagnostic>,
+) {
+    for section in &document.sections {
+        let deepest = section
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Heading { level, .. } => Some(*level),
+                _ => None,
+            })
+            .max();
+        if let Some(level) = deepest.filter(|level| *level > max) {
+            out.push(Diagnostic::error(
+                file,
+                line(section),
+                "heading-depth",
+                format!(
+                    "Seção \"{}\" usa {}; o máximo é {}.",
+                    section.heading,
+                    "#".repeat(level.into()),
+                    "#".repeat(max.into())
+                ),
+            ));
+        }
+    }
+}
+
+fn words(section: &Section) -> usize {
+    let count = |text: &str| plain_text(text).split_whitespace().count();
+    section
+        .blocks
+        .iter()
+        .map(|block| match block {
+            Block::Heading { text, .. }
+            | Block::Paragraph { text }
+            | Block::Callout { text, .. } => count(text),
+            Block::List { items, .. } => items.iter().map(|item| count(item)).sum(),
+            Block::Table(table) => table
+                .header
+                .iter()
+                .chain(table.rows.iter().flatten())
+                .map(|cell| count(cell))
+                .sum(),
+            Block::Code { .. } | Block::CodeTabs | Block::Jsx { .. } => 0,
+        })
+        .sum()
+}
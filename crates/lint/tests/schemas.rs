@@ -0,0 +1,41 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use themelion_lint::structure::load_schemas;
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+#[test]
+fn every_room_has_a_schema() {
+    let schemas = load_schemas(&root()).unwrap();
+    for entry in fs::read_dir(root().join("data/registry/rooms")).unwrap() {
+        let path = entry.unwrap().path();
+        let room = path.file_stem().unwrap().to_string_lossy().into_owned();
+        assert!(
+            schemas.contains_key(&room),
+            "data/schemas/{room}.yaml ausente"
+        );
+    }
+}
+
+/// O agente escreve `**Seção "Quando usar":**`, ou `"O que é + Por que
+/// importa"` para duas seções juntas.
+#[test]
+fn headings_come_from_the_writer_agent() {
+    for (room, schema) in load_schemas(&root()).unwrap() {
+        let Some(agent) = &schema.agent else {
+            continue;
+        };
+        let template = fs::read_to_string(root().join(agent)).unwrap();
+        for rule in &schema.sections {
+            let heading = &rule.heading;
+            assert!(
+                template.contains(&format!("\"{heading}"))
+                    || template.contains(&format!("{heading}\"")),
+                "{room}: \"{heading}\" não aparece em {agent}"
+            );
+        }
+    }
+}
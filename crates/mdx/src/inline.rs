@@ -0,0 +1,54 @@
+//! Texto inline sem marcação.
+
+/// `**negrito**`, `*ênfase*`, `` `código` ``, `~~riscado~~` e `[texto](url)`
+/// viram só o texto. `*` colado numa palavra e `_` ficam, porque aparecem em
+/// `O(n*m)` e em `snake_case`; quebras de linha viram espaço.
+pub fn plain_text(inline: &str) -> String {
+    let mut out = String::with_capacity(inline.len());
+    let mut rest = inline;
+    while let Some(c) = rest.chars().next() {
+        if let Some(tail) = ["**", "__", "~~"]
+            .iter()
+            .find_map(|mark| rest.strip_prefix(mark))
+        {
+            rest = tail;
+        } else if c == '`' {
+            rest = &rest[1..];
+        } else if let Some((text, tail)) =
+            emphasis(rest).filter(|_| !out.ends_with(char::is_alphanumeric))
+        {
+            out.push_str(&plain_text(text));
+            rest = tail;
+        } else if let Some((text, tail)) = link(rest) {
+            out.push_str(&plain_text(text));
+            rest = tail;
+        } else {
+            out.push(if c == '\n' { ' ' } else { c });
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    out
+}
+
+/// `[texto](url)` no começo de `text`: o texto e o que vem depois do link.
+fn link(text: &str) -> Option<(&str, &str)> {
+    let inner = text.strip_prefix('[')?;
+    let close = inner.find(']')?;
+    let after = inner[close + 1..].strip_prefix('(')?;
+    let end = after.find(')')?;
+    Some((&inner[..close], &after[end + 1..]))
+}
+
+/// `*texto*` no começo de `text`, sem espaço logo dentro dos asteriscos.
+fn emphasis(text: &str) -> Option<(&str, &str)> {
+    let inner = text.strip_prefix('*')?;
+    if inner.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let close = inner.find('*')?;
+    let body = &inner[..close];
+    if body.is_empty() || body.ends_with(char::is_whitespace) {
+        return None;
+    }
+    Some((body, &inner[close + 1..]))
+}
@@ -0,0 +1,110 @@
+//! Corpo do `topic.mdx` → árvore de seções, no modelo do `TopicContent` do
+//! site.
+//!
+//! As seções saem exatamente como as do `parseSections`: toda linha que
+//! começa com `## ` abre uma, mesmo dentro de um bloco de código, e a seção
+//! que cita `<CodeTabs` vira só as abas. Dentro de cada seção, os blocos
+//! seguem o Markdown que os tópicos usam: títulos, parágrafos, listas, código
+//! cercado, tabelas, citações (com os avisos `> [!NOTE]` do GitHub) e linhas
+//! de JSX/ESM do MDX. O texto inline fica cru, para quem renderiza decidir o
+//! que fazer com ele; [`plain_text`] tira a marcação.
+//!
+//! Lint, índice de busca e pré-renderização leem o corpo por aqui.
+
+mod inline;
+mod parse;
+
+use serde::Serialize;
+
+pub use inline::plain_text;
+pub use parse::parse;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Document {
+    pub sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Section {
+    /// Texto depois do `## `; vazio na seção antes do primeiro título.
+    pub heading: String,
+    /// Linha do título (ou da primeira linha da seção sem título), contada a
+    /// partir de 1 no texto passado a [`parse`].
+    pub line: usize,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Block {
+    /// `#`, `###` e abaixo; `##` sempre abre uma seção.
+    Heading {
+        level: u8,
+        text: String,
+    },
+    /// Linhas consecutivas, juntas com `\n`.
+    Paragraph {
+        text: String,
+    },
+    /// Cada item com as linhas de continuação (e sublistas) indentadas.
+    List {
+        ordered: bool,
+        items: Vec<String>,
+    },
+    Code {
+        /// Primeira palavra depois da cerca: ` ```python ` → `python`.
+        language: Option<String>,
+        code: String,
+    },
+    Table(Table),
+    Callout {
+        kind: CalloutKind,
+        text: String,
+    },
+    /// Os exemplos do tópico, em abas.
+    CodeTabs,
+    /// `import`/`export` e elementos JSX/HTML, como estão.
+    Jsx {
+        source: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Table {
+    pub header: Vec<String>,
+    pub align: Vec<Align>,
+    pub rows: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Align {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+/// Tipo do aviso; `Quote` é a citação comum, sem `[!...]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CalloutKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+    Quote,
+}
+
+impl Document {
+    /// Seção pelo título, como aparece depois do `## `.
+    pub fn section(&self, heading: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.heading == heading)
+    }
+
+    /// Todos os blocos, na ordem do arquivo.
+    pub fn blocks(&self) -> impl Iterator<Item = &Block> {
+        self.sections.iter().flat_map(|s| &s.blocks)
+    }
+}
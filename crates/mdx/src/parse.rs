@@ -0,0 +1,327 @@
+use crate::{Align, Block, CalloutKind, Document, Section, Table};
+
+/// Seções do corpo e os blocos de cada uma.
+pub fn parse(content: &str) -> Document {
+    let mut sections = Vec::new();
+    let mut heading: Option<(String, usize)> = None;
+    let mut body: Vec<&str> = Vec::new();
+    for (at, line) in content.split('\n').enumerate() {
+        if let Some(title) = line.strip_prefix("## ") {
+            if heading.is_some() || !body.is_empty() {
+                sections.push(section(heading.take(), &body));
+            }
+            heading = Some((title.trim().to_string(), at + 1));
+            body.clear();
+        } else {
+            body.push(line);
+        }
+    }
+    if heading.is_some() || !body.is_empty() {
+        sections.push(section(heading, &body));
+    }
+    Document { sections }
+}
+
+fn section(heading: Option<(String, usize)>, body: &[&str]) -> Section {
+    let (heading, line) = heading.unwrap_or((String::new(), 1));
+    // Como no site: a seção que cita o componente é só o componente
+    let blocks = if body.iter().any(|line| line.contains("<CodeTabs")) {
+        vec![Block::CodeTabs]
+    } else {
+        blocks(body)
+    };
+    Section {
+        heading,
+        line,
+        blocks,
+    }
+}
+
+fn blocks(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+
+        let (block, used) = if let Some(fence) = fence(line) {
+            code(lines, i, fence)
+        } else if let Some((level, text)) = heading(line) {
+            (Block::Heading { level, text }, 1)
+        } else if is_table(lines, i) {
+            table(&lines[i..])
+        } else if line.trim_start().starts_with('>') {
+            callout(&lines[i..])
+        } else if marker(line).is_some() {
+            list(&lines[i..])
+        } else if is_jsx(line) {
+            let used = lines[i..]
+                .iter()
+                .position(|l| l.trim().is_empty())
+                .unwrap_or(lines.len() - i);
+            let source = lines[i..i + used].join("\n");
+            (Block::Jsx { source }, used)
+        } else {
+            let used = 1
+                + (i + 1..lines.len())
+                    .position(|at| lines[at].trim().is_empty() || interrupts(lines, at))
+                    .unwrap_or(lines.len() - i - 1);
+            let text = lines[i..i + used]
+                .iter()
+                .map(|l| l.trim())
+                .collect::<Vec<_>>()
+                .join("\n");
+            (Block::Paragraph { text }, used)
+        };
+        blocks.push(block);
+        i += used;
+    }
+    blocks
+}
+
+/// Linha que encerra um parágrafo sem precisar de linha em branco.
+fn interrupts(lines: &[&str], at: usize) -> bool {
+    let line = lines[at];
+    fence(line).is_some()
+        || heading(line).is_some()
+        || line.trim_start().starts_with('>')
+        || marker(line).is_some()
+        || is_jsx(line)
+        || is_table(lines, at)
+}
+
+/// ` ``` ` ou `~~~` no começo da linha, como o site procura.
+fn fence(line: &str) -> Option<&'static str> {
+    ["```", "~~~"].into_iter().find(|f| line.starts_with(f))
+}
+
+/// O bloco termina na primeira linha que começa com a mesma cerca; sem ela,
+/// no fim da seção.
+fn code(lines: &[&str], start: usize, fence: &str) -> (Block, usize) {
+    let info = lines[start][fence.len()..].trim_start_matches(fence.as_bytes()[0] as char);
+    let language = info.split_whitespace().next().map(str::to_string);
+    let body = &lines[start + 1..];
+    let end = body
+        .iter()
+        .position(|l| l.starts_with(fence))
+        .unwrap_or(body.len());
+    let code = body[..end].join("\n");
+    let used = 1 + end + usize::from(end < body.len());
+    (Block::Code { language, code }, used)
+}
+
+fn heading(line: &str) -> Option<(u8, String)> {
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    let text = line[level..].strip_prefix([' ', '\t'])?;
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let text = text.trim().trim_end_matches('#').trim_end();
+    Some((level as u8, text.to_string()))
+}
+
+/// Item de lista: `- `, `* `, `+ ` ou `1. `/`1) `. Devolve se é numerada e a
+/// largura do marcador, que é a indentação das linhas de continuação.
+fn marker(line: &str) -> Option<(bool, usize)> {
+    if ["- ", "* ", "+ "].iter().any(|m| line.starts_with(m)) {
+        return Some((false, 2));
+    }
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    let rest = &line[digits..];
+    let numbered = (1..=9).contains(&digits) && (rest.starts_with(". ") || rest.starts_with(") "));
+    numbered.then_some((true, digits + 2))
+}
+
+fn list(lines: &[&str]) -> (Block, usize) {
+    let (ordered, _) = marker(lines[0]).unwrap_or((false, 2));
+    let mut items: Vec<String> = Vec::new();
+    let mut indent = 0;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if let Some((kind, width)) = marker(line) {
+            if kind != ordered {
+                break;
+            }
+            items.push(line[width..].trim().to_string());
+            indent = width;
+        } else if line.trim().is_empty() {
+            // Linha em branco só continua a lista se ela segue logo depois
+            let next = lines.get(i + 1).copied().unwrap_or("");
+            let continues = marker(next).is_some_and(|(kind, _)| kind == ordered)
+                || (next.starts_with([' ', '\t']) && !next.trim().is_empty());
+            if !continues {
+                break;
+            }
+            if let Some(item) = items.last_mut() {
+                item.push('\n');
+            }
+        } else if line.starts_with([' ', '\t']) || !interrupts(lines, i) {
+            let stripped = strip_indent(line, indent);
+            if let Some(item) = items.last_mut() {
+                item.push('\n');
+                item.push_str(stripped.trim_end());
+            }
+        } else {
+            break;
+        }
+        i += 1;
+    }
+    let items = items
+        .iter()
+        .map(|item| item.trim_end().to_string())
+        .collect();
+    (Block::List { ordered, items }, i)
+}
+
+/// Tira até `width` espaços do começo (tab conta como quatro).
+fn strip_indent(line: &str, width: usize) -> &str {
+    let mut removed = 0;
+    for (at, c) in line.char_indices() {
+        let size = match c {
+            ' ' => 1,
+            '\t' => 4,
+            _ => return &line[at..],
+        };
+        if removed + size > width {
+            return &line[at..];
+        }
+        removed += size;
+    }
+    ""
+}
+
+fn is_jsx(line: &str) -> bool {
+    let mut chars = line.chars();
+    match chars.next() {
+        Some('<') => chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!'),
+        _ => line.starts_with("import ") || line.starts_with("export "),
+    }
+}
+
+/// Linha de cabeçalho seguida da linha de alinhamento (`|---|:---:|`).
+fn is_table(lines: &[&str], at: usize) -> bool {
+    lines[at].trim_start().starts_with('|')
+        && lines
+            .get(at + 1)
+            .and_then(|l| alignment(l))
+            .is_some_and(|align| align.len() == cells(lines[at]).len())
+}
+
+fn alignment(line: &str) -> Option<Vec<Align>> {
+    if !line.trim_start().starts_with('|') {
+        return None;
+    }
+    cells(line)
+        .iter()
+        .map(|cell| {
+            let left = cell.starts_with(':');
+            let right = cell.ends_with(':');
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.bytes().all(|b| b == b'-') {
+                return None;
+            }
+            Some(match (left, right) {
+                (true, true) => Align::Center,
+                (true, false) => Align::Left,
+                (false, true) => Align::Right,
+                (false, false) => Align::None,
+            })
+        })
+        .collect()
+}
+
+fn table(lines: &[&str]) -> (Block, usize) {
+    let header = cells(lines[0]);
+    let align = alignment(lines[1]).unwrap_or_default();
+    let rows: Vec<Vec<String>> = lines[2..]
+        .iter()
+        .take_while(|l| l.trim_start().starts_with('|'))
+        .map(|l| {
+            // Como no GFM: sobra cortada, falta preenchida com vazio
+            let mut row = cells(l);
+            row.resize(header.len(), String::new());
+            row
+        })
+        .collect();
+    let used = 2 + rows.len();
+    (
+        Block::Table(Table {
+            header,
+            align,
+            rows,
+        }),
+        used,
+    )
+}
+
+/// Células de uma linha de tabela. `\|` e `|` dentro de código não separam.
+fn cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut code = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '`' => {
+                code = !code;
+                cell.push(c);
+            }
+            '|' if !code => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+fn callout(lines: &[&str]) -> (Block, usize) {
+    let quoted: Vec<&str> = lines
+        .iter()
+        .map_while(|l| l.trim_start().strip_prefix('>'))
+        .map(|l| l.strip_prefix(' ').unwrap_or(l))
+        .collect();
+    let used = quoted.len();
+
+    let first = quoted[0].trim();
+    let kind = first
+        .strip_prefix("[!")
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(name, _)| match name.to_ascii_uppercase().as_str() {
+            "NOTE" => Some(CalloutKind::Note),
+            "TIP" => Some(CalloutKind::Tip),
+            "IMPORTANT" => Some(CalloutKind::Important),
+            "WARNING" => Some(CalloutKind::Warning),
+            "CAUTION" => Some(CalloutKind::Caution),
+            _ => None,
+        });
+    let text = match kind {
+        Some(_) => {
+            let rest = first.split_once(']').map_or("", |(_, rest)| rest.trim());
+            std::iter::once(rest)
+                .chain(quoted[1..].iter().copied())
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        None => quoted.join("\n"),
+    };
+    (
+        Block::Callout {
+            kind: kind.unwrap_or(CalloutKind::Quote),
+            text: text.trim().to_string(),
+        },
+        used,
+    )
+}
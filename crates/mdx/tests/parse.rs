@@ -0,0 +1,122 @@
+use themelion_mdx::{Align, Block, CalloutKind, Table, parse, plain_text};
+
+fn paragraph(text: &str) -> Block {
+    Block::Paragraph {
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn splits_sections_like_parse_sections() {
+    let doc = parse(
+        "Introdução solta.\n\n## O que é\n\nUm teste.\n\n##Sem espaço\n\n## Na prática  \n\n```python\n## comentário\nprint(1)\n```\n",
+    );
+    let headings: Vec<(&str, usize)> = doc
+        .sections
+        .iter()
+        .map(|s| (s.heading.as_str(), s.line))
+        .collect();
+    // Como no site, `## ` abre seção até dentro de um bloco de código
+    assert_eq!(
+        headings,
+        [
+            ("", 1),
+            ("O que é", 3),
+            ("Na prática", 9),
+            ("comentário", 12)
+        ]
+    );
+    assert_eq!(doc.sections[0].blocks, [paragraph("Introdução solta.")]);
+    assert_eq!(
+        doc.section("O que é").unwrap().blocks,
+        [paragraph("Um teste."), paragraph("##Sem espaço")]
+    );
+    assert_eq!(
+        doc.section("Na prática").unwrap().blocks,
+        [Block::Code {
+            language: Some("python".to_string()),
+            code: String::new(),
+        }]
+    );
+}
+
+#[test]
+fn a_section_citing_code_tabs_is_only_the_tabs() {
+    let doc = parse(
+        "## Exemplos\n\nVeja nas linguagens:\n\n<CodeTabs />\n\nE depois.\n\n## Resumo\n\nFim.\n",
+    );
+    assert_eq!(doc.section("Exemplos").unwrap().blocks, [Block::CodeTabs]);
+    assert_eq!(doc.section("Resumo").unwrap().blocks, [paragraph("Fim.")]);
+    assert_eq!(doc.blocks().filter(|b| **b == Block::CodeTabs).count(), 1);
+}
+
+#[test]
+fn reads_callouts_and_plain_quotes() {
+    let doc = parse(
+        "## Cuidado\n\n> [!WARNING]\n> Não use MD5\n> para senhas.\n\n> [!tip] Prefira Argon2.\n\n> Uma citação.\nDepois.\n",
+    );
+    assert_eq!(
+        doc.section("Cuidado").unwrap().blocks,
+        [
+            Block::Callout {
+                kind: CalloutKind::Warning,
+                text: "Não use MD5\npara senhas.".to_string(),
+            },
+            Block::Callout {
+                kind: CalloutKind::Tip,
+                text: "Prefira Argon2.".to_string(),
+            },
+            Block::Callout {
+                kind: CalloutKind::Quote,
+                text: "Uma citação.".to_string(),
+            },
+            paragraph("Depois."),
+        ]
+    );
+}
+
+#[test]
+fn reads_fenced_code_tables_and_lists() {
+    let doc = parse(
+        "## Complexidade\n\n| Operação | Custo | `a\\|b` |\n|:---|---:|:-:|\n| busca | `O(1)` |\n\n1. Primeiro\n   continua\n2. Segundo\n\n- item\n\n~~~rust title=\"main.rs\"\nfn main() {}\n~~~\n### Detalhe\n<Aviso tipo=\"info\" />\n",
+    );
+    assert_eq!(
+        doc.section("Complexidade").unwrap().blocks,
+        [
+            Block::Table(Table {
+                header: vec!["Operação".into(), "Custo".into(), "`a|b`".into()],
+                align: vec![Align::Left, Align::Right, Align::Center],
+                rows: vec![vec!["busca".into(), "`O(1)`".into(), String::new()]],
+            }),
+            Block::List {
+                ordered: true,
+                items: vec!["Primeiro\ncontinua".into(), "Segundo".into()],
+            },
+            Block::List {
+                ordered: false,
+                items: vec!["item".into()],
+            },
+            Block::Code {
+                language: Some("rust".to_string()),
+                code: "fn main() {}".to_string(),
+            },
+            Block::Heading {
+                level: 3,
+                text: "Detalhe".to_string(),
+            },
+            Block::Jsx {
+                source: "<Aviso tipo=\"info\" />".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn plain_text_drops_the_inline_markup() {
+    assert_eq!(
+        plain_text(
+            "**Hash** em `O(1)`, veja [a doc](https://x.dev) e *não*\nO(n*m) com snake_case"
+        ),
+        "Hash em O(1), veja a doc e não O(n*m) com snake_case"
+    );
+}
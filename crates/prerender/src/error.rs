@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Content(#[from] themelion_content::Error),
+
+    #[error("Erro ao acessar {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Template sem {0}: use o index.html gerado pelo `npm run build`")]
+    Template(String),
+}
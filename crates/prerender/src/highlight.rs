@@ -0,0 +1,334 @@
+//! Destaque de sintaxe dos exemplos, por classes CSS.
+//!
+//! Um lexer pequeno por linguagem do `CodeTabs` — comentários, strings,
+//! números e palavras reservadas —, o bastante para a página estática ler
+//! como código. As cores ficam em `src/styles/modules/code-tabs.css`.
+
+use crate::escape;
+
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comment: &'static str,
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// `"""` e `'''` do Python.
+    triple_quotes: bool,
+    /// `'a` é lifetime, não o começo de um caractere.
+    lifetimes: bool,
+}
+
+const PYTHON: Syntax = Syntax {
+    keywords: &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+        "if", "import", "in", "is", "lambda", "match", "nonlocal", "not", "or", "pass", "raise",
+        "return", "self", "try", "while", "with", "yield",
+    ],
+    line_comment: "#",
+    block_comment: None,
+    quotes: &['"', '\''],
+    triple_quotes: true,
+    lifetimes: false,
+};
+
+const TYPESCRIPT: Syntax = Syntax {
+    keywords: &[
+        "abstract",
+        "any",
+        "as",
+        "async",
+        "await",
+        "boolean",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "constructor",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "number",
+        "of",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "return",
+        "static",
+        "string",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    triple_quotes: false,
+    lifetimes: false,
+};
+
+const CSHARP: Syntax = Syntax {
+    keywords: &[
+        "abstract",
+        "as",
+        "async",
+        "await",
+        "base",
+        "bool",
+        "break",
+        "byte",
+        "case",
+        "catch",
+        "char",
+        "class",
+        "const",
+        "continue",
+        "decimal",
+        "default",
+        "delegate",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "false",
+        "finally",
+        "float",
+        "for",
+        "foreach",
+        "get",
+        "if",
+        "in",
+        "int",
+        "interface",
+        "internal",
+        "is",
+        "long",
+        "namespace",
+        "new",
+        "null",
+        "object",
+        "out",
+        "override",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "record",
+        "ref",
+        "return",
+        "sealed",
+        "set",
+        "static",
+        "string",
+        "struct",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typeof",
+        "using",
+        "var",
+        "virtual",
+        "void",
+        "while",
+        "yield",
+    ],
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    lifetimes: false,
+};
+
+const GO: Syntax = Syntax {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "fallthrough",
+        "false",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "nil",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "true",
+        "type",
+        "var",
+    ],
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    triple_quotes: false,
+    lifetimes: false,
+};
+
+const RUST: Syntax = Syntax {
+    keywords: &[
+        "Self", "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    lifetimes: true,
+};
+
+/// Sintaxe pelo id de `data/languages/` ou pela linguagem de um bloco
+/// cercado (` ```ts `).
+fn syntax(language: &str) -> Option<&'static Syntax> {
+    match language.to_ascii_lowercase().as_str() {
+        "python" | "py" => Some(&PYTHON),
+        "typescript" | "ts" | "javascript" | "js" | "tsx" | "jsx" => Some(&TYPESCRIPT),
+        "csharp" | "cs" | "c#" => Some(&CSHARP),
+        "go" | "golang" => Some(&GO),
+        "rust" | "rs" => Some(&RUST),
+        _ => None,
+    }
+}
+
+/// `code` escapado para HTML, com `<span class="tok-...">` em volta de cada
+/// token destacado. Linguagem desconhecida sai só escapada.
+pub fn highlight(code: &str, language: &str) -> String {
+    let Some(syntax) = syntax(language) else {
+        return escape(code);
+    };
+
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+    while let Some(c) = rest.chars().next() {
+        let (class, len) = if rest.starts_with(syntax.line_comment) {
+            ("comment", rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some((open, close)) = syntax
+            .block_comment
+            .filter(|(open, _)| rest.starts_with(open))
+        {
+            let end = rest[open.len()..]
+                .find(close)
+                .map_or(rest.len(), |at| open.len() + at + close.len());
+            ("comment", end)
+        } else if syntax.quotes.contains(&c) {
+            match string_len(rest, c, syntax) {
+                Some(len) => ("string", len),
+                None => ("", c.len_utf8()),
+            }
+        } else if c.is_ascii_digit() {
+            (
+                "number",
+                word_len(rest, |c| c.is_alphanumeric() || c == '_' || c == '.'),
+            )
+        } else if c.is_alphabetic() || c == '_' {
+            let len = word_len(rest, |c| c.is_alphanumeric() || c == '_');
+            let class = if syntax.keywords.contains(&&rest[..len]) {
+                "keyword"
+            } else {
+                ""
+            };
+            (class, len)
+        } else {
+            ("", c.len_utf8())
+        };
+
+        let (token, tail) = rest.split_at(len);
+        if class.is_empty() {
+            out.push_str(&escape(token));
+        } else {
+            out.push_str(&format!(
+                "<span class=\"tok-{class}\">{}</span>",
+                escape(token)
+            ));
+        }
+        rest = tail;
+    }
+    out
+}
+
+fn word_len(text: &str, part: impl Fn(char) -> bool) -> usize {
+    text.find(|c: char| !part(c)).unwrap_or(text.len())
+}
+
+/// Tamanho da string que começa em `text`, aspas incluídas; `None` quando a
+/// aspa simples é um lifetime do Rust.
+fn string_len(text: &str, quote: char, syntax: &Syntax) -> Option<usize> {
+    if syntax.triple_quotes {
+        let triple: String = [quote; 3].iter().collect();
+        if let Some(body) = text.strip_prefix(triple.as_str()) {
+            let end = body.find(triple.as_str()).map_or(body.len(), |at| at + 3);
+            return Some(3 + end);
+        }
+    }
+    if syntax.lifetimes && quote == '\'' {
+        let mut chars = text[1..].chars();
+        let is_char = match chars.next() {
+            Some('\\') => true,
+            Some(_) => chars.next() == Some('\''),
+            None => false,
+        };
+        if !is_char {
+            return None;
+        }
+    }
+
+    // Strings de crase (template do TS, raw do Go) podem ter várias linhas
+    let multiline = quote == '`';
+    let mut escaped = false;
+    for (at, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' if !multiline => return Some(at),
+            c if c == quote => return Some(at + c.len_utf8()),
+            _ => {}
+        }
+    }
+    Some(text.len())
+}
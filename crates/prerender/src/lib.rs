@@ -0,0 +1,72 @@
+//! Páginas estáticas dos tópicos, para buscadores e leitores sem JavaScript.
+//!
+//! O site renderiza tudo no cliente a partir de um módulo virtual; aqui cada
+//! tópico implementado vira `/<sala>/<tópico>/index.html`, gerado a partir
+//! do `index.html` do build do Vite: título e meta tags do tópico no
+//! `<head>` e, dentro de `#root`, o corpo do `topic.mdx` renderizado nas
+//! mesmas seções do `TopicContent`, com os `examples/*` destacados na ordem
+//! do `CodeTabs`. Quando o bundle carrega, o React troca esse HTML pelo app,
+//! na rota `/:roomSlug/:topicSlug`. O `postbuild` do npm roda a geração
+//! quando há `cargo`.
+
+mod error;
+mod highlight;
+pub mod markdown;
+mod page;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use themelion_content::Content;
+
+pub use error::Error;
+pub use highlight::highlight;
+pub use page::{Site, prerendered, topic_path};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Grava a página de cada tópico implementado em `out_dir` e devolve os
+/// caminhos escritos.
+pub fn prerender(
+    content: &Content,
+    template: &str,
+    base_url: Option<&str>,
+    out_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    let site = Site {
+        content,
+        template,
+        base_url,
+    };
+    site.check_template()?;
+
+    let mut written = Vec::new();
+    for topic in content.topics.iter().filter(|t| prerendered(t)) {
+        let meta = &topic.frontmatter;
+        let dir = out_dir.join(&meta.room).join(&meta.slug);
+        let path = dir.join("index.html");
+        let io = |source| Error::Io {
+            path: path.clone(),
+            source,
+        };
+        fs::create_dir_all(&dir).map_err(io)?;
+        fs::write(&path, site.render(topic)?).map_err(io)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Escapa texto para HTML, em conteúdo e em atributos entre aspas duplas.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
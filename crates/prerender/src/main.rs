@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use themelion_content::Content;
+use themelion_prerender::prerender;
+
+/// Gera `/<sala>/<tópico>/index.html` para cada tópico implementado.
+#[derive(Parser)]
+#[command(name = "themelion-prerender", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+
+    /// `index.html` do build do site, relativo à raiz
+    #[arg(long, default_value = "dist/index.html")]
+    template: PathBuf,
+
+    /// Onde gravar as páginas, relativo à raiz
+    #[arg(long, default_value = "dist")]
+    out: PathBuf,
+
+    /// Origem pública do site, para `canonical` e `og:url`
+    /// (ex.: https://themelion.dev)
+    #[arg(long)]
+    base_url: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let template_path = cli.root.join(&cli.template);
+    let template = fs::read_to_string(&template_path).with_context(|| {
+        format!(
+            "Erro ao ler {} (rode `npm run build` antes)",
+            template_path.display()
+        )
+    })?;
+    let content = Content::scan(&cli.root).context("Erro ao escanear o conteúdo")?;
+
+    let out = cli.root.join(&cli.out);
+    let written = prerender(&content, &template, cli.base_url.as_deref(), &out)?;
+    eprintln!("{} páginas em {}", written.len(), out.display());
+    Ok(())
+}
@@ -0,0 +1,270 @@
+//! Corpo do `topic.mdx` → HTML, nas seções e com as classes do
+//! `TopicContent` do site. Os blocos vêm do `themelion-mdx`; o pulldown-cmark
+//! só cuida do texto inline de cada um.
+
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd};
+use themelion_content::model::{Language, TopicExample};
+use themelion_mdx::{Align, Block, CalloutKind, Table, plain_text};
+
+use crate::escape;
+use crate::highlight::highlight;
+
+/// Ordem das abas do `CodeTabs`; as demais linguagens vêm depois, na ordem
+/// em que foram encontradas.
+const LANGUAGE_ORDER: [&str; 5] = ["python", "typescript", "csharp", "go", "rust"];
+
+/// Protocolos que o `sanitizeUrl` do site aceita.
+const SAFE_PROTOCOLS: [&str; 4] = ["http", "https", "mailto", "tel"];
+
+/// Conteúdo do tópico: uma `<section>` por seção de [`themelion_mdx`], com o
+/// `CodeTabs` no lugar da seção que o cita.
+pub fn render_content(content: &str, examples: &[TopicExample], languages: &[Language]) -> String {
+    let mut html = String::from("<div class=\"topic-sections\">\n");
+    for section in themelion_mdx::parse(content).sections {
+        html.push_str("<section>\n");
+        if !section.heading.is_empty() {
+            html.push_str(&format!(
+                "<h2 class=\"topic-section-heading\">{}</h2>\n",
+                escape(&section.heading)
+            ));
+        }
+        for block in &section.blocks {
+            match block {
+                Block::CodeTabs => html.push_str(&code_tabs(examples, languages)),
+                block => html.push_str(&render_block(block)),
+            }
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+/// Todas as abas abertas, uma embaixo da outra: sem JavaScript não há como
+/// trocar de aba.
+fn code_tabs(examples: &[TopicExample], languages: &[Language]) -> String {
+    let mut sorted: Vec<&TopicExample> = examples.iter().collect();
+    sorted.sort_by_key(|example| {
+        LANGUAGE_ORDER
+            .iter()
+            .position(|id| *id == example.language_id)
+            .unwrap_or(99)
+    });
+
+    let mut html = String::from("<div class=\"code-tabs\">\n");
+    for example in sorted {
+        let name = languages
+            .iter()
+            .find(|l| l.id == example.language_id)
+            .map_or(example.language_id.as_str(), |l| l.name.as_str());
+        html.push_str(&format!(
+            "<figure class=\"code-block\" data-language=\"{id}\">\
+             <figcaption class=\"code-tabs-trigger\">{name}</figcaption>\
+             <pre class=\"code-block-pre\"><code class=\"code-block-code\">{code}</code></pre>\
+             </figure>\n",
+            id = escape(&example.language_id),
+            name = escape(name),
+            code = highlight(&example.code, &example.language_id),
+        ));
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+fn render_block(block: &Block) -> String {
+    match block {
+        Block::Heading { level, text } => format!("<h{level}>{}</h{level}>\n", inline(text)),
+        Block::Paragraph { text } => {
+            format!("<p class=\"topic-paragraph\">{}</p>\n", inline(text))
+        }
+        Block::List { ordered, items } => {
+            let tag = if *ordered { "ol" } else { "ul" };
+            let mut html = format!("<{tag} class=\"topic-list\">\n");
+            for item in items {
+                html.push_str(&format!(
+                    "<li class=\"topic-list-item\">{}</li>\n",
+                    nested(item)
+                ));
+            }
+            html.push_str(&format!("</{tag}>\n"));
+            html
+        }
+        Block::Code { language, code } => format!(
+            "<div class=\"topic-code-block\"><pre class=\"topic-code-pre\">\
+             <code class=\"topic-code-text\">{}</code></pre></div>\n",
+            highlight(
+                code.trim_end_matches('\n'),
+                language.as_deref().unwrap_or("")
+            )
+        ),
+        Block::Table(table) => render_table(table),
+        Block::Callout { kind, text } => format!(
+            "<blockquote class=\"topic-callout\" data-kind=\"{}\">\n{}</blockquote>\n",
+            callout_kind(*kind),
+            nested(text)
+        ),
+        // Sem os exemplos à mão, as abas ficam de fora
+        Block::CodeTabs => String::new(),
+        // JSX e HTML do MDX não passam como estão: saem como texto
+        Block::Jsx { source } => {
+            format!("<p class=\"topic-paragraph\">{}</p>\n", escape(source))
+        }
+    }
+}
+
+/// Item de lista ou texto de citação: só o inline quando é uma linha de
+/// texto; blocos quando tem sublista, código ou mais de um parágrafo.
+fn nested(text: &str) -> String {
+    let blocks: Vec<Block> = themelion_mdx::parse(text)
+        .sections
+        .into_iter()
+        .flat_map(|s| s.blocks)
+        .collect();
+    match blocks.as_slice() {
+        [Block::Paragraph { text }] => inline(text),
+        blocks => blocks.iter().map(render_block).collect(),
+    }
+}
+
+fn render_table(table: &Table) -> String {
+    let cell = |tag: &str, at: usize, text: &str| {
+        let style = match table.align.get(at) {
+            Some(Align::Left) => " style=\"text-align: left\"",
+            Some(Align::Center) => " style=\"text-align: center\"",
+            Some(Align::Right) => " style=\"text-align: right\"",
+            _ => "",
+        };
+        format!("<{tag}{style}>{}</{tag}>", inline(text))
+    };
+    let mut html = String::from("<table>\n<thead><tr>");
+    for (at, text) in table.header.iter().enumerate() {
+        html.push_str(&cell("th", at, text));
+    }
+    html.push_str("</tr></thead>\n<tbody>\n");
+    for row in &table.rows {
+        html.push_str("<tr>");
+        for (at, text) in row.iter().enumerate() {
+            html.push_str(&cell("td", at, text));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody>\n</table>\n");
+    html
+}
+
+fn callout_kind(kind: CalloutKind) -> &'static str {
+    match kind {
+        CalloutKind::Note => "note",
+        CalloutKind::Tip => "tip",
+        CalloutKind::Important => "important",
+        CalloutKind::Warning => "warning",
+        CalloutKind::Caution => "caution",
+        CalloutKind::Quote => "quote",
+    }
+}
+
+/// Texto inline → HTML: código, negrito, ênfase, riscado e links, com as
+/// classes do `renderInline` do site.
+fn inline(text: &str) -> String {
+    let mut events = Vec::new();
+    // Links abertos, para fechar com a tag certa (link válido ou texto riscado)
+    let mut links: Vec<bool> = Vec::new();
+
+    let text = escape_block_syntax(text);
+    for event in Parser::new_ext(&text, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            // O bloco já veio do parser do MDX
+            Event::Start(Tag::Paragraph) | Event::End(TagEnd::Paragraph) => {}
+            Event::Start(Tag::Strong) => events.push(raw("<strong class=\"topic-bold\">")),
+            Event::Code(text) => events.push(raw(format!(
+                "<code class=\"topic-inline-code\">{}</code>",
+                escape(&text)
+            ))),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                let safe = is_safe_url(&dest_url);
+                links.push(safe);
+                events.push(raw(if safe {
+                    format!(
+                        "<a href=\"{}\" target=\"_blank\" rel=\"noopener noreferrer\" class=\"topic-link\">",
+                        escape(dest_url.trim())
+                    )
+                } else {
+                    "<span class=\"topic-invalid-link\">[".to_string()
+                }));
+            }
+            Event::End(TagEnd::Link) => {
+                events.push(raw(if links.pop().unwrap_or(false) {
+                    "</a>"
+                } else {
+                    "]</span>"
+                }));
+            }
+            Event::Html(text) | Event::InlineHtml(text) => events.push(Event::Text(text)),
+            event => events.push(event),
+        }
+    }
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    html.trim_end().to_string()
+}
+
+/// O bloco já veio do parser do MDX: `1. `, `- `, `#`, `>` e afins no começo
+/// de uma linha do texto são texto, não uma lista ou um título novo.
+fn escape_block_syntax(text: &str) -> String {
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+            let after = &line[digits..];
+            let starts_block = if digits > 0 {
+                after.starts_with(". ") || after.starts_with(") ")
+            } else {
+                let rule = line.len() >= 3 && line.bytes().all(|b| b"-*_= ".contains(&b));
+                line.starts_with(['#', '>', '|', '~'])
+                    || ["- ", "* ", "+ "].iter().any(|m| line.starts_with(m))
+                    || rule
+            };
+            if starts_block {
+                format!("{}\\{}", &line[..digits], after)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+fn raw<'a>(html: impl Into<CowStr<'a>>) -> Event<'a> {
+    Event::InlineHtml(html.into())
+}
+
+/// O `sanitizeUrl` do site: relativas valem; absolutas, só com protocolo
+/// seguro. Como o parser de URL, ignora tabs e quebras de linha no meio.
+fn is_safe_url(url: &str) -> bool {
+    let url: String = url
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let scheme_len = url
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')))
+        .filter(|&at| at > 0 && url[at..].starts_with(':'));
+    match scheme_len {
+        Some(len) if url.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+            SAFE_PROTOCOLS.contains(&url[..len].to_ascii_lowercase().as_str())
+        }
+        _ => true,
+    }
+}
+
+/// Texto do primeiro parágrafo, sem marcação, para `<meta name="description">`.
+pub fn first_paragraph(content: &str) -> String {
+    themelion_mdx::parse(content)
+        .blocks()
+        .find_map(|block| match block {
+            Block::Paragraph { text } => Some(plain_text(text).trim().to_string()),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
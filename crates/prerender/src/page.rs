@@ -0,0 +1,160 @@
+//! Página completa: o `index.html` do build do Vite com título, meta tags e
+//! o conteúdo do tópico dentro de `#root`.
+
+use themelion_content::Content;
+use themelion_content::model::{Topic, TopicStatus};
+
+use crate::markdown::{first_paragraph, render_content};
+use crate::{Error, Result, escape};
+
+const SITE_NAME: &str = "Themelion";
+const ROOT_MARKER: &str = "<div id=\"root\"></div>";
+/// Tamanho máximo da descrição, em caracteres.
+const DESCRIPTION_LEN: usize = 160;
+
+/// Caminho público da página pré-renderizada de um tópico.
+pub fn topic_path(room: &str, slug: &str) -> String {
+    format!("/{room}/{slug}/")
+}
+
+pub struct Site<'a> {
+    pub content: &'a Content,
+    /// `dist/index.html`, com os scripts e estilos do build.
+    pub template: &'a str,
+    /// Origem pública (ex.: `https://themelion.dev`), para `canonical` e
+    /// `og:url`; sem ela, essas tags ficam de fora.
+    pub base_url: Option<&'a str>,
+}
+
+impl Site<'_> {
+    /// Confere os marcadores do template antes de renderizar qualquer página.
+    pub fn check_template(&self) -> Result<()> {
+        for marker in ["<title>", "</title>", ROOT_MARKER] {
+            if !self.template.contains(marker) {
+                return Err(Error::Template(marker.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn render(&self, topic: &Topic) -> Result<String> {
+        self.check_template()?;
+        let head = self.head(topic);
+        let body = format!("<div id=\"root\">\n{}</div>", self.article(topic));
+
+        let start = self.template.find("<title>").unwrap_or_default();
+        let end = self.template.find("</title>").unwrap_or_default() + "</title>".len();
+        let page = format!("{}{head}{}", &self.template[..start], &self.template[end..]);
+        Ok(page.replacen(ROOT_MARKER, &body, 1))
+    }
+
+    fn head(&self, topic: &Topic) -> String {
+        let meta = &topic.frontmatter;
+        let title = format!("{} — {SITE_NAME}", meta.title);
+        let description = truncate(&first_paragraph(&topic.content));
+
+        let mut head = format!("<title>{}</title>", escape(&title));
+        let mut tag = |attr: &str, name: &str, value: &str| {
+            head.push_str(&format!(
+                "\n\t\t<meta {attr}=\"{name}\" content=\"{}\" />",
+                escape(value)
+            ));
+        };
+        if !description.is_empty() {
+            tag("name", "description", &description);
+            tag("property", "og:description", &description);
+        }
+        if !meta.tags.is_empty() {
+            tag("name", "keywords", &meta.tags.join(", "));
+        }
+        tag("property", "og:title", &meta.title);
+        tag("property", "og:type", "article");
+        tag("property", "og:site_name", SITE_NAME);
+        tag("property", "og:locale", "pt_BR");
+        tag("name", "twitter:card", "summary");
+        if let Some(base) = self.base_url {
+            let url = format!(
+                "{}{}",
+                base.trim_end_matches('/'),
+                topic_path(&meta.room, &meta.slug)
+            );
+            tag("property", "og:url", &url);
+            head.push_str(&format!(
+                "\n\t\t<link rel=\"canonical\" href=\"{}\" />",
+                escape(&url)
+            ));
+        }
+        head
+    }
+
+    fn article(&self, topic: &Topic) -> String {
+        let meta = &topic.frontmatter;
+        let mut html =
+            String::from("<article class=\"topic-static\">\n<header class=\"topic-header\">\n");
+        html.push_str(&format!(
+            "<h1 class=\"topic-title\">{}</h1>\n",
+            escape(&meta.title)
+        ));
+        if !meta.tags.is_empty() {
+            html.push_str("<div class=\"topic-badges\">");
+            for tag in &meta.tags {
+                html.push_str(&format!("<span class=\"badge-tag\">{}</span>", escape(tag)));
+            }
+            html.push_str("</div>\n");
+        }
+        if !meta.prerequisites.is_empty() {
+            html.push_str("<p class=\"topic-prereqs\">Pré-requisitos: ");
+            let links: Vec<String> = meta
+                .prerequisites
+                .iter()
+                .map(|slug| {
+                    format!(
+                        "<a href=\"{}\" class=\"topic-prereqs-link\">{}</a>",
+                        escape(&self.href(&meta.room, slug)),
+                        escape(slug)
+                    )
+                })
+                .collect();
+            html.push_str(&links.join(", "));
+            html.push_str("</p>\n");
+        }
+        html.push_str("</header>\n");
+        html.push_str(&render_content(
+            &topic.content,
+            &topic.examples,
+            &self.content.languages,
+        ));
+        html.push_str("</article>\n");
+        html
+    }
+
+    /// Página pré-renderizada quando o tópico tem uma; senão, a rota
+    /// `/<sala>/<slug>` do app. Slugs repetidos resolvem primeiro em `room`.
+    fn href(&self, room: &str, slug: &str) -> String {
+        let topic = self
+            .content
+            .topics
+            .iter()
+            .filter(|t| t.frontmatter.slug == slug)
+            .min_by_key(|t| t.frontmatter.room != room);
+        match topic {
+            Some(t) if prerendered(t) => topic_path(&t.frontmatter.room, slug),
+            Some(t) => format!("/{}/{slug}", t.frontmatter.room),
+            None => format!("/topic/{slug}"),
+        }
+    }
+}
+
+/// Só tópicos implementados e com corpo ganham página.
+pub fn prerendered(topic: &Topic) -> bool {
+    topic.status == TopicStatus::Implemented && !topic.content.is_empty()
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= DESCRIPTION_LEN {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(DESCRIPTION_LEN).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    format!("{}…", cut.trim_end_matches([',', '.', ';', ':']))
+}
@@ -0,0 +1,104 @@
+use serde_json::json;
+use themelion_content::Content;
+use themelion_content::model::Topic;
+use themelion_prerender::Site;
+
+const TEMPLATE: &str = "<html><head><title>Themelion</title></head>\
+                        <body><div id=\"root\"></div></body></html>";
+
+fn topic(room: &str, slug: &str, prerequisites: &[&str], body: &str) -> Topic {
+    serde_json::from_value(json!({
+        "title": slug, "slug": slug, "room": room, "category": "estruturas",
+        "difficulty": "beginner", "order": 1, "prerequisites": prerequisites,
+        "status": "implemented", "contentPath": "", "content": body, "examples": [],
+    }))
+    .unwrap()
+}
+
+fn content(topics: Vec<Topic>) -> Content {
+    Content {
+        rooms: Vec::new(),
+        topics,
+        languages: Vec::new(),
+        roadmaps: Vec::new(),
+    }
+}
+
+/// `href` do link de pré-requisito na página de `fundamentos/tries`.
+fn prerequisite_href(others: Vec<Topic>, prerequisite: &str) -> String {
+    let page = topic(
+        "fundamentos",
+        "tries",
+        &[prerequisite],
+        "## O que é\n\nÁrvore de prefixos.",
+    );
+    let mut topics = vec![page.clone()];
+    topics.extend(others);
+    let content = content(topics);
+    let site = Site {
+        content: &content,
+        template: TEMPLATE,
+        base_url: None,
+    };
+    let html = site.render(&page).unwrap();
+    let link = html
+        .split("<a href=\"")
+        .nth(1)
+        .expect("link de pré-requisito");
+    link[..link.find('"').unwrap()].to_string()
+}
+
+#[test]
+fn shared_slug_resolves_in_the_same_room_first() {
+    let href = prerequisite_href(
+        vec![
+            topic("backend", "hash-tables", &[], "Cache distribuído."),
+            topic("fundamentos", "hash-tables", &[], "Tabela na memória."),
+        ],
+        "hash-tables",
+    );
+    assert_eq!(href, "/fundamentos/hash-tables/");
+}
+
+#[test]
+fn shared_slug_falls_back_to_the_other_room() {
+    let href = prerequisite_href(
+        vec![topic("backend", "hash-tables", &[], "Cache distribuído.")],
+        "hash-tables",
+    );
+    assert_eq!(href, "/backend/hash-tables/");
+}
+
+#[test]
+fn topic_without_a_page_links_to_the_app_route() {
+    let href = prerequisite_href(
+        vec![
+            topic("backend", "hash-tables", &[], "Cache distribuído."),
+            topic("fundamentos", "hash-tables", &[], ""),
+        ],
+        "hash-tables",
+    );
+    assert_eq!(href, "/fundamentos/hash-tables");
+    assert_eq!(prerequisite_href(Vec::new(), "heaps"), "/topic/heaps");
+}
+
+#[test]
+fn renders_meta_tags_and_the_body_into_the_template() {
+    let page = topic(
+        "fundamentos",
+        "tries",
+        &[],
+        "## O que é\n\nÁrvore de <prefixos>.",
+    );
+    let content = content(vec![page.clone()]);
+    let site = Site {
+        content: &content,
+        template: TEMPLATE,
+        base_url: Some("https://themelion.dev/"),
+    };
+    let html = site.render(&page).unwrap();
+    assert!(html.contains("<title>tries — Themelion</title>"));
+    assert!(html.contains("content=\"Árvore de &lt;prefixos&gt;.\""));
+    assert!(html.contains("href=\"https://themelion.dev/fundamentos/tries/\""));
+    assert!(html.contains("<div id=\"root\">\n<article class=\"topic-static\">"));
+}
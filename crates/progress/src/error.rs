@@ -0,0 +1,46 @@
+use thiserror::Error;
+
+use crate::progress::{MAX_ENTRIES, MAX_FILE_SIZE};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Arquivo muito grande ({}KB). Máximo: {}MB.", size / 1024, MAX_FILE_SIZE / (1024 * 1024))]
+    TooLarge { size: usize },
+
+    #[error("JSON inválido: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Formato inválido: esperado um objeto JSON.")]
+    NotAnObject,
+
+    #[error("Arquivo contém {0} entradas (máximo: {MAX_ENTRIES}).")]
+    TooManyEntries(usize),
+
+    #[error("Key perigosa detectada: \"{0}\".")]
+    DangerousKey(String),
+
+    #[error("Slug inválido: \"{0}\". Apenas letras minúsculas, números e hífens.")]
+    InvalidSlug(String),
+
+    #[error("Dados inválidos para o tópico \"{0}\".")]
+    InvalidEntry(String),
+
+    #[error("O progresso sincronizado passaria de {MAX_ENTRIES} tópicos.")]
+    MergeTooLarge,
+
+    #[error("Token de sincronização ausente ou desconhecido.")]
+    UnknownToken,
+
+    #[error("Erro no banco de progresso: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Erro ao gerar token: {0}")]
+    Random(#[from] getrandom::Error),
+}
+
+impl Error {
+    /// Erros causados pelo pedido (e não pelo servidor).
+    pub fn is_client_error(&self) -> bool {
+        !matches!(self, Error::Sqlite(_) | Error::Random(_))
+    }
+}
@@ -0,0 +1,155 @@
+//! Transporte HTTP da sincronização.
+//!
+//! - `POST /tokens` cria um token anônimo: `{ "token": "..." }`
+//! - `GET /progress` devolve o `ProgressMap` do token
+//! - `POST /progress` junta o `ProgressMap` do corpo e devolve o resultado
+//! - `DELETE /progress` apaga o token e o progresso
+//!
+//! O token vai em `Authorization: Bearer <token>`, fora da URL, para não
+//! parar em logs de proxy.
+
+use std::io::Read;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use std::thread;
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+
+use crate::progress::{MAX_FILE_SIZE, parse};
+use crate::{Error, Store};
+
+#[derive(Serialize)]
+struct ErrorReply {
+    error: String,
+}
+
+#[derive(Serialize)]
+struct TokenReply {
+    token: String,
+}
+
+/// Atende pedidos em `workers` threads até o processo ser encerrado.
+pub fn serve(store: Store, addr: impl ToSocketAddrs, workers: usize) -> std::io::Result<()> {
+    let server = Server::http(addr).map_err(std::io::Error::other)?;
+    let server = Arc::new(server);
+    let store = Arc::new(store);
+
+    let handles: Vec<_> = (0..workers.max(1))
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let store = Arc::clone(&store);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(&store, request);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+    Ok(())
+}
+
+fn handle(store: &Store, mut request: Request) {
+    let response = match (request.method(), request.url()) {
+        (Method::Get, "/health") => json(200, &serde_json::json!({ "status": "ok" })),
+        (Method::Options, _) => Response::from_data(Vec::new()).with_status_code(204),
+        (Method::Post, "/tokens") => match store.create_token() {
+            Ok(token) => json(201, &TokenReply { token }),
+            Err(err) => failure(err),
+        },
+        (Method::Get, "/progress") => match store.load(bearer(&request)) {
+            Ok(map) => json(200, &map),
+            Err(err) => failure(err),
+        },
+        (Method::Post, "/progress") => {
+            let result = read_body(&mut request)
+                .and_then(|body| parse(&body).map_err(|err| err.to_string()));
+            match result {
+                Ok(incoming) => match store.sync(bearer(&request), &incoming) {
+                    Ok(map) => json(200, &map),
+                    Err(err) => failure(err),
+                },
+                Err(message) => error(400, message),
+            }
+        }
+        (Method::Delete, "/progress") => match store.delete(bearer(&request)) {
+            Ok(()) => Response::from_data(Vec::new()).with_status_code(204),
+            Err(err) => failure(err),
+        },
+        _ => error(404, "Rota não encontrada.".to_string()),
+    };
+
+    let _ = request.respond(with_cors(response));
+}
+
+/// Token do cabeçalho `Authorization`; vazio se não houver, o que o
+/// [`Store`] trata como token desconhecido.
+fn bearer(request: &Request) -> &str {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "))
+        .map_or("", str::trim)
+}
+
+fn read_body(request: &mut Request) -> Result<Vec<u8>, String> {
+    let too_large = |size: usize| Error::TooLarge { size }.to_string();
+    if let Some(len) = request.body_length()
+        && len > MAX_FILE_SIZE
+    {
+        return Err(too_large(len));
+    }
+
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_FILE_SIZE as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|err| format!("Erro ao ler o corpo: {err}"))?;
+    if body.len() > MAX_FILE_SIZE {
+        return Err(too_large(body.len()));
+    }
+    Ok(body)
+}
+
+fn failure(err: Error) -> Response<std::io::Cursor<Vec<u8>>> {
+    let status = match &err {
+        Error::UnknownToken => 401,
+        err if err.is_client_error() => 400,
+        _ => 500,
+    };
+    error(status, err.to_string())
+}
+
+fn json<T: Serialize>(status: u16, body: &T) -> Response<std::io::Cursor<Vec<u8>>> {
+    let data = serde_json::to_vec(body).unwrap_or_default();
+    Response::from_data(data)
+        .with_status_code(StatusCode(status))
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn error(status: u16, message: String) -> Response<std::io::Cursor<Vec<u8>>> {
+    json(status, &ErrorReply { error: message })
+}
+
+fn with_cors<R: Read>(response: Response<R>) -> Response<R> {
+    response
+        .with_header(header("Access-Control-Allow-Origin", "*"))
+        .with_header(header(
+            "Access-Control-Allow-Methods",
+            "GET, POST, DELETE, OPTIONS",
+        ))
+        .with_header(header(
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type",
+        ))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("header ASCII válido")
+}
@@ -0,0 +1,22 @@
+//! Progresso de estudo no formato `themelion:progress` do site.
+//!
+//! O site guarda um `ProgressMap` no `localStorage` e o exporta como JSON.
+//! [`progress`] lê esse formato com as mesmas regras do import do site e o
+//! junta tópico a tópico ([`merge`]). [`Store`] e [`http`] formam um serviço
+//! opcional de sincronização entre dispositivos: quem quiser sincronizar
+//! pede um token anônimo e envia o progresso local; o servidor devolve a
+//! junção com o que os outros dispositivos já enviaram. [`report`] mede o
+//! avanço de cada aluno nas etapas das trilhas, para mentores acompanharem
+//! uma turma a partir dos arquivos exportados.
+
+mod error;
+pub mod http;
+pub mod progress;
+pub mod report;
+mod store;
+
+pub use error::Error;
+pub use progress::{ProgressMap, TopicProgress, merge, parse};
+pub use store::Store;
+
+pub type Result<T> = std::result::Result<T, Error>;
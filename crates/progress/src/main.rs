@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use themelion_content::Content;
+use themelion_progress::report::{self, Learner, LearnerReport, Topics};
+use themelion_progress::{ProgressMap, Store, http, merge, parse};
+
+/// Sincroniza, junta e resume arquivos de progresso (`themelion:progress`).
+#[derive(Parser)]
+#[command(name = "themelion-progress", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Sobe o serviço de sincronização (POST /tokens, GET|POST|DELETE /progress)
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8788")]
+        addr: String,
+        #[arg(long, default_value_t = 2)]
+        workers: usize,
+        /// Banco SQLite (criado se não existir)
+        #[arg(long, default_value = "themelion-progress.db")]
+        db: PathBuf,
+    },
+    /// Junta progressos exportados pelo site e imprime o resultado no stdout
+    Merge {
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Conclusão, intervalo entre visitas e próximo tópico por aluno e etapa
+    /// de trilha
+    Report {
+        /// `[nome=]arquivo.json`; sem nome, vale o nome do arquivo
+        #[arg(required = true)]
+        learners: Vec<String>,
+        /// Raiz do repositório (onde fica `data/`)
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+        /// Só estas trilhas; por padrão, as que o aluno já visitou
+        #[arg(long = "roadmap")]
+        roadmaps: Vec<String>,
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Serve { addr, workers, db } => {
+            let store =
+                Store::open(&db).with_context(|| format!("Erro ao abrir {}", db.display()))?;
+            eprintln!(
+                "themelion-progress ouvindo em http://{addr} ({})",
+                db.display()
+            );
+            http::serve(store, addr.as_str(), workers)?;
+        }
+        Command::Merge { files } => {
+            let mut merged = ProgressMap::new();
+            for file in &files {
+                merge(&mut merged, &read(file)?);
+            }
+            // Mesma indentação do arquivo exportado pelo site
+            println!("{}", serde_json::to_string_pretty(&merged)?);
+            eprintln!("{} tópicos de {} arquivos", merged.len(), files.len());
+        }
+        Command::Report {
+            learners,
+            root,
+            roadmaps,
+            format,
+        } => {
+            let content = Content::scan(&root).context("Erro ao escanear o conteúdo")?;
+            for slug in &roadmaps {
+                if !content.roadmaps.iter().any(|r| &r.slug == slug) {
+                    bail!("Trilha \"{slug}\" não existe em data/registry/roadmaps/");
+                }
+            }
+            let topics = Topics::new(&content);
+
+            let mut reports = Vec::new();
+            for arg in &learners {
+                let learner = learner(arg)?;
+                let selected = if roadmaps.is_empty() {
+                    report::touched(&content.roadmaps, &learner.progress)
+                } else {
+                    content
+                        .roadmaps
+                        .iter()
+                        .filter(|r| roadmaps.contains(&r.slug))
+                        .collect()
+                };
+                reports.push(report::report(&topics, &learner, &selected));
+            }
+
+            match format {
+                Format::Table => print_table(&reports),
+                Format::Csv => print_csv(&reports),
+                Format::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read(file: &Path) -> Result<ProgressMap> {
+    let raw = fs::read(file).with_context(|| format!("Erro ao ler {}", file.display()))?;
+    parse(&raw).with_context(|| file.display().to_string())
+}
+
+fn learner(arg: &str) -> Result<Learner> {
+    let (name, file) = match arg.split_once('=') {
+        Some((name, file)) => (name.to_string(), Path::new(file)),
+        None => {
+            let file = Path::new(arg);
+            let stem = file.file_stem().map(|s| s.to_string_lossy().into_owned());
+            (stem.unwrap_or_else(|| arg.to_string()), file)
+        }
+    };
+    Ok(Learner {
+        name,
+        progress: read(file)?,
+    })
+}
+
+fn print_table(reports: &[LearnerReport]) {
+    for learner in reports {
+        if learner.roadmaps.is_empty() {
+            println!("{}: nenhuma trilha visitada\n", learner.learner);
+            continue;
+        }
+        for roadmap in &learner.roadmaps {
+            println!(
+                "{} — {}: {}/{} ({:.0}%), próximo: {}",
+                learner.learner,
+                roadmap.name,
+                roadmap.completed,
+                roadmap.topics,
+                roadmap.percent,
+                roadmap.next.as_deref().unwrap_or("-")
+            );
+            println!(
+                "  {:<40}{:>7}{:>6}{:>6}  {:<22}próximo",
+                "etapa", "feitos", "vist.", "%", "mediana entre visitas"
+            );
+            for stage in &roadmap.stages {
+                let gaps = stage.gaps.map_or("-".to_string(), |g| {
+                    format!("{:.1} (máx {:.1})", g.median_days, g.max_days)
+                });
+                println!(
+                    "  {:<40}{:>7}{:>6}{:>6.0}  {:<22}{}",
+                    format!("{}. {}", stage.order, stage.name),
+                    format!("{}/{}", stage.completed, stage.topics),
+                    stage.visited,
+                    stage.percent,
+                    gaps,
+                    stage.next.as_deref().unwrap_or("-")
+                );
+            }
+            println!();
+        }
+    }
+    eprintln!("{} alunos", reports.len());
+}
+
+fn print_csv(reports: &[LearnerReport]) {
+    println!(
+        "learner,roadmap,stage,order,topics,completed,visited,percent,gapCount,gapMeanDays,gapMedianDays,gapMaxDays,next"
+    );
+    for learner in reports {
+        for roadmap in &learner.roadmaps {
+            for stage in &roadmap.stages {
+                let gaps = stage.gaps.map_or(",,,".to_string(), |g| {
+                    format!(
+                        "{},{:.2},{:.2},{:.2}",
+                        g.count, g.mean_days, g.median_days, g.max_days
+                    )
+                });
+                println!(
+                    "{},{},{},{},{},{},{},{:.1},{gaps},{}",
+                    csv(&learner.learner),
+                    roadmap.roadmap,
+                    stage.stage,
+                    stage.order,
+                    stage.topics,
+                    stage.completed,
+                    stage.visited,
+                    stage.percent,
+                    stage.next.as_deref().unwrap_or("")
+                );
+            }
+        }
+    }
+}
+
+/// Aspas só quando o campo precisa (RFC 4180). Slugs nunca precisam.
+fn csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,132 @@
+//! O `ProgressMap` de `src/lib/progress.ts`, com as mesmas regras de
+//! `validateProgressMap` e o merge usado na sincronização.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Error, Result};
+
+/// Tamanho máximo de um arquivo exportado ou do corpo de um pedido.
+pub const MAX_FILE_SIZE: usize = 1024 * 1024;
+pub const MAX_ENTRIES: usize = 10_000;
+pub const MAX_SLUG_LENGTH: usize = 100;
+
+/// Keys que o site recusa por poluírem `Object.prototype`.
+const DANGEROUS_KEYS: [&str; 3] = ["__proto__", "constructor", "prototype"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicProgress {
+    pub completed: bool,
+    pub completed_at: Option<String>,
+    pub last_visited_at: String,
+}
+
+/// Progresso por slug de tópico.
+pub type ProgressMap = BTreeMap<String, TopicProgress>;
+
+/// Lê um `ProgressMap` exportado pelo site ou enviado ao servidor.
+pub fn parse(raw: &[u8]) -> Result<ProgressMap> {
+    if raw.len() > MAX_FILE_SIZE {
+        return Err(Error::TooLarge { size: raw.len() });
+    }
+    let value: Value = serde_json::from_slice(raw)?;
+    validate(&value)
+}
+
+/// `validateProgressMap`: mesmas checagens, na mesma ordem e com as mesmas
+/// mensagens, para o site e o servidor recusarem os mesmos arquivos.
+pub fn validate(value: &Value) -> Result<ProgressMap> {
+    let Value::Object(raw) = value else {
+        return Err(Error::NotAnObject);
+    };
+    if raw.len() > MAX_ENTRIES {
+        return Err(Error::TooManyEntries(raw.len()));
+    }
+
+    let mut map = ProgressMap::new();
+    for (key, entry) in raw {
+        if DANGEROUS_KEYS.contains(&key.as_str()) {
+            return Err(Error::DangerousKey(key.clone()));
+        }
+        if !is_valid_slug(key) {
+            return Err(Error::InvalidSlug(key.clone()));
+        }
+        let progress = topic_progress(entry).ok_or_else(|| Error::InvalidEntry(key.clone()))?;
+        map.insert(key.clone(), progress);
+    }
+    Ok(map)
+}
+
+fn topic_progress(value: &Value) -> Option<TopicProgress> {
+    let Value::Object(entry) = value else {
+        return None;
+    };
+    let completed = entry.get("completed")?.as_bool()?;
+    let completed_at = match entry.get("completedAt")? {
+        Value::Null => None,
+        Value::String(date) if is_iso_date(date) => Some(date.clone()),
+        _ => return None,
+    };
+    let last_visited_at = entry
+        .get("lastVisitedAt")?
+        .as_str()
+        .filter(|date| is_iso_date(date))?
+        .to_string();
+    Some(TopicProgress {
+        completed,
+        completed_at,
+        last_visited_at,
+    })
+}
+
+/// `^[a-z0-9]+(?:-[a-z0-9]+)*$`, com no máximo [`MAX_SLUG_LENGTH`] bytes.
+pub fn is_valid_slug(slug: &str) -> bool {
+    slug.len() <= MAX_SLUG_LENGTH
+        && slug.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+}
+
+/// `^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}.\d{3}Z$`, o formato de
+/// `Date.toISOString()`. Como a largura é fixa, comparar as strings compara
+/// as datas.
+pub fn is_iso_date(date: &str) -> bool {
+    const PATTERN: &[u8; 24] = b"dddd-dd-ddTdd:dd:dd.dddZ";
+    let chars: Vec<char> = date.chars().collect();
+    chars.len() == PATTERN.len()
+        && chars.iter().zip(PATTERN).all(|(&c, &p)| match p {
+            b'd' => c.is_ascii_digit(),
+            // O `.` da regex do site não está escapado: aceita qualquer caractere
+            b'.' => c != '\n' && c != '\r',
+            p => c == p as char,
+        })
+}
+
+/// Junta dois registros do mesmo tópico. A visita mais recente vence e o
+/// tópico concluído em qualquer lado fica concluído, com a conclusão mais
+/// recente. Ordem e repetição não mudam o resultado, então dispositivos
+/// podem sincronizar em qualquer ordem.
+pub fn merge_topic(a: &TopicProgress, b: &TopicProgress) -> TopicProgress {
+    TopicProgress {
+        completed: a.completed || b.completed,
+        completed_at: a.completed_at.clone().max(b.completed_at.clone()),
+        last_visited_at: a.last_visited_at.clone().max(b.last_visited_at.clone()),
+    }
+}
+
+/// [`merge_topic`] slug a slug; tópicos só de um lado entram como estão.
+pub fn merge(into: &mut ProgressMap, other: &ProgressMap) {
+    for (slug, progress) in other {
+        let merged = match into.get(slug) {
+            Some(existing) => merge_topic(existing, progress),
+            None => progress.clone(),
+        };
+        into.insert(slug.clone(), merged);
+    }
+}
@@ -0,0 +1,233 @@
+//! Progresso de cada aluno nas etapas das trilhas de `data/registry/roadmaps/`.
+//!
+//! O formato guarda só a última visita e a última conclusão de cada tópico,
+//! então o intervalo entre visitas é medido entre as últimas visitas de
+//! tópicos diferentes da mesma etapa: mostra o ritmo de quem avança pela
+//! etapa, não quantas vezes um tópico foi relido.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use themelion_content::Content;
+use themelion_content::model::{Roadmap, Stage, TopicStatus, TopicSummary};
+
+use crate::ProgressMap;
+
+const MS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Um arquivo exportado e o nome de quem o exportou.
+pub struct Learner {
+    pub name: String,
+    pub progress: ProgressMap,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LearnerReport {
+    pub learner: String,
+    pub roadmaps: Vec<RoadmapReport>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoadmapReport {
+    pub roadmap: String,
+    pub name: String,
+    pub topics: usize,
+    pub completed: usize,
+    pub percent: f64,
+    /// Recomendação da primeira etapa que ainda tem o que estudar.
+    pub next: Option<String>,
+    pub stages: Vec<StageReport>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageReport {
+    pub stage: String,
+    pub name: String,
+    pub order: u32,
+    pub topics: usize,
+    pub completed: usize,
+    pub visited: usize,
+    pub percent: f64,
+    pub gaps: Option<Gaps>,
+    pub next: Option<String>,
+}
+
+/// Intervalos, em dias, entre visitas consecutivas a tópicos da etapa.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gaps {
+    pub count: usize,
+    pub mean_days: f64,
+    pub median_days: f64,
+    pub max_days: f64,
+}
+
+/// Tópicos do registry por slug, para status e pré-requisitos.
+pub struct Topics<'a>(HashMap<&'a str, &'a TopicSummary>);
+
+impl<'a> Topics<'a> {
+    pub fn new(content: &'a Content) -> Self {
+        let topics = content
+            .rooms
+            .iter()
+            .flat_map(|room| &room.categories)
+            .flat_map(|category| &category.topics)
+            .map(|topic| (topic.slug.as_str(), topic))
+            .collect();
+        Self(topics)
+    }
+
+    fn implemented(&self, slug: &str) -> bool {
+        self.0
+            .get(slug)
+            .is_some_and(|t| t.status == Some(TopicStatus::Implemented))
+    }
+
+    fn prerequisites(&self, slug: &str) -> &'a [String] {
+        self.0.get(slug).map_or(&[], |t| &t.prerequisites)
+    }
+}
+
+/// Trilhas em que o aluno visitou pelo menos um tópico.
+pub fn touched<'a>(roadmaps: &'a [Roadmap], progress: &ProgressMap) -> Vec<&'a Roadmap> {
+    roadmaps
+        .iter()
+        .filter(|roadmap| {
+            roadmap
+                .stages
+                .iter()
+                .flat_map(|stage| &stage.topics)
+                .any(|slug| progress.contains_key(slug))
+        })
+        .collect()
+}
+
+pub fn report(topics: &Topics, learner: &Learner, roadmaps: &[&Roadmap]) -> LearnerReport {
+    let roadmaps = roadmaps
+        .iter()
+        .map(|roadmap| {
+            let mut stages: Vec<&Stage> = roadmap
+                .stages
+                .iter()
+                .filter(|s| !s.topics.is_empty())
+                .collect();
+            stages.sort_by_key(|s| s.order);
+            let stages: Vec<StageReport> = stages
+                .into_iter()
+                .map(|stage| stage_report(topics, &learner.progress, stage))
+                .collect();
+            let total = stages.iter().map(|s| s.topics).sum();
+            let completed = stages.iter().map(|s| s.completed).sum();
+            RoadmapReport {
+                roadmap: roadmap.slug.clone(),
+                name: roadmap.name.clone(),
+                topics: total,
+                completed,
+                percent: percent(completed, total),
+                next: stages.iter().find_map(|s| s.next.clone()),
+                stages,
+            }
+        })
+        .collect();
+    LearnerReport {
+        learner: learner.name.clone(),
+        roadmaps,
+    }
+}
+
+fn stage_report(topics: &Topics, progress: &ProgressMap, stage: &Stage) -> StageReport {
+    let done = |slug: &str| progress.get(slug).is_some_and(|p| p.completed);
+    let completed = stage.topics.iter().filter(|slug| done(slug)).count();
+    let mut visits: Vec<i64> = stage
+        .topics
+        .iter()
+        .filter_map(|slug| progress.get(slug))
+        .filter_map(|p| millis(&p.last_visited_at))
+        .collect();
+    visits.sort_unstable();
+
+    StageReport {
+        stage: stage.slug.clone(),
+        name: stage.name.clone(),
+        order: stage.order,
+        topics: stage.topics.len(),
+        completed,
+        visited: visits.len(),
+        percent: percent(completed, stage.topics.len()),
+        gaps: gaps(&visits),
+        next: next(topics, progress, stage),
+    }
+}
+
+/// Primeiro tópico da etapa, na ordem da trilha, que ainda não foi concluído,
+/// tem conteúdo e tem os pré-requisitos concluídos. Sem nenhum assim, o
+/// primeiro não concluído com conteúdo.
+fn next(topics: &Topics, progress: &ProgressMap, stage: &Stage) -> Option<String> {
+    let done = |slug: &str| progress.get(slug).is_some_and(|p| p.completed);
+    let mut pending = stage
+        .topics
+        .iter()
+        .filter(|slug| !done(slug) && topics.implemented(slug));
+    let first = pending.clone().next();
+    pending
+        .find(|slug| topics.prerequisites(slug).iter().all(|p| done(p)))
+        .or(first)
+        .cloned()
+}
+
+fn gaps(visits: &[i64]) -> Option<Gaps> {
+    let mut days: Vec<f64> = visits
+        .windows(2)
+        .map(|w| (w[1] - w[0]) as f64 / MS_PER_DAY)
+        .collect();
+    if days.is_empty() {
+        return None;
+    }
+    days.sort_by(f64::total_cmp);
+    let mid = days.len() / 2;
+    let median = if days.len().is_multiple_of(2) {
+        (days[mid - 1] + days[mid]) / 2.0
+    } else {
+        days[mid]
+    };
+    Some(Gaps {
+        count: days.len(),
+        mean_days: days.iter().sum::<f64>() / days.len() as f64,
+        median_days: median,
+        max_days: days[days.len() - 1],
+    })
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+/// Milissegundos desde a época de uma data de `Date.toISOString()`, já
+/// validada por [`is_iso_date`](crate::progress::is_iso_date).
+fn millis(date: &str) -> Option<i64> {
+    let field = |range: std::ops::Range<usize>| date.get(range)?.parse::<i64>().ok();
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute, second, ms) = (
+        field(11..13)?,
+        field(14..16)?,
+        field(17..19)?,
+        field(20..23)?,
+    );
+
+    // Dias desde 1970-01-01 no calendário gregoriano proléptico
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    Some((((days * 24 + hour) * 60 + minute) * 60 + second) * 1000 + ms)
+}
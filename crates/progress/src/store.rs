@@ -0,0 +1,164 @@
+//! Progresso sincronizado, em SQLite.
+//!
+//! Cada token anônimo tem seu próprio `ProgressMap`. O banco guarda só o
+//! SHA-256 do token: quem lê o arquivo não consegue se passar por ninguém.
+
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior, params};
+use sha2::{Digest, Sha256};
+
+use crate::progress::{MAX_ENTRIES, ProgressMap, TopicProgress, merge};
+use crate::{Error, Result};
+
+/// Bytes aleatórios por token; vira o dobro em caracteres hexadecimais.
+const TOKEN_BYTES: usize = 32;
+
+const SCHEMA: &str = "
+    PRAGMA journal_mode = WAL;
+    PRAGMA foreign_keys = ON;
+    CREATE TABLE IF NOT EXISTS tokens (
+        hash BLOB PRIMARY KEY,
+        created_at INTEGER NOT NULL
+    ) WITHOUT ROWID;
+    CREATE TABLE IF NOT EXISTS progress (
+        token BLOB NOT NULL REFERENCES tokens (hash) ON DELETE CASCADE,
+        slug TEXT NOT NULL,
+        completed INTEGER NOT NULL,
+        completed_at TEXT,
+        last_visited_at TEXT NOT NULL,
+        PRIMARY KEY (token, slug)
+    ) WITHOUT ROWID;
+";
+
+pub struct Store {
+    conn: Mutex<Connection>,
+}
+
+impl Store {
+    /// Abre (ou cria) o banco em `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Cria um token novo, com progresso vazio.
+    pub fn create_token(&self) -> Result<String> {
+        let mut bytes = [0u8; TOKEN_BYTES];
+        getrandom::fill(&mut bytes)?;
+        let token: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        self.conn().execute(
+            "INSERT INTO tokens (hash, created_at) VALUES (?1, ?2)",
+            params![hash(&token), created_at],
+        )?;
+        Ok(token)
+    }
+
+    pub fn load(&self, token: &str) -> Result<ProgressMap> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let hash = known(&tx, token)?;
+        let map = read(&tx, &hash)?;
+        tx.commit()?;
+        Ok(map)
+    }
+
+    /// Junta `incoming` ao progresso do token e devolve o resultado, que o
+    /// cliente grava no lugar do seu.
+    pub fn sync(&self, token: &str, incoming: &ProgressMap) -> Result<ProgressMap> {
+        let mut conn = self.conn();
+        // Imediata: outro processo no mesmo arquivo não intercala leitura e escrita
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let hash = known(&tx, token)?;
+        let stored = read(&tx, &hash)?;
+        let mut merged = stored.clone();
+        merge(&mut merged, incoming);
+        if merged.len() > MAX_ENTRIES {
+            return Err(Error::MergeTooLarge);
+        }
+
+        {
+            let mut upsert = tx.prepare(
+                "INSERT INTO progress (token, slug, completed, completed_at, last_visited_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (token, slug) DO UPDATE SET
+                     completed = excluded.completed,
+                     completed_at = excluded.completed_at,
+                     last_visited_at = excluded.last_visited_at",
+            )?;
+            for (slug, progress) in &merged {
+                if stored.get(slug) == Some(progress) {
+                    continue;
+                }
+                upsert.execute(params![
+                    hash,
+                    slug,
+                    progress.completed,
+                    progress.completed_at,
+                    progress.last_visited_at
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(merged)
+    }
+
+    /// Apaga o token e todo o progresso dele.
+    pub fn delete(&self, token: &str) -> Result<()> {
+        let removed = self
+            .conn()
+            .execute("DELETE FROM tokens WHERE hash = ?1", params![hash(token)])?;
+        if removed == 0 {
+            return Err(Error::UnknownToken);
+        }
+        Ok(())
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // Uma thread que entrou em pânico não deixa transação aberta: o
+        // rollback acontece no drop
+        self.conn.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn hash(token: &str) -> Vec<u8> {
+    Sha256::digest(token.as_bytes()).to_vec()
+}
+
+fn known(conn: &Connection, token: &str) -> Result<Vec<u8>> {
+    let hash = hash(token);
+    conn.query_row(
+        "SELECT 1 FROM tokens WHERE hash = ?1",
+        params![hash],
+        |_| Ok(()),
+    )
+    .optional()?
+    .ok_or(Error::UnknownToken)?;
+    Ok(hash)
+}
+
+fn read(conn: &Connection, hash: &[u8]) -> Result<ProgressMap> {
+    let mut select = conn.prepare(
+        "SELECT slug, completed, completed_at, last_visited_at FROM progress WHERE token = ?1",
+    )?;
+    let rows = select.query_map(params![hash], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            TopicProgress {
+                completed: row.get(1)?,
+                completed_at: row.get(2)?,
+                last_visited_at: row.get(3)?,
+            },
+        ))
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
@@ -0,0 +1,150 @@
+use serde_json::json;
+use themelion_progress::progress::{
+    MAX_ENTRIES, is_iso_date, is_valid_slug, merge_topic, validate,
+};
+use themelion_progress::{Error, ProgressMap, TopicProgress, merge, parse};
+
+const VISITED: &str = "2025-03-01T10:00:00.000Z";
+
+fn entry(completed: bool, completed_at: Option<&str>, last_visited_at: &str) -> TopicProgress {
+    TopicProgress {
+        completed,
+        completed_at: completed_at.map(str::to_string),
+        last_visited_at: last_visited_at.to_string(),
+    }
+}
+
+#[test]
+fn accepts_an_exported_map() {
+    let raw = json!({
+        "big-o-notation": { "completed": true, "completedAt": VISITED, "lastVisitedAt": VISITED },
+        "arrays": { "completed": false, "completedAt": null, "lastVisitedAt": VISITED },
+    });
+    let map = parse(raw.to_string().as_bytes()).unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["arrays"], entry(false, None, VISITED));
+}
+
+#[test]
+fn accepts_only_to_iso_string_dates() {
+    assert!(is_iso_date("2025-03-01T10:00:00.000Z"));
+    for date in [
+        "2025-03-01",
+        "2025-03-01T10:00:00Z",
+        "2025-03-01T10:00:00.000+03:00",
+        "2025-3-01T10:00:00.000Z",
+        " 2025-03-01T10:00:00.000Z",
+        "2025-03-01T10:00:00.0000Z",
+    ] {
+        assert!(!is_iso_date(date), "{date}");
+    }
+}
+
+#[test]
+fn rejects_bad_dates_in_entries() {
+    for entry in [
+        json!({ "completed": false, "completedAt": null, "lastVisitedAt": "ontem" }),
+        json!({ "completed": true, "completedAt": "2025-03-01", "lastVisitedAt": VISITED }),
+        json!({ "completed": false, "completedAt": null, "lastVisitedAt": 1_740_823_200 }),
+        json!({ "completed": false, "lastVisitedAt": VISITED }),
+    ] {
+        let err = validate(&json!({ "arrays": entry })).unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidEntry(slug) if slug == "arrays"),
+            "{err}"
+        );
+    }
+}
+
+#[test]
+fn rejects_an_unknown_completion_status() {
+    for completed in [json!("sim"), json!(1), json!(null)] {
+        let raw = json!({
+            "arrays": { "completed": completed, "completedAt": null, "lastVisitedAt": VISITED },
+        });
+        assert!(matches!(validate(&raw), Err(Error::InvalidEntry(_))));
+    }
+}
+
+#[test]
+fn rejects_what_the_site_rejects() {
+    assert!(matches!(validate(&json!([])), Err(Error::NotAnObject)));
+    assert!(matches!(validate(&json!(null)), Err(Error::NotAnObject)));
+
+    let valid = json!({ "completed": false, "completedAt": null, "lastVisitedAt": VISITED });
+    let err = validate(&json!({ "__proto__": valid })).unwrap_err();
+    assert_eq!(err.to_string(), "Key perigosa detectada: \"__proto__\".");
+    assert!(matches!(
+        validate(&json!({ "Arrays": valid })),
+        Err(Error::InvalidSlug(_))
+    ));
+    assert!(matches!(
+        validate(&json!({ "a--b": valid })),
+        Err(Error::InvalidSlug(_))
+    ));
+
+    assert!(is_valid_slug(&"a".repeat(100)));
+    assert!(!is_valid_slug(&"a".repeat(101)));
+    assert!(!is_valid_slug("-arrays"));
+
+    let too_many: serde_json::Map<_, _> = (0..=MAX_ENTRIES)
+        .map(|i| (format!("t{i}"), valid.clone()))
+        .collect();
+    assert!(matches!(
+        validate(&serde_json::Value::Object(too_many)),
+        Err(Error::TooManyEntries(n)) if n == MAX_ENTRIES + 1
+    ));
+
+    let big = vec![b' '; 1024 * 1024 + 1];
+    assert!(matches!(parse(&big), Err(Error::TooLarge { .. })));
+}
+
+#[test]
+fn newer_visit_and_completion_win() {
+    let older = entry(
+        true,
+        Some("2025-01-01T00:00:00.000Z"),
+        "2025-01-02T00:00:00.000Z",
+    );
+    let newer = entry(false, None, "2025-02-01T00:00:00.000Z");
+
+    let merged = merge_topic(&older, &newer);
+    assert_eq!(
+        merged,
+        entry(
+            true,
+            Some("2025-01-01T00:00:00.000Z"),
+            "2025-02-01T00:00:00.000Z"
+        )
+    );
+    // Ordem e repetição não mudam o resultado
+    assert_eq!(merge_topic(&newer, &older), merged);
+    assert_eq!(merge_topic(&merged, &older), merged);
+
+    let recompleted = entry(
+        true,
+        Some("2025-03-01T00:00:00.000Z"),
+        "2025-03-01T00:00:00.000Z",
+    );
+    assert_eq!(merge_topic(&merged, &recompleted), recompleted);
+}
+
+#[test]
+fn merges_maps_slug_by_slug() {
+    let mut local = ProgressMap::new();
+    local.insert(
+        "arrays".into(),
+        entry(false, None, "2025-01-01T00:00:00.000Z"),
+    );
+    let mut remote = ProgressMap::new();
+    remote.insert(
+        "arrays".into(),
+        entry(false, None, "2025-02-01T00:00:00.000Z"),
+    );
+    remote.insert("tries".into(), entry(false, None, VISITED));
+
+    merge(&mut local, &remote);
+    assert_eq!(local.len(), 2);
+    assert_eq!(local["arrays"].last_visited_at, "2025-02-01T00:00:00.000Z");
+    assert_eq!(local["tries"], remote["tries"]);
+}
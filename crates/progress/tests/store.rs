@@ -0,0 +1,69 @@
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+use themelion_progress::{Error, ProgressMap, Store, TopicProgress};
+
+fn visit(date: &str) -> TopicProgress {
+    TopicProgress {
+        completed: false,
+        completed_at: None,
+        last_visited_at: date.to_string(),
+    }
+}
+
+#[test]
+fn stores_only_the_token_hash() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("progress.db");
+    let store = Store::open(&path).unwrap();
+    let token = store.create_token().unwrap();
+    assert_eq!(token.len(), 64);
+    assert!(token.bytes().all(|b| b.is_ascii_hexdigit()));
+
+    let conn = Connection::open(&path).unwrap();
+    let hashes: Vec<Vec<u8>> = conn
+        .prepare("SELECT hash FROM tokens")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+    assert_eq!(hashes, [Sha256::digest(token.as_bytes()).to_vec()]);
+}
+
+#[test]
+fn looks_progress_up_by_token() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::open(&dir.path().join("progress.db")).unwrap();
+    let alice = store.create_token().unwrap();
+    let bob = store.create_token().unwrap();
+    assert_ne!(alice, bob);
+
+    let mut phone = ProgressMap::new();
+    phone.insert("arrays".into(), visit("2025-02-01T00:00:00.000Z"));
+    store.sync(&alice, &phone).unwrap();
+
+    let mut laptop = ProgressMap::new();
+    laptop.insert("arrays".into(), visit("2025-01-01T00:00:00.000Z"));
+    laptop.insert("tries".into(), visit("2025-01-01T00:00:00.000Z"));
+    let merged = store.sync(&alice, &laptop).unwrap();
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged["arrays"].last_visited_at, "2025-02-01T00:00:00.000Z");
+
+    assert_eq!(store.load(&alice).unwrap(), merged);
+    assert!(store.load(&bob).unwrap().is_empty());
+}
+
+#[test]
+fn rejects_unknown_and_deleted_tokens() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Store::open(&dir.path().join("progress.db")).unwrap();
+    assert!(matches!(
+        store.load("0".repeat(64).as_str()),
+        Err(Error::UnknownToken)
+    ));
+
+    let token = store.create_token().unwrap();
+    store.delete(&token).unwrap();
+    assert!(matches!(store.load(&token), Err(Error::UnknownToken)));
+    assert!(matches!(store.delete(&token), Err(Error::UnknownToken)));
+}
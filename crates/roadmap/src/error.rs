@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Tópico \"{0}\" não existe em data/registry/rooms/")]
+    UnknownTopic(String),
+
+    #[error("Tópico \"{slug}\" existe em mais de uma sala; use um de: {}", candidates.join(", "))]
+    AmbiguousTopic {
+        slug: String,
+        candidates: Vec<String>,
+    },
+
+    #[error("Pré-requisitos em ciclo: {}", .0.join(", "))]
+    Cycle(Vec<String>),
+}
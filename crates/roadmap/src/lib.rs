@@ -0,0 +1,173 @@
+//! Rascunho de trilha a partir do grafo de pré-requisitos.
+//!
+//! Dados os tópicos-alvo, [`plan`] junta todos os pré-requisitos transitivos,
+//! descarta os que o nível de senioridade já pressupõe e agrupa o resto em
+//! etapas por profundidade e por sala. [`yaml::render`] grava o resultado no
+//! mesmo formato dos arquivos de `data/registry/roadmaps/`.
+
+mod error;
+pub mod yaml;
+
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+
+use themelion_content::model::{Difficulty, Stage};
+use themelion_graph::{Analysis, Graph};
+
+pub use error::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Senioridade da trilha, o mesmo sufixo dos slugs em `data/registry/roadmaps/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Junior,
+    Pleno,
+    Senior,
+}
+
+impl Level {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Junior => "junior",
+            Level::Pleno => "pleno",
+            Level::Senior => "senior",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Junior => "Júnior",
+            Level::Pleno => "Pleno",
+            Level::Senior => "Sênior",
+        }
+    }
+
+    /// Pré-requisitos que o nível já pressupõe e que ficam fora do rascunho.
+    /// Os alvos entram sempre.
+    pub fn assumes(self, difficulty: Difficulty) -> bool {
+        match self {
+            Level::Junior => false,
+            Level::Pleno => difficulty == Difficulty::Beginner,
+            Level::Senior => difficulty != Difficulty::Advanced,
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "junior" => Ok(Level::Junior),
+            "pleno" => Ok(Level::Pleno),
+            "senior" => Ok(Level::Senior),
+            other => Err(format!(
+                "nível \"{other}\" inválido (use junior, pleno ou senior)"
+            )),
+        }
+    }
+}
+
+/// Resolve um alvo escrito como `slug` ou `sala/slug`.
+pub fn resolve_target(graph: &Graph, target: &str) -> Result<usize> {
+    if target.contains('/') {
+        return graph
+            .node_by_id(target)
+            .ok_or_else(|| Error::UnknownTopic(target.to_string()));
+    }
+    match graph.find(target) {
+        [] => Err(Error::UnknownTopic(target.to_string())),
+        [node] => Ok(*node),
+        nodes => Err(Error::AmbiguousTopic {
+            slug: target.to_string(),
+            candidates: nodes.iter().map(|&n| graph.nodes[n].id.clone()).collect(),
+        }),
+    }
+}
+
+/// Etapas do rascunho, em ordem: primeiro pela profundidade, depois pela
+/// ordem das salas. Dentro de uma etapa os tópicos seguem a ordem do registry.
+///
+/// A profundidade conta só os tópicos que entram no rascunho: um
+/// pré-requisito descartado pelo nível não empurra quem depende dele para a
+/// etapa seguinte.
+pub fn plan(
+    graph: &Graph,
+    analysis: &Analysis,
+    targets: &[usize],
+    level: Level,
+) -> Result<Vec<Stage>> {
+    // Fecho transitivo dos alvos
+    let mut reached: HashSet<usize> = HashSet::new();
+    let mut pending: Vec<usize> = targets.to_vec();
+    while let Some(node) = pending.pop() {
+        if !reached.insert(node) {
+            continue;
+        }
+        if let Some(cycle) = analysis.cycle_of(node) {
+            return Err(Error::Cycle(
+                analysis.cycles[cycle]
+                    .iter()
+                    .map(|&n| graph.nodes[n].id.clone())
+                    .collect(),
+            ));
+        }
+        pending.extend(graph.prerequisites(node));
+    }
+
+    let included =
+        |node: usize| targets.contains(&node) || !level.assumes(graph.nodes[node].difficulty);
+
+    // `analysis.order` já traz pré-requisitos antes de quem depende deles
+    let mut depth = vec![0usize; graph.nodes.len()];
+    let mut groups: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+    let room_index = |room: &str| {
+        graph
+            .rooms
+            .iter()
+            .position(|r| r.slug == room)
+            .expect("toda sala de um nó está em graph.rooms")
+    };
+    for &node in analysis.order.iter().filter(|n| reached.contains(n)) {
+        depth[node] = graph
+            .prerequisites(node)
+            .into_iter()
+            .map(|p| depth[p] + usize::from(included(p)))
+            .max()
+            .unwrap_or(0);
+        if included(node) {
+            groups
+                .entry((depth[node], room_index(&graph.nodes[node].room)))
+                .or_default()
+                .push(node);
+        }
+    }
+
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut stages = Vec::new();
+    for ((depth, room), mut nodes) in groups {
+        nodes.sort_unstable();
+        // O mesmo slug em duas salas vira um só tópico na trilha
+        let topics: Vec<String> = nodes
+            .iter()
+            .map(|&n| graph.nodes[n].slug.as_str())
+            .filter(|slug| seen.insert(slug))
+            .map(str::to_string)
+            .collect();
+        if topics.is_empty() {
+            continue;
+        }
+        let info = &graph.rooms[room];
+        let step = depth + 1;
+        stages.push(Stage {
+            slug: format!("{}-etapa-{step}", info.slug),
+            name: format!("{} — Etapa {step}", info.name),
+            description: "Rascunho gerado a partir dos pré-requisitos: revise nome e descrição"
+                .to_string(),
+            order: stages.len() as u32 + 1,
+            topics,
+        });
+    }
+    Ok(stages)
+}
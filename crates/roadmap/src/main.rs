@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail, ensure};
+use clap::Parser;
+use themelion_content::registry::RoadmapFile;
+use themelion_graph::{Analysis, Graph};
+use themelion_roadmap::{Level, plan, resolve_target, yaml};
+
+/// Gera um rascunho de trilha com os pré-requisitos transitivos dos alvos,
+/// em etapas por profundidade e por sala.
+#[derive(Parser)]
+#[command(name = "themelion-roadmap", version)]
+struct Cli {
+    /// Slug da trilha, ex.: backend-developer-junior
+    #[arg(value_parser = parse_slug)]
+    roadmap: String,
+
+    /// Tópico-alvo (`slug` ou `sala/slug`); pode repetir
+    #[arg(short, long = "target", required = true)]
+    targets: Vec<String>,
+
+    /// junior, pleno ou senior; por padrão vem do sufixo do slug
+    #[arg(long)]
+    level: Option<Level>,
+
+    /// Nome da trilha (padrão: o do YAML existente)
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Descrição da trilha (padrão: a do YAML existente)
+    #[arg(long)]
+    description: Option<String>,
+
+    /// Ícone da trilha (padrão: o do YAML existente ou o da sala do primeiro alvo)
+    #[arg(long)]
+    icon: Option<String>,
+
+    /// Grava em data/registry/roadmaps/<trilha>.yaml em vez de imprimir
+    #[arg(long)]
+    write: bool,
+
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let level = match cli.level {
+        Some(level) => level,
+        None => [Level::Junior, Level::Pleno, Level::Senior]
+            .into_iter()
+            .find(|l| cli.roadmap.ends_with(&format!("-{}", l.as_str())))
+            .with_context(|| {
+                format!(
+                    "Não dá para deduzir o nível de \"{}\"; use --level",
+                    cli.roadmap
+                )
+            })?,
+    };
+
+    let path = cli
+        .root
+        .join("data/registry/roadmaps")
+        .join(format!("{}.yaml", cli.roadmap));
+    let existing: Option<RoadmapFile> = if path.is_file() {
+        let raw =
+            fs::read_to_string(&path).with_context(|| format!("Erro ao ler {}", path.display()))?;
+        Some(
+            serde_yaml::from_str(&raw)
+                .with_context(|| format!("YAML inválido em {}", path.display()))?,
+        )
+    } else {
+        None
+    };
+    let header = |value: Option<String>, from_file: fn(&RoadmapFile) -> &String| {
+        value.or_else(|| existing.as_ref().map(|r| from_file(r).clone()))
+    };
+
+    let graph = Graph::load(&cli.root).context("Erro ao carregar o registry")?;
+    let analysis = Analysis::new(&graph);
+    let targets = cli
+        .targets
+        .iter()
+        .map(|t| resolve_target(&graph, t))
+        .collect::<themelion_roadmap::Result<Vec<_>>>()?;
+    let stages = plan(&graph, &analysis, &targets, level)?;
+
+    let roadmap = RoadmapFile {
+        roadmap: cli.roadmap.clone(),
+        name: header(cli.name, |r| &r.name)
+            .unwrap_or_else(|| format!("{} — {}", cli.roadmap, level.label())),
+        description: header(cli.description, |r| &r.description).unwrap_or_default(),
+        icon: header(cli.icon, |r| &r.icon).unwrap_or_else(|| {
+            let room = &graph.nodes[targets[0]].room;
+            graph
+                .rooms
+                .iter()
+                .find(|r| &r.slug == room)
+                .map(|r| r.icon.clone())
+                .unwrap_or_default()
+        }),
+        stages,
+    };
+    let rendered = yaml::render(&roadmap);
+
+    // O rascunho precisa ser lido de volta pelo mesmo schema do scanContent
+    let parsed: RoadmapFile =
+        serde_yaml::from_str(&rendered).context("YAML gerado não segue o schema de trilhas")?;
+    ensure!(
+        parsed.stages == roadmap.stages,
+        "YAML gerado não corresponde às etapas calculadas"
+    );
+
+    let topics: usize = roadmap.stages.iter().map(|s| s.topics.len()).sum();
+    if cli.write {
+        if roadmap.description.is_empty() {
+            bail!("Trilha nova precisa de --description");
+        }
+        fs::write(&path, rendered).with_context(|| format!("Erro ao gravar {}", path.display()))?;
+        eprintln!(
+            "{} etapas, {topics} tópicos → {}",
+            roadmap.stages.len(),
+            path.display()
+        );
+    } else {
+        print!("{rendered}");
+        eprintln!("{} etapas, {topics} tópicos", roadmap.stages.len());
+    }
+    Ok(())
+}
+
+/// O slug vira nome de arquivo em `data/registry/roadmaps/`: só
+/// `[a-z0-9]+(-[a-z0-9]+)*`, sem `/` nem `..` para sair do diretório.
+fn parse_slug(value: &str) -> Result<String, String> {
+    let valid = value.split('-').all(|part| {
+        !part.is_empty()
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    });
+    if valid {
+        Ok(value.to_string())
+    } else {
+        Err("use só letras minúsculas, dígitos e hífens (ex.: backend-developer-junior)".into())
+    }
+}
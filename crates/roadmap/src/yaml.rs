@@ -0,0 +1,37 @@
+//! Escrita no mesmo estilo dos YAMLs feitos à mão em `data/registry/roadmaps/`:
+//! strings entre aspas e uma linha em branco entre etapas.
+
+use std::fmt::Write as _;
+
+use themelion_content::registry::RoadmapFile;
+
+pub fn render(roadmap: &RoadmapFile) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "roadmap: {}", roadmap.roadmap);
+    let _ = writeln!(out, "name: {}", quote(&roadmap.name));
+    let _ = writeln!(out, "description: {}", quote(&roadmap.description));
+    let _ = writeln!(out, "icon: {}", quote(&roadmap.icon));
+    out.push_str("\nstages:\n");
+
+    for stage in &roadmap.stages {
+        out.push('\n');
+        let _ = writeln!(out, "  - slug: {}", quote(&stage.slug));
+        let _ = writeln!(out, "    name: {}", quote(&stage.name));
+        let _ = writeln!(out, "    description: {}", quote(&stage.description));
+        let _ = writeln!(out, "    order: {}", stage.order);
+        if stage.topics.is_empty() {
+            out.push_str("    topics: []\n");
+            continue;
+        }
+        out.push_str("    topics:\n");
+        for topic in &stage.topics {
+            let _ = writeln!(out, "      - {}", quote(topic));
+        }
+    }
+    out
+}
+
+fn quote(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
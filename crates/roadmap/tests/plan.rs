@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::Path;
+
+use themelion_content::registry::RoadmapFile;
+use themelion_graph::{Analysis, Graph};
+use themelion_roadmap::{Error, Level, plan, resolve_target, yaml};
+
+/// `(slug, dificuldade, pré-requisitos)` de cada tópico.
+type Topics<'a> = &'a [(&'a str, &'a str, &'a [&'a str])];
+
+fn write_room(root: &Path, room: &str, order: u32, topics: Topics) {
+    let mut yaml = format!(
+        "room: {room}\nname: \"{room}\"\ndescription: \"\"\nicon: \"brain\"\norder: {order}\n\n\
+         categories:\n  - slug: \"geral\"\n    name: \"Geral\"\n    order: 1\n    topics:\n"
+    );
+    for (i, (slug, difficulty, prerequisites)) in topics.iter().enumerate() {
+        let prerequisites: Vec<String> = prerequisites.iter().map(|p| format!("\"{p}\"")).collect();
+        yaml.push_str(&format!(
+            "      - slug: \"{slug}\"\n        title: \"{slug}\"\n        difficulty: {difficulty}\n        \
+             order: {}\n        prerequisites: [{}]\n",
+            i + 1,
+            prerequisites.join(", ")
+        ));
+    }
+    let dir = root.join("data/registry/rooms");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(format!("{room}.yaml")), yaml).unwrap();
+}
+
+/// `fundamentos` com uma cadeia de pré-requisitos e um tópico solto, e
+/// `backend` dependendo dela.
+fn graph() -> Graph {
+    let root = tempfile::tempdir().unwrap();
+    write_room(
+        root.path(),
+        "fundamentos",
+        1,
+        &[
+            ("arrays", "beginner", &[]),
+            ("hash-tables", "intermediate", &["arrays"]),
+            ("b-trees", "advanced", &["hash-tables"]),
+            ("grafos", "beginner", &[]),
+        ],
+    );
+    write_room(
+        root.path(),
+        "backend",
+        2,
+        &[
+            ("sql", "beginner", &[]),
+            ("indices", "advanced", &["b-trees", "sql"]),
+        ],
+    );
+    Graph::load(root.path()).unwrap()
+}
+
+/// `(slug, tópicos)` de cada etapa.
+fn stages(graph: &Graph, targets: &[&str], level: Level) -> Vec<(String, Vec<String>)> {
+    let analysis = Analysis::new(graph);
+    let targets: Vec<usize> = targets
+        .iter()
+        .map(|t| resolve_target(graph, t).unwrap())
+        .collect();
+    plan(graph, &analysis, &targets, level)
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.slug, s.topics))
+        .collect()
+}
+
+fn stage(slug: &str, topics: &[&str]) -> (String, Vec<String>) {
+    (
+        slug.to_string(),
+        topics.iter().map(|t| t.to_string()).collect(),
+    )
+}
+
+#[test]
+fn junior_gets_every_transitive_prerequisite_in_depth_order() {
+    let graph = graph();
+    assert_eq!(
+        stages(&graph, &["indices"], Level::Junior),
+        [
+            stage("fundamentos-etapa-1", &["arrays"]),
+            stage("backend-etapa-1", &["sql"]),
+            stage("fundamentos-etapa-2", &["hash-tables"]),
+            stage("fundamentos-etapa-3", &["b-trees"]),
+            stage("backend-etapa-4", &["indices"]),
+        ]
+    );
+}
+
+#[test]
+fn the_level_drops_what_it_assumes_and_pulls_dependents_forward() {
+    let graph = graph();
+    assert_eq!(
+        stages(&graph, &["indices"], Level::Pleno),
+        [
+            stage("fundamentos-etapa-1", &["hash-tables"]),
+            stage("fundamentos-etapa-2", &["b-trees"]),
+            stage("backend-etapa-3", &["indices"]),
+        ]
+    );
+    assert_eq!(
+        stages(&graph, &["indices"], Level::Senior),
+        [
+            stage("fundamentos-etapa-1", &["b-trees"]),
+            stage("backend-etapa-2", &["indices"]),
+        ]
+    );
+    // Os alvos entram mesmo quando o nível os pressupõe
+    assert_eq!(
+        stages(&graph, &["grafos"], Level::Senior),
+        [stage("fundamentos-etapa-1", &["grafos"])]
+    );
+}
+
+#[test]
+fn rejects_unknown_ambiguous_and_cyclic_targets() {
+    let root = tempfile::tempdir().unwrap();
+    write_room(
+        root.path(),
+        "fundamentos",
+        1,
+        &[
+            ("hash-tables", "beginner", &[]),
+            ("a", "beginner", &["b"]),
+            ("b", "beginner", &["a"]),
+        ],
+    );
+    write_room(
+        root.path(),
+        "backend",
+        2,
+        &[("hash-tables", "beginner", &[])],
+    );
+    let graph = Graph::load(root.path()).unwrap();
+
+    assert!(matches!(
+        resolve_target(&graph, "nao-existe"),
+        Err(Error::UnknownTopic(_))
+    ));
+    match resolve_target(&graph, "hash-tables") {
+        Err(Error::AmbiguousTopic { candidates, .. }) => {
+            assert_eq!(
+                candidates,
+                ["fundamentos/hash-tables", "backend/hash-tables"]
+            );
+        }
+        other => panic!("esperava AmbiguousTopic, veio {other:?}"),
+    }
+    let target = resolve_target(&graph, "backend/hash-tables").unwrap();
+    assert_eq!(graph.nodes[target].id, "backend/hash-tables");
+
+    let cyclic = resolve_target(&graph, "a").unwrap();
+    match plan(&graph, &Analysis::new(&graph), &[cyclic], Level::Junior) {
+        Err(Error::Cycle(ids)) => assert_eq!(ids, ["fundamentos/a", "fundamentos/b"]),
+        other => panic!("esperava Cycle, veio {other:?}"),
+    }
+}
+
+#[test]
+fn renders_a_roadmap_the_registry_reads_back() {
+    let graph = graph();
+    let analysis = Analysis::new(&graph);
+    let target = resolve_target(&graph, "indices").unwrap();
+    let roadmap = RoadmapFile {
+        roadmap: "backend-developer-pleno".to_string(),
+        name: "Backend \"Pleno\"".to_string(),
+        description: "Rascunho".to_string(),
+        icon: "server".to_string(),
+        stages: plan(&graph, &analysis, &[target], Level::Pleno).unwrap(),
+    };
+
+    let rendered = yaml::render(&roadmap);
+    let parsed: RoadmapFile = serde_yaml::from_str(&rendered).unwrap();
+    assert_eq!(parsed.name, roadmap.name);
+    assert_eq!(parsed.stages, roadmap.stages);
+    assert!(rendered.contains("\n\n  - slug: \"fundamentos-etapa-1\"\n"));
+}
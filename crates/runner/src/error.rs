@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Slug inválido: \"{0}\". Apenas letras minúsculas, números e hífens.")]
+    InvalidSlug(String),
+
+    #[error("Código-fonte muito grande ({size} bytes). Máximo: {max} bytes.")]
+    SourceTooLarge { size: usize, max: usize },
+
+    #[error("Linguagem desconhecida: \"{0}\".")]
+    UnknownLanguage(String),
+
+    #[error("Runner \"{runner}\" da linguagem \"{language}\" não está disponível.")]
+    RunnerUnavailable { language: String, runner: String },
+
+    #[error("Arquivo de linguagem inválido ({}): {message}", path.display())]
+    InvalidLanguageFile { path: PathBuf, message: String },
+
+    #[error("Toolchain indisponível: {0}")]
+    Toolchain(String),
+
+    #[error(transparent)]
+    Sandbox(#[from] themelion_sandbox::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl Error {
+    /// Erros causados pelo pedido (e não pelo servidor).
+    pub fn is_client_error(&self) -> bool {
+        matches!(
+            self,
+            Error::InvalidSlug(_)
+                | Error::SourceTooLarge { .. }
+                | Error::UnknownLanguage(_)
+                | Error::RunnerUnavailable { .. }
+        )
+    }
+}
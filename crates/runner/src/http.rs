@@ -0,0 +1,148 @@
+//! Transporte HTTP: `POST /run` com um [`RunRequest`], `GET /runners` e `GET /health`.
+//!
+//! O serviço executa código, então só responde a navegadores nas origens
+//! configuradas: um site qualquer aberto enquanto o runner está no ar não pode
+//! mandar programas para ele. Pedidos sem `Origin` (curl, scripts) passam.
+
+use std::io::Read;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use std::thread;
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+
+use crate::{ErrorReply, Registry, RunRequest};
+
+const MAX_BODY_BYTES: u64 = 256 * 1024;
+
+/// Atende pedidos em `workers` threads até o processo ser encerrado.
+/// `origins` são as origens (`http://localhost:5173`) aceitas via CORS.
+pub fn serve(
+    registry: Registry,
+    addr: impl ToSocketAddrs,
+    workers: usize,
+    origins: Vec<String>,
+) -> std::io::Result<()> {
+    let server = Server::http(addr).map_err(std::io::Error::other)?;
+    let server = Arc::new(server);
+    let registry = Arc::new(registry);
+    let origins = Arc::new(origins);
+
+    let handles: Vec<_> = (0..workers.max(1))
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let registry = Arc::clone(&registry);
+            let origins = Arc::clone(&origins);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(&registry, &origins, request);
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+    Ok(())
+}
+
+fn handle(registry: &Registry, origins: &[String], mut request: Request) {
+    let origin = header_value(&request, "Origin");
+    if let Some(origin) = &origin
+        && !origins.contains(origin)
+    {
+        let _ = request.respond(error(403, format!("Origem não permitida: {origin}")));
+        return;
+    }
+
+    let response = match (request.method(), request.url()) {
+        (Method::Get, "/health") => json(200, &serde_json::json!({ "status": "ok" })),
+        (Method::Get, "/runners") => json(200, &registry.status()),
+        // Preflight do navegador: o editor roda em outra origem no dev server
+        (Method::Options, _) => Response::from_data(Vec::new()).with_status_code(204),
+        (Method::Post, "/run") => match read_request(&mut request) {
+            Ok(run) => match registry.dispatch(&run) {
+                Ok(result) => json(200, &result),
+                Err(err) if err.is_client_error() => error(400, err.to_string()),
+                Err(err) => error(500, err.to_string()),
+            },
+            Err((status, message)) => error(status, message),
+        },
+        _ => error(404, "Rota não encontrada.".to_string()),
+    };
+
+    let response = match &origin {
+        Some(origin) => with_cors(response, origin),
+        None => response,
+    };
+    let _ = request.respond(response);
+}
+
+fn header_value(request: &Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str().to_string())
+}
+
+/// Erros com o status HTTP: 415 sem `Content-Type: application/json`, que
+/// um `<form>` de outra página não consegue mandar sem preflight; 400 no resto.
+fn read_request(request: &mut Request) -> Result<RunRequest, (u16, String)> {
+    let content_type = header_value(request, "Content-Type").unwrap_or_default();
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    if !media_type.eq_ignore_ascii_case("application/json") {
+        return Err((415, "Content-Type deve ser application/json.".to_string()));
+    }
+
+    if request
+        .body_length()
+        .is_some_and(|len| len as u64 > MAX_BODY_BYTES)
+    {
+        return Err((
+            400,
+            format!("Corpo muito grande. Máximo: {MAX_BODY_BYTES} bytes."),
+        ));
+    }
+
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|err| (400, format!("Erro ao ler o corpo: {err}")))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err((
+            400,
+            format!("Corpo muito grande. Máximo: {MAX_BODY_BYTES} bytes."),
+        ));
+    }
+
+    serde_json::from_slice(&body).map_err(|err| (400, format!("JSON inválido: {err}")))
+}
+
+fn json<T: Serialize>(status: u16, body: &T) -> Response<std::io::Cursor<Vec<u8>>> {
+    let data = serde_json::to_vec(body).unwrap_or_default();
+    Response::from_data(data)
+        .with_status_code(StatusCode(status))
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn error(status: u16, message: String) -> Response<std::io::Cursor<Vec<u8>>> {
+    json(status, &ErrorReply { error: message })
+}
+
+/// Só chamada com uma origem já conferida contra a lista.
+fn with_cors<R: Read>(response: Response<R>, origin: &str) -> Response<R> {
+    response
+        .with_header(header("Access-Control-Allow-Origin", origin))
+        .with_header(header("Vary", "Origin"))
+        .with_header(header("Access-Control-Allow-Methods", "GET, POST, OPTIONS"))
+        .with_header(header("Access-Control-Allow-Headers", "Content-Type"))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("header ASCII válido")
+}
@@ -0,0 +1,27 @@
+//! Contrato comum dos runners declarados em `data/languages/*.json`.
+//!
+//! Cada linguagem aponta para um `runner` (ex.: `"rust-runner"`). Este crate
+//! define o que é um runner — o trait [`Runner`], com fases de compilação e
+//! execução, limites de recursos e um único formato de resultado — e expõe
+//! um [`Registry`] que despacha pedidos pelo `languageId`, via JSON-over-stdio
+//! ([`stdio`]) ou HTTP ([`http`]).
+//!
+//! [`rust::RustRunner`] é a implementação de referência.
+
+mod error;
+pub mod http;
+mod limits;
+pub mod process;
+mod protocol;
+mod registry;
+mod runner;
+pub mod rust;
+pub mod stdio;
+
+pub use error::Error;
+pub use limits::{Limits, RunLimits};
+pub use protocol::{Diagnostic, ErrorReply, RunRequest, RunResult, RunStatus};
+pub use registry::{LanguageEntry, Registry, RunnerStatus};
+pub use runner::{Compilation, Runner, validate};
+
+pub type Result<T> = std::result::Result<T, Error>;
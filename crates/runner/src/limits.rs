@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+const MIB: u64 = 1024 * 1024;
+
+/// Limites de recursos aplicados a um processo filho.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Limits {
+    /// Tempo de CPU (RLIMIT_CPU), em segundos.
+    pub cpu_seconds: u64,
+    /// Espaço de endereçamento (RLIMIT_AS), em bytes.
+    pub memory_bytes: u64,
+    /// Tempo de relógio antes de matar o grupo de processos.
+    pub wall_time_ms: u64,
+    /// Máximo de bytes guardados de stdout e de stderr (cada um).
+    pub output_bytes: usize,
+}
+
+impl Limits {
+    /// Limites da compilação: o `rustc`/LLVM reserva bastante memória virtual.
+    pub const COMPILE: Limits = Limits {
+        cpu_seconds: 30,
+        memory_bytes: 4096 * MIB,
+        wall_time_ms: 30_000,
+        output_bytes: 256 * 1024,
+    };
+
+    /// Limites do programa do aluno.
+    pub const EXECUTE: Limits = Limits {
+        cpu_seconds: 2,
+        memory_bytes: 256 * MIB,
+        wall_time_ms: 5_000,
+        output_bytes: 64 * 1024,
+    };
+}
+
+impl From<Limits> for themelion_sandbox::Limits {
+    fn from(limits: Limits) -> Self {
+        Self {
+            cpu_seconds: limits.cpu_seconds,
+            memory_bytes: limits.memory_bytes,
+            wall_time_ms: limits.wall_time_ms,
+            output_bytes: limits.output_bytes,
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::EXECUTE
+    }
+}
+
+/// Limites de cada fase de um runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunLimits {
+    pub compile: Limits,
+    pub execute: Limits,
+}
+
+impl Default for RunLimits {
+    fn default() -> Self {
+        Self {
+            compile: Limits::COMPILE,
+            execute: Limits::EXECUTE,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use themelion_runner::rust::RustRunner;
+use themelion_runner::{Limits, Registry, RunLimits, RunRequest, http, stdio};
+use themelion_sandbox::{CgroupLimits, Sandbox};
+
+#[derive(Parser)]
+#[command(
+    name = "themelion-runner",
+    version,
+    about = "Despacha exemplos para o runner de cada linguagem"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Diretório com os arquivos de linguagem
+    #[arg(long, global = true, default_value = "data/languages")]
+    languages: PathBuf,
+
+    /// Tempo máximo de relógio da execução, em milissegundos
+    #[arg(long, global = true, default_value_t = Limits::EXECUTE.wall_time_ms)]
+    timeout_ms: u64,
+
+    /// Memória máxima (espaço de endereçamento) da execução, em MiB
+    #[arg(long, global = true, default_value_t = Limits::EXECUTE.memory_bytes / (1024 * 1024))]
+    memory_mb: u64,
+
+    /// Executa os programas só com rlimits, sem namespaces nem seccomp. O
+    /// padrão é isolar (Linux)
+    #[arg(long, global = true, conflicts_with = "cgroup")]
+    no_sandbox: bool,
+
+    /// Cgroup v2 delegado (com memory, cpu e pids em cgroup.subtree_control)
+    /// onde cada execução ganha um cgroup próprio
+    #[arg(long, global = true)]
+    cgroup: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Sobe o serviço HTTP (POST /run, GET /runners, GET /health)
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        addr: String,
+        #[arg(long, default_value_t = 2)]
+        workers: usize,
+        /// Origem que pode chamar o serviço do navegador (repetível); as
+        /// demais recebem 403
+        #[arg(long = "allow-origin", default_value = "http://localhost:5173")]
+        allow_origins: Vec<String>,
+    },
+    /// Lê pedidos JSON do stdin (um por linha) e responde no stdout
+    Stdio,
+    /// Executa um arquivo local e imprime o resultado em JSON
+    Run {
+        /// Ex.: data/content/fundamentos/complexidade/big-o-notation/examples/rust.rs
+        file: PathBuf,
+        /// Slug do tópico (padrão: inferido de `<slug>/examples/<arquivo>`)
+        #[arg(long)]
+        topic: Option<String>,
+        /// Arquivo cujo conteúdo vira o stdin do programa
+        #[arg(long)]
+        stdin: Option<PathBuf>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let limits = RunLimits {
+        execute: Limits {
+            wall_time_ms: cli.timeout_ms,
+            memory_bytes: cli.memory_mb * 1024 * 1024,
+            ..Limits::EXECUTE
+        },
+        ..RunLimits::default()
+    };
+
+    let mut registry = Registry::load(&cli.languages)
+        .with_context(|| format!("Erro ao carregar {}", cli.languages.display()))?;
+    let sandbox = (!cli.no_sandbox).then(|| {
+        let sandbox = Sandbox::new(limits.execute.into());
+        match &cli.cgroup {
+            Some(parent) => sandbox.with_cgroup(CgroupLimits::new(parent)),
+            None => sandbox,
+        }
+    });
+
+    match RustRunner::detect() {
+        Ok(runner) => {
+            let runner = runner.with_limits(limits);
+            registry.register(match sandbox {
+                Some(sandbox) => runner.with_sandbox(sandbox),
+                None => runner,
+            });
+        }
+        Err(err) => eprintln!("Aviso: {} desativado — {err}", RustRunner::NAME),
+    }
+
+    match cli.command {
+        Command::Serve {
+            addr,
+            workers,
+            allow_origins,
+        } => {
+            eprintln!("themelion-runner ouvindo em http://{addr}");
+            if cli.no_sandbox {
+                eprintln!("  Aviso: --no-sandbox, os programas rodam só com rlimits");
+            }
+            for status in registry.status() {
+                let state = if status.available {
+                    "ativo"
+                } else {
+                    "indisponível"
+                };
+                eprintln!("  {} → {} ({state})", status.language_id, status.runner);
+            }
+            eprintln!("  origens permitidas: {}", allow_origins.join(", "));
+            http::serve(registry, addr.as_str(), workers, allow_origins)?;
+        }
+        Command::Stdio => {
+            stdio::serve(&registry, io::stdin().lock(), io::stdout().lock())?;
+        }
+        Command::Run { file, topic, stdin } => {
+            let language = registry.language_for_path(&file).with_context(|| {
+                format!("Nenhuma linguagem usa a extensão de {}", file.display())
+            })?;
+            let source = fs::read_to_string(&file)
+                .with_context(|| format!("Erro ao ler {}", file.display()))?;
+            let stdin = match stdin {
+                Some(path) => fs::read_to_string(&path)
+                    .with_context(|| format!("Erro ao ler {}", path.display()))?,
+                None => String::new(),
+            };
+            let topic = topic
+                .or_else(|| topic_from_path(&file))
+                .context("Não foi possível inferir o tópico; use --topic")?;
+
+            let result = registry.dispatch(&RunRequest {
+                language_id: language.id.clone(),
+                topic,
+                source,
+                stdin,
+            })?;
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// `.../<slug>/examples/<arquivo>` → `<slug>`
+fn topic_from_path(file: &Path) -> Option<String> {
+    let examples = file.parent()?;
+    if examples.file_name()? != "examples" {
+        return None;
+    }
+    Some(
+        examples
+            .parent()?
+            .file_name()?
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
@@ -0,0 +1,208 @@
+use std::io::{self, Read, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Child, Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use themelion_sandbox::{Outcome, Verdict};
+
+use crate::limits::Limits;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Resultado bruto de um processo executado com limites.
+#[derive(Debug)]
+pub struct Output {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub timed_out: bool,
+    pub truncated: bool,
+    pub duration: Duration,
+    /// O que encerrou o processo; com sandbox, inclui memória, saída e
+    /// syscalls proibidas.
+    pub verdict: Verdict,
+}
+
+impl Output {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// O kernel envia SIGXCPU ao ultrapassar o soft limit de RLIMIT_CPU.
+    pub fn cpu_exceeded(&self) -> bool {
+        self.signal == Some(libc::SIGXCPU)
+    }
+}
+
+/// Executa `cmd` em um grupo de processos próprio, com rlimits aplicados
+/// antes do `exec` e timeout de relógio.
+pub fn run(mut cmd: Command, limits: &Limits, stdin: &[u8]) -> io::Result<Output> {
+    let limits_for_child = *limits;
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // SAFETY: o closure roda entre fork e exec; só chama funções
+    // async-signal-safe (setpgid/setrlimit) e não aloca.
+    unsafe {
+        cmd.pre_exec(move || apply_rlimits(&limits_for_child));
+    }
+
+    let started = Instant::now();
+    let mut child = cmd.spawn()?;
+
+    let stdin_pipe = child.stdin.take();
+    let input = stdin.to_vec();
+    let writer = thread::spawn(move || {
+        if let Some(mut pipe) = stdin_pipe {
+            // O filho pode encerrar sem ler tudo (EPIPE): não é erro nosso
+            let _ = pipe.write_all(&input);
+        }
+    });
+
+    let stdout = capture(child.stdout.take(), limits.output_bytes);
+    let stderr = capture(child.stderr.take(), limits.output_bytes);
+
+    let deadline = started + Duration::from_millis(limits.wall_time_ms);
+    let timed_out = wait_until(&child, deadline)?;
+    let duration = started.elapsed();
+    // Netos em background manteriam os pipes abertos e travariam a captura.
+    // O filho ainda não foi colhido, então o pgid continua sendo dele
+    kill_group(&child);
+    let status = child.wait()?;
+
+    let _ = writer.join();
+    let (stdout, stdout_truncated) = stdout.join().unwrap_or_default();
+    let (stderr, stderr_truncated) = stderr.join().unwrap_or_default();
+
+    let verdict = match (status.code(), status.signal()) {
+        _ if timed_out => Verdict::WallTimeLimit {
+            limit_ms: limits.wall_time_ms,
+        },
+        (_, Some(libc::SIGXCPU)) => Verdict::CpuTimeLimit {
+            limit_seconds: limits.cpu_seconds,
+        },
+        (_, Some(libc::SIGXFSZ)) => Verdict::FileSizeLimit,
+        (_, Some(signal)) => Verdict::Signaled { signal },
+        (code, None) => Verdict::Exited {
+            code: code.unwrap_or(-1),
+        },
+    };
+
+    Ok(Output {
+        stdout,
+        stderr,
+        exit_code: status.code(),
+        signal: status.signal(),
+        timed_out,
+        truncated: stdout_truncated || stderr_truncated,
+        duration,
+        verdict,
+    })
+}
+
+impl From<Outcome> for Output {
+    fn from(outcome: Outcome) -> Self {
+        Self {
+            timed_out: outcome.verdict.timed_out(),
+            stdout: outcome.stdout,
+            stderr: outcome.stderr,
+            exit_code: outcome.exit_code,
+            signal: outcome.signal,
+            truncated: outcome.truncated,
+            duration: outcome.duration,
+            verdict: outcome.verdict,
+        }
+    }
+}
+
+fn apply_rlimits(limits: &Limits) -> io::Result<()> {
+    // Grupo próprio: o timeout mata também processos filhos do aluno
+    if unsafe { libc::setpgid(0, 0) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // Hard limit de CPU um segundo acima: o soft gera SIGXCPU, o hard SIGKILL
+    set_rlimit(libc::RLIMIT_CPU, limits.cpu_seconds, limits.cpu_seconds + 1)?;
+    set_rlimit(libc::RLIMIT_AS, limits.memory_bytes, limits.memory_bytes)?;
+    set_rlimit(libc::RLIMIT_FSIZE, 16 * 1024 * 1024, 16 * 1024 * 1024)?;
+    set_rlimit(libc::RLIMIT_NOFILE, 64, 64)?;
+    set_rlimit(libc::RLIMIT_CORE, 0, 0)?;
+    Ok(())
+}
+
+fn set_rlimit(resource: libc::__rlimit_resource_t, soft: u64, hard: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Lê o pipe até o EOF guardando no máximo `max` bytes. O restante é
+/// descartado, mas continua sendo drenado para o filho não travar no `write`.
+fn capture<R: Read + Send + 'static>(pipe: Option<R>, max: usize) -> JoinHandle<(String, bool)> {
+    thread::spawn(move || {
+        let Some(mut pipe) = pipe else {
+            return (String::new(), false);
+        };
+        let mut kept = Vec::new();
+        let mut truncated = false;
+        let mut chunk = [0u8; 8192];
+        loop {
+            match pipe.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    let room = max.saturating_sub(kept.len());
+                    if n > room {
+                        truncated = true;
+                    }
+                    kept.extend_from_slice(&chunk[..n.min(room)]);
+                }
+            }
+        }
+        (String::from_utf8_lossy(&kept).into_owned(), truncated)
+    })
+}
+
+/// Espera o filho terminar ou o prazo vencer, sem colher o status: o
+/// `WNOWAIT` deixa o filho zumbi, e o pid (que é também o pgid do grupo) não
+/// pode ser reaproveitado até o `wait`. Devolve se o prazo venceu.
+fn wait_until(child: &Child, deadline: Instant) -> io::Result<bool> {
+    loop {
+        if exited(child)? {
+            return Ok(false);
+        }
+        if Instant::now() >= deadline {
+            return Ok(true);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn exited(child: &Child) -> io::Result<bool> {
+    // SAFETY: siginfo_t é POD; o waitid só preenche a struct
+    let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+    let flags = libc::WEXITED | libc::WNOWAIT | libc::WNOHANG;
+    if unsafe { libc::waitid(libc::P_PID, child.id(), &mut info, flags) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // Com WNOHANG e nada para colher, o kernel deixa si_pid zerado
+    Ok(unsafe { info.si_pid() } != 0)
+}
+
+/// Só pode ser chamada antes do `wait`: depois dele o pgid pode pertencer a
+/// outro grupo.
+fn kill_group(child: &Child) {
+    let pgid = child.id() as libc::pid_t;
+    // SAFETY: o pgid é o do próprio filho (setpgid(0, 0) no pre_exec), que
+    // ainda não foi colhido
+    unsafe {
+        libc::kill(-pgid, libc::SIGKILL);
+    }
+}
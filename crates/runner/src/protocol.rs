@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use themelion_sandbox::Verdict;
+
+/// Pedido de execução de um exemplo. O `languageId` é o `id` de
+/// `data/languages/*.json`; o slug do tópico identifica de onde veio o código.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RunRequest {
+    pub language_id: String,
+    pub topic: String,
+    pub source: String,
+    #[serde(default)]
+    pub stdin: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RunStatus {
+    Success,
+    CompileError,
+    RuntimeError,
+    TimeLimitExceeded,
+    MemoryLimitExceeded,
+    OutputLimitExceeded,
+    ForbiddenSyscall,
+}
+
+impl RunStatus {
+    /// Status da execução a partir do veredito do processo.
+    pub fn from_verdict(verdict: &Verdict) -> Self {
+        match verdict {
+            _ if verdict.success() => RunStatus::Success,
+            _ if verdict.timed_out() => RunStatus::TimeLimitExceeded,
+            Verdict::MemoryLimit { .. } => RunStatus::MemoryLimitExceeded,
+            Verdict::OutputLimit { .. } | Verdict::FileSizeLimit => RunStatus::OutputLimitExceeded,
+            Verdict::ForbiddenSyscall { .. } => RunStatus::ForbiddenSyscall,
+            _ => RunStatus::RuntimeError,
+        }
+    }
+}
+
+/// Resultado devolvido por qualquer runner, no mesmo formato para todos os desfechos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunResult {
+    pub language_id: String,
+    pub runner: String,
+    pub topic: String,
+    pub status: RunStatus,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub diagnostics: Vec<Diagnostic>,
+    pub compile_time_ms: u64,
+    pub run_time_ms: u64,
+    pub truncated: bool,
+    /// O que encerrou a execução (ausente se não chegou a executar).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<Verdict>,
+}
+
+/// Diagnóstico do compilador, com posições 1-based prontas para marcadores do Monaco.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub level: String,
+    pub message: String,
+    pub code: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub end_line: Option<u32>,
+    pub end_column: Option<u32>,
+    pub rendered: Option<String>,
+}
+
+/// Resposta de erro, igual no stdio e no HTTP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorReply {
+    pub error: String,
+}
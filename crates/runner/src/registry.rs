@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::{RunRequest, RunResult};
+use crate::runner::Runner;
+use crate::{Error, Result};
+
+/// Campos de `data/languages/*.json` relevantes para o despacho.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageEntry {
+    pub id: String,
+    pub name: String,
+    pub ext: String,
+    pub runner: String,
+}
+
+/// Linha de `GET /runners`: qual runner atende cada linguagem e se está ativo.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunnerStatus {
+    pub language_id: String,
+    pub runner: String,
+    pub available: bool,
+}
+
+/// Linguagens conhecidas + runners registrados, indexados pelo campo `runner`.
+#[derive(Default, Clone)]
+pub struct Registry {
+    languages: Vec<LanguageEntry>,
+    runners: HashMap<String, Arc<dyn Runner>>,
+}
+
+impl Registry {
+    /// Lê todos os `*.json` de `data/languages`.
+    pub fn load(languages_dir: &Path) -> Result<Self> {
+        let mut languages = Vec::new();
+        for entry in fs::read_dir(languages_dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let raw = fs::read_to_string(&path)?;
+            let language: LanguageEntry =
+                serde_json::from_str(&raw).map_err(|err| Error::InvalidLanguageFile {
+                    path: path.clone(),
+                    message: err.to_string(),
+                })?;
+            languages.push(language);
+        }
+        languages.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(Self {
+            languages,
+            runners: HashMap::new(),
+        })
+    }
+
+    pub fn register(&mut self, runner: impl Runner + 'static) {
+        self.runners
+            .insert(runner.name().to_string(), Arc::new(runner));
+    }
+
+    pub fn languages(&self) -> &[LanguageEntry] {
+        &self.languages
+    }
+
+    pub fn language(&self, id: &str) -> Option<&LanguageEntry> {
+        self.languages.iter().find(|l| l.id == id)
+    }
+
+    /// Mesma regra do plugin de conteúdo: a extensão do arquivo define a linguagem.
+    pub fn language_for_path(&self, path: &Path) -> Option<&LanguageEntry> {
+        let ext = path.extension()?.to_str()?;
+        self.languages.iter().find(|l| l.ext == format!(".{ext}"))
+    }
+
+    pub fn runner_for(&self, language_id: &str) -> Result<&dyn Runner> {
+        let language = self
+            .language(language_id)
+            .ok_or_else(|| Error::UnknownLanguage(language_id.to_string()))?;
+        self.runners
+            .get(&language.runner)
+            .map(|runner| runner.as_ref())
+            .ok_or_else(|| Error::RunnerUnavailable {
+                language: language.id.clone(),
+                runner: language.runner.clone(),
+            })
+    }
+
+    pub fn dispatch(&self, request: &RunRequest) -> Result<RunResult> {
+        self.runner_for(&request.language_id)?.run(request)
+    }
+
+    pub fn status(&self) -> Vec<RunnerStatus> {
+        self.languages
+            .iter()
+            .map(|l| RunnerStatus {
+                language_id: l.id.clone(),
+                runner: l.runner.clone(),
+                available: self.runners.contains_key(&l.runner),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use themelion_sandbox::Sandbox;
+
+use crate::limits::{Limits, RunLimits};
+use crate::process::{self, Output};
+use crate::protocol::{Diagnostic, RunRequest, RunResult, RunStatus};
+use crate::{Error, Result};
+
+const MAX_SOURCE_BYTES: usize = 64 * 1024;
+const MAX_SLUG_LENGTH: usize = 100;
+
+/// Saída da fase de compilação.
+#[derive(Debug)]
+pub struct Compilation {
+    pub output: Output,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Mensagens legíveis do compilador, exibidas como stderr em caso de erro.
+    pub rendered: String,
+}
+
+impl Compilation {
+    pub fn success(&self) -> bool {
+        self.output.success() && !self.output.timed_out
+    }
+}
+
+/// Um backend capaz de compilar e executar exemplos de uma linguagem.
+///
+/// O nome é o valor do campo `runner` em `data/languages/*.json`. Cada pedido
+/// roda em um diretório temporário próprio, onde o código é gravado em
+/// [`source_file`](Runner::source_file) antes de [`compile`](Runner::compile)
+/// e [`execute`](Runner::execute).
+pub trait Runner: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Nome do arquivo-fonte dentro do diretório de trabalho.
+    fn source_file(&self) -> &str;
+
+    fn limits(&self) -> RunLimits {
+        RunLimits::default()
+    }
+
+    /// Linguagens interpretadas podem devolver `Ok(None)` e pular a fase.
+    fn compile(&self, workdir: &Path, limits: &Limits) -> Result<Option<Compilation>>;
+
+    /// Comando que executa o programa já compilado dentro de `workdir`.
+    fn command(&self, workdir: &Path) -> Command;
+
+    /// Sandbox da fase de execução; sem ele, o programa roda só com rlimits.
+    fn sandbox(&self) -> Option<&Sandbox> {
+        None
+    }
+
+    fn execute(&self, workdir: &Path, stdin: &[u8], limits: &Limits) -> Result<Output> {
+        let mut cmd = self.command(workdir);
+        cmd.current_dir(workdir);
+        match self.sandbox() {
+            Some(sandbox) => {
+                let sandbox = sandbox.clone().with_limits((*limits).into());
+                Ok(sandbox.run(&cmd, stdin)?.into())
+            }
+            None => Ok(process::run(cmd, limits, stdin)?),
+        }
+    }
+
+    /// Valida o pedido, compila, executa e monta o [`RunResult`].
+    fn run(&self, request: &RunRequest) -> Result<RunResult> {
+        validate(request)?;
+        let limits = self.limits();
+
+        // O TempDir remove o diretório (fonte + binário) ao sair do escopo
+        let workdir = tempfile::Builder::new()
+            .prefix(&format!("{}-{}-", self.name(), request.topic))
+            .tempdir()?;
+        fs::write(workdir.path().join(self.source_file()), &request.source)?;
+
+        let mut result = RunResult {
+            language_id: request.language_id.clone(),
+            runner: self.name().to_string(),
+            topic: request.topic.clone(),
+            status: RunStatus::Success,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: None,
+            signal: None,
+            diagnostics: Vec::new(),
+            compile_time_ms: 0,
+            run_time_ms: 0,
+            truncated: false,
+            verdict: None,
+        };
+
+        if let Some(compilation) = self.compile(workdir.path(), &limits.compile)? {
+            result.compile_time_ms = compilation.output.duration.as_millis() as u64;
+            if !compilation.success() {
+                result.status = RunStatus::CompileError;
+                result.stderr = compilation.rendered;
+                result.exit_code = compilation.output.exit_code;
+                result.signal = compilation.output.signal;
+                result.diagnostics = compilation.diagnostics;
+                result.truncated = compilation.output.truncated;
+                return Ok(result);
+            }
+            result.diagnostics = compilation.diagnostics;
+        }
+
+        let output = self.execute(workdir.path(), request.stdin.as_bytes(), &limits.execute)?;
+        result.status = RunStatus::from_verdict(&output.verdict);
+        result.stdout = output.stdout;
+        result.stderr = output.stderr;
+        result.exit_code = output.exit_code;
+        result.signal = output.signal;
+        result.run_time_ms = output.duration.as_millis() as u64;
+        result.truncated = output.truncated;
+        result.verdict = Some(output.verdict);
+        Ok(result)
+    }
+}
+
+/// Regras comuns a todo pedido: slug do tópico e tamanho do código.
+pub fn validate(request: &RunRequest) -> Result<()> {
+    if !is_valid_slug(&request.topic) {
+        return Err(Error::InvalidSlug(request.topic.clone()));
+    }
+    if request.source.len() > MAX_SOURCE_BYTES {
+        return Err(Error::SourceTooLarge {
+            size: request.source.len(),
+            max: MAX_SOURCE_BYTES,
+        });
+    }
+    Ok(())
+}
+
+/// Mesmas regras de `SLUG_PATTERN` em `src/lib/progress.ts`.
+fn is_valid_slug(slug: &str) -> bool {
+    slug.len() <= MAX_SLUG_LENGTH
+        && !slug.is_empty()
+        && slug.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+}
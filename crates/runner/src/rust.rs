@@ -0,0 +1,228 @@
+//! Runner de referência para Rust (`"runner": "rust-runner"`).
+//!
+//! Compila o exemplo com `rustc` puro — sem Cargo, portanto sem crates
+//! externas — e executa o binário com o ambiente limpo. Com sandbox, a
+//! compilação também roda isolada: `include_str!("/etc/...")` só enxerga o
+//! toolchain e o diretório de trabalho.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+use themelion_sandbox::{Mount, Sandbox};
+
+use crate::limits::{Limits, RunLimits};
+use crate::process;
+use crate::protocol::Diagnostic;
+use crate::runner::{Compilation, Runner};
+use crate::{Error, Result};
+
+const SOURCE_FILE: &str = "main.rs";
+const BINARY_FILE: &str = "example";
+
+/// PATH mínimo: o `rustc` só precisa achar o linker (`cc`).
+const SAFE_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+
+/// O que o `rustc` precisa do sistema no sandbox: o linker (`cc`, `ld`), os
+/// objetos de inicialização e a libc para ligar o binário.
+const TOOLCHAIN_DIRS: [&str; 2] = ["/usr", "/etc/alternatives"];
+
+#[derive(Debug, Clone)]
+pub struct RustRunner {
+    rustc: PathBuf,
+    edition: String,
+    limits: RunLimits,
+    sandbox: Option<Sandbox>,
+}
+
+impl RustRunner {
+    pub const NAME: &str = "rust-runner";
+
+    /// Resolve o `rustc` real a partir do sysroot, sem depender do proxy do
+    /// rustup (que precisa de HOME e variáveis que o ambiente isolado não tem).
+    pub fn detect() -> Result<Self> {
+        let output = Command::new("rustc")
+            .args(["--print", "sysroot"])
+            .output()
+            .map_err(|err| Error::Toolchain(format!("rustc não encontrado no PATH ({err})")))?;
+        if !output.status.success() {
+            return Err(Error::Toolchain(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        let sysroot = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        Self::from_path(sysroot.join("bin").join("rustc"))
+    }
+
+    pub fn from_path(rustc: impl Into<PathBuf>) -> Result<Self> {
+        let rustc = rustc.into();
+        if !rustc.is_file() {
+            return Err(Error::Toolchain(format!("{} não existe", rustc.display())));
+        }
+        Ok(Self {
+            rustc,
+            edition: "2021".to_string(),
+            limits: RunLimits::default(),
+            sandbox: None,
+        })
+    }
+
+    pub fn with_edition(mut self, edition: impl Into<String>) -> Self {
+        self.edition = edition.into();
+        self
+    }
+
+    pub fn with_limits(mut self, limits: RunLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Compila e executa no sandbox (os limites de cada fase são os de
+    /// `compile` e `execute`).
+    pub fn with_sandbox(mut self, sandbox: Sandbox) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+}
+
+impl RustRunner {
+    /// O sandbox da execução, sem seccomp (o `rustc` cria processos para o
+    /// linker e grava o binário), com leitura só do sysroot e do sistema e
+    /// escrita só no diretório de trabalho.
+    fn compiler_sandbox(&self, sandbox: &Sandbox, workdir: &Path, limits: &Limits) -> Sandbox {
+        let sysroot = self.rustc.parent().and_then(Path::parent);
+        let mut sandbox = sandbox
+            .clone()
+            .with_limits((*limits).into())
+            .with_seccomp(false)
+            .with_mount(Mount::writable(workdir));
+        for dir in sysroot
+            .into_iter()
+            .chain(TOOLCHAIN_DIRS.iter().map(Path::new))
+        {
+            if dir.exists() {
+                sandbox = sandbox.with_mount(Mount::read_only(dir));
+            }
+        }
+        sandbox
+    }
+}
+
+impl Runner for RustRunner {
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn source_file(&self) -> &str {
+        SOURCE_FILE
+    }
+
+    fn limits(&self) -> RunLimits {
+        self.limits
+    }
+
+    fn compile(&self, workdir: &Path, limits: &Limits) -> Result<Option<Compilation>> {
+        let mut cmd = Command::new(&self.rustc);
+        cmd.current_dir(workdir)
+            .env_clear()
+            .env("PATH", SAFE_PATH)
+            .env("TMPDIR", workdir)
+            .args(["--edition", &self.edition])
+            .args(["--error-format=json", "--crate-name", BINARY_FILE])
+            .args(["-C", "opt-level=1", "-C", "debuginfo=0"])
+            .args(["-o", BINARY_FILE, SOURCE_FILE]);
+
+        let output = match &self.sandbox {
+            Some(sandbox) => self
+                .compiler_sandbox(sandbox, workdir, limits)
+                .run(&cmd, &[])?
+                .into(),
+            None => process::run(cmd, limits, &[])?,
+        };
+        let (diagnostics, rendered) = parse_diagnostics(&output.stderr);
+
+        Ok(Some(Compilation {
+            output,
+            diagnostics,
+            rendered,
+        }))
+    }
+
+    fn sandbox(&self) -> Option<&Sandbox> {
+        self.sandbox.as_ref()
+    }
+
+    fn command(&self, workdir: &Path) -> Command {
+        let mut cmd = Command::new(workdir.join(BINARY_FILE));
+        cmd.env_clear();
+        cmd
+    }
+}
+
+#[derive(Deserialize)]
+struct RawDiagnostic {
+    #[serde(rename = "$message_type")]
+    message_type: Option<String>,
+    message: String,
+    level: String,
+    code: Option<RawCode>,
+    #[serde(default)]
+    spans: Vec<RawSpan>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct RawSpan {
+    line_start: u32,
+    column_start: u32,
+    line_end: u32,
+    column_end: u32,
+    is_primary: bool,
+}
+
+/// Separa o stderr do `rustc` em diagnósticos estruturados e texto legível.
+fn parse_diagnostics(stderr: &str) -> (Vec<Diagnostic>, String) {
+    let mut diagnostics = Vec::new();
+    let mut rendered = String::new();
+
+    for line in stderr.lines() {
+        let raw = match serde_json::from_str::<RawDiagnostic>(line) {
+            Ok(raw) if raw.message_type.as_deref().unwrap_or("diagnostic") == "diagnostic" => raw,
+            Ok(_) => continue,
+            Err(_) => {
+                rendered.push_str(line);
+                rendered.push('\n');
+                continue;
+            }
+        };
+
+        if let Some(text) = &raw.rendered {
+            rendered.push_str(text);
+        }
+
+        let primary = raw
+            .spans
+            .iter()
+            .find(|span| span.is_primary)
+            .or(raw.spans.first());
+
+        diagnostics.push(Diagnostic {
+            level: raw.level,
+            message: raw.message,
+            code: raw.code.map(|code| code.code),
+            line: primary.map(|span| span.line_start),
+            column: primary.map(|span| span.column_start),
+            end_line: primary.map(|span| span.line_end),
+            end_column: primary.map(|span| span.column_end),
+            rendered: raw.rendered,
+        });
+    }
+
+    (diagnostics, rendered)
+}
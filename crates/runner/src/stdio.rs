@@ -0,0 +1,33 @@
+//! Transporte JSON-over-stdio: uma linha JSON por pedido, uma linha JSON por
+//! resposta ([`RunResult`](crate::RunResult) ou [`ErrorReply`]), na mesma ordem.
+//! Linhas em branco são ignoradas; o laço termina no EOF da entrada.
+
+use std::io::{self, BufRead, Write};
+
+use crate::{ErrorReply, Registry, RunRequest};
+
+pub fn serve(registry: &Registry, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let reply = match serde_json::from_str::<RunRequest>(&line) {
+            Ok(request) => match registry.dispatch(&request) {
+                Ok(result) => serde_json::to_string(&result),
+                Err(err) => serde_json::to_string(&ErrorReply {
+                    error: err.to_string(),
+                }),
+            },
+            Err(err) => serde_json::to_string(&ErrorReply {
+                error: format!("JSON inválido: {err}"),
+            }),
+        }
+        .map_err(io::Error::other)?;
+
+        writeln!(output, "{reply}")?;
+        output.flush()?;
+    }
+    Ok(())
+}
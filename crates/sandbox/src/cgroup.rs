@@ -0,0 +1,141 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::{Error, Result};
+
+/// Controladores que o cgroup pai precisa delegar aos filhos.
+const CONTROLLERS: [&str; 3] = ["memory", "cpu", "pids"];
+
+/// Período do `cpu.max`, em microssegundos.
+const CPU_PERIOD_US: u64 = 100_000;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Tetos aplicados via cgroup v2, em um cgroup criado por execução.
+///
+/// `parent` precisa ser um diretório do cgroup2 em que o processo pode
+/// escrever e cujo `cgroup.subtree_control` habilita `memory`, `cpu` e
+/// `pids` (ex.: um cgroup delegado pelo systemd com `Delegate=yes`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CgroupLimits {
+    pub parent: PathBuf,
+    /// Fração de um núcleo (`cpu.max`), em porcentagem.
+    pub cpu_percent: u32,
+    /// Máximo de tarefas (`pids.max`), threads incluídas.
+    pub max_tasks: u32,
+}
+
+impl CgroupLimits {
+    pub fn new(parent: impl Into<PathBuf>) -> Self {
+        Self {
+            parent: parent.into(),
+            cpu_percent: 100,
+            max_tasks: 32,
+        }
+    }
+}
+
+/// Cgroup de uma execução; o `Drop` mata o que sobrou e remove o diretório.
+#[derive(Debug)]
+pub(crate) struct Cgroup {
+    path: PathBuf,
+}
+
+impl Cgroup {
+    pub fn create(limits: &CgroupLimits, memory_bytes: u64) -> Result<Self> {
+        let enabled = fs::read_to_string(limits.parent.join("cgroup.subtree_control"))
+            .map_err(|err| cgroup_error(&limits.parent, err))?;
+        let missing: Vec<&str> = CONTROLLERS
+            .into_iter()
+            .filter(|controller| !enabled.split_whitespace().any(|c| c == *controller))
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::Cgroup {
+                path: limits.parent.clone(),
+                message: format!(
+                    "controladores ausentes em cgroup.subtree_control: {}",
+                    missing.join(", ")
+                ),
+            });
+        }
+
+        let name = format!(
+            "themelion-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let cgroup = Self {
+            path: limits.parent.join(name),
+        };
+        fs::create_dir(&cgroup.path).map_err(|err| cgroup_error(&cgroup.path, err))?;
+
+        let quota = CPU_PERIOD_US * u64::from(limits.cpu_percent) / 100;
+        cgroup.write("memory.max", &memory_bytes.to_string())?;
+        cgroup.write("cpu.max", &format!("{quota} {CPU_PERIOD_US}"))?;
+        cgroup.write("pids.max", &limits.max_tasks.to_string())?;
+        // Sem swap o OOM é imediato; o arquivo só existe com swap no kernel
+        if cgroup.path.join("memory.swap.max").exists() {
+            cgroup.write("memory.swap.max", "0")?;
+        }
+        Ok(cgroup)
+    }
+
+    pub fn add(&self, pid: libc::pid_t) -> Result<()> {
+        self.write("cgroup.procs", &pid.to_string())
+    }
+
+    /// `oom_kill` do `memory.events`: o OOM killer agiu dentro do cgroup.
+    pub fn oom_killed(&self) -> bool {
+        self.read("memory.events")
+            .and_then(|events| {
+                events.lines().find_map(|line| {
+                    line.strip_prefix("oom_kill ")
+                        .and_then(|count| count.trim().parse::<u64>().ok())
+                })
+            })
+            .is_some_and(|count| count > 0)
+    }
+
+    /// `memory.peak` (kernel 5.19+).
+    pub fn peak_memory(&self) -> Option<u64> {
+        self.read("memory.peak")?.trim().parse().ok()
+    }
+
+    pub fn kill(&self) {
+        let _ = fs::write(self.path.join("cgroup.kill"), "1");
+    }
+
+    fn read(&self, file: &str) -> Option<String> {
+        fs::read_to_string(self.path.join(file)).ok()
+    }
+
+    fn write(&self, file: &str, value: &str) -> Result<()> {
+        let path = self.path.join(file);
+        fs::write(&path, value).map_err(|err| cgroup_error(&path, err))
+    }
+}
+
+impl Drop for Cgroup {
+    fn drop(&mut self) {
+        self.kill();
+        // O rmdir falha com EBUSY enquanto o kernel não termina de tirar os
+        // processos mortos do cgroup
+        for _ in 0..50 {
+            if fs::remove_dir(&self.path).is_ok() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+fn cgroup_error(path: &Path, err: io::Error) -> Error {
+    Error::Cgroup {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    }
+}
@@ -0,0 +1,31 @@
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Comando inválido: {0}")]
+    InvalidCommand(String),
+
+    #[error("Não foi possível criar o processo isolado: {0}")]
+    Spawn(io::Error),
+
+    #[error("Falha ao preparar o processo isolado ({stage}): {source}")]
+    Setup {
+        stage: &'static str,
+        source: io::Error,
+    },
+
+    #[error("Não foi possível montar {} no sandbox: {source}", path.display())]
+    Mount { path: PathBuf, source: io::Error },
+
+    #[error("cgroup {}: {message}", path.display())]
+    Cgroup { path: PathBuf, message: String },
+
+    #[error("Filtro seccomp inválido: {0}")]
+    Seccomp(String),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
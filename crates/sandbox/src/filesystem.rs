@@ -0,0 +1,340 @@
+//! Raiz mínima do processo isolado.
+//!
+//! Com namespace de montagem, o filho troca de raiz (`pivot_root`) para um
+//! tmpfs vazio em que só aparecem, por bind mount, os diretórios de
+//! bibliotecas do sistema, o programa, o diretório de trabalho e os caminhos
+//! pedidos com [`Sandbox::with_mount`](crate::Sandbox::with_mount). Tudo é
+//! somente leitura, menos os [`Mount::writable`]; o resto do host (`/etc`,
+//! `/home`, o repositório) simplesmente não existe lá dentro.
+//!
+//! O pai resolve os caminhos, seguindo os symlinks (que são recriados dentro
+//! da raiz, como `/lib -> usr/lib`), e converte tudo em strings C; o filho só
+//! chama `mkdir`, `symlink`, `mount` e `pivot_root`.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::ffi::{CString, OsString};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::spawn::c_string;
+use crate::{Error, Result};
+
+/// Montados quando existem: o programa é ligado dinamicamente à libc.
+const LIBRARY_DIRS: [&str; 5] = ["/lib", "/lib64", "/lib32", "/usr/lib", "/usr/lib64"];
+
+/// Limite de symlinks seguidos ao resolver um caminho, o mesmo do kernel.
+const MAX_SYMLINKS: usize = 40;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Caminho do host visível no sandbox, no mesmo lugar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    pub path: PathBuf,
+    pub writable: bool,
+}
+
+impl Mount {
+    pub fn read_only(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            writable: false,
+        }
+    }
+
+    pub fn writable(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            writable: true,
+        }
+    }
+}
+
+struct Bind {
+    source: CString,
+    target: CString,
+    /// Flags do remount que torna o bind somente leitura (ou só `nosuid`).
+    remount: libc::c_ulong,
+}
+
+/// Raiz já planejada pelo pai. O diretório de montagem fica vazio no host e
+/// é removido no `Drop`.
+pub(crate) struct Root {
+    staging: PathBuf,
+    dir: CString,
+    dirs: Vec<CString>,
+    files: Vec<CString>,
+    /// (alvo, caminho do link)
+    links: Vec<(CString, CString)>,
+    binds: Vec<Bind>,
+    proc: Option<CString>,
+}
+
+impl Root {
+    /// `program` e `cwd` entram somente leitura, além de `mounts`; `proc`
+    /// monta um `/proc` do namespace de PID.
+    pub fn new(mounts: &[Mount], program: &Path, cwd: &Path, proc: bool) -> Result<Self> {
+        let mut requested: Vec<Mount> = LIBRARY_DIRS
+            .iter()
+            .filter(|dir| Path::new(dir).exists())
+            .map(Mount::read_only)
+            .collect();
+        requested.push(Mount::read_only(cwd.join(program)));
+        requested.push(Mount::read_only(cwd));
+        requested.extend(mounts.iter().cloned());
+
+        // Caminho real → gravável; os symlinks do caminho viram links na raiz
+        let mut resolved: BTreeMap<PathBuf, bool> = BTreeMap::new();
+        let mut links: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+        for mount in &requested {
+            let path = resolve(&mount.path, &mut links).map_err(|source| Error::Mount {
+                path: mount.path.clone(),
+                source,
+            })?;
+            *resolved.entry(path).or_default() |= mount.writable;
+        }
+        // O bind de um diretório já cobre o que está embaixo dele
+        let binds: Vec<(PathBuf, bool)> = resolved
+            .iter()
+            .filter(|(path, writable)| {
+                !path.ancestors().skip(1).any(|ancestor| {
+                    resolved
+                        .get(ancestor)
+                        .is_some_and(|outer| *outer || !**writable)
+                })
+            })
+            .map(|(path, writable)| (path.clone(), *writable))
+            .collect();
+
+        let mut dirs = BTreeSet::new();
+        let mut files = Vec::new();
+        for (path, _) in &binds {
+            dirs.extend(ancestors(path));
+            if path.is_dir() {
+                dirs.insert(path.clone());
+            } else {
+                files.push(path.clone());
+            }
+        }
+        for link in links.keys() {
+            dirs.extend(ancestors(link));
+        }
+        if proc {
+            dirs.insert(PathBuf::from("/proc"));
+        }
+
+        let staging = std::env::temp_dir().join(format!(
+            "themelion-root-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir(&staging).map_err(|source| Error::Mount {
+            path: staging.clone(),
+            source,
+        })?;
+        let inside = |path: &Path| {
+            let relative = path.strip_prefix("/").unwrap_or(path);
+            c_path(&staging.join(relative))
+        };
+
+        let root = Self {
+            dir: c_path(&staging)?,
+            dirs: dirs.iter().map(|d| inside(d)).collect::<Result<_>>()?,
+            files: files.iter().map(|f| inside(f)).collect::<Result<_>>()?,
+            links: links
+                .iter()
+                .map(|(link, target)| Ok((c_path(target)?, inside(link)?)))
+                .collect::<Result<_>>()?,
+            binds: binds
+                .iter()
+                .map(|(path, writable)| {
+                    Ok(Bind {
+                        source: c_path(path)?,
+                        target: inside(path)?,
+                        remount: remount_flags(path, *writable)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            proc: proc.then(|| inside(Path::new("/proc"))).transpose()?,
+            staging,
+        };
+        Ok(root)
+    }
+
+    /// Monta o tmpfs e os binds e troca de raiz. Devolve `false` com o errno
+    /// da chamada que falhou.
+    ///
+    /// # Safety
+    ///
+    /// Só no filho, depois do `clone` com namespace de montagem e com as
+    /// montagens já privadas.
+    pub unsafe fn enter(&self) -> bool {
+        let null = std::ptr::null::<libc::c_char>();
+        // SAFETY: syscalls async-signal-safe sobre strings preparadas pelo pai
+        unsafe {
+            if libc::mount(
+                c"tmpfs".as_ptr(),
+                self.dir.as_ptr(),
+                c"tmpfs".as_ptr(),
+                libc::MS_NOSUID | libc::MS_NODEV,
+                c"size=1m,mode=0755".as_ptr().cast(),
+            ) != 0
+            {
+                return false;
+            }
+            for dir in &self.dirs {
+                if libc::mkdir(dir.as_ptr(), 0o755) != 0 {
+                    return false;
+                }
+            }
+            for file in &self.files {
+                let fd = libc::open(file.as_ptr(), libc::O_CREAT | libc::O_WRONLY, 0o644);
+                if fd < 0 {
+                    return false;
+                }
+                libc::close(fd);
+            }
+            for (target, link) in &self.links {
+                if libc::symlink(target.as_ptr(), link.as_ptr()) != 0 {
+                    return false;
+                }
+            }
+            for bind in &self.binds {
+                let flags = libc::MS_BIND | libc::MS_REC;
+                if libc::mount(
+                    bind.source.as_ptr(),
+                    bind.target.as_ptr(),
+                    null,
+                    flags,
+                    null.cast(),
+                ) != 0
+                    || libc::mount(null, bind.target.as_ptr(), null, bind.remount, null.cast()) != 0
+                {
+                    return false;
+                }
+            }
+            if let Some(proc) = &self.proc {
+                // Alguns ambientes (contêineres com /proc mascarado) não
+                // permitem; o programa fica sem /proc
+                libc::mount(
+                    c"proc".as_ptr(),
+                    proc.as_ptr(),
+                    c"proc".as_ptr(),
+                    libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                    null.cast(),
+                );
+            }
+
+            // `pivot_root(".", ".")` empilha a raiz antiga sobre a nova, e o
+            // `umount2` a tira sem precisar de um diretório para ela
+            if libc::chdir(self.dir.as_ptr()) != 0
+                || libc::syscall(libc::SYS_pivot_root, c".".as_ptr(), c".".as_ptr()) != 0
+                || libc::umount2(c".".as_ptr(), libc::MNT_DETACH) != 0
+                || libc::chdir(c"/".as_ptr()) != 0
+            {
+                return false;
+            }
+            let read_only = libc::MS_REMOUNT | libc::MS_BIND | libc::MS_RDONLY;
+            libc::mount(
+                null,
+                c"/".as_ptr(),
+                null,
+                read_only | libc::MS_NOSUID | libc::MS_NODEV,
+                null.cast(),
+            ) == 0
+        }
+    }
+}
+
+impl Drop for Root {
+    fn drop(&mut self) {
+        // As montagens só existem no namespace do filho: aqui o diretório
+        // está vazio
+        let _ = fs::remove_dir(&self.staging);
+    }
+}
+
+/// Caminho absoluto sem symlinks, anotando em `links` cada symlink do
+/// caminho e o alvo dele, como aparece no disco.
+fn resolve(path: &Path, links: &mut BTreeMap<PathBuf, PathBuf>) -> io::Result<PathBuf> {
+    let absolute = std::path::absolute(path)?;
+    let mut pending: VecDeque<OsString> = names(&absolute);
+    let mut resolved = PathBuf::from("/");
+    let mut followed = 0;
+    while let Some(name) = pending.pop_front() {
+        if name == ".." {
+            resolved.pop();
+            continue;
+        }
+        let candidate = resolved.join(&name);
+        if !fs::symlink_metadata(&candidate)?.is_symlink() {
+            resolved = candidate;
+            continue;
+        }
+        followed += 1;
+        if followed > MAX_SYMLINKS {
+            return Err(io::Error::from_raw_os_error(libc::ELOOP));
+        }
+        let target = fs::read_link(&candidate)?;
+        if target.is_absolute() {
+            resolved = PathBuf::from("/");
+        }
+        for name in names(&target).into_iter().rev() {
+            pending.push_front(name);
+        }
+        links.insert(candidate, target);
+    }
+    Ok(resolved)
+}
+
+/// Componentes normais e `..`, sem a raiz nem `.`.
+fn names(path: &Path) -> VecDeque<OsString> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_os_string()),
+            Component::ParentDir => Some(OsString::from("..")),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Diretórios acima de `path`, sem a raiz.
+fn ancestors(path: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+    path.ancestors()
+        .skip(1)
+        .filter(|a| a.parent().is_some())
+        .map(Path::to_path_buf)
+}
+
+/// Num namespace de usuário, o remount não pode soltar as flags que a
+/// montagem de origem já tem (`noexec`, `nodev`, as de atime): elas vêm do
+/// `statvfs`, e `nosuid`/`nodev` entram sempre.
+fn remount_flags(path: &Path, writable: bool) -> Result<libc::c_ulong> {
+    let c = c_path(path)?;
+    // SAFETY: `stat` é só saída
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c.as_ptr(), &mut stat) } != 0 {
+        return Err(Error::Mount {
+            path: path.to_path_buf(),
+            source: io::Error::last_os_error(),
+        });
+    }
+    let locked = [
+        (libc::ST_RDONLY, libc::MS_RDONLY),
+        (libc::ST_NOEXEC, libc::MS_NOEXEC),
+        (libc::ST_NOATIME, libc::MS_NOATIME),
+        (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+        (libc::ST_RELATIME, libc::MS_RELATIME),
+    ]
+    .into_iter()
+    .filter(|(st, _)| stat.f_flag & st != 0)
+    .fold(0, |flags, (_, ms)| flags | ms);
+    let read_only = if writable { 0 } else { libc::MS_RDONLY };
+    Ok(libc::MS_REMOUNT | libc::MS_BIND | libc::MS_NOSUID | libc::MS_NODEV | locked | read_only)
+}
+
+fn c_path(path: &Path) -> Result<CString> {
+    c_string(path.as_os_str())
+}
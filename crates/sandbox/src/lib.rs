@@ -0,0 +1,30 @@
+//! Execução isolada de programas de alunos no Linux.
+//!
+//! O filho nasce de um `clone` com namespaces próprios (usuário, PID, rede,
+//! montagem, IPC e UTS), troca de raiz para um sistema de arquivos mínimo e
+//! somente leitura ([`Mount`]), recebe rlimits, `no_new_privs` e um filtro
+//! seccomp-bpf com uma lista de syscalls permitidas, e opcionalmente entra em
+//! um cgroup v2 com teto de memória, CPU e processos. O processo pai fica como
+//! tracer (`ptrace`): quando o filtro barra uma syscall, ele descobre qual foi.
+//!
+//! O resultado é um [`Outcome`] cujo [`Verdict`] diz exatamente o que
+//! encerrou o programa — saída normal, sinal, tempo de relógio, tempo de CPU,
+//! memória, saída grande demais ou syscall proibida.
+
+mod cgroup;
+mod error;
+mod filesystem;
+mod limits;
+mod sandbox;
+pub mod seccomp;
+mod spawn;
+mod verdict;
+
+pub use cgroup::CgroupLimits;
+pub use error::Error;
+pub use filesystem::Mount;
+pub use limits::{Limits, Namespaces};
+pub use sandbox::{Isolation, Outcome, Sandbox};
+pub use verdict::Verdict;
+
+pub type Result<T> = std::result::Result<T, Error>;
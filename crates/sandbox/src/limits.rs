@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// Limites de recursos aplicados ao programa isolado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Limits {
+    /// Tempo de CPU (RLIMIT_CPU), em segundos.
+    pub cpu_seconds: u64,
+    /// Espaço de endereçamento (RLIMIT_AS) e, com cgroup, `memory.max`, em bytes.
+    pub memory_bytes: u64,
+    /// Tempo de relógio antes de matar o programa.
+    pub wall_time_ms: u64,
+    /// Máximo de bytes de stdout e de stderr (cada um); acima disso o
+    /// programa é encerrado.
+    pub output_bytes: usize,
+}
+
+/// Namespaces em que o programa roda. Todos ligados por padrão.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Namespaces {
+    /// Usuário: o programa vira um uid sem privilégios no host.
+    pub user: bool,
+    /// PID: o programa é o PID 1 e não enxerga outros processos.
+    pub pid: bool,
+    /// Rede: só um loopback desligado.
+    pub net: bool,
+    /// Montagem: propagação privada, raiz mínima e um `/proc` próprio.
+    pub mount: bool,
+    pub ipc: bool,
+    pub uts: bool,
+}
+
+impl Namespaces {
+    pub const ALL: Namespaces = Namespaces {
+        user: true,
+        pid: true,
+        net: true,
+        mount: true,
+        ipc: true,
+        uts: true,
+    };
+
+    pub const NONE: Namespaces = Namespaces {
+        user: false,
+        pid: false,
+        net: false,
+        mount: false,
+        ipc: false,
+        uts: false,
+    };
+
+    pub(crate) fn clone_flags(&self) -> libc::c_int {
+        [
+            (self.user, libc::CLONE_NEWUSER),
+            (self.pid, libc::CLONE_NEWPID),
+            (self.net, libc::CLONE_NEWNET),
+            (self.mount, libc::CLONE_NEWNS),
+            (self.ipc, libc::CLONE_NEWIPC),
+            (self.uts, libc::CLONE_NEWUTS),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .fold(0, |flags, (_, flag)| flags | flag)
+    }
+}
+
+impl Default for Namespaces {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
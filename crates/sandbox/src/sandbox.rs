@@ -0,0 +1,608 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cgroup::{Cgroup, CgroupLimits};
+use crate::filesystem::{Mount, Root};
+use crate::limits::{Limits, Namespaces};
+use crate::spawn::{self, Prepared};
+use crate::verdict::Verdict;
+use crate::{Error, Result, seccomp};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// uid/gid do programa dentro do namespace de usuário. Não é 0: o `execve`
+/// descarta as capabilities que o filho tinha no namespace novo.
+const SANDBOX_ID: u32 = 1000;
+
+/// Configuração do sandbox; cada [`run`](Sandbox::run) é independente.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sandbox {
+    limits: Limits,
+    namespaces: Namespaces,
+    seccomp: bool,
+    cgroup: Option<CgroupLimits>,
+    mounts: Vec<Mount>,
+}
+
+/// Mecanismos que estavam ativos em uma execução.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Isolation {
+    pub namespaces: Namespaces,
+    pub seccomp: bool,
+    pub cgroup: bool,
+}
+
+/// Resultado de uma execução isolada.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub verdict: Verdict,
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    /// Alguma saída passou de `output_bytes` e foi cortada.
+    pub truncated: bool,
+    /// Tempo de relógio.
+    pub duration: Duration,
+    /// Tempo de CPU (usuário + sistema).
+    pub cpu_time: Duration,
+    /// Pico de memória residente (`memory.peak` do cgroup, ou `ru_maxrss`).
+    pub peak_memory_bytes: u64,
+    pub isolation: Isolation,
+}
+
+impl Outcome {
+    pub fn success(&self) -> bool {
+        self.verdict.success()
+    }
+}
+
+impl Sandbox {
+    /// Todos os namespaces e o filtro seccomp ligados, sem cgroup.
+    pub fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            namespaces: Namespaces::ALL,
+            seccomp: true,
+            cgroup: None,
+            mounts: Vec::new(),
+        }
+    }
+
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_namespaces(mut self, namespaces: Namespaces) -> Self {
+        self.namespaces = namespaces;
+        self
+    }
+
+    pub fn with_seccomp(mut self, enabled: bool) -> Self {
+        self.seccomp = enabled;
+        self
+    }
+
+    pub fn with_cgroup(mut self, cgroup: CgroupLimits) -> Self {
+        self.cgroup = Some(cgroup);
+        self
+    }
+
+    /// Mais um caminho visível na raiz mínima, além das bibliotecas do
+    /// sistema, do programa e do diretório de trabalho.
+    pub fn with_mount(mut self, mount: Mount) -> Self {
+        self.mounts.push(mount);
+        self
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Executa `cmd` isolado, escrevendo `stdin` na entrada padrão.
+    ///
+    /// Do `cmd` valem o programa (um caminho), os argumentos, o diretório e
+    /// as variáveis definidas com `env`; o ambiente herdado nunca passa.
+    /// Precisa rodar sempre na mesma thread do começo ao fim, porque ela é
+    /// o tracer do filho.
+    pub fn run(&self, cmd: &Command, stdin: &[u8]) -> Result<Outcome> {
+        let filters = if self.seccomp {
+            seccomp::filters()?
+        } else {
+            Vec::new()
+        };
+        let root = if self.namespaces.mount {
+            let cwd = match cmd.get_current_dir() {
+                Some(dir) => dir.to_path_buf(),
+                None => std::env::current_dir()?,
+            };
+            let program = Path::new(cmd.get_program());
+            Some(Root::new(&self.mounts, program, &cwd, self.namespaces.pid)?)
+        } else {
+            None
+        };
+        let prepared = Prepared::new(cmd, &self.limits, self.namespaces, filters, root)?;
+        let cgroup = self
+            .cgroup
+            .as_ref()
+            .map(|limits| Cgroup::create(limits, self.limits.memory_bytes))
+            .transpose()?;
+
+        let started = Instant::now();
+        let child = spawn::spawn(&prepared)?;
+        let mut tracee = Tracee::new(child.pid, prepared.traced());
+
+        let setup = self
+            .setup(&child, cgroup.as_ref(), &mut tracee)
+            .and_then(|()| match spawn::read_setup_error(&child.error) {
+                Some(err) => Err(err),
+                None => Ok(()),
+            });
+        if let Err(err) = setup {
+            tracee.kill();
+            tracee.reap();
+            return Err(match err {
+                Error::Setup { stage, source } if source.kind() == io::ErrorKind::NotFound => {
+                    Error::Setup {
+                        stage,
+                        source: io::Error::new(
+                            source.kind(),
+                            format!("{} não existe", prepared.program().display()),
+                        ),
+                    }
+                }
+                err => err,
+            });
+        }
+
+        let overflow = Arc::new(AtomicBool::new(false));
+        let writer = feed(child.stdin, stdin.to_vec());
+        let stdout = capture(child.stdout, self.limits.output_bytes, overflow.clone());
+        let stderr = capture(child.stderr, self.limits.output_bytes, overflow.clone());
+
+        let deadline = started + Duration::from_millis(self.limits.wall_time_ms);
+        let mut timed_out = false;
+        let mut output_exceeded = false;
+        let exit = loop {
+            if let Some(exit) = tracee.poll()? {
+                break exit;
+            }
+            if tracee.killed {
+                thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            if overflow.load(Ordering::Relaxed) {
+                output_exceeded = true;
+                tracee.kill();
+            } else if Instant::now() >= deadline {
+                timed_out = true;
+                tracee.kill();
+            } else {
+                thread::sleep(POLL_INTERVAL);
+            }
+            if tracee.killed
+                && let Some(cgroup) = &cgroup
+            {
+                cgroup.kill();
+            }
+        };
+        let duration = started.elapsed();
+
+        let _ = writer.join();
+        let (stdout, stdout_truncated) = stdout.join().unwrap_or_default();
+        let (stderr, stderr_truncated) = stderr.join().unwrap_or_default();
+
+        let cpu_time = timeval(exit.usage.ru_utime) + timeval(exit.usage.ru_stime);
+        let oom_killed = cgroup.as_ref().is_some_and(Cgroup::oom_killed);
+        let peak_memory_bytes = cgroup
+            .as_ref()
+            .and_then(Cgroup::peak_memory)
+            .unwrap_or(exit.usage.ru_maxrss as u64 * 1024);
+
+        let signal = tracee
+            .fatal_signal
+            .or_else(|| libc::WIFSIGNALED(exit.status).then(|| libc::WTERMSIG(exit.status)));
+        let limits = &self.limits;
+        let verdict = if let Some(number) = tracee.forbidden {
+            Verdict::ForbiddenSyscall {
+                number,
+                name: seccomp::name(number).map(str::to_string),
+            }
+        } else if output_exceeded {
+            Verdict::OutputLimit {
+                limit_bytes: limits.output_bytes,
+            }
+        } else if timed_out {
+            Verdict::WallTimeLimit {
+                limit_ms: limits.wall_time_ms,
+            }
+        } else if oom_killed || allocation_failed(signal, &stderr) {
+            Verdict::MemoryLimit {
+                limit_bytes: limits.memory_bytes,
+            }
+        } else if signal == Some(libc::SIGXCPU)
+            || (signal == Some(libc::SIGKILL) && cpu_time.as_secs() >= limits.cpu_seconds)
+        {
+            Verdict::CpuTimeLimit {
+                limit_seconds: limits.cpu_seconds,
+            }
+        } else if signal == Some(libc::SIGXFSZ) {
+            Verdict::FileSizeLimit
+        } else if let Some(signal) = signal {
+            Verdict::Signaled { signal }
+        } else {
+            Verdict::Exited {
+                code: libc::WEXITSTATUS(exit.status),
+            }
+        };
+
+        Ok(Outcome {
+            verdict,
+            exit_code: libc::WIFEXITED(exit.status).then(|| libc::WEXITSTATUS(exit.status)),
+            signal,
+            stdout,
+            stderr,
+            truncated: stdout_truncated || stderr_truncated,
+            duration,
+            cpu_time,
+            peak_memory_bytes,
+            isolation: Isolation {
+                namespaces: self.namespaces,
+                seccomp: self.seccomp,
+                cgroup: cgroup.is_some(),
+            },
+        })
+    }
+
+    /// Passos do pai com o filho ainda parado: mapeia uid/gid, coloca no
+    /// cgroup, libera e, com seccomp, assume como tracer.
+    fn setup(
+        &self,
+        child: &spawn::Spawned,
+        cgroup: Option<&Cgroup>,
+        tracee: &mut Tracee,
+    ) -> Result<()> {
+        if self.namespaces.user {
+            map_ids(child.pid).map_err(|source| Error::Setup {
+                stage: "mapear uid/gid no namespace de usuário",
+                source,
+            })?;
+        }
+        if let Some(cgroup) = cgroup {
+            cgroup.add(child.pid)?;
+        }
+        // SAFETY: escreve um byte de um buffer local
+        if unsafe { libc::write(child.sync.as_raw_fd(), [1u8].as_ptr().cast(), 1) } != 1 {
+            // O filho morreu antes de ler: o pipe de erro explica
+            return match spawn::read_setup_error(&child.error) {
+                Some(err) => Err(err),
+                None => Err(io::Error::last_os_error().into()),
+            };
+        }
+        if tracee.traced {
+            tracee.attach()?;
+        }
+        Ok(())
+    }
+}
+
+/// Estado de espera de um processo final.
+struct Exit {
+    status: libc::c_int,
+    usage: libc::rusage,
+}
+
+/// O filho e suas threads, do ponto de vista do tracer.
+struct Tracee {
+    pid: libc::pid_t,
+    traced: bool,
+    /// Threads conhecidas; com ptrace, cada uma é esperada pelo seu tid.
+    threads: Vec<libc::pid_t>,
+    execs: u32,
+    forbidden: Option<i64>,
+    /// Sinal fatal que o tracer aplicou no lugar do kernel.
+    fatal_signal: Option<i32>,
+    killed: bool,
+    exit: Option<Exit>,
+}
+
+impl Tracee {
+    fn new(pid: libc::pid_t, traced: bool) -> Self {
+        Self {
+            pid,
+            traced,
+            threads: vec![pid],
+            execs: 0,
+            forbidden: None,
+            fatal_signal: None,
+            killed: false,
+            exit: None,
+        }
+    }
+
+    /// Espera o SIGSTOP que o filho manda a si mesmo após o PTRACE_TRACEME,
+    /// liga as opções e o acompanha até o `execve`. Se o filho morrer antes,
+    /// o motivo está no pipe de erro.
+    fn attach(&mut self) -> Result<()> {
+        let Some(status) = self.wait_setup()? else {
+            return Ok(());
+        };
+        if libc::WSTOPSIG(status) != libc::SIGSTOP {
+            return Err(unexpected_stop(status));
+        }
+        let options = libc::PTRACE_O_TRACESECCOMP
+            | libc::PTRACE_O_TRACECLONE
+            | libc::PTRACE_O_TRACEEXEC
+            | libc::PTRACE_O_EXITKILL;
+        // SAFETY: o filho é nosso tracee e está parado
+        unsafe {
+            if ptrace(libc::PTRACE_SETOPTIONS, self.pid, 0, options as usize) != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            ptrace(libc::PTRACE_CONT, self.pid, 0, 0);
+        }
+
+        let Some(status) = self.wait_setup()? else {
+            return Ok(());
+        };
+        if (status >> 16) != libc::PTRACE_EVENT_EXEC {
+            return Err(unexpected_stop(status));
+        }
+        self.execs = 1;
+        // SAFETY: parado no PTRACE_EVENT_EXEC do próprio programa
+        unsafe {
+            ptrace(libc::PTRACE_CONT, self.pid, 0, 0);
+        }
+        Ok(())
+    }
+
+    /// Próxima parada do filho durante a preparação; `None` se ele terminou.
+    fn wait_setup(&mut self) -> Result<Option<libc::c_int>> {
+        let mut status = 0;
+        // SAFETY: espera só pelo próprio filho
+        if unsafe { libc::waitpid(self.pid, &mut status, libc::__WALL) } < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        if libc::WIFSTOPPED(status) {
+            Ok(Some(status))
+        } else {
+            self.threads.clear();
+            Ok(None)
+        }
+    }
+
+    /// Trata as paradas pendentes sem bloquear; devolve o estado final
+    /// quando o processo principal termina.
+    fn poll(&mut self) -> Result<Option<Exit>> {
+        let flags = libc::__WALL | libc::WNOHANG;
+        for tid in self.threads.clone() {
+            let mut status = 0;
+            // SAFETY: `usage` é só saída
+            let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+            let waited = unsafe { libc::wait4(tid, &mut status, flags, &mut usage) };
+            if waited == 0 {
+                continue;
+            }
+            if waited < 0 {
+                let err = io::Error::last_os_error();
+                if err.raw_os_error() == Some(libc::ECHILD) {
+                    self.threads.retain(|&t| t != tid);
+                    continue;
+                }
+                return Err(err.into());
+            }
+            if libc::WIFEXITED(status) || libc::WIFSIGNALED(status) {
+                self.threads.retain(|&t| t != tid);
+                if tid == self.pid {
+                    self.exit = Some(Exit { status, usage });
+                }
+            } else if libc::WIFSTOPPED(status) {
+                self.stopped(tid, status);
+            }
+        }
+        // O líder só é relatado depois das outras threads
+        Ok(self.exit.take_if(|_| self.threads.is_empty()))
+    }
+
+    fn stopped(&mut self, tid: libc::pid_t, status: libc::c_int) {
+        let event = (status >> 16) & 0xff;
+        let mut signal = libc::WSTOPSIG(status);
+        match event {
+            libc::PTRACE_EVENT_SECCOMP => {
+                self.forbidden = Some(syscall_number(tid));
+                self.kill();
+                return;
+            }
+            libc::PTRACE_EVENT_EXEC => {
+                self.execs += 1;
+                if self.execs > 1 {
+                    self.forbidden = Some(libc::SYS_execve);
+                    self.kill();
+                    return;
+                }
+                signal = 0;
+            }
+            libc::PTRACE_EVENT_CLONE => {
+                let mut thread: libc::c_ulong = 0;
+                // SAFETY: tracee parado em PTRACE_EVENT_CLONE
+                unsafe {
+                    ptrace(libc::PTRACE_GETEVENTMSG, tid, 0, (&raw mut thread) as usize);
+                }
+                self.threads.push(thread as libc::pid_t);
+                signal = 0;
+            }
+            // Threads novas nascem com SIGSTOP; um SIGSTOP do próprio programa
+            // só o deixaria parado até o timeout
+            _ if signal == libc::SIGSTOP => signal = 0,
+            // Como PID 1 do namespace, o programa ignora sinais sem handler,
+            // mesmo os fatais (o abort do Rust, um SIGSEGV): o tracer faz o
+            // que o kernel faria e mata o processo
+            _ if is_fatal_by_default(signal) && !has_handler(tid, signal) => {
+                self.fatal_signal = Some(signal);
+                self.kill();
+                return;
+            }
+            _ => {}
+        }
+        // SAFETY: `tid` é um tracee parado
+        unsafe {
+            ptrace(libc::PTRACE_CONT, tid, 0, signal as usize);
+        }
+    }
+
+    /// Mata o grupo de processos (sem namespace de PID) e o processo
+    /// principal, que como PID 1 do namespace leva todos junto.
+    fn kill(&mut self) {
+        // SAFETY: o pgid e o pid são do próprio filho
+        unsafe {
+            libc::kill(-self.pid, libc::SIGKILL);
+            libc::kill(self.pid, libc::SIGKILL);
+        }
+        self.killed = true;
+    }
+
+    /// Espera o fim depois de um erro na preparação.
+    fn reap(&mut self) {
+        while !self.threads.is_empty() {
+            match self.poll() {
+                Ok(Some(_)) | Err(_) => return,
+                Ok(None) => thread::sleep(POLL_INTERVAL),
+            }
+        }
+    }
+}
+
+/// `ptrace` da libc é variádico: `addr` e `data` precisam ir com o tamanho
+/// de um ponteiro, ou o kernel lê lixo nos bits altos do registrador.
+///
+/// # Safety
+///
+/// `data`, quando é um endereço, precisa apontar para memória válida do
+/// tipo que `request` espera.
+unsafe fn ptrace(
+    request: libc::c_uint,
+    tid: libc::pid_t,
+    addr: usize,
+    data: usize,
+) -> libc::c_long {
+    unsafe { libc::ptrace(request, tid, addr, data) }
+}
+
+fn unexpected_stop(status: libc::c_int) -> Error {
+    Error::Setup {
+        stage: "executar o programa",
+        source: io::Error::other(format!(
+            "parada inesperada antes do execve (status {status:#x})"
+        )),
+    }
+}
+
+/// Sinais cuja ação padrão encerra o processo (com ou sem core dump).
+fn is_fatal_by_default(signal: i32) -> bool {
+    !matches!(
+        signal,
+        libc::SIGCHLD
+            | libc::SIGCONT
+            | libc::SIGURG
+            | libc::SIGWINCH
+            | libc::SIGSTOP
+            | libc::SIGTSTP
+            | libc::SIGTTIN
+            | libc::SIGTTOU
+    )
+}
+
+/// `SigCgt` de `/proc/<tid>/status`: sinais com handler instalado.
+fn has_handler(tid: libc::pid_t, signal: i32) -> bool {
+    fs::read_to_string(format!("/proc/{tid}/status"))
+        .ok()
+        .and_then(|status| {
+            let mask = status.lines().find_map(|l| l.strip_prefix("SigCgt:"))?;
+            u64::from_str_radix(mask.trim(), 16).ok()
+        })
+        .is_some_and(|mask| mask & (1 << (signal - 1)) != 0)
+}
+
+/// Número da syscall barrada, lido na parada PTRACE_EVENT_SECCOMP.
+fn syscall_number(tid: libc::pid_t) -> i64 {
+    // SAFETY: a struct é só saída e o tamanho passado é o dela
+    unsafe {
+        let mut info: libc::ptrace_syscall_info = std::mem::zeroed();
+        let size = std::mem::size_of::<libc::ptrace_syscall_info>();
+        let filled = ptrace(
+            libc::PTRACE_GET_SYSCALL_INFO,
+            tid,
+            size,
+            (&raw mut info) as usize,
+        );
+        if filled > 0 && info.op == libc::PTRACE_SYSCALL_INFO_SECCOMP {
+            info.u.seccomp.nr as i64
+        } else {
+            -1
+        }
+    }
+}
+
+/// Mapeia o uid/gid do pai para [`SANDBOX_ID`] dentro do namespace. O
+/// `setgroups` precisa ser negado antes do `gid_map` sem CAP_SETGID.
+fn map_ids(pid: libc::pid_t) -> io::Result<()> {
+    // SAFETY: getuid/getgid não falham
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    let proc = format!("/proc/{pid}");
+    fs::write(format!("{proc}/setgroups"), "deny")?;
+    fs::write(format!("{proc}/uid_map"), format!("{SANDBOX_ID} {uid} 1"))?;
+    fs::write(format!("{proc}/gid_map"), format!("{SANDBOX_ID} {gid} 1"))
+}
+
+/// O Rust aborta com "memory allocation of N bytes failed" quando o
+/// RLIMIT_AS nega uma alocação.
+fn allocation_failed(signal: Option<i32>, stderr: &str) -> bool {
+    signal == Some(libc::SIGABRT) && stderr.contains("memory allocation of")
+}
+
+fn timeval(tv: libc::timeval) -> Duration {
+    Duration::from_secs(tv.tv_sec as u64) + Duration::from_micros(tv.tv_usec as u64)
+}
+
+fn feed(pipe: OwnedFd, input: Vec<u8>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        // O filho pode encerrar sem ler tudo (EPIPE): não é erro nosso
+        let _ = File::from(pipe).write_all(&input);
+    })
+}
+
+/// Lê o pipe até o EOF guardando no máximo `max` bytes. Ao passar disso,
+/// marca `overflow` (o programa vai ser encerrado) e continua drenando.
+fn capture(pipe: OwnedFd, max: usize, overflow: Arc<AtomicBool>) -> JoinHandle<(String, bool)> {
+    thread::spawn(move || {
+        let mut pipe = File::from(pipe);
+        let mut kept = Vec::new();
+        let mut truncated = false;
+        let mut chunk = [0u8; 8192];
+        loop {
+            match pipe.read(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    let room = max.saturating_sub(kept.len());
+                    if n > room {
+                        truncated = true;
+                        overflow.store(true, Ordering::Relaxed);
+                    }
+                    kept.extend_from_slice(&chunk[..n.min(room)]);
+                }
+            }
+        }
+        (String::from_utf8_lossy(&kept).into_owned(), truncated)
+    })
+}
@@ -0,0 +1,225 @@
+//! Filtros seccomp-bpf do sandbox.
+//!
+//! São dois filtros empilhados. A lista de permitidas cobre o que um
+//! programa Rust de linha de comando usa (alocar, ler stdin, escrever
+//! stdout, threads, relógio, abrir arquivos só para leitura); o resto cai em
+//! `SECCOMP_RET_TRACE` e o tracer registra qual syscall foi barrada. O outro
+//! filtro responde `ENOSYS` ao `clone3`, cujos argumentos ficam em uma
+//! struct que o BPF não enxerga: a glibc então volta ao `clone`, em que a
+//! lista exige `CLONE_THREAD` (threads sim, processos novos não).
+
+use std::collections::BTreeMap;
+
+use seccompiler::{
+    BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp, SeccompCondition, SeccompFilter,
+    SeccompRule, TargetArch,
+};
+
+use crate::{Error, Result};
+
+macro_rules! syscalls {
+    ($($name:ident),* $(,)?) => {
+        &[$((libc::$name, stringify!($name))),*]
+    };
+}
+
+/// Permitidas sem restrição de argumentos.
+const ALLOWED: &[(i64, &str)] = syscalls![
+    SYS_read,
+    SYS_write,
+    SYS_readv,
+    SYS_writev,
+    SYS_pread64,
+    SYS_pwrite64,
+    SYS_close,
+    SYS_fstat,
+    SYS_newfstatat,
+    SYS_statx,
+    SYS_lseek,
+    SYS_fcntl,
+    SYS_dup,
+    SYS_dup3,
+    SYS_getcwd,
+    SYS_readlinkat,
+    SYS_getdents64,
+    SYS_faccessat,
+    SYS_faccessat2,
+    SYS_ppoll,
+    SYS_mmap,
+    SYS_mprotect,
+    SYS_munmap,
+    SYS_mremap,
+    SYS_madvise,
+    SYS_brk,
+    SYS_membarrier,
+    SYS_rt_sigaction,
+    SYS_rt_sigprocmask,
+    SYS_rt_sigreturn,
+    SYS_sigaltstack,
+    SYS_futex,
+    SYS_set_robust_list,
+    SYS_set_tid_address,
+    SYS_rseq,
+    SYS_prlimit64,
+    SYS_getrandom,
+    SYS_clock_gettime,
+    SYS_clock_getres,
+    SYS_clock_nanosleep,
+    SYS_nanosleep,
+    SYS_gettimeofday,
+    SYS_sched_yield,
+    SYS_sched_getaffinity,
+    SYS_getpid,
+    SYS_gettid,
+    SYS_getppid,
+    SYS_getuid,
+    SYS_geteuid,
+    SYS_getgid,
+    SYS_getegid,
+    SYS_tgkill,
+    SYS_uname,
+    SYS_sysinfo,
+    SYS_getrusage,
+    SYS_times,
+    SYS_exit,
+    SYS_exit_group,
+    // O primeiro `execve` é o do próprio programa; o tracer barra os demais
+    SYS_execve,
+];
+
+#[cfg(target_arch = "x86_64")]
+const ALLOWED_ARCH: &[(i64, &str)] = syscalls![
+    SYS_arch_prctl,
+    SYS_access,
+    SYS_stat,
+    SYS_lstat,
+    SYS_poll,
+    SYS_readlink,
+    SYS_time,
+    SYS_dup2,
+];
+
+#[cfg(not(target_arch = "x86_64"))]
+const ALLOWED_ARCH: &[(i64, &str)] = &[];
+
+/// Proibidas, mas com nome conhecido para o relatório.
+const KNOWN: &[(i64, &str)] = syscalls![
+    SYS_openat,
+    SYS_ioctl,
+    SYS_clone,
+    SYS_clone3,
+    SYS_execveat,
+    SYS_socket,
+    SYS_socketpair,
+    SYS_connect,
+    SYS_bind,
+    SYS_listen,
+    SYS_accept4,
+    SYS_sendto,
+    SYS_recvfrom,
+    SYS_kill,
+    SYS_ptrace,
+    SYS_mount,
+    SYS_umount2,
+    SYS_unshare,
+    SYS_setns,
+    SYS_chroot,
+    SYS_pivot_root,
+    SYS_reboot,
+    SYS_init_module,
+    SYS_finit_module,
+    SYS_delete_module,
+    SYS_bpf,
+    SYS_perf_event_open,
+    SYS_keyctl,
+    SYS_add_key,
+    SYS_request_key,
+    SYS_process_vm_readv,
+    SYS_process_vm_writev,
+    SYS_open_by_handle_at,
+    SYS_kexec_load,
+    SYS_setuid,
+    SYS_setgid,
+    SYS_sethostname,
+    SYS_setpriority,
+    SYS_personality,
+    SYS_userfaultfd,
+    SYS_io_uring_setup,
+    SYS_mkdirat,
+    SYS_unlinkat,
+    SYS_renameat2,
+    SYS_fchmodat,
+    SYS_fchownat,
+    SYS_truncate,
+    SYS_ftruncate,
+    SYS_pipe2,
+    SYS_wait4,
+];
+
+#[cfg(target_arch = "x86_64")]
+const KNOWN_ARCH: &[(i64, &str)] = syscalls![
+    SYS_open, SYS_creat, SYS_fork, SYS_vfork, SYS_mkdir, SYS_unlink, SYS_rename, SYS_pipe,
+];
+
+#[cfg(not(target_arch = "x86_64"))]
+const KNOWN_ARCH: &[(i64, &str)] = &[];
+
+/// Flags de `open`/`openat` que escrevem ou criam arquivos.
+const WRITE_FLAGS: u64 = (libc::O_WRONLY | libc::O_RDWR | libc::O_CREAT | libc::O_TRUNC) as u64;
+
+/// Nome de uma syscall da arquitetura atual, se for uma das conhecidas.
+pub fn name(number: i64) -> Option<&'static str> {
+    [ALLOWED, ALLOWED_ARCH, KNOWN, KNOWN_ARCH]
+        .into_iter()
+        .flatten()
+        .find(|(nr, _)| *nr == number)
+        .map(|(_, name)| name.trim_start_matches("SYS_"))
+}
+
+/// Filtros na ordem em que são instalados. A lista vem por último: depois
+/// dela, nem o `prctl` que instalaria outro filtro é permitido.
+pub(crate) fn filters() -> Result<Vec<BpfProgram>> {
+    let arch = TargetArch::try_from(std::env::consts::ARCH)
+        .map_err(|err| Error::Seccomp(format!("{err:?}")))?;
+
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = ALLOWED
+        .iter()
+        .chain(ALLOWED_ARCH)
+        .map(|(nr, _)| (*nr, Vec::new()))
+        .collect();
+    rules.insert(
+        libc::SYS_openat,
+        vec![rule(2, SeccompCmpOp::MaskedEq(WRITE_FLAGS), 0)?],
+    );
+    #[cfg(target_arch = "x86_64")]
+    rules.insert(
+        libc::SYS_open,
+        vec![rule(1, SeccompCmpOp::MaskedEq(WRITE_FLAGS), 0)?],
+    );
+    let thread = libc::CLONE_THREAD as u64;
+    rules.insert(
+        libc::SYS_clone,
+        vec![rule(0, SeccompCmpOp::MaskedEq(thread), thread)?],
+    );
+
+    let allowlist = SeccompFilter::new(rules, SeccompAction::Trace(0), SeccompAction::Allow, arch)
+        .map_err(|err| Error::Seccomp(err.to_string()))?;
+    let clone3 = SeccompFilter::new(
+        BTreeMap::from([(libc::SYS_clone3, Vec::new())]),
+        SeccompAction::Allow,
+        SeccompAction::Errno(libc::ENOSYS as u32),
+        arch,
+    )
+    .map_err(|err| Error::Seccomp(err.to_string()))?;
+
+    [clone3, allowlist]
+        .into_iter()
+        .map(|filter| BpfProgram::try_from(filter).map_err(|err| Error::Seccomp(err.to_string())))
+        .collect()
+}
+
+fn rule(arg: u8, op: SeccompCmpOp, value: u64) -> Result<SeccompRule> {
+    SeccompCondition::new(arg, SeccompCmpArgLen::Qword, op, value)
+        .and_then(|condition| SeccompRule::new(vec![condition]))
+        .map_err(|err| Error::Seccomp(err.to_string()))
+}
@@ -0,0 +1,385 @@
+//! Criação do processo isolado.
+//!
+//! Tudo que o filho usa entre o `clone` e o `execve` é montado antes pelo
+//! pai (strings C, vetores de ponteiros, filtros BPF): nesse intervalo o
+//! filho é uma cópia de um processo com várias threads e só pode chamar
+//! funções async-signal-safe, sem alocar.
+
+use std::ffi::{CString, OsStr};
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::process::Command;
+
+use seccompiler::BpfProgram;
+
+use crate::filesystem::Root;
+use crate::limits::{Limits, Namespaces};
+use crate::{Error, Result};
+
+/// RLIMIT_FSIZE: arquivos que o programa criar (o seccomp já barra a escrita
+/// em disco, mas o limite fica como segunda barreira).
+const MAX_FILE_BYTES: u64 = 16 * 1024 * 1024;
+const MAX_OPEN_FILES: u64 = 64;
+const HOSTNAME: &[u8] = b"themelion";
+
+/// Etapas do filho antes do `execve`, na ordem; o índice vai pelo pipe de erro.
+const STAGES: [&str; 12] = [
+    "aguardar o processo pai",
+    "tornar as montagens privadas",
+    "montar a raiz mínima",
+    "definir o hostname",
+    "redirecionar stdin/stdout/stderr",
+    "entrar no diretório de trabalho",
+    "criar o grupo de processos",
+    "aplicar rlimits",
+    "ativar no_new_privs",
+    "ativar o ptrace",
+    "instalar o filtro seccomp",
+    "executar o programa",
+];
+
+#[derive(Clone, Copy)]
+enum Stage {
+    Sync,
+    Mounts,
+    Root,
+    Hostname,
+    Stdio,
+    Chdir,
+    ProcessGroup,
+    Rlimits,
+    NoNewPrivs,
+    Trace,
+    Seccomp,
+    Exec,
+}
+
+/// Programa, ambiente e configuração já convertidos para o filho.
+pub(crate) struct Prepared {
+    program: CString,
+    _args: Vec<CString>,
+    argv: Vec<*const libc::c_char>,
+    _env: Vec<CString>,
+    envp: Vec<*const libc::c_char>,
+    cwd: Option<CString>,
+    rlimits: [(libc::__rlimit_resource_t, libc::rlimit); 5],
+    filters: Vec<BpfProgram>,
+    namespaces: Namespaces,
+    root: Option<Root>,
+}
+
+impl Prepared {
+    /// O ambiente do filho tem só as variáveis definidas explicitamente em
+    /// `cmd` (`env_clear` é implícito) e o programa precisa ser um caminho.
+    /// Com `root`, o filho troca de raiz antes de entrar no diretório.
+    pub fn new(
+        cmd: &Command,
+        limits: &Limits,
+        namespaces: Namespaces,
+        filters: Vec<BpfProgram>,
+        root: Option<Root>,
+    ) -> Result<Self> {
+        let program = cmd.get_program();
+        if !program.as_bytes().contains(&b'/') {
+            return Err(Error::InvalidCommand(format!(
+                "\"{}\" precisa ser um caminho (o sandbox não consulta o PATH)",
+                program.to_string_lossy()
+            )));
+        }
+
+        let args: Vec<CString> = std::iter::once(program)
+            .chain(cmd.get_args())
+            .map(c_string)
+            .collect::<Result<_>>()?;
+        let env: Vec<CString> = cmd
+            .get_envs()
+            .filter_map(|(key, value)| {
+                let value = value?;
+                let mut pair = key.as_bytes().to_vec();
+                pair.push(b'=');
+                pair.extend_from_slice(value.as_bytes());
+                Some(c_string(OsStr::from_bytes(&pair)))
+            })
+            .collect::<Result<_>>()?;
+
+        let rlimit = |soft: u64, hard: u64| libc::rlimit {
+            rlim_cur: soft as libc::rlim_t,
+            rlim_max: hard as libc::rlim_t,
+        };
+        Ok(Self {
+            program: c_string(program)?,
+            argv: null_terminated(&args),
+            _args: args,
+            envp: null_terminated(&env),
+            _env: env,
+            cwd: cmd
+                .get_current_dir()
+                .map(|dir| c_string(dir.as_os_str()))
+                .transpose()?,
+            rlimits: [
+                // Hard um segundo acima: o soft gera SIGXCPU, o hard SIGKILL
+                (
+                    libc::RLIMIT_CPU,
+                    rlimit(limits.cpu_seconds, limits.cpu_seconds + 1),
+                ),
+                (
+                    libc::RLIMIT_AS,
+                    rlimit(limits.memory_bytes, limits.memory_bytes),
+                ),
+                (libc::RLIMIT_FSIZE, rlimit(MAX_FILE_BYTES, MAX_FILE_BYTES)),
+                (libc::RLIMIT_NOFILE, rlimit(MAX_OPEN_FILES, MAX_OPEN_FILES)),
+                (libc::RLIMIT_CORE, rlimit(0, 0)),
+            ],
+            filters,
+            namespaces,
+            root,
+        })
+    }
+
+    pub fn traced(&self) -> bool {
+        !self.filters.is_empty()
+    }
+
+    pub fn program(&self) -> &Path {
+        Path::new(OsStr::from_bytes(self.program.as_bytes()))
+    }
+}
+
+/// Pontas do pai dos pipes criados por [`spawn`].
+pub(crate) struct Spawned {
+    pub pid: libc::pid_t,
+    pub stdin: OwnedFd,
+    pub stdout: OwnedFd,
+    pub stderr: OwnedFd,
+    /// Um byte libera o filho, que espera parado o pai mapear uid/gid e
+    /// colocá-lo no cgroup.
+    pub sync: OwnedFd,
+    /// Fecha no `execve` (O_CLOEXEC); antes disso, o filho escreve aqui a
+    /// etapa e o errno que falharam.
+    pub error: OwnedFd,
+}
+
+/// `clone` com os namespaces de `prepared`. O filho fica parado até receber
+/// um byte em [`Spawned::sync`].
+pub(crate) fn spawn(prepared: &Prepared) -> Result<Spawned> {
+    let (stdin_read, stdin_write) = pipe()?;
+    let (stdout_read, stdout_write) = pipe()?;
+    let (stderr_read, stderr_write) = pipe()?;
+    let (sync_read, sync_write) = pipe()?;
+    let (error_read, error_write) = pipe()?;
+
+    let flags = prepared.namespaces.clone_flags() | libc::SIGCHLD;
+    // SAFETY: sem CLONE_VM e sem pilha nova, o `clone` se comporta como um
+    // `fork` que já nasce nos namespaces pedidos.
+    let pid = unsafe { libc::syscall(libc::SYS_clone, flags as libc::c_ulong, 0, 0, 0, 0) };
+    if pid < 0 {
+        return Err(Error::Spawn(io::Error::last_os_error()));
+    }
+    if pid == 0 {
+        let fds = ChildFds {
+            stdin: stdin_read.as_raw_fd(),
+            stdout: stdout_write.as_raw_fd(),
+            stderr: stderr_write.as_raw_fd(),
+            sync: sync_read.as_raw_fd(),
+            error: error_write.as_raw_fd(),
+        };
+        // SAFETY: processo filho recém-criado; `child` nunca retorna
+        unsafe { child(prepared, &fds) }
+    }
+
+    Ok(Spawned {
+        pid: pid as libc::pid_t,
+        stdin: stdin_write,
+        stdout: stdout_read,
+        stderr: stderr_read,
+        sync: sync_write,
+        error: error_read,
+    })
+}
+
+/// Lê o relatório de falha do filho: `None` se o pipe fechou vazio (o
+/// `execve` deu certo ou o filho morreu sem relatar).
+pub(crate) fn read_setup_error(error: &OwnedFd) -> Option<Error> {
+    let mut report = [0u8; 5];
+    let mut filled = 0;
+    while filled < report.len() {
+        // SAFETY: escreve no máximo o que falta de `report`
+        let n = unsafe {
+            libc::read(
+                error.as_raw_fd(),
+                report[filled..].as_mut_ptr().cast(),
+                report.len() - filled,
+            )
+        };
+        match n {
+            0 => break,
+            n if n < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted => {}
+            n if n < 0 => break,
+            n => filled += n as usize,
+        }
+    }
+    if filled < report.len() {
+        return None;
+    }
+    let errno = i32::from_ne_bytes([report[1], report[2], report[3], report[4]]);
+    Some(Error::Setup {
+        stage: STAGES.get(report[0] as usize).copied().unwrap_or("?"),
+        source: io::Error::from_raw_os_error(errno),
+    })
+}
+
+struct ChildFds {
+    stdin: RawFd,
+    stdout: RawFd,
+    stderr: RawFd,
+    sync: RawFd,
+    error: RawFd,
+}
+
+/// Lado do filho: prepara o ambiente e executa o programa.
+///
+/// # Safety
+///
+/// Só pode ser chamada no filho logo após o `clone`.
+unsafe fn child(prepared: &Prepared, fds: &ChildFds) -> ! {
+    let fail = |stage: Stage| -> ! {
+        let errno = io::Error::last_os_error().raw_os_error().unwrap_or(0);
+        let mut report = [0u8; 5];
+        report[0] = stage as u8;
+        report[1..].copy_from_slice(&errno.to_ne_bytes());
+        // SAFETY: write e _exit são async-signal-safe
+        unsafe {
+            libc::write(fds.error, report.as_ptr().cast(), report.len());
+            libc::_exit(127)
+        }
+    };
+
+    // SAFETY: todas as chamadas abaixo são syscalls async-signal-safe sobre
+    // dados preparados pelo pai.
+    unsafe {
+        let mut byte = 0u8;
+        if libc::read(fds.sync, (&raw mut byte).cast(), 1) != 1 {
+            fail(Stage::Sync);
+        }
+
+        let namespaces = prepared.namespaces;
+        if namespaces.mount {
+            // Nada montado aqui dentro vaza para o host
+            if libc::mount(
+                std::ptr::null(),
+                c"/".as_ptr(),
+                std::ptr::null(),
+                libc::MS_REC | libc::MS_PRIVATE,
+                std::ptr::null(),
+            ) != 0
+            {
+                fail(Stage::Mounts);
+            }
+            match &prepared.root {
+                Some(root) if !root.enter() => fail(Stage::Root),
+                Some(_) => {}
+                None if namespaces.pid => {
+                    // `/proc` do novo namespace de PID; alguns ambientes
+                    // (contêineres com /proc mascarado) não permitem, e aí
+                    // fica o do host
+                    libc::mount(
+                        c"proc".as_ptr(),
+                        c"/proc".as_ptr(),
+                        c"proc".as_ptr(),
+                        libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                        std::ptr::null(),
+                    );
+                }
+                None => {}
+            }
+        }
+        if namespaces.uts && libc::sethostname(HOSTNAME.as_ptr().cast(), HOSTNAME.len()) != 0 {
+            fail(Stage::Hostname);
+        }
+
+        if libc::dup2(fds.stdin, 0) < 0
+            || libc::dup2(fds.stdout, 1) < 0
+            || libc::dup2(fds.stderr, 2) < 0
+        {
+            fail(Stage::Stdio);
+        }
+        if let Some(cwd) = &prepared.cwd
+            && libc::chdir(cwd.as_ptr()) != 0
+        {
+            fail(Stage::Chdir);
+        }
+        // Grupo próprio: sem namespace de PID, o timeout mata o grupo inteiro
+        if libc::setpgid(0, 0) != 0 {
+            fail(Stage::ProcessGroup);
+        }
+        for (resource, limit) in &prepared.rlimits {
+            if libc::setrlimit(*resource, limit) != 0 {
+                fail(Stage::Rlimits);
+            }
+        }
+        if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+            fail(Stage::NoNewPrivs);
+        }
+
+        if prepared.traced() {
+            // O pai precisa ligar PTRACE_O_TRACESECCOMP antes do filtro: sem
+            // tracer atento, SECCOMP_RET_TRACE vira ENOSYS em silêncio
+            if libc::ptrace(libc::PTRACE_TRACEME, 0, 0usize, 0usize) != 0 {
+                fail(Stage::Trace);
+            }
+            libc::raise(libc::SIGSTOP);
+            for filter in &prepared.filters {
+                let program = libc::sock_fprog {
+                    len: filter.len() as libc::c_ushort,
+                    filter: filter.as_ptr() as *mut libc::sock_filter,
+                };
+                if libc::prctl(
+                    libc::PR_SET_SECCOMP,
+                    libc::SECCOMP_MODE_FILTER as libc::c_ulong,
+                    &raw const program,
+                    0,
+                    0,
+                ) != 0
+                {
+                    fail(Stage::Seccomp);
+                }
+            }
+        }
+
+        libc::execve(
+            prepared.program.as_ptr(),
+            prepared.argv.as_ptr(),
+            prepared.envp.as_ptr(),
+        );
+        fail(Stage::Exec)
+    }
+}
+
+fn pipe() -> Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    // SAFETY: `fds` tem espaço para os dois descritores
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    // SAFETY: descritores recém-criados, de posse exclusiva
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+pub(crate) fn c_string(value: &OsStr) -> Result<CString> {
+    CString::new(value.as_bytes()).map_err(|_| {
+        Error::InvalidCommand(format!(
+            "\"{}\" contém um byte nulo",
+            value.to_string_lossy()
+        ))
+    })
+}
+
+fn null_terminated(strings: &[CString]) -> Vec<*const libc::c_char> {
+    strings
+        .iter()
+        .map(|s| s.as_ptr())
+        .chain(std::iter::once(std::ptr::null()))
+        .collect()
+}
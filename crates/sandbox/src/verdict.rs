@@ -0,0 +1,83 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// O que encerrou o programa. Quando um limite foi atingido, diz qual.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum Verdict {
+    /// Terminou sozinho, com este código de saída.
+    Exited { code: i32 },
+    /// Morto por um sinal que não corresponde a nenhum limite.
+    Signaled { signal: i32 },
+    /// Passou do tempo de relógio e foi morto.
+    WallTimeLimit { limit_ms: u64 },
+    /// Passou do tempo de CPU (SIGXCPU do RLIMIT_CPU).
+    CpuTimeLimit { limit_seconds: u64 },
+    /// OOM killer do cgroup ou alocação negada pelo RLIMIT_AS.
+    MemoryLimit { limit_bytes: u64 },
+    /// Escreveu mais que o permitido em stdout ou stderr.
+    OutputLimit { limit_bytes: usize },
+    /// Tentou criar um arquivo maior que o RLIMIT_FSIZE (SIGXFSZ).
+    FileSizeLimit,
+    /// Fez uma syscall fora da lista permitida.
+    ForbiddenSyscall { number: i64, name: Option<String> },
+}
+
+impl Verdict {
+    pub fn success(&self) -> bool {
+        *self == Verdict::Exited { code: 0 }
+    }
+
+    /// `true` se o programa foi encerrado por algum limite do sandbox.
+    pub fn limit_exceeded(&self) -> bool {
+        !matches!(self, Verdict::Exited { .. } | Verdict::Signaled { .. })
+    }
+
+    /// `true` para os limites de tempo (relógio ou CPU).
+    pub fn timed_out(&self) -> bool {
+        matches!(
+            self,
+            Verdict::WallTimeLimit { .. } | Verdict::CpuTimeLimit { .. }
+        )
+    }
+
+    pub fn exit_code(&self) -> Option<i32> {
+        match self {
+            Verdict::Exited { code } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Exited { code } => write!(f, "saiu com código {code}"),
+            Verdict::Signaled { signal } => write!(f, "encerrado pelo sinal {signal}"),
+            Verdict::WallTimeLimit { limit_ms } => {
+                write!(f, "tempo limite excedido ({limit_ms} ms de relógio)")
+            }
+            Verdict::CpuTimeLimit { limit_seconds } => {
+                write!(f, "tempo de CPU excedido ({limit_seconds} s)")
+            }
+            Verdict::MemoryLimit { limit_bytes } => {
+                write!(f, "memória excedida ({} MiB)", limit_bytes / (1024 * 1024))
+            }
+            Verdict::OutputLimit { limit_bytes } => {
+                write!(f, "saída grande demais (mais de {limit_bytes} bytes)")
+            }
+            Verdict::FileSizeLimit => write!(f, "arquivo grande demais"),
+            Verdict::ForbiddenSyscall {
+                name: Some(name), ..
+            } => write!(f, "syscall proibida: {name}"),
+            Verdict::ForbiddenSyscall { number, name: None } => {
+                write!(f, "syscall proibida: número {number}")
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! A raiz mínima: o programa lê o diretório de trabalho, mas não o resto do
+//! host.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use themelion_sandbox::{Limits, Sandbox, Verdict};
+
+const LIMITS: Limits = Limits {
+    cpu_seconds: 2,
+    memory_bytes: 256 * 1024 * 1024,
+    wall_time_ms: 5_000,
+    output_bytes: 64 * 1024,
+};
+
+/// `cat` do sistema dentro do sandbox. Sem seccomp (o `cat` usa
+/// `fadvise64`, fora da lista): quem barra a leitura é só a raiz mínima.
+fn cat(workdir: &Path, file: &Path) -> themelion_sandbox::Outcome {
+    let mut cmd = Command::new("/bin/cat");
+    cmd.arg(file).current_dir(workdir);
+    Sandbox::new(LIMITS)
+        .with_seccomp(false)
+        .run(&cmd, &[])
+        .expect("o sandbox precisa de namespaces de usuário e de montagem")
+}
+
+#[test]
+fn reads_inside_the_workdir() {
+    let workdir = tempfile::tempdir().unwrap();
+    fs::write(workdir.path().join("input.txt"), "dentro\n").unwrap();
+
+    let outcome = cat(workdir.path(), Path::new("input.txt"));
+    assert_eq!(outcome.verdict, Verdict::Exited { code: 0 });
+    assert_eq!(outcome.stdout, "dentro\n");
+}
+
+#[test]
+fn blocks_reads_outside_the_workdir() {
+    let workdir = tempfile::tempdir().unwrap();
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    assert!(manifest.is_file());
+
+    for file in [manifest.as_path(), Path::new("/etc/hostname")] {
+        let outcome = cat(workdir.path(), file);
+        assert_ne!(
+            outcome.verdict,
+            Verdict::Exited { code: 0 },
+            "{}",
+            file.display()
+        );
+        assert!(
+            outcome.stdout.is_empty(),
+            "{} vazou: {}",
+            file.display(),
+            outcome.stdout
+        );
+        assert!(
+            outcome.stderr.contains("No such file"),
+            "{}",
+            outcome.stderr
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Texto → termos do índice.
+//!
+//! No índice, cada palavra vira até dois termos: o stem da forma sem acentos
+//! e a forma sem acentos do stem. O primeiro faz "codificacao" casar com
+//! "codificação" (as duas dobram para o mesmo texto antes do stemmer); o
+//! segundo preserva o que o stemmer só acerta com acentos, como "codificação"
+//! ~ "codificar". Na consulta, a palavra vira só o primeiro termo, que não
+//! depende de acento; a montagem junta ao primeiro as postagens do segundo
+//! (ver [`Analyzer::aliases`]), então as duas grafias dão o mesmo resultado.
+
+use rust_stemmers::{Algorithm, Stemmer};
+use themelion_mdx::{Block, Document, plain_text};
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Palavras mais longas que isso são hashes, URLs coladas ou base64.
+const MAX_WORD_LEN: usize = 40;
+
+/// Palavras frequentes demais para distinguir tópicos.
+const STOPWORDS: &[&str] = &[
+    "a", "ao", "aos", "aquela", "aquelas", "aquele", "aqueles", "aquilo", "as", "ate", "com",
+    "como", "da", "das", "de", "dela", "delas", "dele", "deles", "depois", "do", "dos", "e", "ela",
+    "elas", "ele", "eles", "em", "entre", "era", "essa", "essas", "esse", "esses", "esta", "estas",
+    "este", "estes", "eu", "foi", "ha", "isso", "isto", "ja", "lhe", "lhes", "mais", "mas", "me",
+    "mesmo", "meu", "minha", "muito", "na", "nao", "nas", "nem", "no", "nos", "num", "numa", "o",
+    "os", "ou", "para", "pela", "pelas", "pelo", "pelos", "por", "qual", "quando", "que", "quem",
+    "se", "sem", "ser", "seu", "seus", "so", "sua", "suas", "tambem", "te", "tem", "um", "uma",
+    "voce", "voces",
+];
+
+pub struct Analyzer {
+    stemmer: Stemmer,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer {
+    pub fn new() -> Self {
+        Self {
+            stemmer: Stemmer::create(Algorithm::Portuguese),
+        }
+    }
+
+    /// Termos indexados de uma palavra já separada por [`words`]; vazio para
+    /// stopwords.
+    pub fn terms(&self, word: &str) -> Vec<String> {
+        match self.aliases(word) {
+            Some((term, Some(accented))) => vec![term, accented],
+            Some((term, None)) => vec![term],
+            None => Vec::new(),
+        }
+    }
+
+    /// Termo buscado para uma palavra da consulta: o mesmo com ou sem acentos.
+    pub fn query_term(&self, word: &str) -> Option<String> {
+        self.aliases(word).map(|(term, _)| term)
+    }
+
+    /// O stem da forma sem acentos e, quando difere dele, a forma sem acentos
+    /// do stem; `None` para stopwords.
+    pub fn aliases(&self, word: &str) -> Option<(String, Option<String>)> {
+        let lower = word.to_lowercase();
+        let folded = fold(&lower);
+        if STOPWORDS.contains(&folded.as_str()) {
+            return None;
+        }
+        let term = self.stemmer.stem(&folded).into_owned();
+        let accented = fold(&self.stemmer.stem(&lower));
+        Some(if accented == term {
+            (term, None)
+        } else {
+            (term, Some(accented))
+        })
+    }
+}
+
+/// Minúsculas e sem acentos: "Codificação" → "codificacao".
+pub fn fold(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Palavras de um texto: sequências alfanuméricas com duas letras ou mais.
+pub fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphanumeric()).filter(|word| {
+        let len = word.chars().count();
+        (2..=MAX_WORD_LEN).contains(&len) && !word.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+/// Títulos e prosa do corpo de um `topic.mdx`, já sem marcação. Código, abas
+/// de exemplos e JSX ficam de fora: são quase só palavras reservadas, que
+/// casariam com qualquer busca.
+pub fn sections(document: &Document) -> (Vec<String>, Vec<String>) {
+    let mut headings = Vec::new();
+    let mut prose = Vec::new();
+    for section in &document.sections {
+        if !section.heading.is_empty() {
+            headings.push(plain_text(&section.heading));
+        }
+        for block in &section.blocks {
+            match block {
+                Block::Heading { text, .. } => headings.push(plain_text(text)),
+                Block::Paragraph { text } | Block::Callout { text, .. } => {
+                    prose.push(plain_text(text));
+                }
+                Block::List { items, .. } => prose.extend(items.iter().map(|i| plain_text(i))),
+                Block::Table(table) => prose.extend(
+                    table
+                        .header
+                        .iter()
+                        .chain(table.rows.iter().flatten())
+                        .map(|cell| plain_text(cell)),
+                ),
+                Block::Code { .. } | Block::CodeTabs | Block::Jsx { .. } => {}
+            }
+        }
+    }
+    (headings, prose)
+}
@@ -0,0 +1,178 @@
+//! Montagem do índice a partir do conteúdo escaneado.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use themelion_content::Content;
+use themelion_content::model::TopicStatus;
+use themelion_mdx::{Block, Document, plain_text};
+
+use crate::analyze::{Analyzer, sections, words};
+use crate::index::{Doc, FORMAT_VERSION, Index};
+
+/// Peso de cada ocorrência, por campo.
+const TITLE: u32 = 10;
+const TAG: u32 = 6;
+const HEADING: u32 = 3;
+const CATEGORY: u32 = 2;
+const PROSE: u32 = 1;
+/// Teto do peso vindo da prosa, para tópicos longos não dominarem.
+const MAX_PROSE: u32 = 10;
+
+/// Tamanho máximo do resumo, em caracteres.
+const SUMMARY_LEN: usize = 160;
+
+/// Um documento por tópico do registry, mais os `topic.mdx` sem entrada no
+/// registry, na ordem das salas e categorias. Um tópico é (sala, slug): o
+/// mesmo slug em duas salas são dois documentos.
+pub fn build(content: &Content, analyzer: &Analyzer) -> Index {
+    let mut docs: Vec<Doc> = Vec::new();
+    let mut weights: Vec<HashMap<String, u32>> = Vec::new();
+    let mut by_key: HashMap<(&str, &str), usize> = HashMap::new();
+    let mut aliases: BTreeSet<(String, String)> = BTreeSet::new();
+
+    for room in &content.rooms {
+        for category in &room.categories {
+            for topic in &category.topics {
+                if by_key.contains_key(&(room.slug.as_str(), topic.slug.as_str())) {
+                    continue;
+                }
+                by_key.insert((&room.slug, &topic.slug), docs.len());
+                let mut terms = HashMap::new();
+                add(analyzer, &mut aliases, &mut terms, &topic.title, TITLE);
+                for tag in &topic.tags {
+                    add(analyzer, &mut aliases, &mut terms, tag, TAG);
+                }
+                add(analyzer, &mut aliases, &mut terms, &category.name, CATEGORY);
+                weights.push(terms);
+                docs.push(Doc {
+                    slug: topic.slug.clone(),
+                    title: topic.title.clone(),
+                    room: room.slug.clone(),
+                    category: category.slug.clone(),
+                    status: topic.status.unwrap_or(TopicStatus::Planned),
+                    summary: String::new(),
+                });
+            }
+        }
+    }
+
+    for topic in &content.topics {
+        let meta = &topic.frontmatter;
+        let at = match by_key.get(&(meta.room.as_str(), meta.slug.as_str())) {
+            Some(&at) => at,
+            None => {
+                by_key.insert((&meta.room, &meta.slug), docs.len());
+                weights.push(HashMap::new());
+                docs.push(Doc {
+                    slug: meta.slug.clone(),
+                    title: meta.title.clone(),
+                    room: meta.room.clone(),
+                    category: meta.category.clone(),
+                    status: TopicStatus::Unregistered,
+                    summary: String::new(),
+                });
+                docs.len() - 1
+            }
+        };
+        let doc = &mut docs[at];
+        // O título do frontmatter é o que a página mostra
+        doc.title = meta.title.clone();
+        let document = themelion_mdx::parse(&topic.content);
+        let (headings, prose) = sections(&document);
+        doc.summary = summary(&document);
+
+        let terms = &mut weights[at];
+        let mut fields = HashMap::new();
+        add(analyzer, &mut aliases, &mut fields, &meta.title, TITLE);
+        for tag in &meta.tags {
+            add(analyzer, &mut aliases, &mut fields, tag, TAG);
+        }
+        for heading in &headings {
+            add(analyzer, &mut aliases, &mut fields, heading, HEADING);
+        }
+        let mut body = HashMap::new();
+        for text in &prose {
+            add(analyzer, &mut aliases, &mut body, text, PROSE);
+        }
+        for (term, weight) in body {
+            *fields.entry(term).or_default() += weight.min(MAX_PROSE);
+        }
+        // Registry e frontmatter costumam repetir título e tags: fica o maior
+        for (term, weight) in fields {
+            let entry = terms.entry(term).or_default();
+            *entry = (*entry).max(weight);
+        }
+    }
+
+    let mut inverted: BTreeMap<String, BTreeMap<u32, u32>> = BTreeMap::new();
+    for (doc, terms) in weights.into_iter().enumerate() {
+        for (term, weight) in terms {
+            inverted.entry(term).or_default().insert(doc as u32, weight);
+        }
+    }
+    // A consulta só busca o stem sem acentos: ele leva também os documentos
+    // do stem acentuado da mesma palavra ("codificaca" ← "codific")
+    for (term, accented) in &aliases {
+        let Some(extra) = inverted.get(accented).cloned() else {
+            continue;
+        };
+        let postings = inverted.entry(term.clone()).or_default();
+        for (doc, weight) in extra {
+            let entry = postings.entry(doc).or_default();
+            *entry = (*entry).max(weight);
+        }
+    }
+
+    // Postings em ordem de documento, para o JSON ser estável
+    let (terms, postings) = inverted
+        .into_iter()
+        .map(|(term, postings)| {
+            let flat = postings.into_iter().flat_map(|(doc, w)| [doc, w]).collect();
+            (term, flat)
+        })
+        .unzip();
+    Index {
+        version: FORMAT_VERSION,
+        docs,
+        terms,
+        postings,
+    }
+}
+
+/// Soma `weight` a cada termo das palavras de `text`, anotando em `aliases`
+/// os pares (stem sem acentos, stem acentuado) que diferem.
+fn add(
+    analyzer: &Analyzer,
+    aliases: &mut BTreeSet<(String, String)>,
+    terms: &mut HashMap<String, u32>,
+    text: &str,
+    weight: u32,
+) {
+    for word in words(text) {
+        let Some((term, accented)) = analyzer.aliases(word) else {
+            continue;
+        };
+        if let Some(accented) = accented {
+            *terms.entry(accented.clone()).or_default() += weight;
+            aliases.insert((term.clone(), accented));
+        }
+        *terms.entry(term).or_default() += weight;
+    }
+}
+
+/// Primeiro parágrafo, sem a marcação de ênfase e de código.
+fn summary(document: &Document) -> String {
+    let text = document
+        .blocks()
+        .find_map(|block| match block {
+            Block::Paragraph { text } => Some(plain_text(text)),
+            _ => None,
+        })
+        .unwrap_or_default();
+    if text.chars().count() <= SUMMARY_LEN {
+        return text;
+    }
+    let cut: String = text.chars().take(SUMMARY_LEN).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    format!("{}…", cut.trim_end_matches([',', '.', ';', ':']))
+}
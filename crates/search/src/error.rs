@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Índice de busca inválido: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Índice de busca na versão {found}, esperada {expected}: gere de novo")]
+    Version { found: u32, expected: u32 },
+
+    #[error("Índice de busca corrompido: {terms} termos e {postings} listas de postings")]
+    Postings { terms: usize, postings: usize },
+}
@@ -0,0 +1,137 @@
+//! Índice invertido estático e a busca sobre ele.
+//!
+//! O JSON tem os documentos, os termos em ordem lexicográfica e, para cada
+//! termo, pares `documento, peso` achatados num array. Busca exata é uma
+//! busca binária em `terms`; a última palavra da consulta também casa por
+//! prefixo, para resultados enquanto se digita.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use themelion_content::model::TopicStatus;
+
+use crate::analyze::{Analyzer, fold, words};
+use crate::{Error, Result};
+
+/// Versão do formato do JSON; sobe quando o site precisa mudar a leitura.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Prefixos mais curtos que isso casam com termos demais.
+const MIN_PREFIX: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Index {
+    pub version: u32,
+    pub docs: Vec<Doc>,
+    pub terms: Vec<String>,
+    pub postings: Vec<Vec<u32>>,
+}
+
+/// Um tópico do registry, implementado ou não.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Doc {
+    pub slug: String,
+    pub title: String,
+    pub room: String,
+    pub category: String,
+    pub status: TopicStatus,
+    /// Primeiro parágrafo do `topic.mdx`; vazio em tópicos planejados.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hit {
+    pub doc: usize,
+    pub score: u32,
+}
+
+impl Index {
+    pub fn from_json(raw: &str) -> Result<Self> {
+        let index: Index = serde_json::from_str(raw)?;
+        if index.version != FORMAT_VERSION {
+            return Err(Error::Version {
+                found: index.version,
+                expected: FORMAT_VERSION,
+            });
+        }
+        if index.postings.len() != index.terms.len() {
+            return Err(Error::Postings {
+                terms: index.terms.len(),
+                postings: index.postings.len(),
+            });
+        }
+        Ok(index)
+    }
+
+    /// Documentos que contêm todas as palavras da consulta (stopwords não
+    /// contam), do maior para o menor peso somado.
+    pub fn search(&self, analyzer: &Analyzer, query: &str, limit: usize) -> Vec<Hit> {
+        let query_words: Vec<&str> = words(query).collect();
+        // Sem espaço no fim, a última palavra ainda está sendo digitada
+        let typing = !query.ends_with(char::is_whitespace);
+
+        let mut totals: Option<HashMap<usize, u32>> = None;
+        for (i, word) in query_words.iter().enumerate() {
+            let mut matched: HashMap<usize, u32> = HashMap::new();
+            let mut add = |postings: &[u32]| {
+                for pair in postings.chunks_exact(2) {
+                    let best = matched.entry(pair[0] as usize).or_default();
+                    *best = (*best).max(pair[1]);
+                }
+            };
+            let Some(term) = analyzer.query_term(word) else {
+                continue;
+            };
+            if let Some(postings) = self.postings(&term) {
+                add(postings);
+            }
+            let prefix = fold(word);
+            if typing && i + 1 == query_words.len() && prefix.chars().count() >= MIN_PREFIX {
+                for postings in self.prefixed(&prefix) {
+                    add(postings);
+                }
+            }
+
+            totals = Some(match totals {
+                None => matched,
+                Some(totals) => totals
+                    .into_iter()
+                    .filter_map(|(doc, score)| matched.get(&doc).map(|s| (doc, score + s)))
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<Hit> = totals
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(doc, score)| Hit { doc, score })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| self.implemented(b.doc).cmp(&self.implemented(a.doc)))
+                .then_with(|| a.doc.cmp(&b.doc))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    fn postings(&self, term: &str) -> Option<&[u32]> {
+        let at = self.terms.binary_search_by(|t| t.as_str().cmp(term)).ok()?;
+        Some(&self.postings[at])
+    }
+
+    fn prefixed<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a [u32]> {
+        let start = self.terms.partition_point(|t| t.as_str() < prefix);
+        self.terms[start..]
+            .iter()
+            .zip(&self.postings[start..])
+            .take_while(move |(term, _)| term.starts_with(prefix))
+            .map(|(_, postings)| postings.as_slice())
+    }
+
+    fn implemented(&self, doc: usize) -> bool {
+        self.docs[doc].status == TopicStatus::Implemented
+    }
+}
@@ -0,0 +1,24 @@
+//! Busca por texto nos tópicos, sem servidor.
+//!
+//! [`build`] percorre o registry e os `topic.mdx` e monta um índice
+//! invertido com títulos, tags, títulos de seção e a prosa de cada tópico
+//! (blocos de código ficam de fora), lidos pelo `themelion-mdx`. Os termos passam pelo stemmer de
+//! português e perdem os acentos, então "codificacao", "codificação" e
+//! "codificar" chegam ao mesmo tópico. O índice vira um JSON estático em
+//! `public/`, que o site consulta pelo `themelion-wasm` com o mesmo
+//! [`Analyzer`] usado na montagem.
+
+pub mod analyze;
+mod build;
+mod error;
+mod index;
+
+pub use analyze::Analyzer;
+pub use build::build;
+pub use error::Error;
+pub use index::{Doc, FORMAT_VERSION, Hit, Index};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Onde o índice fica, relativo à raiz do repositório.
+pub const INDEX_PATH: &str = "public/search-index.json";
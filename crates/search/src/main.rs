@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use themelion_content::Content;
+use themelion_content::model::TopicStatus;
+use themelion_search::{Analyzer, Doc, INDEX_PATH, Index, build};
+
+/// Monta e consulta o índice de busca estático dos tópicos.
+#[derive(Parser)]
+#[command(name = "themelion-search", version)]
+struct Cli {
+    /// Raiz do repositório (onde fica `data/`)
+    #[arg(long, default_value = ".", global = true)]
+    root: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Gera o índice a partir do registry e dos `topic.mdx`
+    Build {
+        /// Destino, relativo à raiz (padrão: public/search-index.json)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Busca no índice já gerado, como o site faria
+    Query {
+        /// Texto da busca; sem espaço no fim, a última palavra casa por prefixo
+        #[arg(required = true)]
+        text: Vec<String>,
+        /// Índice a consultar, relativo à raiz (padrão: public/search-index.json)
+        #[arg(long)]
+        index: Option<PathBuf>,
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Match<'a> {
+    score: u32,
+    #[serde(flatten)]
+    doc: &'a Doc,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let analyzer = Analyzer::new();
+
+    match cli.command {
+        Command::Build { out } => {
+            let content = Content::scan(&cli.root).context("Erro ao escanear o conteúdo")?;
+            let index = build(&content, &analyzer);
+            let path = cli.root.join(out.unwrap_or_else(|| INDEX_PATH.into()));
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Erro ao criar {}", parent.display()))?;
+            }
+            let json = serde_json::to_string(&index)?;
+            fs::write(&path, &json)
+                .with_context(|| format!("Erro ao escrever {}", path.display()))?;
+            let implemented = index
+                .docs
+                .iter()
+                .filter(|d| d.status != TopicStatus::Planned)
+                .count();
+            eprintln!(
+                "{} tópicos ({implemented} com conteúdo), {} termos, {} KiB em {}",
+                index.docs.len(),
+                index.terms.len(),
+                json.len().div_ceil(1024),
+                path.display()
+            );
+        }
+        Command::Query {
+            text,
+            index,
+            limit,
+            format,
+        } => {
+            let path = cli.root.join(index.unwrap_or_else(|| INDEX_PATH.into()));
+            let raw = fs::read_to_string(&path).with_context(|| {
+                format!(
+                    "Erro ao ler {} (gere com `themelion-search build`)",
+                    path.display()
+                )
+            })?;
+            let index = Index::from_json(&raw)?;
+            let hits = index.search(&analyzer, &text.join(" "), limit);
+            match format {
+                Format::Text => {
+                    for hit in &hits {
+                        let doc = &index.docs[hit.doc];
+                        let planned = match doc.status {
+                            TopicStatus::Planned => " (planejado)",
+                            _ => "",
+                        };
+                        println!("{:>4}  {:<36}{}{planned}", hit.score, doc.slug, doc.title);
+                    }
+                    eprintln!("{} resultados", hits.len());
+                }
+                Format::Json => {
+                    let results: Vec<Match> = hits
+                        .iter()
+                        .map(|hit| Match {
+                            score: hit.score,
+                            doc: &index.docs[hit.doc],
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&results)?);
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use serde_json::json;
+use themelion_content::Content;
+use themelion_content::model::TopicStatus;
+use themelion_search::analyze::fold;
+use themelion_search::{Analyzer, Index, build};
+
+fn room(slug: &str, topics: &[(&str, &str)]) -> serde_json::Value {
+    let topics: Vec<_> = topics
+        .iter()
+        .enumerate()
+        .map(|(i, (slug, title))| {
+            json!({
+                "slug": slug, "title": title, "difficulty": "beginner", "order": i,
+                "status": "implemented",
+            })
+        })
+        .collect();
+    json!({
+        "slug": slug, "name": slug, "description": "", "icon": "brain", "order": 1,
+        "categories": [{ "slug": "geral", "name": "Geral", "order": 1, "topics": topics }],
+    })
+}
+
+fn topic(room: &str, slug: &str, body: &str) -> serde_json::Value {
+    json!({
+        "title": slug, "slug": slug, "room": room, "category": "geral",
+        "difficulty": "beginner", "order": 1, "status": "implemented",
+        "contentPath": "", "content": body, "examples": [],
+    })
+}
+
+/// `hash-tables` existe nas duas salas, com conteúdo diferente em cada uma.
+fn content() -> Content {
+    Content {
+        rooms: serde_json::from_value(json!([
+            room(
+                "fundamentos",
+                &[
+                    ("character-encoding", "Codificação de Caracteres"),
+                    ("hash-tables", "Hash Tables")
+                ]
+            ),
+            room(
+                "backend",
+                &[("url-e-uri", "URL e URI"), ("hash-tables", "Hash Tables")]
+            ),
+        ]))
+        .unwrap(),
+        topics: serde_json::from_value(json!([
+            topic(
+                "fundamentos",
+                "character-encoding",
+                "UTF-8 é uma codificação de tamanho variável."
+            ),
+            topic(
+                "backend",
+                "url-e-uri",
+                "Caracteres reservados precisam codificar com percent-encoding."
+            ),
+            topic(
+                "fundamentos",
+                "hash-tables",
+                "Colisões resolvidas com encadeamento."
+            ),
+            topic(
+                "backend",
+                "hash-tables",
+                "Cache distribuído com consistent hashing."
+            ),
+        ]))
+        .unwrap(),
+        languages: Vec::new(),
+        roadmaps: Vec::new(),
+    }
+}
+
+fn slugs(index: &Index, query: &str) -> Vec<(String, String)> {
+    index
+        .search(&Analyzer::new(), query, 10)
+        .into_iter()
+        .map(|hit| {
+            let doc = &index.docs[hit.doc];
+            (doc.room.clone(), doc.slug.clone())
+        })
+        .collect()
+}
+
+#[test]
+fn folds_case_and_accents() {
+    assert_eq!(fold("Codificação"), "codificacao");
+    assert_eq!(fold("ÁRVORE Binária"), "arvore binaria");
+}
+
+#[test]
+fn stems_to_the_same_query_term_with_or_without_accents() {
+    let analyzer = Analyzer::new();
+    let accented = analyzer.query_term("codificação").unwrap();
+    assert_eq!(analyzer.query_term("codificacao").unwrap(), accented);
+    assert_eq!(analyzer.query_term("Codificação").unwrap(), accented);
+    assert_eq!(analyzer.query_term("que"), None);
+    // No índice entra também o stem acentuado, que casa com o verbo
+    let terms = analyzer.terms("codificação");
+    assert!(terms.contains(&analyzer.query_term("codificar").unwrap()));
+}
+
+#[test]
+fn accented_and_folded_queries_rank_the_same() {
+    let index = build(&content(), &Analyzer::new());
+    let analyzer = Analyzer::new();
+    let accented = index.search(&analyzer, "codificação ", 10);
+    let folded = index.search(&analyzer, "codificacao ", 10);
+    assert_eq!(accented, folded);
+    assert_eq!(
+        slugs(&index, "codificacao "),
+        [
+            ("fundamentos".to_string(), "character-encoding".to_string()),
+            ("backend".to_string(), "url-e-uri".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn last_word_matches_by_prefix_while_typing() {
+    let index = build(&content(), &Analyzer::new());
+    assert_eq!(slugs(&index, "codif"), slugs(&index, "codificação "),);
+    // Com espaço no fim a palavra está completa: "codif" não é um termo
+    assert!(slugs(&index, "codif ").is_empty());
+    // Prefixos curtos demais não casam
+    assert!(slugs(&index, "co").is_empty());
+}
+
+#[test]
+fn keeps_a_document_per_room_for_shared_slugs() {
+    let index = build(&content(), &Analyzer::new());
+    let shared: Vec<_> = index
+        .docs
+        .iter()
+        .filter(|d| d.slug == "hash-tables")
+        .collect();
+    assert_eq!(shared.len(), 2);
+    assert!(shared.iter().all(|d| d.status == TopicStatus::Implemented));
+
+    assert_eq!(
+        slugs(&index, "distribuído "),
+        [("backend".to_string(), "hash-tables".to_string())]
+    );
+    assert_eq!(
+        slugs(&index, "encadeamento "),
+        [("fundamentos".to_string(), "hash-tables".to_string())]
+    );
+}
+
+#[test]
+fn round_trips_through_json() {
+    let index = build(&content(), &Analyzer::new());
+    let raw = serde_json::to_string(&index).unwrap();
+    let loaded = Index::from_json(&raw).unwrap();
+    assert_eq!(loaded.docs, index.docs);
+    assert_eq!(slugs(&loaded, "cache "), slugs(&index, "cache "));
+}
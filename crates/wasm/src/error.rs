@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON inválido: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Search(#[from] themelion_search::Error),
+
+    #[error("esperava {expected} execuções (exemplos e testes), recebeu {found}")]
+    Executions { expected: usize, found: usize },
+
+    #[error("modo de comparação desconhecido: {0} (use exact, lines ou tokens)")]
+    Compare(String),
+}
@@ -0,0 +1,286 @@
+//! Modo Prática e checagem de exemplos inteiramente no navegador.
+//!
+//! Compilado para `wasm32-unknown-unknown` (`wasm-pack build crates/wasm
+//! --target web`), expõe ao JavaScript as funções de [`js`], que recebem e
+//! devolvem JSON em string para que o site não dependa do layout das structs.
+//! [`API_VERSION`] sobe quando algum desses formatos muda de forma
+//! incompatível.
+//!
+//! Código Rust roda no interpretador de `themelion-interp`, sem rustc, Miri
+//! nem servidor; a avaliação e o placar são os de `themelion-judge-core`, os
+//! mesmos do juiz por runner. Um programa fora do subconjunto do
+//! interpretador recebe CE com a explicação, em vez de um veredito errado.
+//! Para linguagens sem interpretador, o site executa como puder e julga as
+//! saídas com [`score_executions`].
+//!
+//! [`js::SearchIndex`] consulta o índice de `themelion-search` no navegador,
+//! com o mesmo stemmer usado para montá-lo.
+
+mod error;
+
+use serde::{Deserialize, Serialize};
+use themelion_interp::{Config, Exit, Outcome, Program};
+use themelion_judge_core::{Compare, Execution, Submission, Termination, TestCase, compare, score};
+
+pub use error::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Versão dos formatos JSON trocados com o JavaScript.
+pub const API_VERSION: u32 = 1;
+
+/// Linguagem julgada pelo interpretador.
+pub const LANGUAGE: &str = "rust";
+
+/// Desafio como o site o envia: os mesmos campos de `challenges/*.toml`
+/// (os demais, como `title` e `starter`, são ignorados).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Challenge {
+    pub id: String,
+    #[serde(default)]
+    pub compare: Compare,
+    #[serde(default)]
+    pub examples: Vec<TestCase>,
+    pub tests: Vec<TestCase>,
+}
+
+impl Challenge {
+    /// Exemplos visíveis primeiro, depois os testes ocultos.
+    pub fn cases(&self) -> impl Iterator<Item = (&TestCase, bool)> {
+        self.examples
+            .iter()
+            .map(|case| (case, false))
+            .chain(self.tests.iter().map(|case| (case, true)))
+    }
+}
+
+/// Resultado de [`check_example`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExampleCheck {
+    pub matches: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    pub outcome: Outcome,
+}
+
+/// Julga `source` pelo interpretador em todos os casos do desafio.
+pub fn judge(challenge: &Challenge, source: &str) -> Submission {
+    let config = Config::default();
+    let program = match Program::parse(source) {
+        Ok(program) => program,
+        Err(err) => {
+            return Submission::compile_error(
+                &challenge.id,
+                LANGUAGE,
+                challenge.cases(),
+                err.to_string(),
+            );
+        }
+    };
+
+    let scored = score(
+        &challenge.id,
+        LANGUAGE,
+        challenge.compare,
+        challenge.cases(),
+        |case| {
+            let outcome = program.run(&case.input, &config);
+            if outcome.exit.rejected() {
+                return Err(outcome.exit);
+            }
+            Ok(execution(outcome))
+        },
+    );
+    scored.unwrap_or_else(|exit| {
+        Submission::compile_error(&challenge.id, LANGUAGE, challenge.cases(), exit.to_string())
+    })
+}
+
+/// Julga saídas produzidas fora do interpretador, uma por caso, na ordem de
+/// [`Challenge::cases`].
+pub fn score_executions(
+    challenge: &Challenge,
+    language: &str,
+    executions: Vec<Execution>,
+) -> Result<Submission> {
+    let total = challenge.cases().count();
+    if executions.len() != total {
+        return Err(Error::Executions {
+            expected: total,
+            found: executions.len(),
+        });
+    }
+    let mut executions = executions.into_iter();
+    score(
+        &challenge.id,
+        language,
+        challenge.compare,
+        challenge.cases(),
+        |_| executions.next().ok_or(()),
+    )
+    .map_err(|()| Error::Executions {
+        expected: total,
+        found: 0,
+    })
+}
+
+/// Roda um exemplo sem entrada e compara com a saída esperada.
+pub fn check_example(
+    source: &str,
+    expected: &str,
+    mode: Compare,
+) -> themelion_interp::Result<ExampleCheck> {
+    let outcome = themelion_interp::run(source, "", &Config::default())?;
+    let matches = outcome.exit.success() && mode.matches(expected, &outcome.stdout);
+    let diff =
+        (!matches && outcome.exit.success()).then(|| compare::diff(expected, &outcome.stdout));
+    Ok(ExampleCheck {
+        matches,
+        diff,
+        outcome,
+    })
+}
+
+/// `"exact"`, `"lines"` ou `"tokens"`; vazio usa o padrão.
+pub fn parse_compare(mode: &str) -> Result<Compare> {
+    match mode {
+        "" => Ok(Compare::default()),
+        "exact" => Ok(Compare::Exact),
+        "lines" => Ok(Compare::Lines),
+        "tokens" => Ok(Compare::Tokens),
+        other => Err(Error::Compare(other.to_string())),
+    }
+}
+
+/// Sem relógio no wasm, estourar o limite de passos é o TLE.
+fn execution(outcome: Outcome) -> Execution {
+    let termination = match &outcome.exit {
+        exit if exit.success() => Termination::Success,
+        exit @ Exit::StepLimit { .. } => Termination::TimeLimit(exit.to_string()),
+        exit => Termination::Error(exit.to_string()),
+    };
+    Execution {
+        stdout: outcome.stdout,
+        stderr: outcome.stderr,
+        time_ms: 0,
+        termination,
+    }
+}
+
+/// Funções exportadas para o JavaScript (nomes em camelCase).
+pub mod js {
+    use wasm_bindgen::prelude::*;
+
+    use crate::{Challenge, Result};
+
+    fn to_js<T>(result: Result<T>) -> std::result::Result<T, JsError> {
+        result.map_err(|err| JsError::new(&err.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = apiVersion)]
+    pub fn api_version() -> u32 {
+        crate::API_VERSION
+    }
+
+    #[wasm_bindgen(js_name = compareOutput)]
+    pub fn compare_output(
+        expected: &str,
+        actual: &str,
+        mode: &str,
+    ) -> std::result::Result<bool, JsError> {
+        to_js(crate::parse_compare(mode)).map(|mode| mode.matches(expected, actual))
+    }
+
+    #[wasm_bindgen(js_name = diffOutput)]
+    pub fn diff_output(expected: &str, actual: &str) -> String {
+        themelion_judge_core::compare::diff(expected, actual)
+    }
+
+    /// `Outcome` em JSON: `{ stdout, stderr, exit: { kind, ... }, steps }`.
+    #[wasm_bindgen(js_name = runProgram)]
+    pub fn run_program(source: &str, stdin: &str) -> std::result::Result<String, JsError> {
+        let outcome = themelion_interp::run(source, stdin, &Default::default())
+            .map_err(|err| JsError::new(&err.to_string()))?;
+        to_js(serde_json::to_string(&outcome).map_err(Into::into))
+    }
+
+    /// `Submission` em JSON, como `themelion-judge submit --format json`.
+    #[wasm_bindgen(js_name = judgeSubmission)]
+    pub fn judge_submission(challenge: &str, source: &str) -> std::result::Result<String, JsError> {
+        to_js((|| {
+            let challenge: Challenge = serde_json::from_str(challenge)?;
+            Ok(serde_json::to_string(&crate::judge(&challenge, source))?)
+        })())
+    }
+
+    /// `executions`: lista de `{ stdout, stderr?, timeMs?, termination:
+    /// { kind: "success" | "timeLimit" | "error", message? } }`.
+    #[wasm_bindgen(js_name = scoreExecutions)]
+    pub fn score_executions(
+        challenge: &str,
+        language: &str,
+        executions: &str,
+    ) -> std::result::Result<String, JsError> {
+        to_js((|| {
+            let challenge: Challenge = serde_json::from_str(challenge)?;
+            let executions = serde_json::from_str(executions)?;
+            let submission = crate::score_executions(&challenge, language, executions)?;
+            Ok(serde_json::to_string(&submission)?)
+        })())
+    }
+
+    /// Índice de `public/search-index.json`, carregado uma vez por página.
+    #[wasm_bindgen]
+    pub struct SearchIndex {
+        index: themelion_search::Index,
+        analyzer: themelion_search::Analyzer,
+    }
+
+    #[wasm_bindgen]
+    impl SearchIndex {
+        #[wasm_bindgen(constructor)]
+        pub fn new(json: &str) -> std::result::Result<SearchIndex, JsError> {
+            let index = to_js(themelion_search::Index::from_json(json).map_err(Into::into))?;
+            Ok(SearchIndex {
+                index,
+                analyzer: themelion_search::Analyzer::new(),
+            })
+        }
+
+        /// Lista de `{ score, slug, title, room, category, status, summary? }`.
+        pub fn query(&self, text: &str, limit: usize) -> std::result::Result<String, JsError> {
+            let hits: Vec<_> = self
+                .index
+                .search(&self.analyzer, text, limit)
+                .into_iter()
+                .map(|hit| SearchHit {
+                    score: hit.score,
+                    doc: &self.index.docs[hit.doc],
+                })
+                .collect();
+            to_js(serde_json::to_string(&hits).map_err(Into::into))
+        }
+    }
+
+    #[derive(serde::Serialize)]
+    struct SearchHit<'a> {
+        score: u32,
+        #[serde(flatten)]
+        doc: &'a themelion_search::Doc,
+    }
+
+    /// `{ matches, diff?, outcome }` para um `examples/rust.rs`.
+    #[wasm_bindgen(js_name = checkExample)]
+    pub fn check_example(
+        source: &str,
+        expected: &str,
+        mode: &str,
+    ) -> std::result::Result<String, JsError> {
+        let mode = to_js(crate::parse_compare(mode))?;
+        let check = crate::check_example(source, expected, mode)
+            .map_err(|err| JsError::new(&err.to_string()))?;
+        to_js(serde_json::to_string(&check).map_err(Into::into))
+    }
+}
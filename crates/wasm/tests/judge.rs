@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::Path;
+
+use themelion_judge_core::Status;
+use themelion_wasm::{Challenge, judge};
+
+const CHALLENGE: &str =
+    "data/content/fundamentos/estruturas-de-dados/arrays/challenges/soma-e-maximo.toml";
+
+/// O desafio como o site o envia, com o id que ele recebe do nome do
+/// arquivo, e a solução Rust do próprio TOML.
+fn load() -> (Challenge, String) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(CHALLENGE);
+    let text = fs::read_to_string(&path).unwrap();
+    let mut table: toml::Table = toml::from_str(&text).unwrap();
+    let solution = table["solution"]["rust"].as_str().unwrap().to_string();
+    table.insert("id".into(), "soma-e-maximo".into());
+    (table.try_into().unwrap(), solution)
+}
+
+#[test]
+fn accepts_the_reference_solution() {
+    let (challenge, solution) = load();
+    let submission = judge(&challenge, &solution);
+    assert_eq!(submission.status, Status::Accepted, "{submission:?}");
+    assert_eq!(submission.passed, 5);
+    assert_eq!(submission.total, 5);
+}
+
+#[test]
+fn rejects_a_wrong_answer() {
+    let (challenge, solution) = load();
+    let wrong = solution.replace("sum::<i64>()", "len()");
+    let submission = judge(&challenge, &wrong);
+    assert_eq!(submission.status, Status::WrongAnswer, "{submission:?}");
+}
+
+#[test]
+fn reports_syntax_errors_as_compile_errors() {
+    let (challenge, _) = load();
+    let submission = judge(&challenge, "fn main() {");
+    assert_eq!(submission.status, Status::CompileError);
+    assert!(submission.compiler_output.is_some());
+}
@@ -12,6 +12,9 @@ const __dirname = dirname(__filename);
 const VIRTUAL_MODULE_ID = "virtual:content";
 const RESOLVED_VIRTUAL_MODULE_ID = `\0${VIRTUAL_MODULE_ID}`;
 
+// `rust.en.rs`, `topic.pt-PT.mdx`: mesmo formato do `locale_of` de crates/content
+const LOCALE_SUFFIX = /\.[a-z]{2,3}(-[A-Z]{2})?\.[^./]+$/;
+
 interface TopicSummary {
 	slug: string;
 	title: string;
@@ -59,6 +62,7 @@ interface Topic {
 	contentPath: string;
 	content: string;
 	examples: TopicExample[];
+	cargoProject?: string;
 }
 
 interface Language {
@@ -78,8 +82,23 @@ interface Roadmap {
 	stages: { slug: string; name: string; description: string; order: number; topics: string[] }[];
 }
 
-function scanContent(rootDir: string) {
+interface ContentData {
+	rooms: Room[];
+	topics: Topic[];
+	languages: Language[];
+	roadmaps: Roadmap[];
+}
+
+interface CompiledManifest {
+	languages: string;
+	roadmaps: string;
+	rooms: { slug: string; file: string }[];
+	topics: { room: string; slug: string; file: string }[];
+}
+
+function scanContent(rootDir: string): ContentData {
 	const rooms: Room[] = [];
+	// Chave `<sala>/<slug>`: o mesmo slug pode existir em mais de uma sala
 	const topics = new Map<string, Topic>();
 	const languages = new Map<string, Language>();
 	const roadmaps = new Map<string, Roadmap>();
@@ -140,7 +159,10 @@ function scanContent(rootDir: string) {
 		const frontmatter = data as Topic;
 		const exampleDir = file.replace("topic.mdx", "examples");
 		const exampleGlob = `${exampleDir}/*`;
-		const exampleFiles = globSync(exampleGlob, { cwd: rootDir });
+		// Traduções (`rust.en.rs`) ficam de fora: as abas mostram o original
+		const exampleFiles = globSync(exampleGlob, { cwd: rootDir }).filter(
+			(exFile) => !LOCALE_SUFFIX.test(exFile),
+		);
 
 		const examples: TopicExample[] = exampleFiles.map((exFile) => {
 			const ext = exFile.split(".").pop() || "";
@@ -152,7 +174,7 @@ function scanContent(rootDir: string) {
 			};
 		});
 
-		topics.set(frontmatter.slug, {
+		topics.set(`${frontmatter.room}/${frontmatter.slug}`, {
 			...frontmatter,
 			status: "implemented",
 			contentPath: file,
@@ -165,7 +187,7 @@ function scanContent(rootDir: string) {
 	for (const room of rooms) {
 		for (const category of room.categories) {
 			for (const topic of category.topics) {
-				const implemented = topics.get(topic.slug);
+				const implemented = topics.get(`${room.slug}/${topic.slug}`);
 				if (implemented) {
 					topic.status = "implemented";
 				}
@@ -181,31 +203,17 @@ function scanContent(rootDir: string) {
 	};
 }
 
-export function contentPlugin(): Plugin {
-	const rootDir = resolve(__dirname, "..");
-	const watchDirs = ["data/content", "data/registry", "data/languages"].map((d) =>
-		resolve(rootDir, d),
-	);
-
-	return {
-		name: "vite-plugin-content",
-
-		resolveId(id) {
-			if (id === VIRTUAL_MODULE_ID) {
-				return RESOLVED_VIRTUAL_MODULE_ID;
-			}
-		},
-
-		load(id) {
-			if (id === RESOLVED_VIRTUAL_MODULE_ID) {
-				const data = scanContent(rootDir);
-
-				return `
-const _rooms = ${JSON.stringify(data.rooms)};
-const _topics = ${JSON.stringify(data.topics)};
-const _languages = ${JSON.stringify(data.languages)};
-const _roadmaps = ${JSON.stringify(data.roadmaps)};
+// Zip gerado por `themelion-examples export` (crates/examples) em
+// public/examples/<sala>/<slug>.zip, se existir; o `prebuild` do npm roda o export
+// quando há cargo, e no dev server ele precisa ser rodado à mão
+function cargoProjectFor(rootDir: string, room: string, slug: string): string | undefined {
+	const zip = `examples/${room}/${slug}.zip`;
+	return existsSync(resolve(rootDir, "public", zip)) ? `/${zip}` : undefined;
+}
 
+// Exports e buscas comuns aos dois modos; `_rooms`, `_topics`, `_languages` e
+// `_roadmaps` já estão definidos antes
+const MODULE_EXPORTS = `
 export const rooms = _rooms;
 export const topics = _topics;
 export const languages = _languages;
@@ -219,15 +227,88 @@ export function getTopicBySlug(slug) {
 	return _topics.find((t) => t.slug === slug) || null;
 }
 
+export function getTopicByRoomAndSlug(room, slug) {
+	return _topics.find((t) => t.room === room && t.slug === slug) || null;
+}
+
 export function getRoadmapBySlug(slug) {
 	return _roadmaps.find((r) => r.slug === slug) || null;
 }
 `;
+
+// Conteúdo escaneado de data/: tudo serializado no próprio módulo
+function scannedModule(rootDir: string): string {
+	const data = scanContent(rootDir);
+	for (const topic of data.topics) {
+		topic.cargoProject = cargoProjectFor(rootDir, topic.room, topic.slug);
+	}
+
+	return `
+const _rooms = ${JSON.stringify(data.rooms)};
+const _topics = ${JSON.stringify(data.topics)};
+const _languages = ${JSON.stringify(data.languages)};
+const _roadmaps = ${JSON.stringify(data.roadmaps)};
+${MODULE_EXPORTS}`;
+}
+
+// Saída dividida do `themelion-content` (crates/content): cada sala e tópico entra
+// como import do seu JSON, então mudar um tópico só recarrega aquele arquivo e o
+// módulo virtual só é refeito quando o manifest muda
+function compiledModule(rootDir: string, compiledDir: string): string {
+	const manifestPath = resolve(compiledDir, "manifest.json");
+	const manifest = JSON.parse(readFileSync(manifestPath, "utf-8")) as CompiledManifest;
+
+	const imports: string[] = [];
+	const importJson = (file: string): string => {
+		const name = `_json${imports.length}`;
+		imports.push(`import ${name} from ${JSON.stringify(resolve(compiledDir, file))};`);
+		return name;
+	};
+
+	const rooms = manifest.rooms.map((entry) => importJson(entry.file));
+	const topics = manifest.topics.map((entry) => {
+		const cargoProject = cargoProjectFor(rootDir, entry.room, entry.slug);
+		const topic = importJson(entry.file);
+		return cargoProject
+			? `{ ...${topic}, cargoProject: ${JSON.stringify(cargoProject)} }`
+			: topic;
+	});
+
+	return `${imports.join("\n")}
+const _rooms = [${rooms.join(", ")}];
+const _topics = [${topics.join(", ")}];
+const _languages = ${importJson(manifest.languages)};
+const _roadmaps = ${importJson(manifest.roadmaps)};
+${MODULE_EXPORTS}`;
+}
+
+export function contentPlugin(): Plugin {
+	const rootDir = resolve(__dirname, "..");
+	// Opcional: THEMELION_CONTENT_DIR=.content usa o conteúdo já compilado
+	const compiledDir = process.env.THEMELION_CONTENT_DIR
+		? resolve(rootDir, process.env.THEMELION_CONTENT_DIR)
+		: null;
+	const watchDirs = compiledDir
+		? [compiledDir]
+		: ["data/content", "data/registry", "data/languages"].map((d) => resolve(rootDir, d));
+
+	return {
+		name: "vite-plugin-content",
+
+		resolveId(id) {
+			if (id === VIRTUAL_MODULE_ID) {
+				return RESOLVED_VIRTUAL_MODULE_ID;
+			}
+		},
+
+		load(id) {
+			if (id === RESOLVED_VIRTUAL_MODULE_ID) {
+				return compiledDir ? compiledModule(rootDir, compiledDir) : scannedModule(rootDir);
 			}
 		},
 
 		configureServer(server) {
-			// HMR: observar mudanças em content/, registry/, languages/
+			// HMR: observar mudanças em content/, registry/, languages/ (ou no diretório compilado)
 			for (const dir of watchDirs) {
 				if (existsSync(dir)) {
 					server.watcher.add(dir);
@@ -236,11 +317,13 @@ export function getRoadmapBySlug(slug) {
 
 			server.watcher.on("change", (filePath) => {
 				const rel = relative(rootDir, filePath);
-				if (
-					rel.startsWith("data/content/") ||
-					rel.startsWith("data/registry/") ||
-					rel.startsWith("data/languages/")
-				) {
+				// Os JSONs compilados são módulos comuns do Vite; só o manifest muda a lista
+				const isContentChange = compiledDir
+					? filePath === resolve(compiledDir, "manifest.json")
+					: rel.startsWith("data/content/") ||
+						rel.startsWith("data/registry/") ||
+						rel.startsWith("data/languages/");
+				if (isContentChange) {
 					const mod = server.moduleGraph.getModuleById(RESOLVED_VIRTUAL_MODULE_ID);
 					if (mod) {
 						server.moduleGraph.invalidateModule(mod);
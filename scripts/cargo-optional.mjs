@@ -0,0 +1,13 @@
+// Roda `cargo <args>` quando há toolchain Rust; sem ela, avisa e sai com sucesso,
+// para o build do site continuar funcionando em hosts estáticos sem cargo
+import { spawnSync } from "node:child_process";
+
+const args = process.argv.slice(2);
+
+if (spawnSync("cargo", ["--version"], { stdio: "ignore" }).error) {
+	console.warn(`cargo não encontrado, pulando: cargo ${args.join(" ")}`);
+	process.exit(0);
+}
+
+const result = spawnSync("cargo", args, { stdio: "inherit" });
+process.exit(result.status ?? 1);
@@ -33,6 +33,8 @@ export const AppRoutes = () => {
 				<Route path="/roadmaps" element={<RoadmapsPage />} />
 				<Route path="/roadmap/:roadmapSlug" element={<RoadmapPage />} />
 				<Route path="/projects" element={<ProjectsPage />} />
+				{/* Páginas pré-renderizadas (themelion-prerender): /<sala>/<tópico>/ */}
+				<Route path="/:roomSlug/:topicSlug" element={<TopicPage />} />
 			</Routes>
 		</>
 	);
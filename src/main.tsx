@@ -6,6 +6,8 @@ import "./index.css";
 const rootElement = document.getElementById("root");
 if (!rootElement) throw new Error("Elemento root não encontrado");
 
+// Páginas pré-renderizadas trazem HTML estático no #root (não gerado pelo React):
+// createRoot descarta esse conteúdo e monta o app no lugar
 createRoot(rootElement).render(
 	<StrictMode>
 		<App />
@@ -23,6 +23,8 @@ import {
 import { useCallback, useEffect, useRef, useState } from "react";
 import { Link, useLocation } from "react-router";
 
+const TOPIC_PATH = /^\/[^/]+\/[^/]+\/?$/;
+
 export const AppSidebar = () => {
 	const { t } = useLocale();
 	const navItems = getNavItems(t);
@@ -66,10 +68,11 @@ export const AppSidebar = () => {
 		if (pathname === "/home") setActiveSection("home");
 		else if (pathname === "/rooms" || pathname.startsWith("/room/"))
 			setActiveSection("rooms");
-		else if (pathname.startsWith("/topic/"))
-			setActiveSection(getActiveRoadmap() ? "roadmaps" : "rooms");
 		else if (pathname.startsWith("/roadmap")) setActiveSection("roadmaps");
 		else if (pathname.startsWith("/projects")) setActiveSection("projects");
+		// Tópicos: /<sala>/<slug> (canônica) ou /topic/<slug> (legada)
+		else if (TOPIC_PATH.test(pathname))
+			setActiveSection(getActiveRoadmap() ? "roadmaps" : "rooms");
 	}, [pathname, setActiveSection]);
 
 	// Callback para fechar o drawer mobile ao navegar
@@ -3,6 +3,7 @@ import { SidebarSkeleton } from "@client/modules/layout/components/skeletons";
 import { difficultyDotStyles } from "@client/shared/constants/difficulty";
 import type { Room } from "@client/shared/types";
 import { getRoomIcon } from "@client/shared/utils/helpers/room-icons";
+import { topicPath } from "@client/shared/utils/helpers/topic-path";
 import "@client/styles/modules/rooms-nav.css";
 import { Circle } from "lucide-react";
 import type { RefObject } from "react";
@@ -94,7 +95,7 @@ export const RoomsNav = ({
 																<Link
 																	key={topic.slug}
 																	ref={isActive ? activeRef : undefined}
-																	to={`/topic/${topic.slug}`}
+																	to={topicPath(r.slug, topic.slug)}
 																	onClick={() => onNavigate?.()}
 																	className="rooms-nav-topic"
 																	data-active={isActive}
@@ -3,16 +3,18 @@ import { Tabs, TabsContent, TabsList, TabsTrigger } from "@client/shared/compone
 import { COPY_FEEDBACK_DURATION } from "@client/shared/constants/timing";
 import type { TopicExample } from "@client/shared/types";
 import "@client/styles/modules/code-tabs.css";
-import { Check, Copy } from "lucide-react";
+import { Check, Copy, Download } from "lucide-react";
 import { useCallback, useState } from "react";
 
 const LANGUAGE_ORDER = ["python", "typescript", "csharp", "go", "rust"];
 
 interface CodeTabsProps {
 	examples: TopicExample[];
+	// Zip do exemplo em Rust como projeto Cargo (themelion-examples export)
+	cargoProject?: string;
 }
 
-export const CodeTabs = ({ examples }: CodeTabsProps) => {
+export const CodeTabs = ({ examples, cargoProject }: CodeTabsProps) => {
 	const [copiedId, setCopiedId] = useState<string | null>(null);
 
 	const sorted = [...examples].sort((a, b) => {
@@ -70,6 +72,12 @@ export const CodeTabs = ({ examples }: CodeTabsProps) => {
 							<code className="code-block-code">{example.code}</code>
 						</pre>
 					</div>
+					{example.languageId === "rust" && cargoProject && (
+						<a href={cargoProject} download className="code-tabs-download">
+							<Download size={12} />
+							<span>Baixar projeto Cargo</span>
+						</a>
+					)}
 				</TabsContent>
 			))}
 		</Tabs>
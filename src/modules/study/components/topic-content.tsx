@@ -7,6 +7,7 @@ import { CodeTabs } from "./code-tabs";
 interface TopicContentProps {
 	content: string;
 	examples: TopicExample[];
+	cargoProject?: string;
 }
 
 interface ContentSection {
@@ -197,7 +198,7 @@ const renderBlock = (body: string): ReactNode[] => {
 	return elements;
 };
 
-export const TopicContent = ({ content, examples }: TopicContentProps) => {
+export const TopicContent = ({ content, examples, cargoProject }: TopicContentProps) => {
 	const sections = parseSections(content);
 
 	return (
@@ -212,7 +213,11 @@ export const TopicContent = ({ content, examples }: TopicContentProps) => {
 						style={{ animationDelay: `${Math.min(idx + 1, 10) * 0.05}s` }}
 					>
 						{section.heading && <h2 className="topic-section-heading">{section.heading}</h2>}
-						{isCodeTabs ? <CodeTabs examples={examples} /> : renderBlock(section.body)}
+						{isCodeTabs ? (
+							<CodeTabs examples={examples} cargoProject={cargoProject} />
+						) : (
+							renderBlock(section.body)
+						)}
 					</section>
 				);
 			})}
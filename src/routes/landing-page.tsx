@@ -323,7 +323,7 @@ const ProductMockup = () => {
 						<div className="mockup-dot mockup-dot-green" />
 					</div>
 					<div className="mockup-urlbar">
-						<div className="mockup-url">themelion.dev/topic/arrays</div>
+						<div className="mockup-url">themelion.dev/fundamentos/arrays</div>
 					</div>
 					<div style={{ width: "3rem" }} />
 				</div>
@@ -5,6 +5,7 @@ import { setActiveRoadmap } from "@client/lib/active-roadmap";
 import { getAllProgress } from "@client/lib/progress";
 import { CollapsibleSection } from "@client/modules/layout/components/collapsible-section";
 import { PageLayout } from "@client/modules/layout/components/page-layout";
+import { topicPathBySlug } from "@client/shared/utils/helpers/topic-path";
 import { Check, Circle, Map as MapIcon } from "lucide-react";
 import { useEffect, useMemo } from "react";
 import { Link, useParams } from "react-router";
@@ -143,7 +144,7 @@ export const RoadmapPage = () => {
 										return (
 											<Link
 												key={topicSlug}
-												to={`/topic/${topicSlug}`}
+												to={topicPathBySlug(topicSlug)}
 												className="roadmap-topic"
 												data-completed={String(isCompleted)}
 											>
@@ -7,6 +7,7 @@ import { PageLayout } from "@client/modules/layout/components/page-layout";
 import { DifficultyBadge } from "@client/modules/study/components/difficulty-badge";
 import { StatusBadge } from "@client/modules/study/components/status-badge";
 import { getRoomIcon } from "@client/shared/utils/helpers/room-icons";
+import { topicPath } from "@client/shared/utils/helpers/topic-path";
 import { CircleCheck } from "lucide-react";
 import { useEffect, useMemo } from "react";
 import { Link, useParams } from "react-router";
@@ -133,7 +134,7 @@ export const RoomPage = () => {
 									) : (
 										<Link
 											key={topic.slug}
-											to={`/topic/${topic.slug}`}
+											to={topicPath(room.slug, topic.slug)}
 											className="room-topic-card card-glow"
 											data-planned="false"
 											data-completed={String(isCompleted)}
@@ -1,4 +1,10 @@
-import { getRoadmapBySlug, getRoomBySlug, getTopicBySlug, rooms } from "virtual:content";
+import {
+	getRoadmapBySlug,
+	getRoomBySlug,
+	getTopicByRoomAndSlug,
+	getTopicBySlug,
+	rooms,
+} from "virtual:content";
 import { useNavigation } from "@client/contexts/navigation-context";
 import { useLocale } from "@client/hooks/use-locale";
 import { useTopicTitles } from "@client/hooks/use-topic-titles";
@@ -9,6 +15,7 @@ import { AiGeneratedBadge } from "@client/modules/study/components/ai-generated-
 import { DifficultyBadge } from "@client/modules/study/components/difficulty-badge";
 import { TagBadge } from "@client/modules/study/components/tag-badge";
 import { TopicContent } from "@client/modules/study/components/topic-content";
+import { topicPath, topicPathBySlug } from "@client/shared/utils/helpers/topic-path";
 import {
 	ArrowLeft,
 	ArrowRight,
@@ -19,15 +26,18 @@ import {
 	Link as LinkIcon,
 } from "lucide-react";
 import { useCallback, useEffect, useMemo, useState } from "react";
-import { Link, useParams } from "react-router";
+import { Link, Navigate, useParams } from "react-router";
 import "@client/styles/pages/topic.css";
 
 export const TopicPage = () => {
 	const { t } = useLocale();
-	const { topicSlug } = useParams();
+	const { roomSlug, topicSlug } = useParams();
 	const { setCurrentRoom } = useNavigation();
 
-	const topic = getTopicBySlug(topicSlug ?? "");
+	// /<sala>/<slug> é a URL canônica; /topic/<slug> só redireciona para ela
+	const topic = roomSlug
+		? getTopicByRoomAndSlug(roomSlug, topicSlug ?? "")
+		: getTopicBySlug(topicSlug ?? "");
 	const room = topic ? getRoomBySlug(topic.room) : null;
 
 	const [isCompleted, setIsCompleted] = useState(false);
@@ -133,6 +143,10 @@ export const TopicPage = () => {
 		});
 	};
 
+	if (topic && !roomSlug) {
+		return <Navigate to={topicPath(topic.room, topic.slug)} replace />;
+	}
+
 	if (!topic) {
 		return (
 			<PageLayout>
@@ -171,7 +185,7 @@ export const TopicPage = () => {
 
 				<div className="topic-top-nav">
 					{prevTopic ? (
-						<Link to={`/topic/${prevTopic.slug}`} className="topic-top-nav-btn">
+						<Link to={topicPathBySlug(prevTopic.slug, topic.room)} className="topic-top-nav-btn">
 							<ArrowLeft size={14} />
 							<span>{prevTopic.title}</span>
 						</Link>
@@ -179,7 +193,11 @@ export const TopicPage = () => {
 						<div />
 					)}
 					{nextTopic ? (
-						<Link to={`/topic/${nextTopic.slug}`} className="topic-top-nav-btn" data-align="right">
+						<Link
+							to={topicPathBySlug(nextTopic.slug, topic.room)}
+							className="topic-top-nav-btn"
+							data-align="right"
+						>
 							<span>{nextTopic.title}</span>
 							<ArrowRight size={14} />
 						</Link>
@@ -216,7 +234,11 @@ export const TopicPage = () => {
 							<span className="topic-prereqs-label">{t.topicPage.prerequisites}</span>
 							<div className="topic-prereqs-links">
 								{topic.prerequisites.map((prereq) => (
-									<Link key={prereq} to={`/topic/${prereq}`} className="topic-prereqs-link">
+									<Link
+										key={prereq}
+										to={topicPathBySlug(prereq, topic.room)}
+										className="topic-prereqs-link"
+									>
 										{prereq}
 									</Link>
 								))}
@@ -236,7 +258,11 @@ export const TopicPage = () => {
 						<p className="topic-empty-desc">{t.topicPage.comingSoonDescription}</p>
 					</div>
 				) : topic.content ? (
-					<TopicContent content={topic.content} examples={topic.examples} />
+					<TopicContent
+						content={topic.content}
+						examples={topic.examples}
+						cargoProject={topic.cargoProject}
+					/>
 				) : (
 					<div className="topic-empty-small animate-fade-in-up" style={{ animationDelay: "0.1s" }}>
 						<BookOpen size={32} className="topic-empty-small-icon" />
@@ -272,7 +298,10 @@ export const TopicPage = () => {
 			{/* Navegacao prev/next */}
 			<nav className="topic-nav animate-fade-in-up" style={{ animationDelay: "0.25s" }}>
 				{prevTopic ? (
-					<Link to={`/topic/${prevTopic.slug}`} className="topic-nav-card card-glow">
+					<Link
+						to={topicPathBySlug(prevTopic.slug, topic.room)}
+						className="topic-nav-card card-glow"
+					>
 						<ArrowLeft size={16} className="topic-nav-arrow" />
 						<div className="topic-nav-info">
 							<span className="topic-nav-label">{t.common.previous}</span>
@@ -284,7 +313,7 @@ export const TopicPage = () => {
 				)}
 				{nextTopic ? (
 					<Link
-						to={`/topic/${nextTopic.slug}`}
+						to={topicPathBySlug(nextTopic.slug, topic.room)}
 						className="topic-nav-card card-glow"
 						data-align="right"
 					>
@@ -39,6 +39,7 @@ export interface Topic extends TopicFrontmatter {
 	contentPath: string;
 	content: string;
 	examples: TopicExample[];
+	cargoProject?: string;
 }
 
 export interface TopicExample {
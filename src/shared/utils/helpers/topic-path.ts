@@ -0,0 +1,20 @@
+import { getTopicByRoomAndSlug, getTopicBySlug } from "virtual:content";
+
+/**
+ * URL canônica de um tópico: /<sala>/<slug>, a mesma das páginas pré-renderizadas.
+ *
+ * @example
+ * topicPath("fundamentos", "big-o-notation") // "/fundamentos/big-o-notation"
+ */
+export const topicPath = (room: string, slug: string): string => `/${room}/${slug}`;
+
+/**
+ * URL canônica a partir só do slug (pré-requisitos, etapas de trilha). Slugs
+ * repetidos entre salas resolvem primeiro na sala `preferredRoom`; sem tópico
+ * conhecido, cai na rota legada /topic/<slug>.
+ */
+export const topicPathBySlug = (slug: string, preferredRoom?: string): string => {
+	const topic =
+		(preferredRoom ? getTopicByRoomAndSlug(preferredRoom, slug) : null) ?? getTopicBySlug(slug);
+	return topic ? topicPath(topic.room, topic.slug) : `/topic/${slug}`;
+};
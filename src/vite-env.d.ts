@@ -10,5 +10,6 @@ declare module "virtual:content" {
 
 	export function getRoomBySlug(slug: string): Room | null;
 	export function getTopicBySlug(slug: string): Topic | null;
+	export function getTopicByRoomAndSlug(room: string, slug: string): Topic | null;
 	export function getRoadmapBySlug(slug: string): Roadmap | null;
 }